@@ -1,7 +1,11 @@
 use criterion::{criterion_group, criterion_main, Criterion};
+use goobits_repos::core::{find_repos_cached, find_repos_from_path, CacheStatus};
 use std::hint::black_box;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
 
 fn generate_repos(count: usize) -> Vec<(String, PathBuf)> {
     (0..count)
@@ -36,5 +40,50 @@ fn bench_context_cloning(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_context_cloning);
+/// 60 repositories across three levels, each with a few source directories.
+fn discovery_fixture() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    for team in 0..4 {
+        for project in 0..15 {
+            let repo = temp
+                .path()
+                .join(format!("team-{team}"))
+                .join("projects")
+                .join(format!("project-{project}"));
+            for dir in ["src/core", "src/cli", "docs", "tests"] {
+                std::fs::create_dir_all(repo.join(dir)).unwrap();
+                std::fs::write(repo.join(dir).join("mod.rs"), "").unwrap();
+            }
+            let status = Command::new("git")
+                .args(["init", "-q"])
+                .current_dir(&repo)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+    }
+    temp
+}
+
+fn bench_discovery(c: &mut Criterion) {
+    let fleet = discovery_fixture();
+    let cache = TempDir::new().unwrap();
+    std::env::set_var("REPOS_CACHE_DIR", cache.path());
+    let root: &Path = fleet.path();
+
+    // Directory stamps younger than a second are distrusted; let them age
+    // before priming the cache.
+    std::thread::sleep(Duration::from_millis(1100));
+    find_repos_cached(root, true);
+    assert_eq!(find_repos_cached(root, false).1, CacheStatus::Hit);
+
+    let mut group = c.benchmark_group("discovery");
+    group.bench_function("walk", |b| b.iter(|| black_box(find_repos_from_path(root))));
+    group.bench_function("cache_hit", |b| {
+        b.iter(|| black_box(find_repos_cached(root, false)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_context_cloning, bench_discovery);
 criterion_main!(benches);
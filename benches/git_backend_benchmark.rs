@@ -0,0 +1,78 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use goobits_repos::git::{CliBackend, GitBackend, GixBackend};
+use std::hint::black_box;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+use tokio::runtime::Runtime;
+
+fn git(path: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args([
+            "-c",
+            "user.name=Bench",
+            "-c",
+            "user.email=bench@example.com",
+        ])
+        .args(args)
+        .current_dir(path)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// A repository with an upstream, 200 tracked files, and a few local changes.
+fn fixture() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    let remote = temp.path().join("remote.git");
+    let local = temp.path().join("local");
+    git(
+        temp.path(),
+        &["init", "--quiet", "--bare", "-b", "main", "remote.git"],
+    );
+    git(temp.path(), &["init", "--quiet", "-b", "main", "local"]);
+    for i in 0..200 {
+        std::fs::write(local.join(format!("file-{i}.txt")), format!("{i}\n")).unwrap();
+    }
+    git(&local, &["add", "."]);
+    git(&local, &["commit", "--quiet", "-m", "init"]);
+    git(
+        &local,
+        &["remote", "add", "origin", &remote.to_string_lossy()],
+    );
+    git(&local, &["push", "--quiet", "-u", "origin", "main"]);
+    git(
+        &local,
+        &["commit", "--quiet", "--allow-empty", "-m", "ahead"],
+    );
+    std::fs::write(local.join("file-0.txt"), "changed\n").unwrap();
+    std::fs::write(local.join("untracked.txt"), "new\n").unwrap();
+    temp
+}
+
+/// The queries `repos status` makes for one repository.
+async fn inspect(backend: &dyn GitBackend, path: &Path) {
+    black_box(backend.porcelain_status(path).await.unwrap());
+    black_box(backend.head_name(path).await.unwrap());
+    black_box(backend.upstream(path).await.unwrap());
+    black_box(backend.ahead_behind(path).await.unwrap());
+    black_box(backend.remote_urls(path).await.unwrap());
+}
+
+fn bench_status_backends(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let temp = fixture();
+    let path = temp.path().join("local");
+
+    let mut group = c.benchmark_group("status_queries");
+    group.bench_function("cli", |b| {
+        b.to_async(&rt).iter(|| inspect(&CliBackend, &path))
+    });
+    group.bench_function("gix", |b| {
+        b.to_async(&rt).iter(|| inspect(&GixBackend, &path))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_status_backends);
+criterion_main!(benches);
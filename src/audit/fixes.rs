@@ -24,6 +24,8 @@ fn ensure_command_success(output: &std::process::Output, operation: &str) -> Res
 }
 
 use super::hygiene::{HygieneStatistics, HygieneViolation, ViolationType};
+use super::native;
+use super::scanner::SecretEngine;
 
 struct PrivateTempFile {
     path: PathBuf,
@@ -91,6 +93,8 @@ pub struct FixOptions {
     pub skip_confirm: bool,
     /// Only apply fixes to specific repositories
     pub target_repos: Option<Vec<String>>,
+    /// Scanner that locates the secrets `fix_secrets` removes
+    pub engine: SecretEngine,
 }
 
 impl FixOptions {
@@ -106,6 +110,7 @@ impl FixOptions {
             dry_run,
             skip_confirm: false,
             target_repos,
+            engine: SecretEngine::default(),
         }
     }
 }
@@ -602,19 +607,33 @@ async fn fix_large_files(
     ))
 }
 
-/// Fix secrets in Git history using git filter-repo
-async fn fix_secrets_in_history(repo_path: &str, options: &FixOptions) -> Result<String> {
-    if options.dry_run {
-        return Ok("[DRY RUN] Would scan and remove secrets from history".to_string());
-    }
+/// Where `--fix-secrets` found secrets in a repository's history.
+#[derive(Default)]
+struct SecretLocations {
+    /// Files containing secrets.
+    files: HashSet<String>,
+    /// Secrets short enough to redact in place.
+    patterns: HashSet<String>,
+    /// Files removed from history outright, whatever else was redacted: a
+    /// private key match is only the `BEGIN` header, so redacting it would
+    /// leave the key body behind.
+    remove: HashSet<String>,
+}
 
-    // Check if git filter-repo is installed
-    if !check_filter_repo_installed().await {
-        return Err(anyhow!(
-            "git-filter-repo is required to remove secrets. Please install it:\n\
-             pip install git-filter-repo\n\
-             or: brew install git-filter-repo (macOS)"
-        ));
+/// Locates the secrets `--fix-secrets` removes.
+async fn locate_secrets(repo_path: &str, engine: SecretEngine) -> Result<SecretLocations> {
+    let mut locations = SecretLocations::default();
+
+    if engine == SecretEngine::Native {
+        for finding in native::scan_repository(Path::new(repo_path)).await? {
+            if finding.detector == "PrivateKey" {
+                locations.remove.insert(finding.file.clone());
+            } else if finding.secret.len() < 200 {
+                locations.patterns.insert(finding.secret);
+            }
+            locations.files.insert(finding.file);
+        }
+        return Ok(locations);
     }
 
     // Run TruffleHog to get detailed secret information
@@ -639,9 +658,6 @@ async fn fix_secrets_in_history(repo_path: &str, options: &FixOptions) -> Result
 
     // Parse secrets from output to get file paths
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut secret_files = HashSet::new();
-    let mut secret_patterns = HashSet::new();
-
     for line in stdout.lines() {
         if line.trim().is_empty() {
             continue;
@@ -650,18 +666,42 @@ async fn fix_secrets_in_history(repo_path: &str, options: &FixOptions) -> Result
         // Parse JSON to extract file paths and secret patterns
         if let Ok(json) = serde_json::from_str::<serde_json::Value>(line) {
             if let Some(file) = json["SourceMetadata"]["Data"]["Git"]["file"].as_str() {
-                secret_files.insert(file.to_string());
+                locations.files.insert(file.to_string());
             }
             // Extract the actual secret pattern to redact
             if let Some(raw) = json["Raw"].as_str() {
                 // Only add shorter secrets as patterns (avoid adding entire files)
                 if raw.len() < 200 {
-                    secret_patterns.insert(raw.to_string());
+                    locations.patterns.insert(raw.to_string());
                 }
             }
         }
     }
 
+    Ok(locations)
+}
+
+/// Fix secrets in Git history using git filter-repo
+async fn fix_secrets_in_history(repo_path: &str, options: &FixOptions) -> Result<String> {
+    if options.dry_run {
+        return Ok("[DRY RUN] Would scan and remove secrets from history".to_string());
+    }
+
+    // Check if git filter-repo is installed
+    if !check_filter_repo_installed().await {
+        return Err(anyhow!(
+            "git-filter-repo is required to remove secrets. Please install it:\n\
+             pip install git-filter-repo\n\
+             or: brew install git-filter-repo (macOS)"
+        ));
+    }
+
+    let SecretLocations {
+        files: secret_files,
+        patterns: secret_patterns,
+        remove: mut removed_files,
+    } = locate_secrets(repo_path, options.engine).await?;
+
     if secret_files.is_empty() && secret_patterns.is_empty() {
         return Ok("No secrets found".to_string());
     }
@@ -713,10 +753,13 @@ async fn fix_secrets_in_history(repo_path: &str, options: &FixOptions) -> Result
     }
 
     // For files that contain secrets but patterns are too long, remove entire files
-    if !secret_files.is_empty() && secret_patterns.is_empty() {
+    if secret_patterns.is_empty() {
+        removed_files.extend(secret_files.iter().cloned());
+    }
+    if !removed_files.is_empty() {
         eprintln!("    Removing entire files containing secrets...");
 
-        let paths_content: String = secret_files
+        let paths_content: String = removed_files
             .iter()
             .map(|f| format!("literal:{f}\n"))
             .collect();
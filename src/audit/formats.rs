@@ -0,0 +1,526 @@
+//! SARIF and `JUnit` XML renderings of an audit.
+//!
+//! SARIF output is a single run with one rule per secret detector and per
+//! hygiene [`ViolationType`]. Each result is located by a repository-relative
+//! path whose `uriBaseId` names the repository; `originalUriBaseIds` maps those
+//! names to the repository directories under the workspace root. Baselined
+//! secrets are included as suppressed results.
+//!
+//! `JUnit` output has a `secrets` and a `hygiene` suite with one test case per
+//! repository, so CI systems show which repositories failed the audit.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use super::baseline::BASELINE_FILE;
+use super::hygiene::rules::Severity;
+use super::hygiene::{HygieneStatistics, ViolationType};
+use super::scanner::TruffleStatistics;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const WORKSPACE_BASE_ID: &str = "%SRCROOT%";
+
+/// Report format selected for `repos audit`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AuditFormat {
+    /// Progress bars and text reports.
+    #[default]
+    Human,
+    /// The `--json` document.
+    Json,
+    /// SARIF 2.1.0 for code-scanning dashboards.
+    Sarif,
+    /// `JUnit` XML for CI test reports.
+    Junit,
+}
+
+impl AuditFormat {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Human => "human",
+            Self::Json => "json",
+            Self::Sarif => "sarif",
+            Self::Junit => "junit",
+        }
+    }
+}
+
+fn secret_rule_id(detector: &str) -> String {
+    format!("secrets/{detector}")
+}
+
+fn hygiene_rule_id(violation_type: &ViolationType) -> String {
+    format!("hygiene/{}", violation_type.label())
+}
+
+/// Renders the audit as a SARIF 2.1.0 log.
+#[must_use]
+pub fn sarif_report(truffle: &TruffleStatistics, hygiene: &HygieneStatistics) -> serde_json::Value {
+    let detectors: BTreeSet<&str> = truffle
+        .findings
+        .iter()
+        .chain(&truffle.baselined_findings)
+        .map(|finding| finding.detector.as_str())
+        .collect();
+    let mut rules: Vec<serde_json::Value> = detectors
+        .iter()
+        .map(|detector| {
+            serde_json::json!({
+                "id": secret_rule_id(detector),
+                "name": format!("{detector}Secret"),
+                "shortDescription": { "text": format!("{detector} secret committed to the repository") },
+                "defaultConfiguration": { "level": "error" },
+            })
+        })
+        .collect();
+    rules.extend(ViolationType::ALL.iter().map(|violation_type| {
+        serde_json::json!({
+            "id": hygiene_rule_id(violation_type),
+            "name": violation_type.label(),
+            "shortDescription": { "text": violation_type.description() },
+            "defaultConfiguration": { "level": "warning" },
+        })
+    }));
+    let rule_index = |id: &str| rules.iter().position(|rule| rule["id"] == id);
+
+    let mut base_ids = BTreeMap::new();
+    for (name, path) in &truffle.repositories {
+        base_ids.insert(name.clone(), path.clone());
+    }
+
+    let mut results = Vec::new();
+    let mut findings = truffle.findings.clone();
+    findings.sort_by(|left, right| {
+        left.repository
+            .cmp(&right.repository)
+            .then_with(|| left.file.cmp(&right.file))
+            .then_with(|| left.detector.cmp(&right.detector))
+    });
+    for finding in &findings {
+        let rule_id = secret_rule_id(&finding.detector);
+        let state = if finding.verified {
+            "verified"
+        } else {
+            "unverified"
+        };
+        results.push(serde_json::json!({
+            "ruleId": rule_id,
+            "ruleIndex": rule_index(&rule_id),
+            "level": if finding.verified { "error" } else { "warning" },
+            "message": { "text": format!("{} secret ({state}) in {}", finding.detector, finding.file) },
+            "locations": [location(&finding.repository, &finding.file)],
+            "properties": { "repository": finding.repository, "verified": finding.verified },
+        }));
+    }
+    let mut baselined = truffle.baselined_findings.clone();
+    baselined.sort_by(|left, right| {
+        left.repository
+            .cmp(&right.repository)
+            .then_with(|| left.file.cmp(&right.file))
+            .then_with(|| left.detector.cmp(&right.detector))
+    });
+    for finding in &baselined {
+        let rule_id = secret_rule_id(&finding.detector);
+        results.push(serde_json::json!({
+            "ruleId": rule_id,
+            "ruleIndex": rule_index(&rule_id),
+            "level": "note",
+            "message": { "text": format!("{} secret in {} (baselined)", finding.detector, finding.file) },
+            "locations": [location(&finding.repository, &finding.file)],
+            "baselineState": "unchanged",
+            "suppressions": [{
+                "kind": "external",
+                "justification": format!("Accepted in {BASELINE_FILE}"),
+            }],
+            "properties": { "repository": finding.repository, "verified": finding.verified },
+        }));
+    }
+
+    let mut violation_repos = hygiene.get_violation_repos();
+    violation_repos.sort_by(|left, right| left.0.cmp(&right.0));
+    for (repository, path, violations) in &violation_repos {
+        base_ids
+            .entry(repository.clone())
+            .or_insert_with(|| path.clone());
+        for violation in violations {
+            let rule_id = hygiene_rule_id(&violation.violation_type);
+            let description = violation
+                .rule
+                .as_ref()
+                .map_or(violation.violation_type.description(), |rule| {
+                    rule.message.as_str()
+                });
+            let mut text = format!("{description}: {}", violation.file_path);
+            if let Some(size) = violation.size_bytes {
+                let _ = write!(text, " ({size} bytes)");
+            }
+            let level = match violation.rule.as_ref().map(|rule| rule.severity) {
+                Some(Severity::Error) => "error",
+                Some(Severity::Info) => "note",
+                Some(Severity::Warning) | None => "warning",
+            };
+            let mut properties = serde_json::json!({ "repository": repository });
+            if let Some(rule) = &violation.rule {
+                properties["hygieneRule"] = serde_json::json!(rule.name);
+            }
+            results.push(serde_json::json!({
+                "ruleId": rule_id,
+                "ruleIndex": rule_index(&rule_id),
+                "level": level,
+                "message": { "text": text },
+                "locations": [location(repository, &violation.file_path)],
+                "properties": properties,
+            }));
+        }
+    }
+
+    let mut failures: Vec<String> = truffle
+        .failed_repos
+        .iter()
+        .map(|(repository, error)| format!("{repository}: secret scan failed: {error}"))
+        .collect();
+    failures.extend(
+        hygiene
+            .get_failed_repos()
+            .into_iter()
+            .map(|(repository, _, error)| format!("{repository}: hygiene scan failed: {error}")),
+    );
+    failures.sort();
+    let notifications: Vec<serde_json::Value> = failures
+        .into_iter()
+        .map(|text| serde_json::json!({ "level": "error", "message": { "text": text } }))
+        .collect();
+
+    let original_uri_base_ids: serde_json::Map<String, serde_json::Value> = base_ids
+        .into_iter()
+        .map(|(name, path)| {
+            let uri = directory_uri(&path);
+            (
+                name,
+                serde_json::json!({ "uri": uri, "uriBaseId": WORKSPACE_BASE_ID }),
+            )
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "repos",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "originalUriBaseIds": original_uri_base_ids,
+            "invocations": [{
+                "executionSuccessful": notifications.is_empty(),
+                "toolExecutionNotifications": notifications,
+            }],
+            "results": results,
+        }]
+    })
+}
+
+fn location(repository: &str, file: &str) -> serde_json::Value {
+    serde_json::json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": encode_uri_path(file), "uriBaseId": repository }
+        }
+    })
+}
+
+/// Repository directory relative to the workspace root, as a SARIF base URI.
+fn directory_uri(path: &str) -> String {
+    let relative = path.trim_start_matches("./").trim_end_matches('/');
+    if relative.is_empty() || relative == "." {
+        "./".to_string()
+    } else {
+        format!("{}/", encode_uri_path(relative))
+    }
+}
+
+/// Percent-encodes a relative path for use as a URI reference.
+fn encode_uri_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// Renders the audit as `JUnit` XML.
+#[must_use]
+pub fn junit_report(truffle: &TruffleStatistics, hygiene: &HygieneStatistics) -> String {
+    let violation_repos = hygiene.get_violation_repos();
+    let hygiene_failures = hygiene.get_failed_repos();
+
+    let mut repositories: BTreeSet<&str> = truffle
+        .repositories
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    repositories.extend(truffle.findings.iter().map(|f| f.repository.as_str()));
+    repositories.extend(truffle.failed_repos.iter().map(|(name, _)| name.as_str()));
+    repositories.extend(violation_repos.iter().map(|(name, _, _)| name.as_str()));
+    repositories.extend(hygiene_failures.iter().map(|(name, _, _)| name.as_str()));
+
+    let mut secrets = Vec::new();
+    let mut hygiene_cases = Vec::new();
+    for repository in &repositories {
+        let secret_case = if let Some((_, error)) = truffle
+            .failed_repos
+            .iter()
+            .find(|(name, _)| name == repository)
+        {
+            TestOutcome::Error(error.clone())
+        } else {
+            let mut lines: Vec<String> = truffle
+                .findings
+                .iter()
+                .filter(|finding| finding.repository == *repository)
+                .map(|finding| {
+                    let state = if finding.verified {
+                        "verified"
+                    } else {
+                        "unverified"
+                    };
+                    format!("{} ({state}) {}", finding.detector, finding.file)
+                })
+                .collect();
+            lines.sort();
+            TestOutcome::from_findings("secret", lines)
+        };
+        secrets.push((*repository, secret_case));
+
+        let hygiene_case = if let Some((_, _, error)) = hygiene_failures
+            .iter()
+            .find(|(name, _, _)| name == repository)
+        {
+            TestOutcome::Error(error.clone())
+        } else {
+            let lines = violation_repos
+                .iter()
+                .filter(|(name, _, _)| name == repository)
+                .flat_map(|(_, _, violations)| violations)
+                .map(|violation| {
+                    format!(
+                        "{} {}",
+                        violation.violation_type.label(),
+                        violation.file_path
+                    )
+                })
+                .collect();
+            TestOutcome::from_findings("violation", lines)
+        };
+        hygiene_cases.push((*repository, hygiene_case));
+    }
+
+    let suites = [
+        ("secrets", &secrets, truffle.scan_duration.as_secs_f64()),
+        ("hygiene", &hygiene_cases, 0.0),
+    ];
+    let count = |cases: &[(&str, TestOutcome)], error: bool| {
+        cases
+            .iter()
+            .filter(|(_, outcome)| match outcome {
+                TestOutcome::Error(_) => error,
+                TestOutcome::Failure { .. } => !error,
+                TestOutcome::Passed => false,
+            })
+            .count()
+    };
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"repos audit\" tests=\"{}\" failures=\"{}\" errors=\"{}\">",
+        secrets.len() + hygiene_cases.len(),
+        count(&secrets, false) + count(&hygiene_cases, false),
+        count(&secrets, true) + count(&hygiene_cases, true),
+    );
+    for (suite, cases, time) in suites {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{suite}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{time:.3}\">",
+            cases.len(),
+            count(cases, false),
+            count(cases, true),
+        );
+        for (repository, outcome) in cases.iter() {
+            let name = escape_xml(repository);
+            let classname = format!("repos.audit.{suite}");
+            match outcome {
+                TestOutcome::Passed => {
+                    let _ = writeln!(
+                        xml,
+                        "    <testcase name=\"{name}\" classname=\"{classname}\"/>"
+                    );
+                }
+                TestOutcome::Failure { message, details } => {
+                    let _ = writeln!(
+                        xml,
+                        "    <testcase name=\"{name}\" classname=\"{classname}\">\n      <failure message=\"{}\" type=\"{suite}\">{}</failure>\n    </testcase>",
+                        escape_xml(message),
+                        escape_xml(details),
+                    );
+                }
+                TestOutcome::Error(error) => {
+                    let _ = writeln!(
+                        xml,
+                        "    <testcase name=\"{name}\" classname=\"{classname}\">\n      <error message=\"{}\" type=\"scan_failed\"/>\n    </testcase>",
+                        escape_xml(error),
+                    );
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+enum TestOutcome {
+    Passed,
+    Failure { message: String, details: String },
+    Error(String),
+}
+
+impl TestOutcome {
+    fn from_findings(noun: &str, lines: Vec<String>) -> Self {
+        if lines.is_empty() {
+            return Self::Passed;
+        }
+        let plural = if lines.len() == 1 { "" } else { "s" };
+        Self::Failure {
+            message: format!("{} {noun}{plural} found", lines.len()),
+            details: lines.join("\n"),
+        }
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 cannot represent most control characters at all.
+            ch if ch.is_control() && !matches!(ch, '\n' | '\t' | '\r') => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::hygiene::report::HygieneStatus;
+    use crate::audit::hygiene::HygieneViolation;
+    use crate::audit::scanner::SecretFinding;
+
+    fn audit() -> (TruffleStatistics, HygieneStatistics) {
+        let mut truffle = TruffleStatistics::new();
+        truffle.add_repo_result(
+            "api",
+            &[SecretFinding {
+                detector_name: "AWS".to_string(),
+                verified: true,
+                file_path: "config/prod env.sh".to_string(),
+                fingerprint: String::new(),
+            }],
+        );
+        truffle.add_repo_result("web", &[]);
+        truffle.add_repo_failure("legacy", "history scan failed");
+        truffle.repositories = vec![
+            ("api".to_string(), "./services/api".to_string()),
+            ("web".to_string(), "./web".to_string()),
+            ("legacy".to_string(), "./legacy".to_string()),
+        ];
+
+        let mut hygiene = HygieneStatistics::new();
+        hygiene.update(
+            "web",
+            "./web",
+            &HygieneStatus::Violations,
+            "",
+            vec![HygieneViolation {
+                file_path: "dist/<bundle>.js".to_string(),
+                violation_type: ViolationType::LargeFile,
+                size_bytes: Some(2_000_000),
+                rule: None,
+            }],
+        );
+        hygiene.update("api", "./services/api", &HygieneStatus::Clean, "", vec![]);
+        hygiene.update("legacy", "./legacy", &HygieneStatus::Clean, "", vec![]);
+        (truffle, hygiene)
+    }
+
+    #[test]
+    fn sarif_has_rules_per_detector_and_violation_type() {
+        let (truffle, hygiene) = audit();
+        let sarif = sarif_report(&truffle, &hygiene);
+        let run = &sarif["runs"][0];
+
+        let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                "secrets/AWS",
+                "hygiene/gitignore_violation",
+                "hygiene/universal_bad_pattern",
+                "hygiene/large_file"
+            ]
+        );
+
+        let secret = &run["results"][0];
+        assert_eq!(secret["ruleIndex"], 0);
+        assert_eq!(secret["level"], "error");
+        let artifact = &secret["locations"][0]["physicalLocation"]["artifactLocation"];
+        assert_eq!(artifact["uri"], "config/prod%20env.sh");
+        assert_eq!(artifact["uriBaseId"], "api");
+        assert_eq!(run["originalUriBaseIds"]["api"]["uri"], "services/api/");
+
+        let large = &run["results"][1];
+        assert_eq!(large["ruleId"], "hygiene/large_file");
+        assert_eq!(large["ruleIndex"], 3);
+
+        let invocation = &run["invocations"][0];
+        assert_eq!(invocation["executionSuccessful"], false);
+        assert_eq!(
+            invocation["toolExecutionNotifications"][0]["message"]["text"],
+            "legacy: secret scan failed: history scan failed"
+        );
+    }
+
+    #[test]
+    fn junit_has_a_case_per_repository_in_each_suite() {
+        let (truffle, hygiene) = audit();
+        let xml = junit_report(&truffle, &hygiene);
+
+        assert!(xml
+            .contains("<testsuites name=\"repos audit\" tests=\"6\" failures=\"2\" errors=\"1\">"));
+        assert!(xml.contains("<testsuite name=\"secrets\" tests=\"3\" failures=\"1\" errors=\"1\""));
+        assert!(xml.contains("<failure message=\"1 secret found\" type=\"secrets\">AWS (verified) config/prod env.sh</failure>"));
+        assert!(xml.contains("<error message=\"history scan failed\" type=\"scan_failed\"/>"));
+        assert!(xml.contains("<testcase name=\"web\" classname=\"repos.audit.secrets\"/>"));
+        assert!(xml.contains("large_file dist/&lt;bundle&gt;.js"));
+        assert!(xml.contains("<testcase name=\"legacy\" classname=\"repos.audit.hygiene\"/>"));
+    }
+}
@@ -1,5 +1,6 @@
 //! Hygiene reporting and statistics
 
+use super::rules::RuleMatch;
 use crate::core::config::PATH_DISPLAY_WIDTH;
 use crate::utils::{compare_repository_locations, shorten_path};
 use std::time::Duration;
@@ -39,12 +40,44 @@ pub enum ViolationType {
     LargeFile,           // File is unusually large
 }
 
+impl ViolationType {
+    /// Every violation type, in report order.
+    pub const ALL: [Self; 3] = [
+        Self::GitignoreViolation,
+        Self::UniversalBadPattern,
+        Self::LargeFile,
+    ];
+
+    /// Returns the snake-case name used in JSON output.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::GitignoreViolation => "gitignore_violation",
+            Self::UniversalBadPattern => "universal_bad_pattern",
+            Self::LargeFile => "large_file",
+        }
+    }
+
+    /// Returns a one-line explanation of the violation.
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::GitignoreViolation => "Tracked file matches .gitignore",
+            Self::UniversalBadPattern => "Tracked file matches a hygiene rule",
+            Self::LargeFile => "Tracked file exceeds the large file threshold",
+        }
+    }
+}
+
 /// Individual hygiene violation
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct HygieneViolation {
     pub file_path: String,
     pub violation_type: ViolationType,
     pub size_bytes: Option<u64>,
+    /// The hygiene rule a `UniversalBadPattern` violation matched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<RuleMatch>,
 }
 
 /// Statistics for hygiene scanning results
@@ -73,6 +106,12 @@ impl HygieneStatistics {
         self.violation_repos.clone()
     }
 
+    /// Get the repositories whose hygiene scan failed as (name, path, error)
+    #[must_use]
+    pub fn get_failed_repos(&self) -> Vec<(String, String, String)> {
+        self.failed_repos.clone()
+    }
+
     #[must_use]
     pub fn error_count(&self) -> u32 {
         self.error_repos
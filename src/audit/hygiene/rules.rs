@@ -1,28 +1,485 @@
-//! Hygiene rules and constants
-
-// Universal patterns that should never be committed to git
-pub const UNIVERSAL_BAD_PATTERNS: &[&str] = &[
-    "node_modules/",
-    "vendor/",
-    "dist/",
-    "build/",
-    "target/debug/",
-    "target/release/",
-    ".env",
-    "*.log",
-    ".DS_Store",
-    "Thumbs.db",
-    "*.tmp",
-    "*.cache",
-    "__pycache__/",
-    ".venv/",
-    ".idea/",
-    ".vscode/settings.json",
-    "*.key",
-    "*.pem",
-    "*.p12",
-    "*.jks",
+//! Hygiene rules: tracked-file patterns that should not be committed.
+//!
+//! Built-in rules cover dependency and build directories, logs, editor and OS
+//! metadata, env files, and private keys. A `.repos-hygiene.toml` at the audit
+//! root applies to every repository, and one in a repository's root applies to
+//! that repository only, after the workspace file:
+//!
+//! ```toml
+//! # Drop the built-in rules entirely (default: true)
+//! builtin_rules = true
+//! # Per-file threshold for large-file violations, in bytes
+//! large_file_threshold = 5242880
+//!
+//! [[rule]]
+//! name = "sqlite"
+//! patterns = ["*.sqlite"]
+//! exempt = ["fixtures/"]
+//! severity = "error"
+//! message = "SQLite databases belong in fixtures/"
+//!
+//! # Adjust or disable a built-in rule by name
+//! [[rule]]
+//! name = "private-keys"
+//! exempt = ["tests/data/*.pem"]
+//!
+//! [[rule]]
+//! name = "dependencies"
+//! enabled = false
+//! ```
+//!
+//! Patterns and exemptions use gitignore syntax and are matched with the
+//! `ignore` crate: `dist/` matches a `dist` directory at any depth while
+//! `/build/` only matches one at the repository root. A later rule with an
+//! existing name replaces the fields it sets; `exempt` entries accumulate.
+
+use anyhow::{anyhow, bail, Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::Deserialize;
+use std::path::{Component, Path};
+
+use crate::core::settings::settings;
+
+// Default large file threshold in bytes (1MB); configurable as `audit.large_file_threshold`
+pub use crate::core::config::LARGE_FILE_THRESHOLD;
+
+/// File name of a workspace or repository hygiene rule file.
+pub const HYGIENE_RULES_FILE: &str = ".repos-hygiene.toml";
+
+/// How serious a rule violation is.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            other => bail!("invalid severity '{other}'; expected 'info', 'warning' or 'error'"),
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// The rule a tracked file matched.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct RuleMatch {
+    pub name: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+struct BuiltinRule {
+    name: &'static str,
+    patterns: &'static [&'static str],
+    exempt: &'static [&'static str],
+    severity: Severity,
+    message: &'static str,
+}
+
+const BUILTIN_RULES: &[BuiltinRule] = &[
+    BuiltinRule {
+        name: "dependencies",
+        patterns: &["node_modules/", "/vendor/", ".venv/"],
+        exempt: &[],
+        severity: Severity::Warning,
+        message: "Installed dependencies should be restored, not committed",
+    },
+    BuiltinRule {
+        name: "build-output",
+        patterns: &[
+            "dist/",
+            "/build/",
+            "/target/debug/",
+            "/target/release/",
+            "__pycache__/",
+        ],
+        exempt: &[],
+        severity: Severity::Warning,
+        message: "Build output should be generated, not committed",
+    },
+    BuiltinRule {
+        name: "env-files",
+        patterns: &[".env", ".env.*"],
+        exempt: &[".env.example", ".env.sample"],
+        severity: Severity::Error,
+        message: "Environment files usually hold credentials",
+    },
+    BuiltinRule {
+        name: "logs-and-temp",
+        patterns: &["*.log", "*.tmp", "*.cache"],
+        exempt: &[],
+        severity: Severity::Warning,
+        message: "Logs and temporary files should not be committed",
+    },
+    BuiltinRule {
+        name: "os-metadata",
+        patterns: &[".DS_Store", "Thumbs.db"],
+        exempt: &[],
+        severity: Severity::Info,
+        message: "Operating system metadata should not be committed",
+    },
+    BuiltinRule {
+        name: "editor-settings",
+        patterns: &[".idea/", ".vscode/settings.json"],
+        exempt: &[],
+        severity: Severity::Info,
+        message: "Personal editor settings should not be committed",
+    },
+    BuiltinRule {
+        name: "private-keys",
+        patterns: &["*.key", "*.pem", "*.p12", "*.jks"],
+        exempt: &[],
+        severity: Severity::Error,
+        message: "Private keys and keystores should not be committed",
+    },
 ];
 
-// Large file threshold in bytes (1MB)
-pub const LARGE_FILE_THRESHOLD: u64 = 1_048_576;
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    #[serde(default)]
+    builtin_rules: Option<bool>,
+    #[serde(default)]
+    large_file_threshold: Option<u64>,
+    #[serde(default, rename = "rule")]
+    rules: Vec<RuleSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    name: String,
+    #[serde(default)]
+    patterns: Option<Vec<String>>,
+    #[serde(default)]
+    exempt: Vec<String>,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    enabled: Option<bool>,
+}
+
+#[derive(Clone, Debug)]
+struct RuleDefinition {
+    name: String,
+    patterns: Vec<String>,
+    exempt: Vec<String>,
+    severity: Severity,
+    message: String,
+    enabled: bool,
+}
+
+struct CompiledRule {
+    definition: RuleDefinition,
+    patterns: Gitignore,
+    exempt: Gitignore,
+}
+
+/// The resolved rules for one repository.
+pub struct HygieneRules {
+    rules: Vec<CompiledRule>,
+    /// Size in bytes above which a file in history is a violation.
+    pub large_file_threshold: u64,
+}
+
+impl HygieneRules {
+    /// Built-in rules and the configured `audit.large_file_threshold`.
+    pub fn builtin() -> Result<Self> {
+        Self::resolve(&[])
+    }
+
+    /// Resolves the rules for the repository at `repo_path`: built-ins, then
+    /// the workspace rule file under `workspace`, then the repository's own.
+    pub fn load(workspace: &Path, repo_path: &Path) -> Result<Self> {
+        let mut files = vec![read_rule_file(&workspace.join(HYGIENE_RULES_FILE))?];
+        if !same_directory(workspace, repo_path) {
+            files.push(read_rule_file(&repo_path.join(HYGIENE_RULES_FILE))?);
+        }
+        Self::resolve(&files)
+    }
+
+    fn resolve(files: &[(RuleFile, String)]) -> Result<Self> {
+        let mut definitions: Vec<RuleDefinition> = BUILTIN_RULES
+            .iter()
+            .map(|rule| RuleDefinition {
+                name: rule.name.to_string(),
+                patterns: rule.patterns.iter().map(ToString::to_string).collect(),
+                exempt: rule.exempt.iter().map(ToString::to_string).collect(),
+                severity: rule.severity,
+                message: rule.message.to_string(),
+                enabled: true,
+            })
+            .collect();
+        let mut large_file_threshold = settings().large_file_threshold.value;
+
+        for (file, source) in files {
+            if file.builtin_rules == Some(false) {
+                definitions.retain(|rule| {
+                    !BUILTIN_RULES
+                        .iter()
+                        .any(|builtin| builtin.name == rule.name)
+                });
+            }
+            if let Some(threshold) = file.large_file_threshold {
+                large_file_threshold = threshold;
+            }
+            for spec in &file.rules {
+                let severity = spec
+                    .severity
+                    .as_deref()
+                    .map(Severity::parse)
+                    .transpose()
+                    .with_context(|| format!("{source}: rule '{}'", spec.name))?;
+                if let Some(existing) = definitions.iter_mut().find(|rule| rule.name == spec.name) {
+                    if let Some(patterns) = &spec.patterns {
+                        existing.patterns.clone_from(patterns);
+                    }
+                    existing.exempt.extend(spec.exempt.iter().cloned());
+                    if let Some(severity) = severity {
+                        existing.severity = severity;
+                    }
+                    if let Some(message) = &spec.message {
+                        existing.message.clone_from(message);
+                    }
+                    if let Some(enabled) = spec.enabled {
+                        existing.enabled = enabled;
+                    }
+                    continue;
+                }
+                let patterns = spec.patterns.clone().ok_or_else(|| {
+                    anyhow!(
+                        "{source}: rule '{}' needs patterns (no built-in rule has that name)",
+                        spec.name
+                    )
+                })?;
+                definitions.push(RuleDefinition {
+                    name: spec.name.clone(),
+                    patterns,
+                    exempt: spec.exempt.clone(),
+                    severity: severity.unwrap_or(Severity::Warning),
+                    message: spec
+                        .message
+                        .clone()
+                        .unwrap_or_else(|| format!("Matches hygiene rule '{}'", spec.name)),
+                    enabled: spec.enabled.unwrap_or(true),
+                });
+            }
+        }
+
+        let rules = definitions
+            .into_iter()
+            .filter(|rule| rule.enabled)
+            .map(|definition| {
+                let patterns = compile(&definition.name, &definition.patterns)?;
+                let exempt = compile(&definition.name, &definition.exempt)?;
+                Ok(CompiledRule {
+                    definition,
+                    patterns,
+                    exempt,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            rules,
+            large_file_threshold,
+        })
+    }
+
+    /// The most severe rule matching a tracked file, if any; the first rule
+    /// wins ties.
+    #[must_use]
+    pub fn check(&self, file_path: &str) -> Option<RuleMatch> {
+        let path = Path::new(file_path);
+        self.rules
+            .iter()
+            .filter(|rule| {
+                rule.patterns
+                    .matched_path_or_any_parents(path, false)
+                    .is_ignore()
+                    && !rule
+                        .exempt
+                        .matched_path_or_any_parents(path, false)
+                        .is_ignore()
+            })
+            .fold(None::<&RuleDefinition>, |best, rule| match best {
+                Some(best) if best.severity >= rule.definition.severity => Some(best),
+                _ => Some(&rule.definition),
+            })
+            .map(|rule| RuleMatch {
+                name: rule.name.clone(),
+                severity: rule.severity,
+                message: rule.message.clone(),
+            })
+    }
+}
+
+fn read_rule_file(path: &Path) -> Result<(RuleFile, String)> {
+    let source = path.display().to_string();
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok((RuleFile::default(), source))
+        }
+        Err(e) => return Err(e).with_context(|| format!("failed to read {source}")),
+    };
+    let file = toml::from_str(&contents).with_context(|| format!("{source}: invalid rules"))?;
+    Ok((file, source))
+}
+
+fn compile(rule: &str, patterns: &[String]) -> Result<Gitignore> {
+    // Matching is always against repository-relative paths.
+    let mut builder = GitignoreBuilder::new("");
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .with_context(|| format!("rule '{rule}': invalid pattern '{pattern}'"))?;
+    }
+    builder
+        .build()
+        .with_context(|| format!("rule '{rule}': invalid patterns"))
+}
+
+fn same_directory(left: &Path, right: &Path) -> bool {
+    fn normalize(path: &Path) -> Vec<Component<'_>> {
+        path.components()
+            .filter(|component| *component != Component::CurDir)
+            .collect()
+    }
+    normalize(left) == normalize(right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(toml: &str) -> HygieneRules {
+        HygieneRules::resolve(&[(toml::from_str(toml).unwrap(), "test".to_string())]).unwrap()
+    }
+
+    fn rule_name(rules: &HygieneRules, path: &str) -> Option<String> {
+        rules.check(path).map(|rule| rule.name)
+    }
+
+    #[test]
+    fn builtin_rules_use_gitignore_semantics() {
+        let rules = HygieneRules::builtin().unwrap();
+        assert_eq!(
+            rule_name(&rules, "build/app.js").as_deref(),
+            Some("build-output")
+        );
+        assert_eq!(rule_name(&rules, "src/build/mod.rs"), None);
+        assert_eq!(
+            rule_name(&rules, "web/node_modules/left-pad/index.js").as_deref(),
+            Some("dependencies")
+        );
+        assert_eq!(
+            rule_name(&rules, "config/.env").as_deref(),
+            Some("env-files")
+        );
+        assert_eq!(rule_name(&rules, "docs/.env.example"), None);
+        assert_eq!(rule_name(&rules, ".env.sample"), None);
+        for env_file in [
+            ".env.local",
+            ".env.production",
+            "web/.env.development.local",
+        ] {
+            assert_eq!(
+                rule_name(&rules, env_file).as_deref(),
+                Some("env-files"),
+                "{env_file}"
+            );
+        }
+        assert_eq!(
+            rule_name(&rules, "packages/ui/dist/index.js").as_deref(),
+            Some("build-output")
+        );
+        assert_eq!(rule_name(&rules, "src/environment.rs"), None);
+        assert_eq!(
+            rules.check("certs/server.pem").unwrap().severity,
+            Severity::Error
+        );
+    }
+
+    #[test]
+    fn rule_files_add_override_and_disable_rules() {
+        let rules = rules(
+            r#"
+            large_file_threshold = 10
+
+            [[rule]]
+            name = "sqlite"
+            patterns = ["*.sqlite"]
+            exempt = ["fixtures/"]
+            severity = "error"
+
+            [[rule]]
+            name = "private-keys"
+            exempt = ["tests/data/*.pem"]
+
+            [[rule]]
+            name = "logs-and-temp"
+            enabled = false
+            "#,
+        );
+        assert_eq!(rules.large_file_threshold, 10);
+        assert_eq!(
+            rule_name(&rules, "data/app.sqlite").as_deref(),
+            Some("sqlite")
+        );
+        assert_eq!(rule_name(&rules, "fixtures/seed/app.sqlite"), None);
+        assert_eq!(rule_name(&rules, "tests/data/sample.pem"), None);
+        assert_eq!(
+            rule_name(&rules, "deploy/server.pem").as_deref(),
+            Some("private-keys")
+        );
+        assert_eq!(rule_name(&rules, "debug.log"), None);
+    }
+
+    #[test]
+    fn the_most_severe_matching_rule_is_reported() {
+        let rules = rules(
+            r#"
+            [[rule]]
+            name = "no-secrets-dir"
+            patterns = ["secrets/"]
+            severity = "info"
+            "#,
+        );
+        assert_eq!(
+            rule_name(&rules, "secrets/prod.pem").as_deref(),
+            Some("private-keys")
+        );
+    }
+
+    #[test]
+    fn invalid_rules_are_errors() {
+        let without_patterns = HygieneRules::resolve(&[(
+            toml::from_str("[[rule]]\nname = \"custom\"").unwrap(),
+            "test".to_string(),
+        )]);
+        assert!(without_patterns.is_err());
+        let bad_severity = HygieneRules::resolve(&[(
+            toml::from_str("[[rule]]\nname = \"env-files\"\nseverity = \"fatal\"").unwrap(),
+            "test".to_string(),
+        )]);
+        assert!(bad_severity.is_err());
+        assert!(toml::from_str::<RuleFile>("[[rule]]\nnme = \"typo\"").is_err());
+    }
+}
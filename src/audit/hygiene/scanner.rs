@@ -1,7 +1,7 @@
 //! Hygiene scanning logic
 
 use super::report::{HygieneStatus, HygieneViolation, ViolationType};
-use super::rules::{LARGE_FILE_THRESHOLD, UNIVERSAL_BAD_PATTERNS};
+use super::rules::HygieneRules;
 use crate::core::config::{GIT_OBJECTS_CHUNK_SIZE, LARGE_FILES_DISPLAY_LIMIT};
 use anyhow::Result;
 use std::path::Path;
@@ -35,6 +35,7 @@ async fn check_gitignore_violations(repo_path: &Path) -> Result<Vec<HygieneViola
                 file_path: line.to_string(),
                 violation_type: ViolationType::GitignoreViolation,
                 size_bytes: None,
+                rule: None,
             });
         }
     }
@@ -42,10 +43,13 @@ async fn check_gitignore_violations(repo_path: &Path) -> Result<Vec<HygieneViola
     Ok(violations)
 }
 
-/// Checks for universal bad patterns in tracked files
-async fn check_universal_patterns(repo_path: &Path) -> Result<Vec<HygieneViolation>> {
+/// Checks tracked files against the repository's hygiene rules
+async fn check_universal_patterns(
+    repo_path: &Path,
+    rules: &HygieneRules,
+) -> Result<Vec<HygieneViolation>> {
     let output = Command::new("git")
-        .arg("ls-files")
+        .args(["-c", "core.quotePath=false", "ls-files"])
         .current_dir(repo_path)
         .output()
         .await?;
@@ -66,25 +70,14 @@ async fn check_universal_patterns(repo_path: &Path) -> Result<Vec<HygieneViolati
             continue;
         }
 
-        // Check against universal bad patterns
-        for pattern in UNIVERSAL_BAD_PATTERNS {
-            let pattern_matches = if pattern.ends_with('/') {
-                line.starts_with(pattern) || line.contains(&format!("/{pattern}"))
-            } else if pattern.starts_with("*.") {
-                let extension = &pattern[1..]; // Remove *
-                line.ends_with(extension)
-            } else {
-                line == *pattern || line.contains(pattern)
-            };
-
-            if pattern_matches {
-                violations.push(HygieneViolation {
-                    file_path: line.to_string(),
-                    violation_type: ViolationType::UniversalBadPattern,
-                    size_bytes: None,
-                });
-                break; // Only report each file once
-            }
+        // Each file is reported once, under its most severe rule
+        if let Some(rule) = rules.check(line) {
+            violations.push(HygieneViolation {
+                file_path: line.to_string(),
+                violation_type: ViolationType::UniversalBadPattern,
+                size_bytes: None,
+                rule: Some(rule),
+            });
         }
     }
 
@@ -92,7 +85,7 @@ async fn check_universal_patterns(repo_path: &Path) -> Result<Vec<HygieneViolati
 }
 
 /// Checks for large files in git history
-async fn check_large_files(repo_path: &Path) -> Result<Vec<HygieneViolation>> {
+async fn check_large_files(repo_path: &Path, threshold: u64) -> Result<Vec<HygieneViolation>> {
     let output = Command::new("git")
         .args(["rev-list", "--objects", "--all"])
         .current_dir(repo_path)
@@ -145,11 +138,12 @@ async fn check_large_files(repo_path: &Path) -> Result<Vec<HygieneViolation>> {
             let Ok(size) = size.parse::<u64>() else {
                 continue;
             };
-            if size > LARGE_FILE_THRESHOLD && !file_path.is_empty() {
+            if size > threshold && !file_path.is_empty() {
                 violations.push(HygieneViolation {
                     file_path: file_path.to_string(),
                     violation_type: ViolationType::LargeFile,
                     size_bytes: Some(size),
+                    rule: None,
                 });
             }
         }
@@ -168,6 +162,17 @@ pub async fn check_repo_hygiene(
 ) -> (HygieneStatus, String, Vec<HygieneViolation>) {
     let mut all_violations = Vec::new();
 
+    let rules = match HygieneRules::load(Path::new("."), repo_path) {
+        Ok(rules) => rules,
+        Err(e) => {
+            return (
+                HygieneStatus::Error,
+                format!("hygiene rules: {e:#}"),
+                Vec::new(),
+            );
+        }
+    };
+
     // Check gitignore violations
     match check_gitignore_violations(repo_path).await {
         Ok(mut violations) => all_violations.append(&mut violations),
@@ -181,7 +186,7 @@ pub async fn check_repo_hygiene(
     }
 
     // Check universal bad patterns
-    match check_universal_patterns(repo_path).await {
+    match check_universal_patterns(repo_path, &rules).await {
         Ok(mut violations) => all_violations.append(&mut violations),
         Err(e) => {
             return (
@@ -193,7 +198,7 @@ pub async fn check_repo_hygiene(
     }
 
     // Check large files
-    match check_large_files(repo_path).await {
+    match check_large_files(repo_path, rules.large_file_threshold).await {
         Ok(mut violations) => all_violations.append(&mut violations),
         Err(e) => {
             return (
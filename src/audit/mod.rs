@@ -3,6 +3,9 @@
 //! These modules back the `repos audit` CLI. Prefer the CLI for normal use; the
 //! types remain public for integration tests and advanced automation.
 
+pub mod baseline;
 pub mod fixes;
+pub mod formats;
 pub mod hygiene;
+pub mod native;
 pub mod scanner;
@@ -2,19 +2,22 @@
 //!
 //! This module orchestrates the complete audit process including:
 //! - `TruffleHog` installation and verification
-//! - Secret scanning across repositories
+//! - Secret scanning across repositories with `TruffleHog` or the built-in
+//!   scanner (`--engine native`)
 //! - Repository hygiene checking integration
 //! - Statistical reporting and progress tracking
 
 use anyhow::{anyhow, Result};
 use serde_json;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
 
+use super::baseline::{fingerprint, Baseline, BASELINE_FILE};
 use super::hygiene::{process_hygiene_repositories, HygieneStatistics};
+use super::native::{self, NativeFinding};
 use crate::core::{
     create_generic_processing_context, init_command, init_command_quiet,
     set_terminal_title_and_flush, GenericProcessingContext, HYGIENE_CONCURRENT_LIMIT,
@@ -37,6 +40,8 @@ pub struct ReportedSecretFinding {
     pub detector: String,
     pub verified: bool,
     pub file: String,
+    /// Fingerprint of the secret, matched against `.repos-audit-baseline`
+    pub fingerprint: String,
 }
 
 /// Comprehensive statistics for `TruffleHog` scanning
@@ -51,6 +56,10 @@ pub struct TruffleStatistics {
     pub findings: Vec<ReportedSecretFinding>,
     pub failed_repos: Vec<(String, String)>, // (repo_name, error_message)
     pub scan_duration: Duration,
+    /// Every audited repository as (name, path), for per-repository reports
+    pub repositories: Vec<(String, String)>,
+    /// Findings accepted in a baseline; not counted in the totals above
+    pub baselined_findings: Vec<ReportedSecretFinding>,
 }
 
 impl TruffleStatistics {
@@ -83,11 +92,40 @@ impl TruffleStatistics {
                     detector: secret.detector_name.clone(),
                     verified: secret.verified,
                     file: secret.file_path.clone(),
+                    fingerprint: secret.fingerprint.clone(),
                 });
             }
         }
     }
 
+    /// Moves findings accepted in `baseline` out of the reported totals.
+    pub fn apply_baseline(&mut self, baseline: &Baseline) {
+        let (baselined, new): (Vec<_>, Vec<_>) = std::mem::take(&mut self.findings)
+            .into_iter()
+            .partition(|finding| baseline.contains(finding));
+        for finding in &baselined {
+            self.total_secrets -= 1;
+            if finding.verified {
+                self.verified_secrets -= 1;
+            } else {
+                self.unverified_secrets -= 1;
+            }
+            if let Some(count) = self.secrets_by_detector.get_mut(&finding.detector) {
+                *count -= 1;
+                if *count == 0 {
+                    self.secrets_by_detector.remove(&finding.detector);
+                }
+            }
+        }
+        self.repos_with_secrets = new
+            .iter()
+            .map(|finding| finding.repository.as_str())
+            .collect::<HashSet<_>>()
+            .len() as u32;
+        self.findings = new;
+        self.baselined_findings.extend(baselined);
+    }
+
     pub fn add_repo_failure(&mut self, repo_name: &str, error: &str) {
         self.total_repos_scanned += 1;
         self.failed_repos
@@ -96,6 +134,15 @@ impl TruffleStatistics {
 
     #[must_use]
     pub fn generate_summary(&self) -> String {
+        let summary = self.generate_new_findings_summary();
+        if self.baselined_findings.is_empty() {
+            summary
+        } else {
+            format!("{summary} • {} baselined", self.baselined_findings.len())
+        }
+    }
+
+    fn generate_new_findings_summary(&self) -> String {
         let duration_secs = self.scan_duration.as_secs_f64();
 
         if self.verified_secrets > 0 {
@@ -144,6 +191,16 @@ impl TruffleStatistics {
                 report.push(String::new());
             }
 
+            if !self.baselined_findings.is_empty() {
+                report.push(format!("⚪ BASELINED ({})", self.baselined_findings.len()));
+                report.push(format!(
+                    "   Accepted in {BASELINE_FILE}; not counted above."
+                ));
+                append_findings(&mut report, &self.baselined_findings, true);
+                append_findings(&mut report, &self.baselined_findings, false);
+                report.push(String::new());
+            }
+
             if !self.secrets_by_detector.is_empty() {
                 report.push("📊 SECRETS BY TYPE".to_string());
                 let mut detectors: Vec<_> = self.secrets_by_detector.iter().collect();
@@ -178,6 +235,14 @@ impl TruffleStatistics {
                 .then_with(|| left.detector.cmp(&right.detector))
         });
 
+        let mut baselined_findings = self.baselined_findings.clone();
+        baselined_findings.sort_by(|left, right| {
+            left.repository
+                .cmp(&right.repository)
+                .then_with(|| left.file.cmp(&right.file))
+                .then_with(|| left.detector.cmp(&right.detector))
+        });
+
         let mut failed_repos = self.failed_repos.clone();
         failed_repos.sort();
 
@@ -188,9 +253,11 @@ impl TruffleStatistics {
                 "total_secrets": self.total_secrets,
                 "verified_secrets": self.verified_secrets,
                 "unverified_secrets": self.unverified_secrets,
+                "baselined_secrets": baselined_findings.len(),
                 "scan_duration_seconds": self.scan_duration.as_secs_f64()
             },
             "findings": findings,
+            "baselined_findings": baselined_findings,
             "secrets_by_detector": self.secrets_by_detector,
             "failed_repos": failed_repos.into_iter().map(|(repository, error)| {
                 serde_json::json!({
@@ -222,17 +289,51 @@ fn append_findings(report: &mut Vec<String>, findings: &[ReportedSecretFinding],
     }
 }
 
-/// Individual secret finding from `TruffleHog`
+/// Individual secret finding from `TruffleHog` or the native scanner
 #[derive(Debug, Clone)]
 pub struct SecretFinding {
     pub detector_name: String,
     pub verified: bool,
     pub file_path: String,
+    /// See [`fingerprint`]
+    pub fingerprint: String,
+}
+
+impl From<NativeFinding> for SecretFinding {
+    fn from(finding: NativeFinding) -> Self {
+        Self {
+            detector_name: finding.detector.to_string(),
+            verified: false,
+            file_path: finding.file,
+            fingerprint: fingerprint(&finding.secret),
+        }
+    }
+}
+
+/// Secret scanner selected with `repos audit --engine`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SecretEngine {
+    /// Run `TruffleHog` (installed separately or with `--install-tools`).
+    #[default]
+    Trufflehog,
+    /// Built-in rules; works offline and never verifies findings.
+    Native,
 }
 
-/// Runs complete `TruffleHog` secret scanning and hygiene checking
+impl SecretEngine {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Trufflehog => "trufflehog",
+            Self::Native => "native",
+        }
+    }
+}
+
+/// Runs secret scanning with the selected engine plus hygiene checking
 /// Returns (`truffle_stats`, `hygiene_stats`)
 pub async fn run_truffle_scan(
+    engine: SecretEngine,
     install_tools: bool,
     verify: bool,
     json: bool,
@@ -286,17 +387,18 @@ pub async fn run_truffle_scan(
     }
 
     // Install TruffleHog if requested and not already installed
-    if install_tools {
+    if install_tools && engine == SecretEngine::Trufflehog {
         ensure_trufflehog_installed().await?;
     }
 
     // Check if TruffleHog is available
-    if !is_trufflehog_installed().await {
+    if engine == SecretEngine::Trufflehog && !is_trufflehog_installed().await {
         return Err(anyhow!(
             "TruffleHog is not installed. Please install it or use --install-tools:\n\
              brew install trufflesecurity/trufflehog/trufflehog (macOS)\n\
              Install a trusted TruffleHog release for your platform (Linux)\n\
-             Or use: repos audit --install-tools"
+             Or use: repos audit --install-tools\n\
+             Or scan offline with the built-in rules: repos audit --engine native"
         ));
     }
 
@@ -312,7 +414,7 @@ pub async fn run_truffle_scan(
     )?;
 
     // Run TruffleHog scanning
-    let truffle_stats = run_truffle_scanning(truffle_context, verify).await?;
+    let truffle_stats = run_truffle_scanning(truffle_context, engine, verify).await?;
 
     // Create processing context for hygiene checking
     let hygiene_context = create_generic_processing_context(
@@ -329,6 +431,14 @@ pub async fn run_truffle_scan(
     let final_truffle_stats = {
         let mut stats = truffle_stats;
         stats.scan_duration = start_time.elapsed();
+        stats.repositories = repos_arc
+            .iter()
+            .map(|(name, path)| (name.clone(), path.display().to_string()))
+            .collect();
+        stats.apply_baseline(&Baseline::load(
+            std::path::Path::new("."),
+            &stats.repositories,
+        )?);
         stats
     };
 
@@ -352,9 +462,10 @@ pub async fn run_truffle_scan(
     Ok((final_truffle_stats, hygiene_stats))
 }
 
-/// Process `TruffleHog` scanning across repositories
+/// Process secret scanning across repositories
 async fn run_truffle_scanning(
     context: GenericProcessingContext<TruffleStatistics>,
+    engine: SecretEngine,
     verify: bool,
 ) -> Result<TruffleStatistics> {
     use futures::stream::{FuturesUnordered, StreamExt};
@@ -383,8 +494,13 @@ async fn run_truffle_scanning(
             pb.set_prefix(format!("🟡 {repo_name:max_name_length$}"));
             pb.set_message("scanning secrets...");
 
-            // Run TruffleHog scan
-            match scan_repository_secrets(repo_path, verify).await {
+            let scan = match engine {
+                SecretEngine::Trufflehog => scan_repository_secrets(repo_path, verify).await,
+                SecretEngine::Native => native::scan_repository(repo_path)
+                    .await
+                    .map(|findings| findings.into_iter().map(SecretFinding::from).collect()),
+            };
+            match scan {
                 Ok(secrets) => {
                     let status_symbol = if secrets.iter().any(|s| s.verified) {
                         "🔴" // Verified secrets found
@@ -496,10 +612,17 @@ fn parse_truffle_findings(output: &[u8]) -> Result<Vec<SecretFinding>> {
             .unwrap_or("unknown")
             .to_string();
 
+        let raw = json["RawV2"]
+            .as_str()
+            .filter(|raw| !raw.is_empty())
+            .or_else(|| json["Raw"].as_str())
+            .unwrap_or_default();
+
         findings.push(SecretFinding {
             detector_name,
             verified,
             file_path,
+            fingerprint: fingerprint(raw),
         });
     }
 
@@ -2,43 +2,60 @@
 //!
 //! This module handles the audit command which performs:
 //! - Repository hygiene checking
-//! - Secret scanning with `TruffleHog`
+//! - Secret scanning with `TruffleHog` or the built-in rules
 //! - Automated fixing of detected issues
 
 use anyhow::Result;
 
 use crate::audit::{
+    baseline::update_baseline,
     fixes::{apply_fixes, FixResult},
+    formats::{junit_report, sarif_report, AuditFormat},
     hygiene::HygieneStatistics,
-    scanner::{run_truffle_scan, TruffleStatistics},
+    scanner::{run_truffle_scan, SecretEngine, TruffleStatistics},
 };
 use crate::core::{set_terminal_title, set_terminal_title_and_flush};
 
 /// Main handler for the audit command with fix capabilities
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_audit_command(
+    engine: SecretEngine,
     install_tools: bool,
     verify: bool,
-    json: bool,
+    format: AuditFormat,
     interactive: bool,
     fix_gitignore: bool,
     fix_large: bool,
     fix_secrets: bool,
     fix_all: bool,
     dry_run: bool,
+    update: bool,
     target_repos: Option<Vec<String>>,
 ) -> Result<()> {
-    if !json {
+    if verify && engine == SecretEngine::Native {
+        anyhow::bail!(
+            "--verify requires --engine trufflehog; the native engine cannot check whether secrets are live"
+        );
+    }
+
+    let structured = format != AuditFormat::Human;
+    if !structured {
         set_terminal_title("🚀 repos audit");
     }
 
-    // Run TruffleHog secret scanning
-    let (truffle_stats, hygiene_stats) =
-        run_truffle_scan(install_tools, verify, json, target_repos.clone()).await?;
+    // Run secret scanning
+    let (truffle_stats, hygiene_stats) = run_truffle_scan(
+        engine,
+        install_tools,
+        verify,
+        structured,
+        target_repos.clone(),
+    )
+    .await?;
 
     if !truffle_stats.failed_repos.is_empty() || hygiene_stats.error_count() > 0 {
-        if json {
-            print_audit_json(&truffle_stats, &hygiene_stats, None, dry_run)?;
+        if structured {
+            print_audit_report(format, &truffle_stats, &hygiene_stats, None, dry_run)?;
         }
         anyhow::bail!(
             "audit incomplete: {} secret scans and {} hygiene scans failed",
@@ -47,11 +64,23 @@ pub async fn handle_audit_command(
         );
     }
 
+    if update {
+        for (path, count) in update_baseline(std::path::Path::new("."), &truffle_stats)? {
+            if !structured {
+                let noun = if count == 1 { "finding" } else { "findings" };
+                println!("📌 Baseline: {count} accepted {noun} in {}", path.display());
+            }
+        }
+    }
+
     // If any fix options are specified, apply them
     let mut fix_results = None;
     if interactive || fix_gitignore || fix_large || fix_secrets || fix_all {
         let fix_options = if fix_all {
-            crate::audit::fixes::FixOptions::fix_all(dry_run, target_repos.clone())
+            crate::audit::fixes::FixOptions {
+                engine,
+                ..crate::audit::fixes::FixOptions::fix_all(dry_run, target_repos.clone())
+            }
         } else {
             crate::audit::fixes::FixOptions {
                 interactive,
@@ -62,6 +91,7 @@ pub async fn handle_audit_command(
                 dry_run,
                 skip_confirm: false,
                 target_repos: target_repos.clone(),
+                engine,
             }
         };
 
@@ -72,8 +102,9 @@ pub async fn handle_audit_command(
             .count();
         fix_results = Some(results);
         if failed_fixes > 0 {
-            if json {
-                print_audit_json(
+            if structured {
+                print_audit_report(
+                    format,
                     &truffle_stats,
                     &hygiene_stats,
                     fix_results.as_deref(),
@@ -84,8 +115,9 @@ pub async fn handle_audit_command(
         }
     }
 
-    if json {
-        print_audit_json(
+    if structured {
+        print_audit_report(
+            format,
             &truffle_stats,
             &hygiene_stats,
             fix_results.as_deref(),
@@ -93,7 +125,7 @@ pub async fn handle_audit_command(
         )?;
     }
 
-    if !json {
+    if !structured {
         set_terminal_title_and_flush("✅ repos");
     }
 
@@ -105,6 +137,28 @@ pub async fn handle_audit_command(
     Ok(())
 }
 
+fn print_audit_report(
+    format: AuditFormat,
+    truffle_stats: &TruffleStatistics,
+    hygiene_stats: &HygieneStatistics,
+    fix_results: Option<&[FixResult]>,
+    dry_run: bool,
+) -> Result<()> {
+    match format {
+        AuditFormat::Human => Ok(()),
+        AuditFormat::Json => print_audit_json(truffle_stats, hygiene_stats, fix_results, dry_run),
+        AuditFormat::Sarif => {
+            let report = sarif_report(truffle_stats, hygiene_stats);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            Ok(())
+        }
+        AuditFormat::Junit => {
+            print!("{}", junit_report(truffle_stats, hygiene_stats));
+            Ok(())
+        }
+    }
+}
+
 fn print_audit_json(
     truffle_stats: &TruffleStatistics,
     hygiene_stats: &HygieneStatistics,
@@ -0,0 +1,348 @@
+//! Workspace bootstrap command implementation
+//!
+//! `repos bootstrap` (alias `repos clone`) reads the workspace manifest and
+//! clones every listed repository that is missing on disk, then reports
+//! repositories found on disk that the manifest does not list.
+
+use anyhow::Result;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::core::{
+    acquire_semaphore_permit, acquire_stats_lock, clean_error_message, create_footer_progress_bar,
+    create_processing_context, create_progress_bar, create_separator_progress_bar,
+    find_repos_from_path, format_relative_repo_path, set_terminal_title,
+    set_terminal_title_and_flush, BatchOperation, ManifestRepository, SyncStatistics,
+    WorkspaceManifest, MANIFEST_FILE_NAME,
+};
+use crate::git::failure::GitFailure;
+use crate::git::operations::run_git;
+use crate::git::remote::{context_from_url, policy_violation, RemoteDirection};
+use crate::git::Status;
+
+const RESET: &str = "\x1b[0m";
+const BOLD_PURPLE: &str = "\x1b[1;38;5;141m";
+const DIM: &str = "\x1b[2m";
+
+/// Handles `repos bootstrap`.
+pub async fn handle_bootstrap_command(
+    dry_run: bool,
+    jobs: Option<usize>,
+    sequential: bool,
+) -> Result<()> {
+    use crate::core::config::get_git_concurrency;
+
+    set_terminal_title("📦 repos bootstrap");
+    let start_time = std::time::Instant::now();
+
+    let Some(manifest) = WorkspaceManifest::load(Path::new("."))? else {
+        set_terminal_title_and_flush("✅ repos bootstrap");
+        anyhow::bail!(
+            "no {MANIFEST_FILE_NAME} found in current directory; list each repository with a path and url"
+        );
+    };
+
+    let manifest = Arc::new(manifest);
+    let repositories = manifest
+        .repositories()
+        .iter()
+        .map(|repository| {
+            (
+                repository.name().to_string(),
+                manifest.checkout_path(repository),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if repositories.is_empty() {
+        println!("\nNo repositories listed in {MANIFEST_FILE_NAME}.");
+        set_terminal_title_and_flush("✅ repos bootstrap");
+        return Ok(());
+    }
+
+    let total_repos = repositories.len();
+    let repo_word = if total_repos == 1 {
+        "repository"
+    } else {
+        "repositories"
+    };
+    let action = if dry_run {
+        "Planning bootstrap"
+    } else {
+        "Bootstrapping"
+    };
+    print!("\n📦 {action} {total_repos} {repo_word} from {MANIFEST_FILE_NAME}\n\n");
+
+    let context = match create_processing_context(
+        Arc::new(repositories),
+        start_time,
+        get_git_concurrency(jobs, sequential),
+    ) {
+        Ok(context) => context,
+        Err(e) => {
+            set_terminal_title_and_flush("✅ repos bootstrap");
+            return Err(e);
+        }
+    };
+
+    let result = process_bootstrap_repositories(context, Arc::clone(&manifest), dry_run).await;
+
+    let root = manifest.root().to_path_buf();
+    let discovered = tokio::task::spawn_blocking(move || find_repos_from_path(root))
+        .await
+        .unwrap_or_default();
+    let unmanaged = manifest.unmanaged(&discovered);
+    if !unmanaged.is_empty() {
+        println!("{}\n", format_unmanaged_section(&unmanaged));
+    }
+
+    set_terminal_title_and_flush("✅ repos bootstrap");
+    result
+}
+
+async fn process_bootstrap_repositories(
+    context: crate::core::ProcessingContext,
+    manifest: Arc<WorkspaceManifest>,
+    dry_run: bool,
+) -> Result<()> {
+    let operation = BatchOperation::Bootstrap { dry_run };
+
+    let mut progress_bars = Vec::new();
+    for (repo_name, _) in context.repositories.iter() {
+        let progress_bar =
+            create_progress_bar(&context.multi_progress, &context.progress_style, repo_name);
+        progress_bar.set_message(if dry_run { "planning..." } else { "cloning..." });
+        progress_bars.push(progress_bar);
+    }
+
+    let _separator_pb = create_separator_progress_bar(&context.multi_progress);
+    let footer_pb = create_footer_progress_bar(&context.multi_progress);
+    let initial_stats = SyncStatistics::new();
+    footer_pb
+        .set_message(initial_stats.generate_batch_live_summary(operation, context.total_repos));
+    let _separator_pb2 = create_separator_progress_bar(&context.multi_progress);
+
+    let max_name_length = context.max_name_length;
+    let start_time = context.start_time;
+    let total_repos = context.total_repos;
+
+    let mut futures = FuturesUnordered::new();
+    for (((repo_name, repo_path), repository), progress_bar) in context
+        .repositories
+        .iter()
+        .zip(manifest.repositories())
+        .zip(progress_bars)
+    {
+        let semaphore = Arc::clone(&context.semaphore);
+        let stats = Arc::clone(&context.statistics);
+        let footer = footer_pb.clone();
+        let root = manifest.root();
+
+        let future = async move {
+            let _permit = acquire_semaphore_permit(&semaphore).await;
+
+            let (status, message, failure) =
+                clone_one_repo(root, repository, repo_path, dry_run).await;
+
+            progress_bar.set_prefix(format!(
+                "{} {:width$}",
+                status.symbol(),
+                repo_name,
+                width = max_name_length
+            ));
+            progress_bar.set_message(format!("{:<12}   {}", status.text(), message));
+            progress_bar.finish();
+
+            let stats_guard = acquire_stats_lock(&stats);
+            stats_guard.update_with_failure(
+                repo_name,
+                &repo_path.to_string_lossy(),
+                &status,
+                &message,
+                false,
+                failure.as_ref(),
+            );
+            footer.set_message(stats_guard.generate_batch_live_summary(operation, total_repos));
+        };
+
+        futures.push(future);
+    }
+
+    while futures.next().await.is_some() {}
+
+    footer_pb.finish();
+
+    let final_stats = acquire_stats_lock(&context.statistics);
+    println!(
+        "\n{}\n",
+        final_stats.generate_batch_report(operation, start_time.elapsed())
+    );
+
+    let error_count = final_stats
+        .error_repos
+        .load(std::sync::atomic::Ordering::Relaxed);
+    drop(final_stats);
+    if error_count > 0 {
+        anyhow::bail!("{error_count} repositories failed to clone");
+    }
+
+    Ok(())
+}
+
+/// Clones a single manifest entry unless its checkout already exists.
+async fn clone_one_repo(
+    root: &Path,
+    repository: &ManifestRepository,
+    target: &Path,
+    dry_run: bool,
+) -> (Status, String, Option<GitFailure>) {
+    if target.join(".git").exists() {
+        return (Status::Synced, "already cloned".to_string(), None);
+    }
+    if target
+        .read_dir()
+        .is_ok_and(|mut entries| entries.next().is_some())
+    {
+        return (
+            Status::Skip,
+            "path exists and is not a git repository".to_string(),
+            None,
+        );
+    }
+
+    let remote = context_from_url("origin", RemoteDirection::Fetch, &repository.url);
+    match policy_violation(std::slice::from_ref(&remote)) {
+        Ok(Some(violation)) => {
+            let failure = GitFailure::from_policy(violation);
+            return (Status::Error, failure.reason(), Some(failure));
+        }
+        Ok(None) => {}
+        Err(e) => return (Status::Error, e.to_string(), None),
+    }
+
+    let source = remote
+        .identity
+        .clone()
+        .unwrap_or_else(|| remote.transport.label().to_string());
+    let branch_suffix = repository
+        .branch
+        .as_deref()
+        .map(|branch| format!(" ({branch})"))
+        .unwrap_or_default();
+
+    if dry_run {
+        return (
+            Status::Cloned,
+            format!("would clone from {source}{branch_suffix}"),
+            None,
+        );
+    }
+
+    if let Some(parent) = target.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            return (
+                Status::Error,
+                format!("could not create {}: {e}", parent.display()),
+                None,
+            );
+        }
+    }
+
+    let target_arg = target.to_string_lossy();
+    let mut args = vec!["clone"];
+    if let Some(branch) = repository.branch.as_deref() {
+        args.extend(["--branch", branch]);
+    }
+    args.extend(["--", repository.url.as_str(), target_arg.as_ref()]);
+
+    match run_git(root, &args).await {
+        Ok((true, _, _)) => (
+            Status::Cloned,
+            format!("cloned from {source}{branch_suffix}"),
+            None,
+        ),
+        Ok((false, _, stderr)) => (Status::Error, clean_error_message(&stderr), None),
+        Err(e) => (Status::Error, format!("clone failed: {e}"), None),
+    }
+}
+
+fn format_unmanaged_section(unmanaged: &[(String, PathBuf)]) -> String {
+    let mut lines = vec![format!("{BOLD_PURPLE}▌ Not in {MANIFEST_FILE_NAME}{RESET}")];
+    for (name, path) in unmanaged {
+        lines.push(format!("  {DIM}·{RESET} {name}"));
+        lines.push(format!(
+            "    ↳ path: {}",
+            format_relative_repo_path(&path.to_string_lossy())
+        ));
+    }
+    lines.push(format!(
+        "    ↳ next: add these to {MANIFEST_FILE_NAME} or remove them from the workspace"
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(url: &str) -> ManifestRepository {
+        ManifestRepository {
+            path: "lib".to_string(),
+            url: url.to_string(),
+            branch: None,
+            groups: Vec::new(),
+            tags: Vec::new(),
+            registry: None,
+            tag_template: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn skips_existing_directories_that_are_not_repositories() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("lib");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::write(target.join("notes.txt"), "local").unwrap();
+
+        let (status, message, _) = clone_one_repo(
+            temp.path(),
+            &entry("git@github.com:o/lib.git"),
+            &target,
+            false,
+        )
+        .await;
+
+        assert_eq!(status, Status::Skip);
+        assert!(message.contains("not a git repository"));
+    }
+
+    #[tokio::test]
+    async fn dry_run_plans_clone_without_exposing_credentials() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("lib");
+
+        let (status, message, _) = clone_one_repo(
+            temp.path(),
+            &entry("git@github.com:acme/lib.git"),
+            &target,
+            true,
+        )
+        .await;
+
+        assert_eq!(status, Status::Cloned);
+        assert_eq!(message, "would clone from github.com/acme/lib.git");
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn unmanaged_section_lists_each_repository() {
+        let section =
+            format_unmanaged_section(&[("scratch".to_string(), PathBuf::from("scratch"))]);
+
+        assert!(section.contains("Not in repos.toml"));
+        assert!(section.contains("scratch"));
+        assert!(section.contains("path: ./scratch"));
+    }
+}
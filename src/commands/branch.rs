@@ -0,0 +1,349 @@
+//! Fleet-wide branch management.
+//!
+//! `repos switch` checks out (or creates) the same branch everywhere, skipping
+//! dirty worktrees the way `repos pull` does. `repos branch --list` shows which
+//! repositories have a branch, and `repos prune` deletes local branches that
+//! are already merged into the remote default branch. Switches and prunes are
+//! journaled so `repos undo` can return to the previous branch or recreate the
+//! deleted ones.
+
+use anyhow::Result;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::core::settings::settings;
+use crate::core::{
+    acquire_semaphore_permit, acquire_stats_lock, clean_error_message, create_footer_progress_bar,
+    create_processing_context, create_progress_bar, create_separator_progress_bar, init_command,
+    report_no_repositories, set_terminal_title, set_terminal_title_and_flush, BatchOperation,
+    DeletedBranch, HeadState, JournalEntry, JournalRecorder, RestoreMode, SyncStatistics,
+};
+use crate::git::branch::{
+    current_branch, find_branches, list_remotes, merged_branches, remote_default_branch,
+};
+use crate::git::operations::run_git;
+use crate::git::{has_uncommitted_changes, Status};
+
+const SCANNING_MESSAGE: &str = "🔍 Scanning for git repositories...";
+
+/// Handles `repos switch <branch> [--create]`.
+pub async fn handle_switch_command(branch: String, create: bool) -> Result<()> {
+    let operation = BatchOperation::Switch;
+    let journal = Arc::new(JournalRecorder::new(operation.command(), RestoreMode::Keep));
+    let branch = Arc::new(branch);
+    let recorder = Arc::clone(&journal);
+    let result = run_batch(
+        operation,
+        format!("Switching to {branch}"),
+        "switching...",
+        move |name, path| {
+            let branch = Arc::clone(&branch);
+            let journal = Arc::clone(&recorder);
+            async move {
+                journal
+                    .track(&name, &path, switch_one_repo(&path, &branch, create))
+                    .await
+            }
+        },
+    )
+    .await;
+    journal.finish();
+    result
+}
+
+/// Handles `repos branch --list <pattern>`.
+pub async fn handle_branch_list_command(pattern: String) -> Result<()> {
+    let pattern = Arc::new(pattern);
+    run_batch(
+        BatchOperation::BranchList,
+        format!("Looking for {pattern}"),
+        "checking...",
+        move |_, path| {
+            let pattern = Arc::clone(&pattern);
+            async move { list_one_repo(&path, &pattern).await }
+        },
+    )
+    .await
+}
+
+/// Handles `repos prune`.
+pub async fn handle_prune_command(dry_run: bool) -> Result<()> {
+    let action = if dry_run {
+        "Planning prune"
+    } else {
+        "Pruning merged branches"
+    };
+    let operation = BatchOperation::Prune { dry_run };
+    let journal = Arc::new(JournalRecorder::new(operation.command(), RestoreMode::Keep));
+    let recorder = Arc::clone(&journal);
+    let result = run_batch(
+        operation,
+        action.to_string(),
+        if dry_run { "planning..." } else { "pruning..." },
+        move |name, path| {
+            let journal = Arc::clone(&recorder);
+            async move { prune_one_repo(&name, &path, dry_run, &journal).await }
+        },
+    )
+    .await;
+    journal.finish();
+    result
+}
+
+/// Runs one operation per repository with the shared progress display and
+/// finishes with the operation's batch report.
+pub(super) async fn run_batch<F, Fut>(
+    operation: BatchOperation,
+    action: String,
+    running_message: &'static str,
+    run: F,
+) -> Result<()>
+where
+    F: Fn(String, PathBuf) -> Fut,
+    Fut: Future<Output = (Status, String)>,
+{
+    let command = operation.command();
+    let running_title = format!("🌿 repos {command}");
+    let done_title = format!("✅ repos {command}");
+    set_terminal_title(&running_title);
+
+    let (start_time, repos) = init_command(SCANNING_MESSAGE).await;
+    if repos.is_empty() {
+        report_no_repositories(command, start_time);
+        set_terminal_title_and_flush(&done_title);
+        return Ok(());
+    }
+
+    let total_repos = repos.len();
+    let repo_word = if total_repos == 1 {
+        "repository"
+    } else {
+        "repositories"
+    };
+    print!("\r🌿 {action} in {total_repos} {repo_word}                    \n\n");
+
+    let context = match create_processing_context(
+        Arc::new(repos),
+        start_time,
+        settings().git_concurrency.value,
+    ) {
+        Ok(context) => context,
+        Err(e) => {
+            set_terminal_title_and_flush(&done_title);
+            return Err(e);
+        }
+    };
+
+    let mut progress_bars = Vec::new();
+    for (repo_name, _) in context.repositories.iter() {
+        let progress_bar =
+            create_progress_bar(&context.multi_progress, &context.progress_style, repo_name);
+        progress_bar.set_message(running_message);
+        progress_bars.push(progress_bar);
+    }
+
+    let _separator_pb = create_separator_progress_bar(&context.multi_progress);
+    let footer_pb = create_footer_progress_bar(&context.multi_progress);
+    let initial_stats = SyncStatistics::new();
+    footer_pb
+        .set_message(initial_stats.generate_batch_live_summary(operation, context.total_repos));
+    let _separator_pb2 = create_separator_progress_bar(&context.multi_progress);
+
+    let max_name_length = context.max_name_length;
+    let total_repos = context.total_repos;
+
+    let mut futures = FuturesUnordered::new();
+    for ((repo_name, repo_path), progress_bar) in context.repositories.iter().zip(progress_bars) {
+        let semaphore = Arc::clone(&context.semaphore);
+        let stats = Arc::clone(&context.statistics);
+        let footer = footer_pb.clone();
+        let repository_run = run(repo_name.clone(), repo_path.clone());
+
+        let future = async move {
+            let _permit = acquire_semaphore_permit(&semaphore).await;
+
+            let (status, message) = repository_run.await;
+
+            progress_bar.set_prefix(format!(
+                "{} {:width$}",
+                status.symbol(),
+                repo_name,
+                width = max_name_length
+            ));
+            progress_bar.set_message(format!("{:<12}   {}", status.text(), message));
+            progress_bar.finish();
+
+            let stats_guard = acquire_stats_lock(&stats);
+            stats_guard.update(
+                repo_name,
+                &repo_path.to_string_lossy(),
+                &status,
+                &message,
+                false,
+            );
+            footer.set_message(stats_guard.generate_batch_live_summary(operation, total_repos));
+        };
+
+        futures.push(future);
+    }
+
+    while futures.next().await.is_some() {}
+
+    footer_pb.finish();
+
+    let final_stats = acquire_stats_lock(&context.statistics);
+    final_stats.print_batch_report(operation, context.start_time.elapsed());
+
+    let error_count = final_stats
+        .error_repos
+        .load(std::sync::atomic::Ordering::Relaxed);
+    drop(final_stats);
+    set_terminal_title_and_flush(&done_title);
+    if error_count > 0 {
+        anyhow::bail!("{error_count} repositories failed to {command}");
+    }
+
+    Ok(())
+}
+
+async fn switch_one_repo(repo_path: &Path, branch: &str, create: bool) -> (Status, String) {
+    match current_branch(repo_path).await {
+        Ok(Some(current)) if current == branch => {
+            return (Status::Synced, format!("already on {branch}"));
+        }
+        Ok(_) => {}
+        Err(e) => return (Status::Error, format!("branch check failed: {e}")),
+    }
+
+    match has_uncommitted_changes(repo_path).await {
+        Ok(true) => {
+            return (
+                Status::Skip,
+                "dirty worktree; commit or stash before switch".to_string(),
+            );
+        }
+        Ok(false) => {}
+        Err(e) => return (Status::Error, format!("status failed: {e}")),
+    }
+
+    let matches = match find_branches(repo_path, branch).await {
+        Ok(matches) => matches,
+        Err(e) => return (Status::Error, format!("branch lookup failed: {e}")),
+    };
+
+    let (args, message) = if matches.local.iter().any(|local| local == branch) {
+        (vec!["switch", branch], format!("switched to {branch}"))
+    } else if let Some(remote) = matches.remote.first() {
+        (
+            vec!["switch", "--track", remote.as_str()],
+            format!("switched to {branch}, tracking {remote}"),
+        )
+    } else if create {
+        (vec!["switch", "-c", branch], format!("created {branch}"))
+    } else {
+        return (Status::Skip, format!("no branch {branch}"));
+    };
+
+    match run_git(repo_path, &args).await {
+        Ok((true, _, _)) => (Status::Switched, message),
+        Ok((false, _, stderr)) => (Status::Error, clean_error_message(&stderr)),
+        Err(e) => (Status::Error, format!("switch failed: {e}")),
+    }
+}
+
+async fn list_one_repo(repo_path: &Path, pattern: &str) -> (Status, String) {
+    let matches = match find_branches(repo_path, pattern).await {
+        Ok(matches) => matches,
+        Err(e) => return (Status::Error, format!("branch lookup failed: {e}")),
+    };
+    if matches.is_empty() {
+        return (Status::NoChanges, format!("no branch matching {pattern}"));
+    }
+
+    let current = current_branch(repo_path).await.ok().flatten();
+    let mut names = matches
+        .local
+        .iter()
+        .map(|branch| {
+            if current.as_deref() == Some(branch.as_str()) {
+                format!("{branch} (current)")
+            } else {
+                branch.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    names.extend(matches.remote);
+    (Status::Synced, names.join(", "))
+}
+
+async fn prune_one_repo(
+    repo_name: &str,
+    repo_path: &Path,
+    dry_run: bool,
+    journal: &JournalRecorder,
+) -> (Status, String) {
+    let remotes = match list_remotes(repo_path).await {
+        Ok(remotes) if remotes.is_empty() => {
+            return (Status::NoRemote, "no remote configured".to_string());
+        }
+        Ok(remotes) => remotes,
+        Err(e) => return (Status::Error, format!("remote lookup failed: {e}")),
+    };
+    let base = match remote_default_branch(repo_path, &remotes).await {
+        Ok(Some(base)) => base,
+        Ok(None) => {
+            return (Status::Skip, "remote default branch unknown".to_string());
+        }
+        Err(e) => return (Status::Error, format!("default branch lookup failed: {e}")),
+    };
+
+    let merged = match merged_branches(repo_path, &base).await {
+        Ok(merged) => merged,
+        Err(e) => return (Status::Error, format!("merge check failed: {e}")),
+    };
+    if merged.is_empty() {
+        return (Status::NoChanges, format!("no branches merged into {base}"));
+    }
+
+    if dry_run {
+        return (
+            Status::Pruned,
+            format!("would delete {} (merged into {base})", merged.join(", ")),
+        );
+    }
+
+    // Remember each tip so `repos undo` can recreate the branches.
+    let mut deleted = Vec::with_capacity(merged.len());
+    for name in &merged {
+        let refname = format!("refs/heads/{name}");
+        match run_git(repo_path, &["rev-parse", "--verify", &refname]).await {
+            Ok((true, commit, _)) => deleted.push(DeletedBranch {
+                name: name.clone(),
+                commit,
+            }),
+            Ok((false, _, stderr)) => return (Status::Error, clean_error_message(&stderr)),
+            Err(e) => return (Status::Error, format!("branch lookup failed: {e}")),
+        }
+    }
+
+    // Merged into the remote default branch was checked above, so force the
+    // delete: `-d` would refuse branches that are not merged into HEAD.
+    let mut args = vec!["branch", "-D"];
+    args.extend(merged.iter().map(String::as_str));
+    match run_git(repo_path, &args).await {
+        Ok((true, _, _)) => {
+            let head = HeadState::read(repo_path).await.unwrap_or_default();
+            let mut entry = JournalEntry::new(repo_name, repo_path, head.clone(), head);
+            entry.deleted_branches = deleted;
+            journal.record(entry);
+            (
+                Status::Pruned,
+                format!("deleted {} (merged into {base})", merged.join(", ")),
+            )
+        }
+        Ok((false, _, stderr)) => (Status::Error, clean_error_message(&stderr)),
+        Err(e) => (Status::Error, format!("branch delete failed: {e}")),
+    }
+}
@@ -0,0 +1,298 @@
+//! `repos changelog`: release notes from conventional commits.
+//!
+//! For each package, commits since its last release tag (or `--since-tag`)
+//! are grouped into breaking changes, features, and fixes and prepended to
+//! its `CHANGELOG.md` under the version they suggest. Release tags follow the
+//! package's tag template, as `repos publish` creates them. `repos version
+//! bump auto` applies the same suggestion.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::core::manifest::WorkspaceManifest;
+use crate::core::{
+    init_command, set_terminal_title, set_terminal_title_and_flush, NO_REPOS_MESSAGE,
+};
+use crate::package::changelog::{
+    has_section, release_notes, write_section, ReleaseNotes, CHANGELOG_FILE,
+};
+use crate::package::version::Version;
+use crate::package::{detect_manager, tag};
+
+const SCANNING_MESSAGE: &str = "🔍 Scanning for git repositories...";
+
+/// Options for `repos changelog`.
+pub struct ChangelogOptions {
+    /// Repository names to include; empty includes every repository.
+    pub targets: Vec<String>,
+    /// Start from this tag instead of each repository's last version tag
+    pub since_tag: Option<String>,
+    /// Print the sections instead of writing `CHANGELOG.md`
+    pub dry_run: bool,
+}
+
+/// One package's changelog and how its releases are tagged.
+struct ChangelogTarget {
+    /// Repository name, or `repo/package` for a workspace member.
+    label: String,
+    repo_path: PathBuf,
+    /// Directory holding the package and its `CHANGELOG.md`.
+    path: PathBuf,
+    name: String,
+    version: Option<String>,
+    workspace_member: bool,
+    tag_template: String,
+}
+
+/// Handles `repos changelog`.
+pub async fn handle_changelog_command(options: ChangelogOptions) -> Result<()> {
+    set_terminal_title("📝 repos");
+
+    let (_, mut repos) = init_command(SCANNING_MESSAGE).await;
+    if !options.targets.is_empty() {
+        repos.retain(|(name, _)| options.targets.contains(name));
+    }
+    if repos.is_empty() {
+        println!("\r{NO_REPOS_MESSAGE}");
+        set_terminal_title_and_flush("✅ repos");
+        return Ok(());
+    }
+    println!(
+        "\r📝 Collecting release notes for {} repositories\n",
+        repos.len()
+    );
+
+    let manifest = WorkspaceManifest::load(Path::new("."))?;
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let mut written = 0;
+    let mut failures = Vec::new();
+    for target in changelog_targets(&repos, manifest.as_ref()).await {
+        let name = &target.label;
+        let tag_match =
+            tag::match_pattern(&target.tag_template, &target.name, target.workspace_member);
+        let package_path = target
+            .path
+            .strip_prefix(&target.repo_path)
+            .ok()
+            .filter(|path| !path.as_os_str().is_empty());
+        let notes = match release_notes(
+            &target.repo_path,
+            options.since_tag.as_deref(),
+            &tag_match,
+            package_path,
+        )
+        .await
+        {
+            Ok(notes) => notes,
+            Err(e) => {
+                failures.push((name.clone(), e.to_string()));
+                continue;
+            }
+        };
+        let since = notes
+            .since_tag
+            .as_deref()
+            .map_or_else(|| "the first commit".to_string(), str::to_string);
+        if notes.is_empty() {
+            println!("  · {name:<30} no features or fixes since {since}");
+            continue;
+        }
+
+        let current = current_version(&target, &notes);
+        let heading = release_heading(&notes, current.as_ref(), &date);
+        println!(
+            "  📝 {name:<30} {} since {since}{}",
+            summarize(&notes),
+            notes
+                .suggested_bump(current.as_ref())
+                .map_or_else(String::new, |level| format!(
+                    " → {} ({})",
+                    heading_version(&heading),
+                    level.label()
+                ))
+        );
+
+        let section = notes.render(&heading);
+        if options.dry_run {
+            println!("\n{}", indent(&section));
+            continue;
+        }
+        let changelog_path = target.path.join(CHANGELOG_FILE);
+        let existing = std::fs::read_to_string(&changelog_path).ok();
+        let version = heading_version(&heading);
+        if existing
+            .as_deref()
+            .is_some_and(|existing| has_section(existing, version))
+        {
+            println!("      replacing the existing {version} section");
+        }
+        if let Err(e) = std::fs::write(
+            &changelog_path,
+            write_section(existing.as_deref(), version, &section),
+        ) {
+            failures.push((
+                name.clone(),
+                format!("failed to write {CHANGELOG_FILE}: {e}"),
+            ));
+            continue;
+        }
+        written += 1;
+    }
+
+    if options.dry_run {
+        println!("\nDry run - no changelogs written\n");
+    } else {
+        println!(
+            "\n✅ Updated {CHANGELOG_FILE} for {written} {}\n",
+            if written == 1 { "package" } else { "packages" }
+        );
+    }
+    set_terminal_title_and_flush("✅ repos");
+
+    if !failures.is_empty() {
+        for (repo, error) in &failures {
+            eprintln!("❌ {repo}: {error}");
+        }
+        anyhow::bail!("{} packages could not be summarized", failures.len());
+    }
+    Ok(())
+}
+
+/// Every package in the repositories, including workspace members; a
+/// repository without a recognized package is summarized on its own.
+async fn changelog_targets(
+    repos: &[(String, PathBuf)],
+    manifest: Option<&WorkspaceManifest>,
+) -> Vec<ChangelogTarget> {
+    let mut targets = Vec::new();
+    for (repo, repo_path) in repos {
+        let manager = detect_manager(repo_path).await;
+        let tag_template =
+            tag::template_for(manifest, repo_path, manager.as_ref().map(|m| m.name()));
+        let packages = match &manager {
+            Some(manager) => manager.list_packages(repo_path).await,
+            None => Vec::new(),
+        };
+        if packages.is_empty() {
+            targets.push(ChangelogTarget {
+                label: repo.clone(),
+                repo_path: repo_path.clone(),
+                path: repo_path.clone(),
+                name: repo.clone(),
+                version: None,
+                workspace_member: false,
+                tag_template,
+            });
+            continue;
+        }
+        let workspace_member = packages.len() > 1 || packages[0].0 != *repo_path;
+        for (path, info) in packages {
+            targets.push(ChangelogTarget {
+                label: if path == *repo_path {
+                    repo.clone()
+                } else {
+                    format!("{repo}/{}", info.name)
+                },
+                repo_path: repo_path.clone(),
+                path,
+                name: info.name,
+                version: Some(info.version),
+                workspace_member,
+                tag_template: tag_template.clone(),
+            });
+        }
+    }
+    targets
+}
+
+/// Version the notes start from: the tag's version, else the package's.
+fn current_version(target: &ChangelogTarget, notes: &ReleaseNotes) -> Option<Version> {
+    if let Some(version) = notes.since_tag.as_deref().and_then(|since| {
+        tag::version_in(
+            &target.tag_template,
+            &target.name,
+            target.workspace_member,
+            since,
+        )
+    }) {
+        if let Ok(version) = Version::parse(version) {
+            return Some(version);
+        }
+    }
+    Version::parse(target.version.as_deref()?).ok()
+}
+
+/// `1.3.0 - 2026-10-17` after a tag, the package's version for a first
+/// release, or `Unreleased` when no version is known.
+fn release_heading(notes: &ReleaseNotes, current: Option<&Version>, date: &str) -> String {
+    let next = match (&notes.since_tag, current) {
+        (Some(_), Some(current)) => notes
+            .suggested_bump(Some(current))
+            .and_then(|level| current.bump(level, None).ok()),
+        (None, Some(current)) => Some(current.clone()),
+        (_, None) => None,
+    };
+    next.map_or_else(
+        || "Unreleased".to_string(),
+        |version| format!("{version} - {date}"),
+    )
+}
+
+fn heading_version(heading: &str) -> &str {
+    heading.split(" - ").next().unwrap_or(heading)
+}
+
+fn summarize(notes: &ReleaseNotes) -> String {
+    let mut parts = Vec::new();
+    for (count, singular, plural) in [
+        (notes.breaking.len(), "breaking change", "breaking changes"),
+        (notes.features.len(), "feature", "features"),
+        (notes.fixes.len(), "fix", "fixes"),
+    ] {
+        if count > 0 {
+            parts.push(format!(
+                "{count} {}",
+                if count == 1 { singular } else { plural }
+            ));
+        }
+    }
+    parts.join(", ")
+}
+
+fn indent(section: &str) -> String {
+    section
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("    {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::changelog::ConventionalCommit;
+
+    #[test]
+    fn headings_name_the_suggested_release() {
+        let fix = ConventionalCommit::parse("abc", "fix: crash", "").unwrap();
+        let tagged = ReleaseNotes::from_commits(Some("v1.2.0".into()), vec![fix.clone()]);
+        let current = Version::parse("1.2.0").unwrap();
+        assert_eq!(
+            release_heading(&tagged, Some(&current), "2026-10-17"),
+            "1.2.1 - 2026-10-17"
+        );
+
+        let first = ReleaseNotes::from_commits(None, vec![fix]);
+        assert_eq!(
+            release_heading(&first, Some(&current), "2026-10-17"),
+            "1.2.0 - 2026-10-17"
+        );
+        assert_eq!(release_heading(&first, None, "2026-10-17"), "Unreleased");
+    }
+}
@@ -7,17 +7,20 @@ use anyhow::Result;
 use std::io::{self, Write};
 use std::path::PathBuf;
 
+use crate::core::settings::{settings, ConfigOrigin, ConfigPaths, Setting, Settings};
 use crate::core::{
     create_processing_context, init_command, set_terminal_title, set_terminal_title_and_flush,
-    BatchOperation, ProcessingContext, CONFIG_SYNCING_MESSAGE, GIT_CONCURRENT_CAP,
-    NO_REPOS_MESSAGE,
+    BatchOperation, ProcessingContext, CONFIG_SYNCING_MESSAGE, NO_REPOS_MESSAGE,
 };
+use crate::git::remote::git_config_transport_policy;
 use crate::git::{
     check_repo_config, get_current_user_config, get_global_user_config, validate_user_config,
     ConfigArgs, ConfigCommand, ConfigSource, PromptFn, UserConfig,
 };
 
 const SCANNING_MESSAGE: &str = "🔍 Scanning for git repositories...";
+// Longer values (such as directory lists) are not padded to keep origins readable
+const EFFECTIVE_VALUE_WIDTH: usize = 24;
 
 /// Shows interactive prompt for config selection when no arguments provided
 async fn show_config_selection_prompt() -> Result<Option<ConfigArgs>> {
@@ -276,15 +279,17 @@ pub async fn handle_config_command(args: ConfigArgs) -> Result<()> {
     println!();
 
     // Create processing context
-    let context =
-        match create_processing_context(std::sync::Arc::new(repos), start_time, GIT_CONCURRENT_CAP)
-        {
-            Ok(context) => context,
-            Err(e) => {
-                set_terminal_title_and_flush("✅ repos");
-                return Err(e);
-            }
-        };
+    let context = match create_processing_context(
+        std::sync::Arc::new(repos),
+        start_time,
+        settings().git_concurrency.value,
+    ) {
+        Ok(context) => context,
+        Err(e) => {
+            set_terminal_title_and_flush("✅ repos");
+            return Err(e);
+        }
+    };
 
     // Process all repositories concurrently for config sync
     process_config_repositories(context, resolved_args.command, target_config).await?;
@@ -411,3 +416,79 @@ async fn process_config_repositories(
 
     Ok(())
 }
+
+/// Handles `repos config show`, printing the resolved `repos` configuration.
+///
+/// With `effective`, each key is annotated with the layer that set it and the
+/// configuration files that were consulted are listed.
+pub fn handle_config_show_command(effective: bool) -> Result<()> {
+    let mut resolved = settings().clone();
+    if resolved.transport_policy.origin == ConfigOrigin::Default {
+        if let Some(Ok(policy)) = git_config_transport_policy() {
+            resolved.transport_policy = Setting {
+                value: Some(policy),
+                origin: ConfigOrigin::GitConfig,
+            };
+        }
+    }
+
+    if effective {
+        println!(
+            "{}",
+            format_effective_settings(&resolved, &ConfigPaths::discover())
+        );
+    } else {
+        println!("{}", format_settings(&resolved));
+    }
+    Ok(())
+}
+
+fn format_settings(settings: &Settings) -> String {
+    let mut lines = Vec::new();
+    let mut current_section = "";
+    for (key, value, _) in settings.entries() {
+        let (section, name) = key.split_once('.').unwrap_or(("", key));
+        if section != current_section {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{section}]"));
+            current_section = section;
+        }
+        lines.push(format!("{name} = {value}"));
+    }
+    lines.join("\n")
+}
+
+fn format_effective_settings(settings: &Settings, paths: &ConfigPaths) -> String {
+    let entries = settings.entries();
+    let key_width = entries
+        .iter()
+        .map(|(key, _, _)| key.len())
+        .max()
+        .unwrap_or(0);
+    let value_width = entries
+        .iter()
+        .map(|(_, value, _)| value.len())
+        .filter(|width| *width <= EFFECTIVE_VALUE_WIDTH)
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = entries
+        .iter()
+        .map(|(key, value, origin)| format!("{key:key_width$} = {value:value_width$}  # {origin}"))
+        .collect::<Vec<_>>();
+
+    lines.push(String::new());
+    lines.push("Sources (lowest to highest precedence):".to_string());
+    for (path, origin) in paths.layers() {
+        let state = if path.is_file() {
+            "loaded"
+        } else {
+            "not found"
+        };
+        lines.push(format!("  {origin}: {state}"));
+    }
+    lines.push("  env REPOS_<SECTION>_<KEY>, then --set KEY=VALUE".to_string());
+    lines.join("\n")
+}
@@ -0,0 +1,48 @@
+//! `repos discover`: list the repositories every fleet command operates on.
+//!
+//! Discovery goes through the on-disk cache, so this is also the quickest way
+//! to check whether the cache is current or to refresh it with `--rescan`.
+
+use anyhow::Result;
+
+use crate::core::{
+    apply_active_selection, discovery_cache_path, find_repos_cached, format_relative_repo_path,
+    rescan_requested, truncate_text, NO_REPOS_MESSAGE,
+};
+
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Handles `repos discover`.
+pub async fn handle_discover_command() -> Result<()> {
+    let rescan = rescan_requested();
+    let (repos, status) =
+        tokio::task::spawn_blocking(move || find_repos_cached(".", rescan)).await?;
+    let repos = apply_active_selection(repos);
+
+    if repos.is_empty() {
+        println!("{NO_REPOS_MESSAGE}");
+    }
+    for (name, path) in &repos {
+        println!(
+            "  {:24} {}",
+            truncate_text(name, 24),
+            format_relative_repo_path(&path.to_string_lossy())
+        );
+    }
+
+    let repo_word = if repos.len() == 1 {
+        "repository"
+    } else {
+        "repositories"
+    };
+    let cache = discovery_cache_path()
+        .map(|path| format!(" · {}", path.display()))
+        .unwrap_or_default();
+    println!(
+        "{DIM}{} {repo_word} · {}{cache}{RESET}",
+        repos.len(),
+        status.label()
+    );
+    Ok(())
+}
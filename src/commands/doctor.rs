@@ -3,15 +3,17 @@
 use anyhow::Result;
 use futures::stream::{FuturesUnordered, StreamExt};
 
+use crate::core::settings::settings;
 use crate::core::{
     acquire_semaphore_permit, clean_error_message, create_processing_context, init_command,
-    set_terminal_title, set_terminal_title_and_flush, GIT_CONCURRENT_CAP, NO_REPOS_MESSAGE,
+    set_terminal_title, set_terminal_title_and_flush, NO_REPOS_MESSAGE,
 };
 use crate::git::failure::GitFailure;
 use crate::git::operations::run_git;
 use crate::git::remote::{
-    context_from_url, inspect_remote, policy_violation, RemoteContext, RemoteDirection,
-    RemotePolicyViolation, RemoteTransport,
+    configured_urls, context_from_url, inspect_remote, policy_violation, transport_policy,
+    PolicyRule, RemoteContext, RemoteDirection, RemotePolicyViolation, RemoteTransport,
+    TransportPolicy,
 };
 use crate::utils::compare_repository_locations;
 
@@ -221,15 +223,17 @@ pub async fn handle_doctor_command() -> Result<()> {
     };
     print!("\r🩺 Diagnosing {total_repos} {repo_word}                    \n\n");
 
-    let context =
-        match create_processing_context(std::sync::Arc::new(repos), start_time, GIT_CONCURRENT_CAP)
-        {
-            Ok(context) => context,
-            Err(error) => {
-                set_terminal_title_and_flush("✅ repos doctor");
-                return Err(error);
-            }
-        };
+    let context = match create_processing_context(
+        std::sync::Arc::new(repos),
+        start_time,
+        settings().git_concurrency.value,
+    ) {
+        Ok(context) => context,
+        Err(error) => {
+            set_terminal_title_and_flush("✅ repos doctor");
+            return Err(error);
+        }
+    };
 
     let report = run_diagnostics(context).await;
     println!("\n{}\n", report.render(start_time.elapsed()));
@@ -405,6 +409,8 @@ async fn diagnose_remote(
     let fetch_urls = configured_urls(path, remote, RemoteDirection::Fetch).await;
     let push_urls = configured_urls(path, remote, RemoteDirection::Push).await;
     let explicit_push = push_urls.as_ref().is_ok_and(|urls| !urls.is_empty());
+    // HTTPS remotes are what an https-only fleet wants; don't suggest SSH.
+    let advise_ssh = !matches!(transport_policy(), Ok(TransportPolicy::HttpsOnly));
 
     let fetch = inspect_direction(
         path,
@@ -419,7 +425,7 @@ async fn diagnose_remote(
 
     let mut fetch_advisory = false;
     if let Ok(urls) = fetch_urls {
-        if !fetch.blocked {
+        if !fetch.blocked && advise_ssh {
             if let Some(url) = urls
                 .iter()
                 .find(|url| RemoteTransport::from_url(url).is_http())
@@ -452,7 +458,7 @@ async fn diagnose_remote(
 
     let mut push_advisory = fetch_advisory && !explicit_push;
     if let Ok(urls) = push_urls {
-        if !push.blocked {
+        if !push.blocked && advise_ssh {
             if let Some(url) = urls
                 .iter()
                 .find(|url| RemoteTransport::from_url(url).is_http())
@@ -478,7 +484,7 @@ async fn diagnose_remote(
         .contexts
         .iter()
         .find(|context| context.transport.is_http());
-    if !fetch.blocked && !fetch_advisory {
+    if !fetch.blocked && !fetch_advisory && advise_ssh {
         if let Some(context) = effective_fetch_http {
             diagnosis.advisories.push(http_advisory(
                 display_path,
@@ -488,7 +494,7 @@ async fn diagnose_remote(
             ));
         }
     }
-    if !push.blocked && !push_advisory {
+    if !push.blocked && !push_advisory && advise_ssh {
         if let Some(context) = push
             .contexts
             .iter()
@@ -583,24 +589,6 @@ async fn inspect_direction(
     }
 }
 
-async fn configured_urls(
-    path: &std::path::Path,
-    remote: &str,
-    direction: RemoteDirection,
-) -> std::result::Result<Vec<String>, ()> {
-    let suffix = if direction == RemoteDirection::Push {
-        "pushurl"
-    } else {
-        "url"
-    };
-    let key = format!("remote.{remote}.{suffix}");
-    match run_git(path, &["config", "--get-all", &key]).await {
-        Ok((true, urls, _)) => Ok(urls.lines().map(str::to_string).collect()),
-        Ok((false, _, stderr)) if stderr.trim().is_empty() => Ok(Vec::new()),
-        Ok((false, _, _)) | Err(_) => Err(()),
-    }
-}
-
 fn config_inspection_failure(
     display_path: &str,
     remote: &str,
@@ -643,7 +631,11 @@ fn http_advisory(
         "{} uses HTTP(S) for {scope}; convert to SSH to avoid credential prompts{skipped}",
         context.remote
     );
-    let next = GitFailure::from_policy(RemotePolicyViolation { context }).next_action(display_path);
+    let next = GitFailure::from_policy(RemotePolicyViolation {
+        context,
+        rule: PolicyRule::Transport(TransportPolicy::SshOnly),
+    })
+    .next_action(display_path);
     DoctorFinding::new(message, next)
 }
 
@@ -0,0 +1,373 @@
+//! Fleet-wide command execution.
+//!
+//! `repos exec -- <cmd>` runs one command in every selected repository with
+//! the shared concurrency limiter and progress bars. Each repository's exit
+//! code and captured stdout/stderr are reported, with failures grouped by
+//! project in the attention section.
+
+use anyhow::Result;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+use crate::core::config::get_git_concurrency;
+use crate::core::{
+    acquire_semaphore_permit, acquire_stats_lock, create_footer_progress_bar,
+    create_processing_context, create_progress_bar, create_separator_progress_bar, emit_start,
+    init_command, is_human_output, report_no_repositories, set_terminal_title,
+    set_terminal_title_and_flush, truncate_text, BatchOperation, CommandCapture, SyncStatistics,
+};
+use crate::git::Status;
+use crate::utils::shell_join;
+
+const SCANNING_MESSAGE: &str = "🔍 Scanning for git repositories...";
+
+// Longest stderr/stdout excerpt shown next to a failed repository
+const FAILURE_EXCERPT_LENGTH: usize = 80;
+
+/// Handles `repos exec -- <cmd>`.
+///
+/// A single argument is run through the platform shell (`sh -c`, or `cmd /C`
+/// on Windows) so pipes and `&&` work; several arguments are run directly.
+pub async fn handle_exec_command(
+    command: Vec<String>,
+    only_if_files: Vec<String>,
+    fail_fast: bool,
+    timeout_secs: Option<u64>,
+    jobs: Option<usize>,
+    sequential: bool,
+) -> Result<()> {
+    set_terminal_title("⚡ repos exec");
+
+    let command_line = if command.len() == 1 {
+        command[0].clone()
+    } else {
+        shell_join(&command)
+    };
+
+    let (start_time, repos) = init_command(SCANNING_MESSAGE).await;
+
+    if repos.is_empty() {
+        report_no_repositories("exec", start_time);
+        set_terminal_title_and_flush("✅ repos exec");
+        return Ok(());
+    }
+
+    let total_repos = repos.len();
+    let repo_word = if total_repos == 1 {
+        "repository"
+    } else {
+        "repositories"
+    };
+    if is_human_output() {
+        print!(
+            "\r⚡ Running `{command_line}` in {total_repos} {repo_word}                    \n\n"
+        );
+    }
+    emit_start("exec", total_repos);
+
+    let context = match create_processing_context(
+        Arc::new(repos),
+        start_time,
+        get_git_concurrency(jobs, sequential),
+    ) {
+        Ok(context) => context,
+        Err(e) => {
+            set_terminal_title_and_flush("✅ repos exec");
+            return Err(e);
+        }
+    };
+
+    let options = ExecOptions {
+        command: Arc::new(command),
+        only_if_files: Arc::new(only_if_files),
+        fail_fast,
+        timeout: timeout_secs.map(Duration::from_secs),
+    };
+    let result = process_exec_repositories(context, options, &command_line).await;
+
+    set_terminal_title_and_flush("✅ repos exec");
+    result
+}
+
+#[derive(Clone)]
+struct ExecOptions {
+    command: Arc<Vec<String>>,
+    only_if_files: Arc<Vec<String>>,
+    fail_fast: bool,
+    timeout: Option<Duration>,
+}
+
+async fn process_exec_repositories(
+    context: crate::core::ProcessingContext,
+    options: ExecOptions,
+    command_line: &str,
+) -> Result<()> {
+    let operation = BatchOperation::Exec;
+    acquire_stats_lock(&context.statistics).stream_events_as(operation.command());
+
+    let mut progress_bars = Vec::new();
+    for (repo_name, _) in context.repositories.iter() {
+        let progress_bar =
+            create_progress_bar(&context.multi_progress, &context.progress_style, repo_name);
+        progress_bar.set_message("running...");
+        progress_bars.push(progress_bar);
+    }
+
+    let _separator_pb = create_separator_progress_bar(&context.multi_progress);
+    let footer_pb = create_footer_progress_bar(&context.multi_progress);
+    let initial_stats = SyncStatistics::new();
+    footer_pb
+        .set_message(initial_stats.generate_batch_live_summary(operation, context.total_repos));
+    let _separator_pb2 = create_separator_progress_bar(&context.multi_progress);
+
+    let max_name_length = context.max_name_length;
+    let start_time = context.start_time;
+    let total_repos = context.total_repos;
+    let failed = Arc::new(AtomicBool::new(false));
+
+    let mut futures = FuturesUnordered::new();
+    for ((repo_name, repo_path), progress_bar) in context.repositories.iter().zip(progress_bars) {
+        let semaphore = Arc::clone(&context.semaphore);
+        let stats = Arc::clone(&context.statistics);
+        let footer = footer_pb.clone();
+        let options = options.clone();
+        let failed = Arc::clone(&failed);
+
+        let future = async move {
+            let _permit = acquire_semaphore_permit(&semaphore).await;
+
+            let (status, message, capture) = if options.fail_fast && failed.load(Ordering::Relaxed)
+            {
+                (
+                    Status::Skip,
+                    "not run after an earlier failure (--fail-fast)".to_string(),
+                    CommandCapture::default(),
+                )
+            } else {
+                exec_one_repo(repo_path, &options).await
+            };
+            if status == Status::Error {
+                failed.store(true, Ordering::Relaxed);
+            }
+
+            progress_bar.set_prefix(format!(
+                "{} {:width$}",
+                status.symbol(),
+                repo_name,
+                width = max_name_length
+            ));
+            progress_bar.set_message(format!("{:<12}   {}", status.text(), message));
+            progress_bar.finish();
+
+            let stats_guard = acquire_stats_lock(&stats);
+            stats_guard.update_with_capture(
+                repo_name,
+                &repo_path.to_string_lossy(),
+                &status,
+                &message,
+                capture,
+            );
+            footer.set_message(stats_guard.generate_batch_live_summary(operation, total_repos));
+        };
+
+        futures.push(future);
+    }
+
+    while futures.next().await.is_some() {}
+
+    footer_pb.finish();
+
+    let final_stats = acquire_stats_lock(&context.statistics);
+    final_stats.print_exec_report(command_line, start_time.elapsed());
+
+    let error_count = final_stats.error_repos.load(Ordering::Relaxed);
+    drop(final_stats);
+    if error_count > 0 {
+        anyhow::bail!("{error_count} repositories failed to run `{command_line}`");
+    }
+
+    Ok(())
+}
+
+/// Runs the command in one repository unless an `--only-if-file` predicate fails.
+async fn exec_one_repo(
+    repo_path: &Path,
+    options: &ExecOptions,
+) -> (Status, String, CommandCapture) {
+    if let Some(missing) = options
+        .only_if_files
+        .iter()
+        .find(|file| !repo_path.join(file).exists())
+    {
+        return (
+            Status::Skip,
+            format!("no {missing} (--only-if-file)"),
+            CommandCapture::default(),
+        );
+    }
+
+    let started = Instant::now();
+    let capture = run_command(repo_path, &options.command, options.timeout).await;
+    let elapsed = started.elapsed();
+
+    match capture {
+        Ok(capture) if capture.timed_out => {
+            let secs = options.timeout.unwrap_or_default().as_secs();
+            (Status::Error, format!("timed out after {secs}s"), capture)
+        }
+        Ok(capture) if capture.exit_code == Some(0) => (
+            Status::Executed,
+            format!("exit 0 in {:.1}s", elapsed.as_secs_f64()),
+            capture,
+        ),
+        Ok(capture) => {
+            let status = capture.exit_code.map_or_else(
+                || "terminated by signal".to_string(),
+                |code| format!("exit {code}"),
+            );
+            let message = failure_excerpt(&capture)
+                .map_or_else(|| status.clone(), |excerpt| format!("{status}: {excerpt}"));
+            (Status::Error, message, capture)
+        }
+        Err(e) => (
+            Status::Error,
+            format!("failed to start: {e}"),
+            CommandCapture::default(),
+        ),
+    }
+}
+
+async fn run_command(
+    repo_path: &Path,
+    command: &[String],
+    timeout: Option<Duration>,
+) -> std::io::Result<CommandCapture> {
+    let mut process = if command.len() == 1 {
+        shell_command(&command[0])
+    } else {
+        let mut process = Command::new(&command[0]);
+        process.args(&command[1..]);
+        process
+    };
+    process
+        .current_dir(repo_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let child = process.spawn()?;
+    let output = match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, child.wait_with_output()).await {
+            Ok(output) => output?,
+            // Dropping the timed-out future kills the child (kill_on_drop).
+            Err(_) => {
+                return Ok(CommandCapture {
+                    timed_out: true,
+                    ..CommandCapture::default()
+                })
+            }
+        },
+        None => child.wait_with_output().await?,
+    };
+
+    Ok(CommandCapture {
+        exit_code: output.status.code(),
+        timed_out: false,
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
+
+#[cfg(windows)]
+fn shell_command(command_line: &str) -> Command {
+    let mut process = Command::new("cmd");
+    process.args(["/C", command_line]);
+    process
+}
+
+#[cfg(not(windows))]
+fn shell_command(command_line: &str) -> Command {
+    let mut process = Command::new("sh");
+    process.args(["-c", command_line]);
+    process
+}
+
+/// Returns the last non-empty line of stderr (or stdout) for the failure summary.
+fn failure_excerpt(capture: &CommandCapture) -> Option<String> {
+    [&capture.stderr, &capture.stdout]
+        .into_iter()
+        .find_map(|stream| {
+            stream
+                .lines()
+                .rev()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+        })
+        .map(|line| truncate_text(line, FAILURE_EXCERPT_LENGTH))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn options(command: &[&str], only_if_files: &[&str]) -> ExecOptions {
+        ExecOptions {
+            command: Arc::new(command.iter().map(ToString::to_string).collect()),
+            only_if_files: Arc::new(only_if_files.iter().map(ToString::to_string).collect()),
+            fail_fast: false,
+            timeout: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn skips_repositories_missing_a_required_file() {
+        let temp = TempDir::new().unwrap();
+
+        let (status, message, _) =
+            exec_one_repo(temp.path(), &options(&["true"], &["package.json"])).await;
+
+        assert_eq!(status, Status::Skip);
+        assert_eq!(message, "no package.json (--only-if-file)");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn captures_exit_code_and_output_through_the_shell() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("package.json"), "{}").unwrap();
+
+        let (status, message, capture) = exec_one_repo(
+            temp.path(),
+            &options(&["echo out; echo broken >&2; exit 3"], &["package.json"]),
+        )
+        .await;
+
+        assert_eq!(status, Status::Error);
+        assert_eq!(message, "exit 3: broken");
+        assert_eq!(capture.exit_code, Some(3));
+        assert_eq!(capture.stdout, "out\n");
+        assert_eq!(capture.stderr, "broken\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn kills_commands_that_exceed_the_timeout() {
+        let temp = TempDir::new().unwrap();
+        let mut options = options(&["sleep", "5"], &[]);
+        options.timeout = Some(Duration::from_millis(100));
+
+        let (status, message, capture) = exec_one_repo(temp.path(), &options).await;
+
+        assert_eq!(status, Status::Error);
+        assert!(message.starts_with("timed out after"), "{message}");
+        assert!(capture.timed_out);
+        assert_eq!(capture.exit_code, None);
+    }
+}
@@ -1,7 +1,15 @@
 pub mod audit;
+pub mod bootstrap;
+pub mod branch;
+pub mod changelog;
 pub mod config;
+pub mod discover;
 pub mod doctor;
+pub mod exec;
 pub mod publish;
+pub mod remote;
 pub mod save;
 pub mod staging;
 pub mod sync;
+pub mod undo;
+pub mod version;
@@ -1,17 +1,26 @@
-use super::planner::PackageToPublish;
+use super::planner::{PackageToPublish, RegistryCheck};
+use super::preflight::{self, PreflightCheck};
+use super::state::{PackageState, PublishState, STATE_DIR};
 use crate::core::{
     clean_error_message, create_processing_context, create_progress_bar, format_relative_repo_path,
     truncate_text,
 };
-use crate::git::create_and_push_tag;
-use crate::package::PublishStatus;
+use crate::git::{create_tag, push_tag};
+use crate::package::changelog::tag_message;
+use crate::package::{PackSummary, PublishStatus};
 use crate::utils::compare_repository_locations;
 use futures::stream::{FuturesUnordered, StreamExt};
+use indicatif::ProgressBar;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const PUBLISHING_MESSAGE: &str = "publishing...";
+const PREFLIGHT_MESSAGE: &str = "pre-flight checks...";
+const PREFLIGHT_FAILED: &str = "pre-flight failed";
+const REGISTRY_WAIT_TIMEOUT_SECS: u64 = 300;
+const REGISTRY_POLL_INTERVAL_SECS: u64 = 5;
 const RESET: &str = "\x1b[0m";
 const BOLD_BLUE: &str = "\x1b[1;38;5;75m";
 const BOLD_PURPLE: &str = "\x1b[1;38;5;141m";
@@ -33,6 +42,8 @@ struct PublishOutcome {
     path: PathBuf,
     kind: PublishOutcomeKind,
     message: String,
+    /// What the pre-flight check packed.
+    packed: Option<String>,
 }
 
 /// Statistics for publish operations
@@ -42,7 +53,14 @@ struct PublishStatistics {
 }
 
 impl PublishStatistics {
-    fn update(&mut self, status: &PublishStatus, package: &str, path: &Path, message: &str) {
+    fn update(
+        &mut self,
+        status: &PublishStatus,
+        package: &str,
+        path: &Path,
+        message: &str,
+        packed: Option<&PackSummary>,
+    ) {
         let kind = match status {
             PublishStatus::Published => PublishOutcomeKind::Published,
             PublishStatus::AlreadyPublished => PublishOutcomeKind::AlreadyPublished,
@@ -54,6 +72,7 @@ impl PublishStatistics {
             path: path.to_path_buf(),
             kind,
             message: clean_error_message(message),
+            packed: packed.map(PackSummary::describe),
         });
     }
 
@@ -178,18 +197,152 @@ fn append_outcome_section(
             "    {DIM}↳ path: {}{RESET}",
             format_relative_repo_path(&outcome.path.to_string_lossy())
         ));
+        if let Some(packed) = &outcome.packed {
+            lines.push(format!("    {DIM}↳ packed: {packed}{RESET}"));
+        }
         if kind == PublishOutcomeKind::Failed {
+            let next = if outcome.message.starts_with(PREFLIGHT_FAILED) {
+                "fix the package build so it packs cleanly"
+            } else {
+                "inspect registry credentials/version"
+            };
             lines.push(format!(
-                "    {DIM}↳ next: inspect registry credentials/version, then retry `repos publish {}`{RESET}",
+                "    {DIM}↳ next: {next}, then retry `repos publish {}`{RESET}",
                 outcome.package
             ));
         }
     }
 }
 
+/// Polls the registry until it serves the version just published, so
+/// packages in the next wave can resolve it.
+async fn wait_for_registry(pkg: &PackageToPublish) -> Result<(), String> {
+    let Some(info) = &pkg.info else {
+        return Ok(());
+    };
+    let deadline = Instant::now() + Duration::from_secs(REGISTRY_WAIT_TIMEOUT_SECS);
+    let mut last_error = None;
+    loop {
+        match pkg
+            .manager
+            .is_version_published(&info.name, &info.version, pkg.target.as_ref())
+            .await
+        {
+            Ok(true) => return Ok(()),
+            Ok(false) => {}
+            Err(e) => last_error = Some(e.to_string()),
+        }
+        if Instant::now() >= deadline {
+            let reason = last_error.map_or_else(String::new, |error| format!(" ({error})"));
+            return Err(format!(
+                "{} v{} not visible on the registry after {REGISTRY_WAIT_TIMEOUT_SECS}s{reason}",
+                info.name, info.version
+            ));
+        }
+        tokio::time::sleep(Duration::from_secs(REGISTRY_POLL_INTERVAL_SECS)).await;
+    }
+}
+
+async fn publish_package(
+    pkg: &PackageToPublish,
+    progress: PackageState,
+    sign: bool,
+    state: &Mutex<PublishState>,
+) -> (PublishStatus, String) {
+    let record = |change: &dyn Fn(&mut PackageState)| {
+        state
+            .lock()
+            .expect("Mutex poisoned")
+            .record(&pkg.name, change);
+    };
+
+    // A resumed run skips packages the interrupted run already published,
+    // including ones it published without getting to record it.
+    let resumed = progress.published || pkg.registry == RegistryCheck::AlreadyPublished;
+    let (mut status, message) = if resumed {
+        (
+            PublishStatus::AlreadyPublished,
+            "published by the interrupted run".to_string(),
+        )
+    } else {
+        let (success, message) = pkg
+            .manager
+            .publish(&pkg.path, pkg.target.as_ref(), false)
+            .await;
+        let status = if !success {
+            PublishStatus::Error
+        } else if message.contains("already") {
+            PublishStatus::AlreadyPublished
+        } else {
+            PublishStatus::Published
+        };
+        (status, message)
+    };
+    if matches!(status, PublishStatus::Error) {
+        return (status, message);
+    }
+    // Versions the registry already had are not tagged by this run.
+    let tag_pending = resumed || matches!(status, PublishStatus::Published);
+    record(&|package| {
+        package.published = true;
+        if !tag_pending {
+            package.tag_name = None;
+        }
+    });
+
+    let mut final_message = message.clone();
+    if let Some(tag_name) = progress.tag_name.as_deref().filter(|_| tag_pending) {
+        if progress.pushed_tag {
+            return (status, final_message);
+        }
+        // A tag created by the interrupted run is pushed, not created again.
+        let existed = if progress.tagged {
+            None
+        } else {
+            // Signed tags are annotated with the version's changelog section.
+            let annotation = match (&pkg.info, sign) {
+                (Some(info), true) => Some(tag_message(&pkg.path, &info.name, &info.version).await),
+                _ => None,
+            };
+            match create_tag(&pkg.path, tag_name, annotation.as_deref()).await {
+                Ok(existed) => {
+                    record(&|package| package.tagged = true);
+                    Some(existed)
+                }
+                Err(tag_message) => {
+                    return (
+                        PublishStatus::Error,
+                        format!("{message}; tag failed: {tag_message}"),
+                    );
+                }
+            }
+        };
+        let (pushed, push_message) = push_tag(&pkg.path, tag_name).await;
+        if pushed {
+            record(&|package| package.pushed_tag = true);
+            let pushed_message = match existed {
+                Some(true) => format!("existing tag pushed {tag_name}"),
+                Some(false) => format!("tagged & pushed {tag_name}"),
+                None => push_message,
+            };
+            final_message = format!("{message}, {pushed_message}");
+        } else {
+            status = PublishStatus::Error;
+            final_message = format!("{message}; tag failed: {push_message}");
+        }
+    }
+
+    (status, final_message)
+}
+
+/// Publishes packages wave by wave in the planner's order. Packages within a
+/// wave publish concurrently; a package whose dependency failed or never
+/// appeared on the registry is reported as failed without being published,
+/// and one the planner found on the registry is reported without publishing.
+/// Each step is recorded in `state` so `repos publish --resume` can continue.
 pub async fn execute_publish(
     packages: Vec<PackageToPublish>,
-    tag: bool,
+    state: PublishState,
     start_time: std::time::Instant,
 ) -> anyhow::Result<()> {
     if packages.is_empty() {
@@ -197,6 +350,10 @@ pub async fn execute_publish(
     }
 
     let total_packages = packages.len();
+    let required: HashSet<String> = packages
+        .iter()
+        .flat_map(|pkg| pkg.depends_on.iter().cloned())
+        .collect();
 
     // Create processing context
     let repos_for_context: Vec<(String, PathBuf)> = packages
@@ -207,18 +364,23 @@ pub async fn execute_publish(
     let context = create_processing_context(
         std::sync::Arc::new(repos_for_context),
         start_time,
-        crate::core::GIT_CONCURRENT_CAP,
+        crate::core::settings::settings().git_concurrency.value,
     )?;
 
-    let mut futures = FuturesUnordered::new();
     let statistics = Arc::new(Mutex::new(PublishStatistics::default()));
+    let sign = state.options.sign;
+    let state = Arc::new(Mutex::new(state));
 
     // Create progress bars
     let mut repo_progress_bars = Vec::new();
-    for (repo_name, _) in context.repositories.iter() {
+    for ((repo_name, _), pkg) in context.repositories.iter().zip(&packages) {
         let progress_bar =
             create_progress_bar(&context.multi_progress, &context.progress_style, repo_name);
-        progress_bar.set_message(PUBLISHING_MESSAGE);
+        if pkg.wave == 0 {
+            progress_bar.set_message(PUBLISHING_MESSAGE);
+        } else {
+            progress_bar.set_message(format!("waiting for {}", pkg.depends_on.join(", ")));
+        }
         repo_progress_bars.push(progress_bar);
     }
 
@@ -231,65 +393,111 @@ pub async fn execute_publish(
     let max_name_length = context.max_name_length;
     let publish_semaphore = Arc::new(tokio::sync::Semaphore::new(8));
 
-    for ((pkg, progress_bar), _) in packages
-        .into_iter()
-        .zip(repo_progress_bars)
-        .zip(context.repositories.iter())
-    {
-        let stats_clone = Arc::clone(&statistics);
-        let semaphore_clone = Arc::clone(&publish_semaphore);
-        let footer_clone = footer_pb.clone();
-
-        let future = async move {
-            let _permit = semaphore_clone.acquire().await.expect("Semaphore closed");
-
-            let (success, message) = pkg.manager.publish(&pkg.path, false).await;
-
-            let mut status = if success {
-                if message.contains("already") {
-                    PublishStatus::AlreadyPublished
-                } else {
-                    PublishStatus::Published
-                }
-            } else {
-                PublishStatus::Error
+    let finish = |progress_bar: &ProgressBar,
+                  pkg: &PackageToPublish,
+                  status: &PublishStatus,
+                  message: &str| {
+        progress_bar.set_prefix(format!(
+            "{} {:width$}",
+            status.symbol(),
+            pkg.name,
+            width = max_name_length
+        ));
+        progress_bar.set_message(format!("{:<20}   {}", status.text(), message));
+        progress_bar.finish();
+
+        let packed = match &pkg.preflight {
+            PreflightCheck::Passed(summary) => Some(summary),
+            _ => None,
+        };
+        let mut stats_guard = statistics.lock().expect("Mutex poisoned");
+        stats_guard.update(status, &pkg.name, &pkg.path, message, packed);
+        footer_pb.set_message(stats_guard.generate_live_summary(total_packages));
+    };
+
+    // Why each package that others depend on cannot be relied on.
+    let mut blocked: HashMap<String, String> = HashMap::new();
+    let mut queue = packages.into_iter().zip(repo_progress_bars).peekable();
+
+    while let Some((first, _)) = queue.peek() {
+        let wave = first.wave;
+        let mut futures = FuturesUnordered::new();
+
+        while let Some((pkg, progress_bar)) = queue.next_if(|(pkg, _)| pkg.wave == wave) {
+            if let Some(reason) = pkg.depends_on.iter().find_map(|dep| blocked.get(dep)) {
+                let message = format!("not published: {reason}");
+                finish(&progress_bar, &pkg, &PublishStatus::Error, &message);
+                blocked.insert(pkg.name.clone(), format!("dependency {} failed", pkg.name));
+                continue;
+            }
+            if let PreflightCheck::Failed(reason) = &pkg.preflight {
+                let message = format!("{PREFLIGHT_FAILED}: {reason}");
+                finish(&progress_bar, &pkg, &PublishStatus::Error, &message);
+                blocked.insert(pkg.name.clone(), format!("dependency {} failed", pkg.name));
+                continue;
+            }
+            let progress = state
+                .lock()
+                .expect("Mutex poisoned")
+                .package(&pkg.name)
+                .cloned();
+            // Packages outside the run's state were on the registry before it started.
+            let Some(progress) = progress else {
+                let message = pkg.info.as_ref().map_or_else(String::new, |info| {
+                    format!("v{} already on the registry", info.version)
+                });
+                finish(
+                    &progress_bar,
+                    &pkg,
+                    &PublishStatus::AlreadyPublished,
+                    &message,
+                );
+                continue;
             };
 
-            let mut final_message = message.clone();
-            if tag && matches!(status, PublishStatus::Published) {
-                if let Some(info) = pkg.manager.get_info(&pkg.path).await {
-                    let tag_name = format!("v{}", info.version);
-                    let (tag_success, tag_message) =
-                        create_and_push_tag(&pkg.path, &tag_name).await;
-                    if tag_success {
-                        final_message = format!("{message}, {tag_message}");
-                    } else {
-                        status = PublishStatus::Error;
-                        final_message = format!("{message}; tag failed: {tag_message}");
+            progress_bar.set_message(PUBLISHING_MESSAGE);
+            let semaphore_clone = Arc::clone(&publish_semaphore);
+            let state = Arc::clone(&state);
+            futures.push(async move {
+                let mut pkg = pkg;
+                let _permit = semaphore_clone.acquire().await.expect("Semaphore closed");
+                // Dependents are packed once their dependencies are on the registry.
+                if pkg.preflight == PreflightCheck::AfterDependencies {
+                    progress_bar.set_message(PREFLIGHT_MESSAGE);
+                    pkg.preflight = preflight::check(&pkg).await;
+                    if let PreflightCheck::Failed(reason) = &pkg.preflight {
+                        let message = format!("{PREFLIGHT_FAILED}: {reason}");
+                        return (pkg, progress_bar, PublishStatus::Error, message);
                     }
+                    progress_bar.set_message(PUBLISHING_MESSAGE);
                 }
-            }
-
-            progress_bar.set_prefix(format!(
-                "{} {:width$}",
-                status.symbol(),
-                pkg.name,
-                width = max_name_length
-            ));
-            progress_bar.set_message(format!("{:<20}   {}", status.text(), final_message));
-            progress_bar.finish();
+                let (status, message) = publish_package(&pkg, progress, sign, &state).await;
+                (pkg, progress_bar, status, message)
+            });
+        }
 
-            {
-                let mut stats_guard = stats_clone.lock().expect("Mutex poisoned");
-                stats_guard.update(&status, &pkg.name, &pkg.path, &final_message);
-                footer_clone.set_message(stats_guard.generate_live_summary(total_packages));
+        let mut awaiting_registry = Vec::new();
+        while let Some((pkg, progress_bar, status, message)) = futures.next().await {
+            if required.contains(&pkg.name) {
+                match status {
+                    PublishStatus::Error => {
+                        blocked.insert(pkg.name.clone(), format!("dependency {} failed", pkg.name));
+                    }
+                    PublishStatus::Published => awaiting_registry.push(pkg.clone()),
+                    _ => {}
+                }
             }
-        };
+            finish(&progress_bar, &pkg, &status, &message);
+        }
 
-        futures.push(future);
+        for pkg in awaiting_registry {
+            footer_pb.set_message(format!("waiting for the registry to serve {}", pkg.name));
+            if let Err(reason) = wait_for_registry(&pkg).await {
+                blocked.insert(pkg.name.clone(), reason);
+            }
+        }
     }
 
-    while futures.next().await.is_some() {}
     footer_pb.finish();
 
     let final_stats = statistics.lock().expect("Mutex poisoned");
@@ -297,6 +505,18 @@ pub async fn execute_publish(
 
     let error_count = final_stats.count(PublishOutcomeKind::Failed);
     drop(final_stats);
+
+    let state = state.lock().expect("Mutex poisoned");
+    if state.is_complete() {
+        if let Err(e) = state.remove() {
+            eprintln!("⚠️  Could not remove the publish state: {e:#}");
+        }
+    } else {
+        println!(
+            "{DIM}↳ progress saved in {STATE_DIR} · continue with `repos publish --resume`{RESET}\n"
+        );
+    }
+    drop(state);
     if error_count > 0 {
         anyhow::bail!("{error_count} packages failed to publish completely");
     }
@@ -316,18 +536,24 @@ mod tests {
             "alpha",
             Path::new("alpha"),
             "published v1.2.3",
+            Some(&PackSummary {
+                files: vec!["Cargo.toml".to_string(), "src/lib.rs".to_string()],
+                size: Some("1.2KiB".to_string()),
+            }),
         );
         stats.update(
             &PublishStatus::AlreadyPublished,
             "beta",
             Path::new("beta"),
             "version already published",
+            None,
         );
         stats.update(
             &PublishStatus::Error,
             "gamma",
             Path::new("gamma"),
             "registry rejected package",
+            None,
         );
 
         let report = stats.generate_report(Duration::from_secs(3));
@@ -339,6 +565,7 @@ mod tests {
         assert!(report.contains("Checked           3"));
         assert!(report.contains("▌ Published"));
         assert!(report.contains("path: ./alpha"));
+        assert!(report.contains("packed: 2 files, 1.2KiB"));
         assert!(report.contains("▌ Already published"));
         assert!(report.contains("path: ./beta"));
         assert!(report.contains("▌ Failed"));
@@ -2,27 +2,57 @@
 
 mod executor;
 mod planner;
+mod preflight;
+mod state;
 
 use crate::core::{
     init_command, set_terminal_title, set_terminal_title_and_flush, NO_REPOS_MESSAGE,
 };
 use anyhow::Result;
 use executor::execute_publish;
-use planner::{plan_publish, PlannerOptions};
+use planner::{plan_publish, PackageToPublish, PlannerOptions, RegistryCheck};
+use preflight::{run_preflight, PreflightCheck};
+use state::{PublishRunOptions, PublishState, STATE_DIR};
+use std::path::Path;
 
 const SCANNING_MESSAGE: &str = "🔍 Scanning for packages...";
+/// Files listed per package in the dry-run before the rest are counted.
+const DRY_RUN_FILE_LIMIT: usize = 20;
 
 /// Handles the repository publish command
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_publish_command(
     target_repos: Vec<String>,
     dry_run: bool,
     tag: bool,
+    sign: bool,
     allow_dirty: bool,
+    skip_preflight: bool,
     all: bool,
     private_only: bool,
+    resume: bool,
 ) -> Result<()> {
     set_terminal_title("📦 repos");
 
+    // A resumed run plans with the interrupted run's choices.
+    let root = Path::new(".");
+    let previous = PublishState::load(root)?;
+    let (target_repos, tag, sign, all, private_only) = if resume {
+        let Some(previous) = &previous else {
+            anyhow::bail!("No unfinished publish to resume in {STATE_DIR}");
+        };
+        let options = &previous.options;
+        (
+            options.targets.clone(),
+            options.tag,
+            options.sign,
+            options.all,
+            options.private_only,
+        )
+    } else {
+        (target_repos, tag, sign, all, private_only)
+    };
+
     let (start_time, repos) = init_command(SCANNING_MESSAGE).await;
 
     if repos.is_empty() {
@@ -40,7 +70,7 @@ pub async fn handle_publish_command(
         dry_run,
     };
 
-    let plan = plan_publish(repos, options).await;
+    let mut plan = plan_publish(repos, options).await?;
 
     if !plan.inspection_errors.is_empty() {
         for (repo, error) in &plan.inspection_errors {
@@ -88,6 +118,30 @@ pub async fn handle_publish_command(
         anyhow::bail!("publishing blocked by uncommitted changes");
     }
 
+    if let Some(previous) = previous.as_ref().filter(|_| resume) {
+        plan.packages
+            .retain(|pkg| previous.package(&pkg.name).is_some());
+        for pkg in &plan.packages {
+            let recorded = previous
+                .package(&pkg.name)
+                .and_then(|package| package.version.as_deref());
+            let current = pkg.info.as_ref().map(|info| info.version.as_str());
+            if recorded != current {
+                anyhow::bail!(
+                    "{} is now v{} but the interrupted run was publishing v{}; start a new run without --resume",
+                    pkg.name,
+                    current.unwrap_or("?"),
+                    recorded.unwrap_or("?")
+                );
+            }
+        }
+        for name in previous.unfinished() {
+            if !plan.packages.iter().any(|pkg| pkg.name == name) {
+                eprintln!("⚠️  {name} from the interrupted run was not found; skipping it");
+            }
+        }
+    }
+
     if plan.packages.is_empty() {
         if target_repos.is_empty() {
             println!("\r📦 No packages found in any repository\n");
@@ -101,35 +155,155 @@ pub async fn handle_publish_command(
         return Ok(());
     }
 
+    // A package name git cannot tag (Maven's `group:artifact`) would only
+    // fail after the package was deployed, so refuse before publishing.
+    if tag && !dry_run {
+        let invalid: Vec<_> = plan
+            .packages
+            .iter()
+            .filter_map(|pkg| pkg.tag_error().map(|error| (&pkg.name, error)))
+            .collect();
+        if !invalid.is_empty() {
+            for (name, error) in &invalid {
+                eprintln!("❌ {name}: {error}");
+            }
+            println!("\nSet a tag_template in repos.toml that renders a valid name, or publish without --tag.\n");
+            set_terminal_title_and_flush("✅ repos");
+            anyhow::bail!("{} release tags are not valid git tag names", invalid.len());
+        }
+    }
+
+    // Pre-flight: pack every package this run will publish. Packages the
+    // interrupted run already published are not packed again.
+    if !skip_preflight {
+        let resumed = previous.as_ref().filter(|_| resume);
+        let pending = |pkg: &PackageToPublish| {
+            pkg.info.is_some()
+                && pkg.registry != RegistryCheck::AlreadyPublished
+                && !resumed
+                    .and_then(|previous| previous.package(&pkg.name))
+                    .is_some_and(|package| package.published)
+        };
+        let count = plan.packages.iter().filter(|pkg| pending(pkg)).count();
+        if count > 0 {
+            let message = format!("🧪 Running pre-flight checks for {count} packages...");
+            print!("\r{message}");
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            run_preflight(&mut plan.packages, pending).await;
+            print!("\r{:width$}", "", width = message.len());
+        }
+    }
+
     // Dry Run
     if dry_run {
+        let wave_count = plan.packages.last().map_or(0, |pkg| pkg.wave + 1);
         println!(
-            "\r📦 Found {} packages (dry-run mode)\n",
-            plan.packages.len()
+            "\r📦 Found {} packages in {wave_count} {} (dry-run mode)",
+            plan.packages.len(),
+            if wave_count == 1 { "wave" } else { "waves" }
         );
 
+        let mut current_wave = None;
         for pkg in &plan.packages {
-            if let Some(info) = pkg.manager.get_info(&pkg.path).await {
-                println!(
-                    "  {} {:<30} ({:<7}) v{}",
-                    pkg.manager.icon(),
-                    pkg.name,
-                    pkg.manager.name(),
-                    info.version
-                );
-            } else {
-                println!(
-                    "  {} {:<30} ({:<7}) version unknown",
-                    pkg.manager.icon(),
-                    pkg.name,
-                    pkg.manager.name()
-                );
+            if current_wave != Some(pkg.wave) {
+                current_wave = Some(pkg.wave);
+                println!("\n  Wave {}", pkg.wave + 1);
+            }
+            let version = pkg.info.as_ref().map_or_else(
+                || "version unknown".to_string(),
+                |info| format!("v{}", info.version),
+            );
+            let mut line = format!(
+                "  {} {:<30} ({:<7}) {version}",
+                pkg.manager.icon(),
+                pkg.name,
+                pkg.manager.name(),
+            );
+            if let Some((registry, destination)) = pkg.destination() {
+                line.push_str(&format!("  to {registry} ({destination})"));
+            }
+            if let Some(info) = pkg.info.as_ref().filter(|_| tag) {
+                if !pkg.manager.publishes_by_tag() {
+                    let kind = if sign { "signed tag" } else { "tag" };
+                    line.push_str(&format!("  {kind} {}", pkg.tag_name(info)));
+                    if pkg.tag_error().is_some() {
+                        line.push_str(" (invalid tag name)");
+                    }
+                }
+            }
+            if !pkg.depends_on.is_empty() {
+                line.push_str(&format!("  after {}", pkg.depends_on.join(", ")));
+            }
+            match &pkg.registry {
+                RegistryCheck::Unpublished => {}
+                RegistryCheck::AlreadyPublished => line.push_str("  already published"),
+                RegistryCheck::Unknown(reason) => {
+                    if pkg.info.is_some() {
+                        line.push_str(&format!("  registry check failed: {reason}"));
+                    }
+                }
+            }
+            println!("{line}");
+            if let Some(error) = pkg.tag_error().filter(|_| tag) {
+                println!("      ❌ {error}");
+            }
+            match &pkg.preflight {
+                PreflightCheck::NotRun => {}
+                PreflightCheck::AfterDependencies => println!(
+                    "      pre-flight checks run once {} {} published",
+                    pkg.depends_on.join(", "),
+                    if pkg.depends_on.len() == 1 {
+                        "is"
+                    } else {
+                        "are"
+                    }
+                ),
+                PreflightCheck::Passed(summary) => {
+                    println!("      packed {}", summary.describe());
+                    for file in summary.files.iter().take(DRY_RUN_FILE_LIMIT) {
+                        println!("        {file}");
+                    }
+                    if summary.files.len() > DRY_RUN_FILE_LIMIT {
+                        println!(
+                            "        … {} more",
+                            summary.files.len() - DRY_RUN_FILE_LIMIT
+                        );
+                    }
+                }
+                PreflightCheck::Failed(reason) => {
+                    println!("      ❌ pre-flight failed: {reason}");
+                }
             }
         }
-        println!(
-            "\nWould publish {} packages (dry-run - nothing published)\n",
-            plan.packages.len()
+        let already_published = plan
+            .packages
+            .iter()
+            .filter(|pkg| pkg.registry == RegistryCheck::AlreadyPublished)
+            .count();
+        let blocked = plan
+            .packages
+            .iter()
+            .filter(|pkg| matches!(pkg.preflight, PreflightCheck::Failed(_)))
+            .count();
+        let mut summary = format!(
+            "\nWould publish {} packages",
+            plan.packages.len() - already_published
         );
+        if already_published > 0 {
+            summary.push_str(&format!(", skip {already_published} already published"));
+        }
+        if blocked > 0 {
+            summary.push_str(&format!("; {blocked} blocked by failing pre-flight checks"));
+        }
+        let invalid_tags = plan
+            .packages
+            .iter()
+            .filter(|pkg| tag && pkg.tag_error().is_some())
+            .count();
+        if invalid_tags > 0 {
+            summary.push_str(&format!("; {invalid_tags} with invalid tag names"));
+        }
+        println!("{summary} (dry-run - nothing published)\n");
         set_terminal_title_and_flush("✅ repos");
         return Ok(());
     }
@@ -144,7 +318,30 @@ pub async fn handle_publish_command(
     print!("\r📦 Publishing {total_packages} {package_word}                    \n");
     println!();
 
-    execute_publish(plan.packages, tag, start_time).await?;
+    let state = match previous {
+        Some(previous) if resume => previous,
+        previous => {
+            if let Some(previous) = previous.filter(|previous| !previous.is_complete()) {
+                println!(
+                    "⚠️  Replacing the unfinished publish from {} ({} packages left); use --resume to continue it instead\n",
+                    previous.started_at,
+                    previous.unfinished().len()
+                );
+            }
+            let options = PublishRunOptions {
+                targets: target_repos,
+                all,
+                private_only,
+                tag,
+                sign,
+            };
+            let state = PublishState::new(root, options, &plan.packages);
+            state.save()?;
+            state
+        }
+    };
+
+    execute_publish(plan.packages, state, start_time).await?;
 
     set_terminal_title_and_flush("✅ repos");
     Ok(())
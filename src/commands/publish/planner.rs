@@ -1,10 +1,20 @@
+use super::preflight::PreflightCheck;
+use crate::core::manifest::WorkspaceManifest;
+use crate::core::selection::RegistryRoutes;
 use crate::git::{get_repo_visibility, has_uncommitted_changes, RepoVisibility};
-use crate::package::{detect_manager, PackageManager};
-use futures::stream::{FuturesUnordered, StreamExt};
-use std::path::PathBuf;
+use crate::package::{detect_manager, pypi, tag, PackageInfo, PackageManager, PublishRegistry};
+use anyhow::Result;
+use futures::stream::{self, FuturesUnordered, StreamExt};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Registry queries in flight at once while planning.
+const REGISTRY_CHECK_CONCURRENCY: usize = 8;
+
 pub struct PublishPlan {
+    /// Packages in publish order: every package comes after the fleet
+    /// packages it depends on.
     pub packages: Vec<PackageToPublish>,
     pub dirty_repos: Vec<String>,
     pub skipped_count: usize,
@@ -14,9 +24,71 @@ pub struct PublishPlan {
 
 #[derive(Clone)]
 pub struct PackageToPublish {
+    /// Repository name, or `repo/crate` for a workspace member.
     pub name: String,
     pub path: PathBuf,
     pub manager: Arc<dyn PackageManager>,
+    pub info: Option<PackageInfo>,
+    /// Zero-based publish wave; a wave starts once every earlier wave is done.
+    pub wave: usize,
+    /// Names of the planned packages this one depends on.
+    pub depends_on: Vec<String>,
+    /// True for a member of a multi-package workspace.
+    pub workspace_member: bool,
+    /// What the registry said about this version before publishing.
+    pub registry: RegistryCheck,
+    /// Private registry from `repos.toml`; `None` publishes to the public one.
+    pub target: Option<PublishRegistry>,
+    /// Release tag template from `repos.toml` or the `publish.tag_template` setting.
+    pub tag_template: String,
+    /// Whether the package built and packed cleanly before publishing.
+    pub preflight: PreflightCheck,
+}
+
+/// Result of asking the registry whether a planned version already exists.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RegistryCheck {
+    /// The version is not on the registry; it will be published.
+    Unpublished,
+    /// The version is already on the registry; publishing is skipped.
+    AlreadyPublished,
+    /// The registry could not be asked (or the version is unknown); publishing
+    /// is attempted and the package manager reports the outcome.
+    Unknown(String),
+}
+
+impl PackageToPublish {
+    /// The private registry and the URL or name this package goes to there,
+    /// when the target registry overrides its ecosystem.
+    pub fn destination(&self) -> Option<(&str, &str)> {
+        let target = self.target.as_ref()?;
+        let destination = target.destination(self.manager.name())?;
+        Some((target.name.as_str(), destination))
+    }
+
+    /// Release tag for the package from its tag template: `v1.2.3` by
+    /// default, or `core-lib-v1.2.3` for a workspace member so members of one
+    /// repository do not collide.
+    pub fn tag_name(&self, info: &PackageInfo) -> String {
+        tag::render(
+            &self.tag_template,
+            &info.name,
+            &info.version,
+            self.workspace_member,
+        )
+    }
+
+    /// Why this package's release tag would be rejected by git, if it would.
+    /// Packages released by pushing their own tag are not tagged here.
+    pub fn tag_error(&self) -> Option<String> {
+        let info = self.info.as_ref()?;
+        if self.manager.publishes_by_tag() {
+            return None;
+        }
+        tag::validate_name(&self.tag_name(info))
+            .err()
+            .map(|e| e.to_string())
+    }
 }
 
 pub struct PlannerOptions {
@@ -31,7 +103,14 @@ fn is_targeted(name: &str, targets: &[String]) -> bool {
     targets.iter().any(|target| name == target)
 }
 
-pub async fn plan_publish(repos: Vec<(String, PathBuf)>, options: PlannerOptions) -> PublishPlan {
+pub async fn plan_publish(
+    repos: Vec<(String, PathBuf)>,
+    options: PlannerOptions,
+) -> Result<PublishPlan> {
+    let root = Path::new(".");
+    let manifest = WorkspaceManifest::load(root)?;
+    let routes = RegistryRoutes::resolve(root, manifest.as_ref())?;
+
     // Filter repositories if specific targets were requested
     let mut filtered_repos = repos;
     if !options.target_repos.is_empty() {
@@ -62,7 +141,11 @@ pub async fn plan_publish(repos: Vec<(String, PathBuf)>, options: PlannerOptions
                             Ok(false)
                         }
                     });
-                (name, path, visibility, manager, dirty_result)
+                let packages = match &manager {
+                    Some(manager) => manager.list_packages(&path).await,
+                    None => Vec::new(),
+                };
+                (name, path, visibility, manager, packages, dirty_result)
             }
         })
         .collect();
@@ -77,7 +160,7 @@ pub async fn plan_publish(repos: Vec<(String, PathBuf)>, options: PlannerOptions
         inspection_errors: Vec::new(),
     };
 
-    for (name, path, visibility, manager, dirty_result) in analysis_results {
+    for (name, path, visibility, manager, packages, dirty_result) in analysis_results {
         // Apply visibility filter
         if let Some(desired) = filter_visibility {
             if visibility != desired {
@@ -104,20 +187,246 @@ pub async fn plan_publish(repos: Vec<(String, PathBuf)>, options: PlannerOptions
             if is_dirty {
                 plan.dirty_repos.push(name.clone());
             }
-            plan.packages.push(PackageToPublish {
-                name,
-                path,
-                manager: mgr,
-            });
+            let target = routes.registry_for(&name, &path)?.cloned();
+            let tag_template = tag::template_for(manifest.as_ref(), &path, Some(mgr.name()));
+            if packages.is_empty() {
+                plan.packages.push(PackageToPublish {
+                    name,
+                    path,
+                    manager: mgr,
+                    info: None,
+                    wave: 0,
+                    depends_on: Vec::new(),
+                    workspace_member: false,
+                    registry: RegistryCheck::Unknown("version unknown".to_string()),
+                    target,
+                    tag_template,
+                    preflight: PreflightCheck::NotRun,
+                });
+                continue;
+            }
+            let workspace_member = packages.len() > 1 || packages[0].0 != path;
+            for (package_path, info) in packages {
+                let package_name = if package_path == path {
+                    name.clone()
+                } else {
+                    format!("{name}/{}", info.name)
+                };
+                plan.packages.push(PackageToPublish {
+                    name: package_name,
+                    path: package_path,
+                    manager: Arc::clone(&mgr),
+                    info: Some(info),
+                    wave: 0,
+                    depends_on: Vec::new(),
+                    workspace_member,
+                    registry: RegistryCheck::Unknown("not checked".to_string()),
+                    target: target.clone(),
+                    tag_template: tag_template.clone(),
+                    preflight: PreflightCheck::NotRun,
+                });
+            }
         }
     }
 
-    plan
+    plan.packages = check_registries(order_by_dependencies(plan.packages)?).await;
+    Ok(plan)
+}
+
+/// Asks each package's registry whether its version is already published,
+/// so the plan (and the dry-run) shows only what would change.
+async fn check_registries(packages: Vec<PackageToPublish>) -> Vec<PackageToPublish> {
+    stream::iter(packages)
+        .map(|mut pkg| async move {
+            if let Some(info) = &pkg.info {
+                pkg.registry = match pkg
+                    .manager
+                    .is_version_published(&info.name, &info.version, pkg.target.as_ref())
+                    .await
+                {
+                    Ok(true) => RegistryCheck::AlreadyPublished,
+                    Ok(false) => RegistryCheck::Unpublished,
+                    Err(e) => RegistryCheck::Unknown(e.to_string()),
+                };
+            }
+            pkg
+        })
+        .buffered(REGISTRY_CHECK_CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// Key a dependency is matched on: registries compare names per ecosystem,
+/// PyPI ignores case and `-`/`_`/`.` differences, and NuGet ignores case.
+fn registry_key(manager: &str, package: &str) -> (String, String) {
+    let package = match manager {
+        "python" => pypi::normalize_name(package),
+        "nuget" => package.to_ascii_lowercase(),
+        _ => package.to_string(),
+    };
+    (manager.to_string(), package)
+}
+
+/// Assigns each package to a publish wave after the planned packages it
+/// depends on and returns them sorted by wave. Dependencies on packages
+/// outside the plan are already on their registry and do not order anything.
+fn order_by_dependencies(mut packages: Vec<PackageToPublish>) -> Result<Vec<PackageToPublish>> {
+    let by_key: HashMap<(String, String), usize> = packages
+        .iter()
+        .enumerate()
+        .filter_map(|(index, pkg)| {
+            let info = pkg.info.as_ref()?;
+            Some((registry_key(pkg.manager.name(), &info.name), index))
+        })
+        .collect();
+
+    let edges: Vec<Vec<usize>> = packages
+        .iter()
+        .enumerate()
+        .map(|(index, pkg)| {
+            let mut upstream: Vec<usize> = pkg
+                .info
+                .iter()
+                .flat_map(|info| &info.dependencies)
+                .filter_map(|dependency| {
+                    by_key
+                        .get(&registry_key(pkg.manager.name(), dependency))
+                        .copied()
+                })
+                .filter(|&upstream| upstream != index)
+                .collect();
+            upstream.sort_unstable();
+            upstream.dedup();
+            upstream
+        })
+        .collect();
+
+    // Kahn-style layering: a package's wave is one past its latest dependency.
+    let mut waves: Vec<Option<usize>> = vec![None; packages.len()];
+    let mut remaining = packages.len();
+    while remaining > 0 {
+        let ready: Vec<(usize, usize)> = (0..packages.len())
+            .filter(|&index| waves[index].is_none())
+            .filter_map(|index| {
+                edges[index]
+                    .iter()
+                    .try_fold(0, |wave, &upstream| {
+                        waves[upstream].map(|w| wave.max(w + 1))
+                    })
+                    .map(|wave| (index, wave))
+            })
+            .collect();
+        if ready.is_empty() {
+            let mut cycle: Vec<&str> = (0..packages.len())
+                .filter(|&index| waves[index].is_none())
+                .map(|index| packages[index].name.as_str())
+                .collect();
+            cycle.sort_unstable();
+            anyhow::bail!(
+                "cannot order packages for publishing: dependency cycle among {}",
+                cycle.join(", ")
+            );
+        }
+        remaining -= ready.len();
+        for (index, wave) in ready {
+            waves[index] = Some(wave);
+        }
+    }
+
+    let names: Vec<String> = packages.iter().map(|pkg| pkg.name.clone()).collect();
+    for (index, pkg) in packages.iter_mut().enumerate() {
+        pkg.wave = waves[index].unwrap_or_default();
+        pkg.depends_on = edges[index]
+            .iter()
+            .map(|&upstream| names[upstream].clone())
+            .collect();
+    }
+
+    packages.sort_by(|left, right| {
+        left.wave
+            .cmp(&right.wave)
+            .then_with(|| left.name.cmp(&right.name))
+    });
+    Ok(packages)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::is_targeted;
+    use super::*;
+    use crate::package::{cargo::Cargo, pypi::PyPI};
+
+    fn package(name: &str, manager: Arc<dyn PackageManager>, deps: &[&str]) -> PackageToPublish {
+        PackageToPublish {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            info: Some(PackageInfo {
+                manager_name: manager.name().to_string(),
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                dependencies: deps.iter().map(ToString::to_string).collect(),
+            }),
+            manager,
+            wave: 0,
+            depends_on: Vec::new(),
+            workspace_member: false,
+            registry: RegistryCheck::Unpublished,
+            target: None,
+            tag_template: tag::DEFAULT_TAG_TEMPLATE.to_string(),
+            preflight: PreflightCheck::NotRun,
+        }
+    }
+
+    #[test]
+    fn dependents_publish_in_later_waves_than_their_dependencies() {
+        let packages = vec![
+            package("app", Arc::new(Cargo), &["core-lib", "serde", "http-kit"]),
+            package("http-kit", Arc::new(Cargo), &["core-lib"]),
+            package("core-lib", Arc::new(Cargo), &["serde"]),
+            package("cli", Arc::new(Cargo), &[]),
+        ];
+
+        let ordered = order_by_dependencies(packages).unwrap();
+        let waves: Vec<(&str, usize)> = ordered
+            .iter()
+            .map(|pkg| (pkg.name.as_str(), pkg.wave))
+            .collect();
+
+        assert_eq!(
+            waves,
+            vec![("cli", 0), ("core-lib", 0), ("http-kit", 1), ("app", 2)]
+        );
+        assert_eq!(ordered[3].depends_on, vec!["http-kit", "core-lib"]);
+    }
+
+    #[test]
+    fn dependencies_match_within_one_ecosystem_using_registry_name_rules() {
+        let packages = vec![
+            package("tool", Arc::new(PyPI), &["core-lib"]),
+            package("Core_Lib", Arc::new(PyPI), &[]),
+            // Same name in another ecosystem is a different package.
+            package("web", Arc::new(Cargo), &["Core_Lib"]),
+        ];
+
+        let ordered = order_by_dependencies(packages).unwrap();
+        let tool = ordered.iter().find(|pkg| pkg.name == "tool").unwrap();
+        let web = ordered.iter().find(|pkg| pkg.name == "web").unwrap();
+
+        assert_eq!(tool.wave, 1);
+        assert_eq!(tool.depends_on, vec!["Core_Lib"]);
+        assert_eq!(web.wave, 0);
+    }
+
+    #[test]
+    fn dependency_cycles_are_reported_by_name() {
+        let packages = vec![
+            package("a", Arc::new(Cargo), &["b"]),
+            package("b", Arc::new(Cargo), &["a"]),
+            package("c", Arc::new(Cargo), &[]),
+        ];
+
+        let error = order_by_dependencies(packages).err().unwrap().to_string();
+        assert!(error.contains("dependency cycle among a, b"), "{error}");
+    }
 
     #[test]
     fn publish_targets_match_repository_names_exactly() {
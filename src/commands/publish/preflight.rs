@@ -0,0 +1,75 @@
+//! Pre-flight checks: every package is built and packed the way its
+//! registry will receive it before anything is published, and packages that
+//! fail are blocked.
+
+use super::planner::PackageToPublish;
+use crate::package::PackSummary;
+use futures::stream::{self, StreamExt};
+use std::collections::HashSet;
+
+/// Package builds in flight at once; packing is CPU- and disk-heavy.
+const PREFLIGHT_CONCURRENCY: usize = 4;
+
+/// Outcome of a package's pre-flight check.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum PreflightCheck {
+    /// Not checked: skipped, not needed by this run, or the ecosystem has no check.
+    #[default]
+    NotRun,
+    /// Checked right before publishing, once the fleet packages it depends
+    /// on are on the registry.
+    AfterDependencies,
+    /// Packed cleanly.
+    Passed(PackSummary),
+    /// Packing failed; the package is not published.
+    Failed(String),
+}
+
+/// Runs the pre-flight check of one package.
+pub async fn check(pkg: &PackageToPublish) -> PreflightCheck {
+    match pkg.manager.preflight(&pkg.path, pkg.target.as_ref()).await {
+        Ok(Some(summary)) => PreflightCheck::Passed(summary),
+        Ok(None) => PreflightCheck::NotRun,
+        Err(e) => PreflightCheck::Failed(format!("{e:#}")),
+    }
+}
+
+/// Checks the packages `pending` selects. A package whose ecosystem packs
+/// against the registry and that depends on another pending package is left
+/// for the executor, since its dependency's new version is not published yet.
+pub async fn run_preflight(
+    packages: &mut [PackageToPublish],
+    pending: impl Fn(&PackageToPublish) -> bool,
+) {
+    let pending_names: HashSet<String> = packages
+        .iter()
+        .filter(|pkg| pending(pkg))
+        .map(|pkg| pkg.name.clone())
+        .collect();
+
+    let mut checks = Vec::new();
+    for (index, pkg) in packages.iter_mut().enumerate() {
+        if !pending_names.contains(&pkg.name) {
+            continue;
+        }
+        let waits = pkg.manager.packs_against_registry()
+            && pkg.depends_on.iter().any(|dep| pending_names.contains(dep));
+        if waits {
+            pkg.preflight = PreflightCheck::AfterDependencies;
+        } else {
+            checks.push(index);
+        }
+    }
+
+    let results: Vec<(usize, PreflightCheck)> = stream::iter(checks)
+        .map(|index| {
+            let pkg = &packages[index];
+            async move { (index, check(pkg).await) }
+        })
+        .buffer_unordered(PREFLIGHT_CONCURRENCY)
+        .collect()
+        .await;
+    for (index, result) in results {
+        packages[index].preflight = result;
+    }
+}
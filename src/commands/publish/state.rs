@@ -0,0 +1,232 @@
+//! Publish run state behind `repos publish --resume`.
+//!
+//! A publish run records how far each planned package got in
+//! `.repos/publish/state.json` under the workspace root, saving after every
+//! step so an interrupted or partially failed run can continue:
+//!
+//! ```json
+//! {
+//!   "started_at": "2026-10-17T14:25:01.120+02:00",
+//!   "options": { "targets": [], "all": false, "private_only": false, "tag": true, "sign": false },
+//!   "packages": [
+//!     { "package": "core-lib", "version": "1.2.0", "tag_name": "v1.2.0",
+//!       "published": true, "tagged": true, "pushed_tag": false }
+//!   ]
+//! }
+//! ```
+//!
+//! The file is removed once every package is published (and its tag pushed,
+//! when the run tags).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::planner::{PackageToPublish, RegistryCheck};
+use crate::core::journal::create_state_dir;
+
+/// State directory, resolved from the workspace root.
+pub(crate) const STATE_DIR: &str = ".repos/publish";
+const STATE_FILE: &str = "state.json";
+
+/// The command-line choices a resumed run plans with again.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PublishRunOptions {
+    pub targets: Vec<String>,
+    pub all: bool,
+    pub private_only: bool,
+    pub tag: bool,
+    /// Tags are signed and annotated.
+    #[serde(default)]
+    pub sign: bool,
+}
+
+/// How far one package got.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PackageState {
+    pub package: String,
+    pub version: Option<String>,
+    /// Release tag the run creates for this package, when it tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_name: Option<String>,
+    /// The registry accepted the version (or already had it).
+    pub published: bool,
+    /// The release tag exists locally.
+    pub tagged: bool,
+    /// The release tag reached the remote.
+    pub pushed_tag: bool,
+}
+
+impl PackageState {
+    fn is_done(&self, tag: bool) -> bool {
+        self.published && (!tag || self.tag_name.is_none() || self.pushed_tag)
+    }
+}
+
+/// Progress of one `repos publish` run.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PublishState {
+    pub started_at: String,
+    pub options: PublishRunOptions,
+    pub packages: Vec<PackageState>,
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+impl PublishState {
+    /// Starts the state of a run over `packages`. Versions the planner found
+    /// on the registry need nothing from this run and are left out.
+    pub fn new(root: &Path, options: PublishRunOptions, packages: &[PackageToPublish]) -> Self {
+        let packages = packages
+            .iter()
+            .filter(|pkg| pkg.registry != RegistryCheck::AlreadyPublished)
+            .map(|pkg| PackageState {
+                package: pkg.name.clone(),
+                version: pkg.info.as_ref().map(|info| info.version.clone()),
+                tag_name: pkg
+                    .info
+                    .as_ref()
+                    .filter(|_| options.tag && !pkg.manager.publishes_by_tag())
+                    .map(|info| pkg.tag_name(info)),
+                ..PackageState::default()
+            })
+            .collect();
+        Self {
+            started_at: chrono::Local::now().to_rfc3339(),
+            options,
+            packages,
+            root: root.to_path_buf(),
+        }
+    }
+
+    /// Loads the state left by an unfinished run in the workspace at `root`.
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = root.join(STATE_DIR).join(STATE_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut state: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid publish state {}", path.display()))?;
+        state.root = root.to_path_buf();
+        Ok(Some(state))
+    }
+
+    /// Writes the state, creating the state directory on first use.
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = create_state_dir(&self.root, STATE_DIR)?.join(STATE_FILE);
+        let json = serde_json::to_string_pretty(self).context("Failed to encode publish state")?;
+        std::fs::write(&path, format!("{json}\n"))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Deletes the state file once the run has nothing left to do.
+    pub fn remove(&self) -> Result<()> {
+        let path = self.root.join(STATE_DIR).join(STATE_FILE);
+        if path.is_file() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    pub fn package(&self, name: &str) -> Option<&PackageState> {
+        self.packages.iter().find(|package| package.package == name)
+    }
+
+    /// Applies `change` to the package's entry and saves the state.
+    pub fn record(&mut self, name: &str, change: impl FnOnce(&mut PackageState)) {
+        if let Some(package) = self
+            .packages
+            .iter_mut()
+            .find(|package| package.package == name)
+        {
+            change(package);
+            if let Err(e) = self.save() {
+                eprintln!("⚠️  Could not write the publish state: {e:#}");
+            }
+        }
+    }
+
+    /// True when every package is done.
+    pub fn is_complete(&self) -> bool {
+        self.packages
+            .iter()
+            .all(|package| package.is_done(self.options.tag))
+    }
+
+    /// Names of the packages that still have work left.
+    pub fn unfinished(&self) -> Vec<&str> {
+        self.packages
+            .iter()
+            .filter(|package| !package.is_done(self.options.tag))
+            .map(|package| package.package.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, published: bool, tagged: bool, pushed_tag: bool) -> PackageState {
+        PackageState {
+            package: name.to_string(),
+            version: Some("1.0.0".to_string()),
+            tag_name: Some("v1.0.0".to_string()),
+            published,
+            tagged,
+            pushed_tag,
+        }
+    }
+
+    #[test]
+    fn state_round_trips_and_tracks_unfinished_packages() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut state = PublishState {
+            started_at: "2026-10-17T14:25:01+02:00".to_string(),
+            options: PublishRunOptions {
+                tag: true,
+                ..PublishRunOptions::default()
+            },
+            packages: vec![
+                package("core-lib", true, true, true),
+                package("http-kit", true, true, false),
+                package("app", false, false, false),
+            ],
+            root: temp.path().to_path_buf(),
+        };
+        state.save().unwrap();
+        assert!(temp.path().join(".repos/.gitignore").is_file());
+
+        let loaded = PublishState::load(temp.path()).unwrap().unwrap();
+        assert_eq!(loaded.packages, state.packages);
+        assert_eq!(loaded.unfinished(), vec!["http-kit", "app"]);
+        assert!(!loaded.is_complete());
+
+        state.record("http-kit", |package| package.pushed_tag = true);
+        state.record("app", |package| {
+            package.published = true;
+            package.tagged = true;
+            package.pushed_tag = true;
+        });
+        let loaded = PublishState::load(temp.path()).unwrap().unwrap();
+        assert!(loaded.is_complete());
+
+        loaded.remove().unwrap();
+        assert!(PublishState::load(temp.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn untagged_runs_only_need_the_registry() {
+        let state = PublishState {
+            started_at: String::new(),
+            options: PublishRunOptions::default(),
+            packages: vec![package("core-lib", true, false, false)],
+            root: PathBuf::new(),
+        };
+        assert!(state.is_complete());
+    }
+}
@@ -0,0 +1,695 @@
+//! Fleet-wide remote management.
+//!
+//! `repos remote rewrite --to ssh|https` converts the fetch and push URLs of
+//! every remote to one transport, using the same URL parsing that backs the
+//! transport policy. Only hosts whose SSH and HTTPS forms share a path
+//! (GitHub, GitLab, Bitbucket) are converted; other URLs are reported and left
+//! alone.
+//!
+//! `repos remote add` and `repos remote set-push` build a URL per repository
+//! from a template such as `git@git.internal:{owner}/{repo}.git`, filling the
+//! placeholders from the repository's existing remote, and `repos remote list`
+//! groups the fleet by the host and owner its remotes point at.
+
+use anyhow::{bail, Result};
+use futures::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::branch::run_batch;
+use crate::core::settings::settings;
+use crate::core::{
+    clean_error_message, format_relative_repo_path, init_command, set_terminal_title,
+    set_terminal_title_and_flush, truncate_text, BatchOperation, NO_REPOS_MESSAGE,
+};
+use crate::git::branch::list_remotes;
+use crate::git::operations::run_git;
+use crate::git::remote::{
+    configured_urls, context_from_url, effective_urls, policy_violation, set_configured_urls,
+    RemoteContext, RemoteDirection, RemoteTransport,
+};
+use crate::git::Status;
+use crate::subrepo::normalize_remote_url;
+
+const SCANNING_MESSAGE: &str = "🔍 Scanning for git repositories...";
+const BOLD_BLUE: &str = "\x1b[1;38;5;75m";
+const BOLD_PURPLE: &str = "\x1b[1;38;5;141m";
+const RED: &str = "\x1b[1;38;5;203m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+const TEMPLATE_PLACEHOLDERS: &[&str] = &["{host}", "{owner}", "{repo}"];
+
+/// Transport `repos remote rewrite` converts URLs to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RewriteTarget {
+    Ssh,
+    Https,
+}
+
+impl RewriteTarget {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Ssh => "SSH",
+            Self::Https => "HTTPS",
+        }
+    }
+
+    fn rewrite(self, context: &RemoteContext) -> Rewrite {
+        let convert = match self {
+            Self::Ssh => context.transport.is_http(),
+            Self::Https => matches!(
+                context.transport,
+                RemoteTransport::Ssh | RemoteTransport::Http
+            ),
+        };
+        if !convert {
+            return Rewrite::Keep;
+        }
+        let url = match self {
+            Self::Ssh => context.ssh_url.clone(),
+            Self::Https => context.https_url(),
+        };
+        url.map_or(Rewrite::Unavailable, Rewrite::To)
+    }
+}
+
+/// What `repos remote rewrite` does with one URL.
+enum Rewrite {
+    /// Already uses an acceptable transport.
+    Keep,
+    To(String),
+    /// The target form cannot be derived from this URL.
+    Unavailable,
+}
+
+/// Handles `repos remote rewrite --to <transport> [--dry-run]`.
+pub async fn handle_remote_rewrite_command(to: RewriteTarget, dry_run: bool) -> Result<()> {
+    let action = if dry_run {
+        format!("Planning {} remote rewrite", to.label())
+    } else {
+        format!("Rewriting remotes to {}", to.label())
+    };
+    run_batch(
+        BatchOperation::RemoteRewrite { dry_run },
+        action,
+        if dry_run {
+            "planning..."
+        } else {
+            "rewriting..."
+        },
+        move |_, path| async move { rewrite_one_repo(&path, to, dry_run).await },
+    )
+    .await
+}
+
+async fn rewrite_one_repo(repo_path: &Path, to: RewriteTarget, dry_run: bool) -> (Status, String) {
+    let remotes = match list_remotes(repo_path).await {
+        Ok(remotes) if remotes.is_empty() => {
+            return (Status::NoRemote, "no remote configured".to_string());
+        }
+        Ok(remotes) => remotes,
+        Err(e) => return (Status::Error, format!("remote lookup failed: {e}")),
+    };
+
+    let verb = if dry_run { "would rewrite" } else { "rewrote" };
+    let mut changes = Vec::new();
+    let mut unconvertible = Vec::new();
+    for remote in &remotes {
+        for direction in [RemoteDirection::Fetch, RemoteDirection::Push] {
+            let urls = match configured_urls(repo_path, remote, direction).await {
+                Ok(urls) => urls,
+                Err(e) => return (Status::Error, clean_error_message(&e.to_string())),
+            };
+
+            let mut rewritten = Vec::with_capacity(urls.len());
+            let mut changed = false;
+            for url in urls {
+                let context = context_from_url(remote, direction, &url);
+                match to.rewrite(&context) {
+                    Rewrite::To(new_url) => {
+                        changes.push(format!(
+                            "{verb} {remote} {}: {} → {new_url}",
+                            direction.label(),
+                            context.transport.label()
+                        ));
+                        rewritten.push(new_url);
+                        changed = true;
+                    }
+                    Rewrite::Keep => rewritten.push(url),
+                    Rewrite::Unavailable => {
+                        unconvertible.push(format!(
+                            "no {} form for {} {}",
+                            to.label(),
+                            direction.label(),
+                            context.display()
+                        ));
+                        rewritten.push(url);
+                    }
+                }
+            }
+
+            if changed && !dry_run {
+                if let Err(e) = set_configured_urls(repo_path, remote, direction, &rewritten).await
+                {
+                    return (Status::Error, clean_error_message(&e.to_string()));
+                }
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        return if unconvertible.is_empty() {
+            (Status::ConfigSynced, format!("already {}", to.label()))
+        } else {
+            (Status::Skip, unconvertible.join("; "))
+        };
+    }
+
+    changes.extend(unconvertible);
+    (Status::ConfigUpdated, changes.join("; "))
+}
+
+/// Checks that a remote URL template names the repository and uses only
+/// known placeholders.
+pub fn validate_url_template(template: &str) -> Result<()> {
+    if !template.contains("{repo}") {
+        bail!("remote URL template '{template}' must contain {{repo}}");
+    }
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let placeholder = rest[start..]
+            .find('}')
+            .map_or(&rest[start..], |end| &rest[start..=start + end]);
+        if !TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+            bail!(
+                "remote URL template '{template}' has unknown placeholder {placeholder} (use {})",
+                TEMPLATE_PLACEHOLDERS.join(", ")
+            );
+        }
+        rest = &rest[start + placeholder.len()..];
+    }
+    Ok(())
+}
+
+/// Host, owner, and repository name a remote URL points at.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct RemoteCoordinates {
+    host: String,
+    owner: String,
+    repo: String,
+}
+
+impl RemoteCoordinates {
+    /// Splits a network remote URL; local and unrecognized URLs have none.
+    fn from_url(url: &str) -> Option<Self> {
+        if matches!(
+            RemoteTransport::from_url(url),
+            RemoteTransport::Local | RemoteTransport::Other
+        ) {
+            return None;
+        }
+        let key = normalize_remote_url(url);
+        let (host, path) = key.split_once('/')?;
+        let (owner, repo) = path.rsplit_once('/')?;
+        (!host.is_empty() && !owner.is_empty() && !repo.is_empty()).then(|| Self {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{host}", &self.host)
+            .replace("{owner}", &self.owner)
+            .replace("{repo}", &self.repo)
+    }
+}
+
+/// Renders `template` from the first fetch URL of `source`, or explains why
+/// it cannot be.
+async fn templated_url(
+    repo_path: &Path,
+    source: &str,
+    template: &str,
+) -> std::result::Result<String, (Status, String)> {
+    let urls = configured_urls(repo_path, source, RemoteDirection::Fetch)
+        .await
+        .map_err(|e| (Status::Error, clean_error_message(&e.to_string())))?;
+    let Some(url) = urls.first() else {
+        return Err((Status::Skip, format!("remote {source} has no URL")));
+    };
+    let Some(coordinates) = RemoteCoordinates::from_url(url) else {
+        let context = context_from_url(source, RemoteDirection::Fetch, url);
+        return Err((
+            Status::Skip,
+            format!("cannot read owner and repo from {}", context.display()),
+        ));
+    };
+    Ok(coordinates.render(template))
+}
+
+/// Rejects URLs the transport policy or host allowlist would block.
+fn check_policy(
+    remote: &str,
+    direction: RemoteDirection,
+    url: &str,
+) -> std::result::Result<(), (Status, String)> {
+    match policy_violation(&[context_from_url(remote, direction, url)]) {
+        Ok(None) => Ok(()),
+        Ok(Some(violation)) => Err((Status::Skip, violation.message())),
+        Err(e) => Err((Status::Error, e.to_string())),
+    }
+}
+
+/// Handles `repos remote add <name> --template <template> [--from <remote>] [--dry-run]`.
+pub async fn handle_remote_add_command(
+    name: String,
+    template: String,
+    from: String,
+    dry_run: bool,
+) -> Result<()> {
+    validate_url_template(&template)?;
+    let action = if dry_run {
+        format!("Planning remote {name}")
+    } else {
+        format!("Adding remote {name}")
+    };
+    let args = Arc::new((name, template, from));
+    run_batch(
+        BatchOperation::RemoteAdd { dry_run },
+        action,
+        if dry_run { "planning..." } else { "adding..." },
+        move |_, path| {
+            let args = Arc::clone(&args);
+            async move {
+                let (name, template, from) = args.as_ref();
+                add_one_repo(&path, name, template, from, dry_run)
+                    .await
+                    .unwrap_or_else(|outcome| outcome)
+            }
+        },
+    )
+    .await
+}
+
+async fn add_one_repo(
+    repo_path: &Path,
+    name: &str,
+    template: &str,
+    from: &str,
+    dry_run: bool,
+) -> std::result::Result<(Status, String), (Status, String)> {
+    let remotes = match list_remotes(repo_path).await {
+        Ok(remotes) if remotes.is_empty() => {
+            return Err((Status::NoRemote, "no remote configured".to_string()));
+        }
+        Ok(remotes) => remotes,
+        Err(e) => return Err((Status::Error, format!("remote lookup failed: {e}"))),
+    };
+    if !remotes.iter().any(|remote| remote == from) {
+        return Err((
+            Status::Skip,
+            format!("no remote {from} to read the URL from"),
+        ));
+    }
+
+    let url = templated_url(repo_path, from, template).await?;
+    check_policy(name, RemoteDirection::Fetch, &url)?;
+
+    if remotes.iter().any(|remote| remote == name) {
+        let existing = configured_urls(repo_path, name, RemoteDirection::Fetch)
+            .await
+            .map_err(|e| (Status::Error, clean_error_message(&e.to_string())))?;
+        return if existing == [url.clone()] {
+            Ok((
+                Status::ConfigSynced,
+                format!("{name} already points at {url}"),
+            ))
+        } else {
+            Err((
+                Status::Skip,
+                format!("remote {name} already exists with another URL"),
+            ))
+        };
+    }
+
+    if dry_run {
+        return Ok((Status::ConfigUpdated, format!("would add {name} → {url}")));
+    }
+    match run_git(repo_path, &["remote", "add", name, &url]).await {
+        Ok((true, _, _)) => Ok((Status::ConfigUpdated, format!("added {name} → {url}"))),
+        Ok((false, _, stderr)) => Err((Status::Error, clean_error_message(&stderr))),
+        Err(e) => Err((Status::Error, format!("remote add failed: {e}"))),
+    }
+}
+
+/// Handles `repos remote rename <old> <new>`.
+pub async fn handle_remote_rename_command(old: String, new: String) -> Result<()> {
+    let action = format!("Renaming remote {old} to {new}");
+    let names = Arc::new((old, new));
+    run_batch(
+        BatchOperation::RemoteRename,
+        action,
+        "renaming...",
+        move |_, path| {
+            let names = Arc::clone(&names);
+            async move {
+                let (old, new) = names.as_ref();
+                rename_one_repo(&path, old, new).await
+            }
+        },
+    )
+    .await
+}
+
+async fn rename_one_repo(repo_path: &Path, old: &str, new: &str) -> (Status, String) {
+    let remotes = match list_remotes(repo_path).await {
+        Ok(remotes) if remotes.is_empty() => {
+            return (Status::NoRemote, "no remote configured".to_string());
+        }
+        Ok(remotes) => remotes,
+        Err(e) => return (Status::Error, format!("remote lookup failed: {e}")),
+    };
+    let has = |name: &str| remotes.iter().any(|remote| remote == name);
+    match (has(old), has(new)) {
+        (false, true) => return (Status::ConfigSynced, format!("already renamed to {new}")),
+        (false, false) => return (Status::Skip, format!("no remote {old}")),
+        (true, true) => return (Status::Skip, format!("remote {new} already exists")),
+        (true, false) => {}
+    }
+
+    match run_git(repo_path, &["remote", "rename", old, new]).await {
+        Ok((true, _, _)) => (Status::ConfigUpdated, format!("renamed {old} → {new}")),
+        Ok((false, _, stderr)) => (Status::Error, clean_error_message(&stderr)),
+        Err(e) => (Status::Error, format!("remote rename failed: {e}")),
+    }
+}
+
+/// Handles `repos remote set-push <remote> --template <template> [--dry-run]`.
+pub async fn handle_remote_set_push_command(
+    remote: String,
+    template: String,
+    dry_run: bool,
+) -> Result<()> {
+    validate_url_template(&template)?;
+    let action = if dry_run {
+        format!("Planning push mirrors for {remote}")
+    } else {
+        format!("Adding push mirrors to {remote}")
+    };
+    let args = Arc::new((remote, template));
+    run_batch(
+        BatchOperation::RemoteSetPush { dry_run },
+        action,
+        if dry_run {
+            "planning..."
+        } else {
+            "mirroring..."
+        },
+        move |_, path| {
+            let args = Arc::clone(&args);
+            async move {
+                let (remote, template) = args.as_ref();
+                set_push_one_repo(&path, remote, template, dry_run)
+                    .await
+                    .unwrap_or_else(|outcome| outcome)
+            }
+        },
+    )
+    .await
+}
+
+async fn set_push_one_repo(
+    repo_path: &Path,
+    remote: &str,
+    template: &str,
+    dry_run: bool,
+) -> std::result::Result<(Status, String), (Status, String)> {
+    match list_remotes(repo_path).await {
+        Ok(remotes) if remotes.is_empty() => {
+            return Err((Status::NoRemote, "no remote configured".to_string()));
+        }
+        Ok(remotes) if !remotes.iter().any(|name| name == remote) => {
+            return Err((Status::Skip, format!("no remote {remote}")));
+        }
+        Ok(_) => {}
+        Err(e) => return Err((Status::Error, format!("remote lookup failed: {e}"))),
+    }
+
+    let url = templated_url(repo_path, remote, template).await?;
+    check_policy(remote, RemoteDirection::Push, &url)?;
+
+    let read = |direction| async move {
+        configured_urls(repo_path, remote, direction)
+            .await
+            .map_err(|e| (Status::Error, clean_error_message(&e.to_string())))
+    };
+    let mut push_urls = read(RemoteDirection::Push).await?;
+    // Without an explicit push URL, pushes go to the fetch URLs; keep them so
+    // the new URL mirrors instead of replacing them.
+    if push_urls.is_empty() {
+        push_urls = read(RemoteDirection::Fetch).await?;
+    }
+    if push_urls.contains(&url) {
+        return Ok((
+            Status::ConfigSynced,
+            format!("{remote} already pushes to {url}"),
+        ));
+    }
+
+    if dry_run {
+        return Ok((
+            Status::ConfigUpdated,
+            format!("would add {remote} push URL {url}"),
+        ));
+    }
+    push_urls.push(url.clone());
+    match set_configured_urls(repo_path, remote, RemoteDirection::Push, &push_urls).await {
+        Ok(()) => Ok((
+            Status::ConfigUpdated,
+            format!("added {remote} push URL {url}"),
+        )),
+        Err(e) => Err((Status::Error, clean_error_message(&e.to_string()))),
+    }
+}
+
+/// One remote URL in the `repos remote list` table.
+struct RemoteRow {
+    /// `host/owner` the URL points at, or `local`/`other`.
+    group: String,
+    repository: String,
+    remote: String,
+    direction: RemoteDirection,
+    transport: RemoteTransport,
+    /// Repository name on the host, or the path of a local remote.
+    target: String,
+}
+
+/// Handles `repos remote list`.
+pub async fn handle_remote_list_command() -> Result<()> {
+    set_terminal_title("🔗 repos remote list");
+
+    let (_, repos) = init_command(SCANNING_MESSAGE).await;
+    if repos.is_empty() {
+        println!("\r{NO_REPOS_MESSAGE}");
+        set_terminal_title_and_flush("✅ repos remote list");
+        return Ok(());
+    }
+    let repo_word = if repos.len() == 1 {
+        "repository"
+    } else {
+        "repositories"
+    };
+    print!(
+        "\r🔗 Listing remotes of {} {repo_word}                    \n\n",
+        repos.len()
+    );
+
+    let results = stream::iter(repos)
+        .map(|(name, path)| async move {
+            let rows = list_one_repo(&name, &path).await;
+            (name, path, rows)
+        })
+        .buffer_unordered(settings().git_concurrency.value)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut groups: BTreeMap<String, Vec<RemoteRow>> = BTreeMap::new();
+    let mut no_remote = Vec::new();
+    let mut failed = Vec::new();
+    for (name, path, rows) in results {
+        match rows {
+            Ok(rows) if rows.is_empty() => no_remote.push((name, path)),
+            Ok(rows) => {
+                for row in rows {
+                    groups.entry(row.group.clone()).or_default().push(row);
+                }
+            }
+            Err(e) => failed.push((name, path, clean_error_message(&e.to_string()))),
+        }
+    }
+
+    println!("{}", render_remote_list(groups, no_remote, failed));
+    set_terminal_title_and_flush("✅ repos remote list");
+    Ok(())
+}
+
+/// Lists every fetch URL of a repository's remotes, plus push URLs that are
+/// not also fetch URLs.
+async fn list_one_repo(name: &str, repo_path: &Path) -> Result<Vec<RemoteRow>> {
+    let mut rows = Vec::new();
+    for remote in list_remotes(repo_path).await? {
+        let fetch = effective_urls(repo_path, &remote, RemoteDirection::Fetch).await?;
+        let push = effective_urls(repo_path, &remote, RemoteDirection::Push).await?;
+        let urls = fetch.iter().map(|url| (RemoteDirection::Fetch, url)).chain(
+            push.iter()
+                .filter(|url| !fetch.contains(url))
+                .map(|url| (RemoteDirection::Push, url)),
+        );
+        for (direction, url) in urls {
+            let transport = RemoteTransport::from_url(url);
+            let (group, target) = match RemoteCoordinates::from_url(url) {
+                Some(coordinates) => (
+                    format!("{}/{}", coordinates.host, coordinates.owner),
+                    coordinates.repo,
+                ),
+                None if transport == RemoteTransport::Local => {
+                    ("local".to_string(), url.trim().to_string())
+                }
+                None => ("other".to_string(), String::new()),
+            };
+            rows.push(RemoteRow {
+                group,
+                repository: name.to_string(),
+                remote: remote.clone(),
+                direction,
+                transport,
+                target,
+            });
+        }
+    }
+    Ok(rows)
+}
+
+fn render_remote_list(
+    groups: BTreeMap<String, Vec<RemoteRow>>,
+    mut no_remote: Vec<(String, PathBuf)>,
+    mut failed: Vec<(String, PathBuf, String)>,
+) -> String {
+    let mut lines = vec![format!("{BOLD_BLUE}repos remote list{RESET}")];
+    let group_count = groups.len();
+    let mut repositories = std::collections::BTreeSet::new();
+
+    for (group, mut rows) in groups {
+        rows.sort_by(|left, right| {
+            (&left.repository, &left.remote, left.direction.label()).cmp(&(
+                &right.repository,
+                &right.remote,
+                right.direction.label(),
+            ))
+        });
+        let count = rows
+            .iter()
+            .map(|row| row.repository.as_str())
+            .collect::<std::collections::BTreeSet<_>>()
+            .len();
+        let repo_word = if count == 1 {
+            "repository"
+        } else {
+            "repositories"
+        };
+        lines.push(String::new());
+        lines.push(format!(
+            "{BOLD_PURPLE}▌ {group}{RESET} {DIM}· {count} {repo_word}{RESET}"
+        ));
+        for row in rows {
+            lines.push(
+                format!(
+                    "  {:24} {:12} {:5} {:6} {}",
+                    truncate_text(&row.repository, 24),
+                    truncate_text(&row.remote, 12),
+                    row.direction.label(),
+                    row.transport.label(),
+                    row.target
+                )
+                .trim_end()
+                .to_string(),
+            );
+            repositories.insert(row.repository);
+        }
+    }
+
+    if !no_remote.is_empty() {
+        no_remote.sort();
+        lines.push(String::new());
+        lines.push(format!("{BOLD_PURPLE}▌ No remote{RESET}"));
+        for (name, path) in &no_remote {
+            lines.push(format!(
+                "  {:24} {}",
+                truncate_text(name, 24),
+                format_relative_repo_path(&path.to_string_lossy())
+            ));
+        }
+    }
+    if !failed.is_empty() {
+        failed.sort();
+        lines.push(String::new());
+        lines.push(format!("{BOLD_PURPLE}▌ Failed{RESET}"));
+        for (name, path, message) in &failed {
+            lines.push(format!(
+                "  {RED}!{RESET} {:24} {message}",
+                truncate_text(name, 24)
+            ));
+            lines.push(format!(
+                "    ↳ path: {}",
+                format_relative_repo_path(&path.to_string_lossy())
+            ));
+        }
+    }
+
+    let group_word = if group_count == 1 { "group" } else { "groups" };
+    lines.push(String::new());
+    lines.push(format!(
+        "{DIM}{} repositories with remotes · {group_count} host/owner {group_word}{RESET}",
+        repositories.len()
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_url_template, RemoteCoordinates};
+
+    #[test]
+    fn templates_render_from_existing_remotes() {
+        let coordinates = RemoteCoordinates::from_url("https://token@github.com/Goobits/Repos.git")
+            .expect("network remote");
+        assert_eq!(
+            coordinates.render("git@git.internal:{owner}/{repo}.git"),
+            "git@git.internal:goobits/repos.git"
+        );
+
+        let coordinates =
+            RemoteCoordinates::from_url("ssh://git@gitlab.example.com:2222/group/sub/app.git")
+                .expect("network remote");
+        assert_eq!(coordinates.host, "gitlab.example.com:2222");
+        assert_eq!(coordinates.owner, "group/sub");
+        assert_eq!(
+            coordinates.render("https://{host}/mirror/{owner}/{repo}"),
+            "https://gitlab.example.com:2222/mirror/group/sub/app"
+        );
+
+        assert_eq!(RemoteCoordinates::from_url("../local.git"), None);
+        assert_eq!(RemoteCoordinates::from_url("git@github.com:repo.git"), None);
+    }
+
+    #[test]
+    fn templates_need_the_repo_and_known_placeholders() {
+        assert!(validate_url_template("git@git.internal:{owner}/{repo}.git").is_ok());
+        assert!(validate_url_template("git@git.internal:mirror.git").is_err());
+        assert!(validate_url_template("git@{server}:{owner}/{repo}.git").is_err());
+    }
+}
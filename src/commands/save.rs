@@ -7,10 +7,11 @@
 use anyhow::Result;
 use futures::stream::{FuturesUnordered, StreamExt};
 
+use crate::core::settings::settings;
 use crate::core::{
-    acquire_semaphore_permit, clean_error_message, create_processing_context, init_command,
-    set_terminal_title, set_terminal_title_and_flush, BatchOperation, GIT_CONCURRENT_CAP,
-    NO_REPOS_MESSAGE,
+    acquire_semaphore_permit, clean_error_message, create_processing_context, emit_start,
+    init_command, is_human_output, report_no_repositories, set_terminal_title,
+    set_terminal_title_and_flush, BatchOperation, JournalRecorder, RestoreMode,
 };
 use crate::git::{
     commit_changes, fetch_and_analyze, get_staging_status, has_staged_changes, is_detached_head,
@@ -32,7 +33,7 @@ pub async fn handle_save_command(
     let (start_time, repos) = init_command(SCANNING_MESSAGE).await;
 
     if repos.is_empty() {
-        println!("\r{NO_REPOS_MESSAGE}");
+        report_no_repositories("save", start_time);
         set_terminal_title_and_flush("✅ repos save");
         return Ok(());
     }
@@ -44,17 +45,22 @@ pub async fn handle_save_command(
         "repositories"
     };
     let action = if dry_run { "Planning save" } else { "Saving" };
-    print!("\r💾 {action} across {total_repos} {repo_word}                    \n\n");
-
-    let context =
-        match create_processing_context(std::sync::Arc::new(repos), start_time, GIT_CONCURRENT_CAP)
-        {
-            Ok(context) => context,
-            Err(e) => {
-                set_terminal_title_and_flush("✅ repos save");
-                return Err(e);
-            }
-        };
+    if is_human_output() {
+        print!("\r💾 {action} across {total_repos} {repo_word}                    \n\n");
+    }
+    emit_start("save", total_repos);
+
+    let context = match create_processing_context(
+        std::sync::Arc::new(repos),
+        start_time,
+        settings().git_concurrency.value,
+    ) {
+        Ok(context) => context,
+        Err(e) => {
+            set_terminal_title_and_flush("✅ repos save");
+            return Err(e);
+        }
+    };
 
     process_save_repositories(
         context,
@@ -79,6 +85,7 @@ async fn process_save_repositories(
     use crate::core::{acquire_stats_lock, create_progress_bar};
 
     let operation = BatchOperation::Save { dry_run };
+    acquire_stats_lock(&context.statistics).stream_events_as(operation.command());
 
     let mut progress_bars = Vec::new();
     for (repo_name, _) in context.repositories.iter() {
@@ -98,6 +105,8 @@ async fn process_save_repositories(
     let max_name_length = context.max_name_length;
     let start_time = context.start_time;
     let total_repos = context.total_repos;
+    let journal = JournalRecorder::new(operation.command(), RestoreMode::Mixed);
+    let journal = &journal;
 
     let mut futures = FuturesUnordered::new();
     for ((repo_name, repo_path), progress_bar) in context.repositories.iter().zip(progress_bars) {
@@ -109,14 +118,19 @@ async fn process_save_repositories(
         let future = async move {
             let _permit = acquire_semaphore_permit(&semaphore).await;
 
-            let (status, message, has_uncommitted) = save_one_repo(
-                repo_path,
-                &commit_message,
-                include_untracked,
-                auto_upstream,
-                dry_run,
-            )
-            .await;
+            let (status, message, has_uncommitted) = journal
+                .track(
+                    repo_name,
+                    repo_path,
+                    save_one_repo(
+                        repo_path,
+                        &commit_message,
+                        include_untracked,
+                        auto_upstream,
+                        dry_run,
+                    ),
+                )
+                .await;
 
             progress_bar.set_prefix(format!(
                 "{} {:width$}",
@@ -146,10 +160,8 @@ async fn process_save_repositories(
     footer_pb.finish();
 
     let final_stats = acquire_stats_lock(&context.statistics);
-    println!(
-        "\n{}\n",
-        final_stats.generate_batch_report(operation, start_time.elapsed())
-    );
+    final_stats.print_batch_report(operation, start_time.elapsed());
+    journal.finish();
 
     let error_count = final_stats
         .error_repos
@@ -9,14 +9,16 @@
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+use crate::core::settings::settings;
 use crate::core::{
-    clean_error_message, create_processing_context, format_relative_repo_path, init_command,
-    set_terminal_title, set_terminal_title_and_flush, truncate_text, BatchOperation,
-    GIT_CONCURRENT_CAP, NO_REPOS_MESSAGE,
+    clean_error_message, create_processing_context, emit_report, emit_repository, emit_start,
+    format_relative_repo_path, init_command, is_human_output, report_no_repositories,
+    set_terminal_title, set_terminal_title_and_flush, sort_records, truncate_text, BatchOperation,
+    RepositoryRecord, UpstreamRecord,
 };
+use crate::git::operations::git_backend;
 use crate::git::{
-    commit_changes, get_staging_status, has_staged_changes, is_detached_head, stage_files,
-    unstage_files, Status,
+    commit_changes, has_staged_changes, is_detached_head, stage_files, unstage_files, Status,
 };
 use crate::utils::compare_repository_locations;
 
@@ -66,6 +68,22 @@ struct FleetStatusEntry {
     status: FleetStatus,
 }
 
+impl FleetStatusEntry {
+    fn record(&self) -> RepositoryRecord {
+        RepositoryRecord {
+            name: self.repository.clone(),
+            path: self.path.to_string_lossy().to_string(),
+            phase: "status",
+            status: self.status.status,
+            message: self.status.message.clone(),
+            has_uncommitted: self.status.dirty(),
+            failure: None,
+            upstream: Some(self.status.upstream.record()),
+            exec: None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Eq, PartialEq)]
 enum FleetStatusKind {
     Healthy,
@@ -165,6 +183,7 @@ impl FleetStatus {
 /// Handles the repository stage command
 pub async fn handle_stage_command(pattern: String) -> Result<()> {
     let Some(context) = prepare_batch_command(
+        BatchOperation::Stage,
         "🚀 repos stage",
         "✅ repos stage",
         format!("Staging {pattern}"),
@@ -182,6 +201,7 @@ pub async fn handle_stage_command(pattern: String) -> Result<()> {
 /// Handles the repository unstage command
 pub async fn handle_unstage_command(pattern: String) -> Result<()> {
     let Some(context) = prepare_batch_command(
+        BatchOperation::Unstage,
         "🚀 repos unstage",
         "✅ repos unstage",
         format!("Unstaging {pattern}"),
@@ -197,6 +217,7 @@ pub async fn handle_unstage_command(pattern: String) -> Result<()> {
 }
 
 async fn prepare_batch_command(
+    operation: BatchOperation,
     running_title: &str,
     done_title: &str,
     action: String,
@@ -205,7 +226,7 @@ async fn prepare_batch_command(
 
     let (start_time, repos) = init_command(SCANNING_MESSAGE).await;
     if repos.is_empty() {
-        println!("\r{NO_REPOS_MESSAGE}");
+        report_no_repositories(operation.command(), start_time);
         set_terminal_title_and_flush(done_title);
         return Ok(None);
     }
@@ -216,10 +237,17 @@ async fn prepare_batch_command(
     } else {
         "repositories"
     };
-    print!("\r🚀 {action} in {total_repos} {repo_word}                    \n");
-    println!();
+    if is_human_output() {
+        print!("\r🚀 {action} in {total_repos} {repo_word}                    \n");
+        println!();
+    }
+    emit_start(operation.command(), total_repos);
 
-    match create_processing_context(std::sync::Arc::new(repos), start_time, GIT_CONCURRENT_CAP) {
+    match create_processing_context(
+        std::sync::Arc::new(repos),
+        start_time,
+        settings().git_concurrency.value,
+    ) {
         Ok(context) => Ok(Some(context)),
         Err(e) => {
             set_terminal_title_and_flush(done_title);
@@ -240,8 +268,8 @@ pub async fn handle_staging_status_command(
     repos = filter_status_repositories(repos, &targets);
 
     if repos.is_empty() {
-        if targets.is_empty() {
-            println!("\r{NO_REPOS_MESSAGE}");
+        if targets.is_empty() || !is_human_output() {
+            report_no_repositories("status", start_time);
         } else {
             println!("\rNo repositories matched: {}", targets.join(", "));
         }
@@ -256,20 +284,25 @@ pub async fn handle_staging_status_command(
     } else {
         "repositories"
     };
-    print!("\r🚀 Checking status of {total_repos} {repo_word}                    \n");
-    println!();
+    if is_human_output() {
+        print!("\r🚀 Checking status of {total_repos} {repo_word}                    \n");
+        println!();
+    }
+    emit_start("status", total_repos);
 
     // Create processing context
-    let context =
-        match create_processing_context(std::sync::Arc::new(repos), start_time, GIT_CONCURRENT_CAP)
-        {
-            Ok(context) => context,
-            Err(e) => {
-                // If context creation fails, set completion title and return error
-                set_terminal_title_and_flush("✅ repos status");
-                return Err(e);
-            }
-        };
+    let context = match create_processing_context(
+        std::sync::Arc::new(repos),
+        start_time,
+        settings().git_concurrency.value,
+    ) {
+        Ok(context) => context,
+        Err(e) => {
+            // If context creation fails, set completion title and return error
+            set_terminal_title_and_flush("✅ repos status");
+            return Err(e);
+        }
+    };
 
     // Process all repositories concurrently for status
     process_status_repositories(context, filters).await;
@@ -340,6 +373,7 @@ async fn process_staging_repositories(
     } else {
         BatchOperation::Unstage
     };
+    acquire_stats_lock(&context.statistics).stream_events_as(operation.command());
 
     // First, create all repository progress bars
     let mut repo_progress_bars = Vec::new();
@@ -426,10 +460,7 @@ async fn process_staging_repositories(
     footer_pb.finish();
 
     let final_stats = acquire_stats_lock(&context.statistics);
-    println!(
-        "\n{}\n",
-        final_stats.generate_batch_report(operation, start_time.elapsed())
-    );
+    final_stats.print_batch_report(operation, start_time.elapsed());
 
     let error_count = final_stats
         .error_repos
@@ -489,11 +520,13 @@ async fn process_status_repositories(
             progress_bar.set_message(format!("{:<12}   {}", status.status.text(), status.message));
             progress_bar.finish_and_clear();
 
-            FleetStatusEntry {
+            let entry = FleetStatusEntry {
                 repository,
                 path,
                 status,
-            }
+            };
+            emit_repository(&entry.record());
+            entry
         };
 
         futures.push(future);
@@ -505,10 +538,20 @@ async fn process_status_repositories(
         entries.push(entry);
     }
 
-    println!(
-        "\n{}\n",
-        generate_status_report(&entries, filters, start_time.elapsed())
-    );
+    if is_human_output() {
+        println!(
+            "\n{}\n",
+            generate_status_report(&entries, filters, start_time.elapsed())
+        );
+    } else {
+        let mut records = entries
+            .iter()
+            .filter(|entry| entry.status.matches_filters(filters))
+            .map(FleetStatusEntry::record)
+            .collect::<Vec<_>>();
+        sort_records(&mut records);
+        emit_report("status", start_time.elapsed(), &records);
+    }
 }
 
 fn generate_status_report(
@@ -616,11 +659,10 @@ fn append_status_section(
 }
 
 async fn get_fleet_status(repo_path: &std::path::Path, show_details: bool) -> FleetStatus {
-    use crate::git::operations::run_git;
-
-    let status_result = get_staging_status(repo_path).await;
+    let backend = git_backend();
+    let status_result = backend.porcelain_status(repo_path).await;
     let (working_status, mut parts, details) = match status_result {
-        Ok((stdout, _)) => summarize_worktree(&stdout, show_details),
+        Ok(stdout) => summarize_worktree(&stdout, show_details),
         Err(e) => {
             return FleetStatus {
                 status: Status::StagingError,
@@ -630,10 +672,10 @@ async fn get_fleet_status(repo_path: &std::path::Path, show_details: bool) -> Fl
         }
     };
 
-    let branch = match run_git(repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]).await {
-        Ok((true, branch, _)) => branch,
-        _ => "unknown".to_string(),
-    };
+    let branch = backend
+        .head_name(repo_path)
+        .await
+        .unwrap_or_else(|_| "unknown".to_string());
     parts.insert(0, format!("branch {branch}"));
 
     let upstream = summarize_upstream(repo_path).await;
@@ -760,44 +802,30 @@ impl UpstreamSummary {
             _ => None,
         }
     }
+
+    fn record(&self) -> UpstreamRecord {
+        let (state, ahead, behind) = match self {
+            UpstreamSummary::Remote { ahead, behind, .. } => {
+                ("tracking", Some(*ahead), Some(*behind))
+            }
+            UpstreamSummary::NoRemote => ("no_remote", None, None),
+            UpstreamSummary::NoUpstream => ("no_upstream", None, None),
+            UpstreamSummary::Unknown => ("unknown", None, None),
+        };
+        UpstreamRecord {
+            state,
+            ahead,
+            behind,
+        }
+    }
 }
 
 async fn summarize_upstream(repo_path: &std::path::Path) -> UpstreamSummary {
-    use crate::git::operations::run_git;
-
-    let upstream = run_git(repo_path, &["rev-parse", "--abbrev-ref", "@{upstream}"])
-        .await
-        .ok();
-    let Some(upstream) = upstream else {
+    let backend = git_backend();
+    let Ok(Some(upstream)) = backend.upstream(repo_path).await else {
         return summarize_missing_upstream(repo_path).await;
     };
-    if !upstream.0 {
-        return summarize_missing_upstream(repo_path).await;
-    }
-
-    let ahead = run_git(repo_path, &["rev-list", "--count", "HEAD", "^@{upstream}"])
-        .await
-        .ok()
-        .and_then(|(success, count, _)| {
-            if success {
-                count.parse::<u32>().ok()
-            } else {
-                None
-            }
-        })
-        .unwrap_or(0);
-
-    let behind = run_git(repo_path, &["rev-list", "--count", "@{upstream}", "^HEAD"])
-        .await
-        .ok()
-        .and_then(|(success, count, _)| {
-            if success {
-                count.parse::<u32>().ok()
-            } else {
-                None
-            }
-        })
-        .unwrap_or(0);
+    let (ahead, behind) = backend.ahead_behind(repo_path).await.unwrap_or((0, 0));
 
     let remote = if ahead > 0 && behind > 0 {
         format!("diverged ({ahead} ahead, {behind} behind)")
@@ -806,7 +834,7 @@ async fn summarize_upstream(repo_path: &std::path::Path) -> UpstreamSummary {
     } else if behind > 0 {
         format!("behind {behind}")
     } else {
-        format!("synced with {}", upstream.1)
+        format!("synced with {upstream}")
     };
 
     UpstreamSummary::Remote {
@@ -817,10 +845,8 @@ async fn summarize_upstream(repo_path: &std::path::Path) -> UpstreamSummary {
 }
 
 async fn summarize_missing_upstream(repo_path: &std::path::Path) -> UpstreamSummary {
-    use crate::git::operations::run_git;
-
-    match run_git(repo_path, &["remote"]).await {
-        Ok((true, remotes, _)) if !remotes.trim().is_empty() => UpstreamSummary::NoUpstream,
+    match git_backend().remote_urls(repo_path).await {
+        Ok(remotes) if !remotes.is_empty() => UpstreamSummary::NoUpstream,
         _ => UpstreamSummary::NoRemote,
     }
 }
@@ -828,6 +854,7 @@ async fn summarize_missing_upstream(repo_path: &std::path::Path) -> UpstreamSumm
 /// Handles the repository commit command
 pub async fn handle_commit_command(message: String, include_empty: bool) -> Result<()> {
     let Some(context) = prepare_batch_command(
+        BatchOperation::Commit,
         "🚀 repos commit",
         "✅ repos commit",
         "Committing changes".to_string(),
@@ -851,8 +878,12 @@ async fn process_commit_repositories(
     use crate::core::{acquire_semaphore_permit, acquire_stats_lock, create_progress_bar};
     use futures::stream::{FuturesUnordered, StreamExt};
 
-    let mut futures = FuturesUnordered::new();
     let operation = BatchOperation::Commit;
+    let journal =
+        crate::core::JournalRecorder::new(operation.command(), crate::core::RestoreMode::Soft);
+    let journal = &journal;
+    let mut futures = FuturesUnordered::new();
+    acquire_stats_lock(&context.statistics).stream_events_as(operation.command());
 
     // First, create all repository progress bars
     let mut repo_progress_bars = Vec::new();
@@ -893,8 +924,13 @@ async fn process_commit_repositories(
         let future = async move {
             let _permit = acquire_semaphore_permit(&semaphore_clone).await;
 
-            let (status, message) =
-                perform_commit_operation(repo_path, &message_clone, include_empty).await;
+            let (status, message) = journal
+                .track(
+                    repo_name,
+                    repo_path,
+                    perform_commit_operation(repo_path, &message_clone, include_empty),
+                )
+                .await;
 
             progress_bar.set_prefix(format!(
                 "{} {:width$}",
@@ -931,10 +967,8 @@ async fn process_commit_repositories(
     footer_pb.finish();
 
     let final_stats = acquire_stats_lock(&context.statistics);
-    println!(
-        "\n{}\n",
-        final_stats.generate_batch_report(operation, start_time.elapsed())
-    );
+    final_stats.print_batch_report(operation, start_time.elapsed());
+    journal.finish();
 
     let error_count = final_stats
         .error_repos
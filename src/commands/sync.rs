@@ -5,9 +5,11 @@
 
 use anyhow::Result;
 
+use crate::core::settings::settings;
 use crate::core::{
-    create_processing_context, generate_sync_report, init_command, set_terminal_title,
-    set_terminal_title_and_flush, NO_REPOS_MESSAGE,
+    create_processing_context, emit_report, emit_start, generate_sync_report, init_command,
+    is_human_output, report_no_repositories, repository_records, set_terminal_title,
+    set_terminal_title_and_flush, sort_records, JournalRecorder, RestoreMode,
 };
 use crate::git::Status;
 
@@ -108,16 +110,19 @@ async fn prepare_transfer_context(
 
     set_terminal_title(operation.running_title());
     let (start_time, repos) = init_command(SCANNING_MESSAGE).await;
-    println!();
 
     if repos.is_empty() {
-        println!("\r{NO_REPOS_MESSAGE}");
+        report_no_repositories(operation.command(), start_time);
         set_terminal_title_and_flush(operation.completed_title());
         return Ok(None);
     }
+    if is_human_output() {
+        println!();
+    }
+    emit_start(operation.command(), repos.len());
 
     let concurrent_limit = get_git_concurrency(jobs, sequential);
-    if verbose {
+    if verbose && is_human_output() {
         let repo_word = if repos.len() == 1 {
             "repository"
         } else {
@@ -247,17 +252,20 @@ pub async fn handle_sync_command(
 
     set_terminal_title("🔄 repos sync");
     let (start_time, repos) = init_command(SCANNING_MESSAGE).await;
-    println!();
 
     if repos.is_empty() {
-        println!("\r{NO_REPOS_MESSAGE}");
+        report_no_repositories("sync", start_time);
         set_terminal_title_and_flush("✅ repos sync");
         return Ok(());
     }
+    if is_human_output() {
+        println!();
+    }
+    emit_start("sync", repos.len());
 
     let concurrent_limit = get_git_concurrency(jobs, sequential);
     let repositories = std::sync::Arc::new(repos);
-    if verbose {
+    if verbose && is_human_output() {
         let repo_word = if repositories.len() == 1 {
             "repository"
         } else {
@@ -280,10 +288,19 @@ pub async fn handle_sync_command(
         start_time,
         concurrent_limit,
     )?;
-    let pull_run =
-        process_pull_repositories(pull_context, true, verbose, show_changes, true, false).await;
+    let journal = JournalRecorder::new("sync", RestoreMode::Keep);
+    let pull_run = process_pull_repositories(
+        pull_context,
+        true,
+        verbose,
+        show_changes,
+        true,
+        false,
+        &journal,
+    )
+    .await;
 
-    if verbose {
+    if verbose && is_human_output() {
         println!("{DIM}Phase 2/2 · push{RESET}");
     }
     let push_run = process_push_repositories(
@@ -296,25 +313,33 @@ pub async fn handle_sync_command(
     )
     .await;
 
-    let (drift_count, drift_lines) = if no_drift_check {
-        (0, Vec::new())
-    } else {
-        format_nested_drift_work_items()
-    };
     let pull_stats = crate::core::acquire_stats_lock(&pull_run.statistics);
     let push_stats = crate::core::acquire_stats_lock(&push_run.statistics);
-    let report = generate_sync_report(
-        &pull_stats,
-        &push_stats,
-        start_time.elapsed(),
-        repositories.len(),
-        show_changes,
-        drift_count,
-        &drift_lines,
-    );
+    if is_human_output() {
+        let (drift_count, drift_lines) = if no_drift_check {
+            (0, Vec::new())
+        } else {
+            format_nested_drift_work_items()
+        };
+        let report = generate_sync_report(
+            &pull_stats,
+            &push_stats,
+            start_time.elapsed(),
+            repositories.len(),
+            show_changes,
+            drift_count,
+            &drift_lines,
+        );
+        println!("{report}\n");
+    } else {
+        let mut records = repository_records("pull", &pull_stats);
+        records.extend(repository_records("push", &push_stats));
+        sort_records(&mut records);
+        emit_report("sync", start_time.elapsed(), &records);
+    }
     drop(push_stats);
     drop(pull_stats);
-    println!("{report}\n");
+    journal.finish();
     set_terminal_title_and_flush("✅ repos sync");
 
     let total_errors = pull_run.error_count + push_run.error_count;
@@ -355,6 +380,7 @@ async fn process_fetch_repositories(
     use futures::stream::{FuturesUnordered, StreamExt};
 
     let statistics = std::sync::Arc::clone(&context.statistics);
+    acquire_stats_lock(&statistics).stream_events_as("fetch");
     let footer_message = statistics
         .lock()
         .unwrap()
@@ -379,13 +405,13 @@ async fn process_fetch_repositories(
         let max_name_length = context.max_name_length;
 
         futures.push(async move {
-            use crate::core::config::SLOW_REPO_THRESHOLD_SECS;
+            let slow_repo_threshold_secs = settings().slow_repo_threshold_secs.value;
 
             let started = std::time::Instant::now();
             let mut watchdog = spawn_slow_repo_watchdog(
                 single.as_ref(),
                 repo_name,
-                std::time::Duration::from_secs(SLOW_REPO_THRESHOLD_SECS),
+                std::time::Duration::from_secs(slow_repo_threshold_secs),
             );
             let _permit = match semaphore.acquire().await {
                 Ok(permit) => permit,
@@ -408,7 +434,7 @@ async fn process_fetch_repositories(
             stop_slow_repo_watchdog(&mut watchdog).await;
 
             let elapsed = started.elapsed();
-            let display_message = if elapsed.as_secs() >= SLOW_REPO_THRESHOLD_SECS {
+            let display_message = if elapsed.as_secs() >= slow_repo_threshold_secs {
                 format!("{} ({:.1}s)", result.message, elapsed.as_secs_f32())
             } else {
                 result.message.clone()
@@ -458,11 +484,19 @@ async fn process_fetch_repositories(
     footer_pb.finish_and_clear();
 
     let final_stats = acquire_stats_lock(&statistics);
-    println!(
-        "{}",
-        final_stats.generate_fetch_report(start_time.elapsed())
-    );
-    println!();
+    if is_human_output() {
+        println!(
+            "{}",
+            final_stats.generate_fetch_report(start_time.elapsed())
+        );
+        println!();
+    } else {
+        emit_report(
+            "fetch",
+            start_time.elapsed(),
+            &repository_records("fetch", &final_stats),
+        );
+    }
     let error_count = final_stats
         .error_repos
         .load(std::sync::atomic::Ordering::Relaxed);
@@ -521,10 +555,10 @@ async fn process_push_repositories(
     use futures::stream::{FuturesUnordered, StreamExt};
 
     // Use 2x concurrency for fetch phase (I/O bound), standard concurrency for push phase
-    use crate::core::config::FETCH_CONCURRENT_CAP;
-    let fetch_concurrency = (context.max_concurrency * 2).min(FETCH_CONCURRENT_CAP);
+    let fetch_concurrency = (context.max_concurrency * 2).min(settings().fetch_concurrency.value);
     let fetch_semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(fetch_concurrency));
     let statistics = std::sync::Arc::clone(&context.statistics);
+    acquire_stats_lock(&statistics).stream_events_as("push");
 
     let push_footer = context
         .statistics
@@ -563,14 +597,14 @@ async fn process_push_repositories(
         let total_repos_clone = context.total_repos;
 
         let future = async move {
-            use crate::core::config::SLOW_REPO_THRESHOLD_SECS;
+            let slow_repo_threshold_secs = settings().slow_repo_threshold_secs.value;
 
             // Track start time for this repo
             let repo_start_time = std::time::Instant::now();
             let mut slow_repo_watchdog = spawn_slow_repo_watchdog(
                 single_pb_clone.as_ref(),
                 repo_name,
-                std::time::Duration::from_secs(SLOW_REPO_THRESHOLD_SECS),
+                std::time::Duration::from_secs(slow_repo_threshold_secs),
             );
 
             // PHASE 1: Fetch with high concurrency
@@ -662,7 +696,7 @@ async fn process_push_repositories(
                 };
 
             // Add elapsed time warning if repo took longer than threshold
-            let display_message = if repo_elapsed.as_secs() >= SLOW_REPO_THRESHOLD_SECS {
+            let display_message = if repo_elapsed.as_secs() >= slow_repo_threshold_secs {
                 format!("{display_message} ({repo_elapsed_secs:.1}s)")
             } else {
                 display_message
@@ -724,7 +758,13 @@ async fn process_push_repositories(
     footer_pb.finish_and_clear();
 
     let final_stats = acquire_stats_lock(&statistics);
-    if render_report {
+    if render_report && !is_human_output() {
+        emit_report(
+            "push",
+            context.start_time.elapsed(),
+            &repository_records("push", &final_stats),
+        );
+    } else if render_report {
         let (drift_count, drift_lines) = if no_drift_check {
             (0, Vec::new())
         } else {
@@ -776,6 +816,7 @@ pub async fn handle_pull_command(
         return Ok(());
     };
 
+    let journal = JournalRecorder::new(FleetTransfer::Pull.command(), RestoreMode::Keep);
     let run = process_pull_repositories(
         context,
         use_rebase,
@@ -783,8 +824,10 @@ pub async fn handle_pull_command(
         show_changes,
         no_drift_check,
         true,
+        &journal,
     )
     .await;
+    journal.finish();
 
     set_terminal_title_and_flush(FleetTransfer::Pull.completed_title());
     run.ensure_success(FleetTransfer::Pull.command())
@@ -801,6 +844,7 @@ async fn process_pull_repositories(
     show_changes: bool,
     no_drift_check: bool,
     render_report: bool,
+    journal: &JournalRecorder,
 ) -> TransferRun {
     use crate::core::acquire_stats_lock;
     use crate::git::fetch_and_analyze_for_pull;
@@ -808,10 +852,10 @@ async fn process_pull_repositories(
     use futures::stream::{FuturesUnordered, StreamExt};
 
     // Use 2x concurrency for fetch phase (I/O bound), standard concurrency for pull phase
-    use crate::core::config::FETCH_CONCURRENT_CAP;
-    let fetch_concurrency = (context.max_concurrency * 2).min(FETCH_CONCURRENT_CAP);
+    let fetch_concurrency = (context.max_concurrency * 2).min(settings().fetch_concurrency.value);
     let fetch_semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(fetch_concurrency));
     let statistics = std::sync::Arc::clone(&context.statistics);
+    acquire_stats_lock(&statistics).stream_events_as("pull");
 
     let pull_footer = context
         .statistics
@@ -846,7 +890,7 @@ async fn process_pull_repositories(
         let total_repos_clone = context.total_repos;
 
         let future = async move {
-            use crate::core::config::SLOW_REPO_THRESHOLD_SECS;
+            let slow_repo_threshold_secs = settings().slow_repo_threshold_secs.value;
 
             // Track start time for this repo
             let repo_start_time = std::time::Instant::now();
@@ -890,35 +934,38 @@ async fn process_pull_repositories(
             // Attempt pull with retry on rate limit
             let mut attempt = 0;
             let max_attempts = 2;
-            let result = loop {
-                attempt += 1;
-                let mut result =
-                    pull_if_needed_with_context(repo_path, &fetch_result, use_rebase).await;
-
-                // Check for rate limit error
-                if result.message.contains("⚠️ RATE LIMIT") {
-                    has_rate_limit_clone.store(true, std::sync::atomic::Ordering::Release);
-                    rate_limit_count_clone.fetch_add(1, std::sync::atomic::Ordering::Release);
-
-                    if attempt < max_attempts {
-                        // Wait briefly and retry
-                        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                        continue;
-                    }
-                    // Max attempts reached, return with suggestion
-                    let suggestion = format!(
-                        "{} (try reducing concurrency with --jobs N or --sequential)",
-                        result.message.replace("⚠️ RATE LIMIT: ", "")
-                    );
-                    result.message.clone_from(&suggestion);
-                    if let Some(failure) = &mut result.failure {
-                        failure.message = suggestion;
+            let pull_with_retry = async {
+                loop {
+                    attempt += 1;
+                    let mut result =
+                        pull_if_needed_with_context(repo_path, &fetch_result, use_rebase).await;
+
+                    // Check for rate limit error
+                    if result.message.contains("⚠️ RATE LIMIT") {
+                        has_rate_limit_clone.store(true, std::sync::atomic::Ordering::Release);
+                        rate_limit_count_clone.fetch_add(1, std::sync::atomic::Ordering::Release);
+
+                        if attempt < max_attempts {
+                            // Wait briefly and retry
+                            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                            continue;
+                        }
+                        // Max attempts reached, return with suggestion
+                        let suggestion = format!(
+                            "{} (try reducing concurrency with --jobs N or --sequential)",
+                            result.message.replace("⚠️ RATE LIMIT: ", "")
+                        );
+                        result.message.clone_from(&suggestion);
+                        if let Some(failure) = &mut result.failure {
+                            failure.message = suggestion;
+                        }
+                        break result;
                     }
+
                     break result;
                 }
-
-                break result;
             };
+            let result = journal.track(repo_name, repo_path, pull_with_retry).await;
 
             let status = result.status;
             let message = &result.message;
@@ -936,7 +983,7 @@ async fn process_pull_repositories(
                 };
 
             // Add elapsed time warning if repo took longer than threshold
-            let display_message = if repo_elapsed.as_secs() >= SLOW_REPO_THRESHOLD_SECS {
+            let display_message = if repo_elapsed.as_secs() >= slow_repo_threshold_secs {
                 format!("{display_message} ({repo_elapsed_secs:.1}s)")
             } else {
                 display_message
@@ -998,7 +1045,13 @@ async fn process_pull_repositories(
     footer_pb.finish_and_clear();
 
     let final_stats = acquire_stats_lock(&statistics);
-    if render_report {
+    if render_report && !is_human_output() {
+        emit_report(
+            "pull",
+            context.start_time.elapsed(),
+            &repository_records("pull", &final_stats),
+        );
+    } else if render_report {
         let (drift_count, drift_lines) = if no_drift_check {
             (0, Vec::new())
         } else {
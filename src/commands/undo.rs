@@ -0,0 +1,269 @@
+//! `repos undo`: restore the HEADs recorded in an operation journal.
+//!
+//! Every repository is checked before it is touched: when its HEAD no longer
+//! matches what the journaled operation left behind, the repository moved on
+//! since and is skipped instead of being reset over newer work.
+
+use anyhow::Result;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::core::{
+    clean_error_message, format_relative_repo_path, is_human_output, set_terminal_title,
+    set_terminal_title_and_flush, short_commit, BatchOperation, DeletedBranch, HeadState, Journal,
+    JournalEntry, RestoreMode, SyncStatistics,
+};
+use crate::git::operations::run_git;
+use crate::git::{has_uncommitted_changes, Status};
+
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Handles `repos undo [--last | <id>]` and `repos undo --list`.
+pub async fn handle_undo_command(id: Option<String>, list: bool) -> Result<()> {
+    let root = std::env::current_dir()?;
+    if list {
+        print_journal_list(&Journal::list(&root)?);
+        return Ok(());
+    }
+
+    let mut journal = match id {
+        Some(id) => {
+            let journal = Journal::load(&root, &id)?;
+            if let Some(undone_at) = &journal.undone_at {
+                anyhow::bail!("Journal '{id}' was already undone at {undone_at}");
+            }
+            journal
+        }
+        None => match Journal::latest(&root)? {
+            Some(journal) => journal,
+            None => {
+                println!("Nothing to undo: no operation journal in this workspace");
+                return Ok(());
+            }
+        },
+    };
+
+    set_terminal_title("↩️ repos undo");
+    let start_time = Instant::now();
+    let repo_word = if journal.repositories.len() == 1 {
+        "repository"
+    } else {
+        "repositories"
+    };
+    if is_human_output() {
+        println!(
+            "↩️  Undoing {} ({}) in {} {repo_word}",
+            journal.command,
+            journal.id,
+            journal.repositories.len()
+        );
+    }
+
+    let stats = SyncStatistics::new();
+    for entry in &journal.repositories {
+        let (status, message) = undo_entry(entry, journal.restore).await;
+        stats.update(
+            &entry.repository,
+            &format_relative_repo_path(&entry.path.to_string_lossy()),
+            &status,
+            &message,
+            false,
+        );
+    }
+    stats.print_batch_report(BatchOperation::Undo, start_time.elapsed());
+
+    // Restored and already-restored repositories both count as synced; any
+    // skip or failure keeps the journal open so `repos undo` can be rerun.
+    let resolved = stats
+        .synced_repos
+        .load(std::sync::atomic::Ordering::Relaxed);
+    let error_count = stats.error_repos.load(std::sync::atomic::Ordering::Relaxed);
+    if resolved == journal.repositories.len() as u64 {
+        journal.mark_undone(&root)?;
+    }
+    set_terminal_title_and_flush("✅ repos undo");
+    if error_count > 0 {
+        anyhow::bail!("{error_count} repositories failed to undo");
+    }
+    Ok(())
+}
+
+fn print_journal_list(journals: &[Journal]) {
+    if journals.is_empty() {
+        println!("No operation journals in this workspace");
+        return;
+    }
+    for journal in journals.iter().rev() {
+        let repo_word = if journal.repositories.len() == 1 {
+            "repository"
+        } else {
+            "repositories"
+        };
+        let undone = if journal.undone_at.is_some() {
+            format!("   {DIM}undone{RESET}")
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:32} {:8} {} {repo_word}{undone}",
+            journal.id,
+            journal.command,
+            journal.repositories.len()
+        );
+    }
+}
+
+/// Restores one journaled repository, returning the report status and message.
+async fn undo_entry(entry: &JournalEntry, restore: RestoreMode) -> (Status, String) {
+    let path = entry.path.as_path();
+    if !path.is_dir() {
+        return (Status::Error, "repository no longer exists".to_string());
+    }
+    let current = match HeadState::read(path).await {
+        Ok(current) => current,
+        Err(e) => return (Status::Error, format!("HEAD check failed: {e}")),
+    };
+
+    let mut restored = Vec::new();
+    if current == entry.after && entry.before != entry.after {
+        match restore_head(path, entry, restore).await {
+            Ok(message) => restored.push(message),
+            Err(outcome) => return outcome,
+        }
+    } else if current != entry.before {
+        return (
+            Status::Skip,
+            format!("moved on: now {}", current.describe()),
+        );
+    }
+
+    let mut recreated = Vec::new();
+    for branch in &entry.deleted_branches {
+        match recreate_branch(path, branch).await {
+            Ok(true) => recreated.push(branch.name.as_str()),
+            Ok(false) => {}
+            Err(outcome) => return outcome,
+        }
+    }
+    if !recreated.is_empty() {
+        restored.push(format!("recreated {}", recreated.join(", ")));
+    }
+
+    if let Some(stash) = &entry.stash {
+        match pop_stash(path, stash).await {
+            Ok(true) => restored.push("popped the stash".to_string()),
+            Ok(false) => {}
+            Err(outcome) => return outcome,
+        }
+    }
+
+    if restored.is_empty() {
+        (
+            Status::Synced,
+            format!("already at {}", entry.before.describe()),
+        )
+    } else {
+        (Status::Restored, restored.join("; "))
+    }
+}
+
+/// Moves HEAD back to `entry.before`, which the caller verified is safe.
+async fn restore_head(
+    path: &Path,
+    entry: &JournalEntry,
+    restore: RestoreMode,
+) -> std::result::Result<String, (Status, String)> {
+    let Some(commit) = entry.before.commit.as_deref() else {
+        return Err((Status::Skip, "no commit to restore".to_string()));
+    };
+
+    if entry.before.branch == entry.after.branch {
+        run_git_step(path, &["reset", "--quiet", restore.reset_flag(), commit]).await?;
+        return Ok(format!("restored {}", entry.before.describe()));
+    }
+
+    // Checking out another branch rewrites the worktree; never do that over
+    // changes made after the operation.
+    match has_uncommitted_changes(path).await {
+        Ok(false) => {}
+        Ok(true) => {
+            return Err((
+                Status::Skip,
+                "dirty worktree; commit or stash first".to_string(),
+            ));
+        }
+        Err(e) => return Err((Status::Error, format!("status failed: {e}"))),
+    }
+
+    match &entry.before.branch {
+        Some(branch) => {
+            let refname = format!("refs/heads/{branch}");
+            let tip = run_git(path, &["rev-parse", "--quiet", "--verify", &refname])
+                .await
+                .ok()
+                .filter(|(success, _, _)| *success)
+                .map(|(_, tip, _)| tip);
+            match tip {
+                Some(tip) if tip == commit => {
+                    run_git_step(path, &["switch", "--quiet", branch]).await?;
+                }
+                Some(tip) => {
+                    return Err((
+                        Status::Skip,
+                        format!("branch {branch} moved: now at {}", short_commit(&tip)),
+                    ));
+                }
+                None => {
+                    run_git_step(path, &["switch", "--quiet", "-c", branch, commit]).await?;
+                }
+            }
+            Ok(format!("switched back to {}", entry.before.describe()))
+        }
+        None => {
+            run_git_step(path, &["switch", "--quiet", "--detach", commit]).await?;
+            Ok(format!("restored {}", entry.before.describe()))
+        }
+    }
+}
+
+/// Recreates a pruned branch. Returns `false` when it already exists at the
+/// recorded commit.
+async fn recreate_branch(
+    path: &Path,
+    branch: &DeletedBranch,
+) -> std::result::Result<bool, (Status, String)> {
+    let refname = format!("refs/heads/{}", branch.name);
+    match run_git(path, &["rev-parse", "--quiet", "--verify", &refname]).await {
+        Ok((true, tip, _)) if tip == branch.commit => Ok(false),
+        Ok((true, tip, _)) => Err((
+            Status::Skip,
+            format!("branch {} exists at {}", branch.name, short_commit(&tip)),
+        )),
+        _ => {
+            run_git_step(path, &["branch", &branch.name, &branch.commit]).await?;
+            Ok(true)
+        }
+    }
+}
+
+/// Pops the journaled stash if it is still on the stash list.
+async fn pop_stash(path: &Path, stash: &str) -> std::result::Result<bool, (Status, String)> {
+    let (_, list, _) = run_git(path, &["stash", "list", "--format=%H"])
+        .await
+        .map_err(|e| (Status::Error, format!("stash lookup failed: {e}")))?;
+    let Some(index) = list.lines().position(|commit| commit == stash) else {
+        return Ok(false);
+    };
+    let reference = format!("stash@{{{index}}}");
+    run_git_step(path, &["stash", "pop", "--quiet", "--index", &reference]).await?;
+    Ok(true)
+}
+
+async fn run_git_step(path: &Path, args: &[&str]) -> std::result::Result<(), (Status, String)> {
+    match run_git(path, args).await {
+        Ok((true, _, _)) => Ok(()),
+        Ok((false, _, stderr)) => Err((Status::Error, clean_error_message(&stderr))),
+        Err(e) => Err((Status::Error, format!("git {} failed: {e}", args[0]))),
+    }
+}
@@ -0,0 +1,529 @@
+//! Fleet-wide version bumps.
+//!
+//! `repos version bump` reads each package's version through its package
+//! manager (so Cargo workspace members bump individually, or through their
+//! shared `[workspace.package]` version), writes the next version into the
+//! manifest, and moves fleet packages that pin the old version along with it.
+//! Each changed repository gets one commit, journaled for `repos undo`.
+//! `auto` picks each package's level from its conventional commits since
+//! its last release tag, as `repos changelog` suggests.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::core::manifest::WorkspaceManifest;
+use crate::core::{
+    init_command, set_terminal_title, set_terminal_title_and_flush, JournalRecorder, RestoreMode,
+    NO_REPOS_MESSAGE,
+};
+use crate::git::operations::run_git;
+use crate::package::changelog::{release_notes, ReleaseNotes};
+use crate::package::version::{BumpLevel, Version};
+use crate::package::{detect_manager, tag, PackageInfo, PackageManager};
+
+const SCANNING_MESSAGE: &str = "🔍 Scanning for packages...";
+
+/// Options for `repos version bump`.
+pub struct VersionBumpOptions {
+    /// Repository names to bump; empty bumps every package in the fleet.
+    pub targets: Vec<String>,
+    /// `None` bumps by the level conventional commits suggest (`auto`).
+    pub level: Option<BumpLevel>,
+    /// Pre-release identifier (`rc` gives `1.3.0-rc.0`)
+    pub pre_id: Option<String>,
+    pub dry_run: bool,
+    /// Commit the changed manifests in each repository
+    pub commit: bool,
+}
+
+struct FleetPackage {
+    repo: String,
+    repo_path: PathBuf,
+    path: PathBuf,
+    manager: Arc<dyn PackageManager>,
+    info: PackageInfo,
+    /// True for a member of a multi-package workspace.
+    workspace_member: bool,
+    /// Release tag template, as `repos publish` resolves it.
+    tag_template: String,
+}
+
+struct PlannedBump<'a> {
+    package: &'a FleetPackage,
+    level: BumpLevel,
+    new: String,
+}
+
+/// What changed in one repository, for its commit message and the report.
+#[derive(Default)]
+struct RepoChanges {
+    path: PathBuf,
+    files: BTreeSet<PathBuf>,
+    /// (package, old version, new version)
+    bumped: Vec<(String, String, String)>,
+    /// (dependent package, dependency, new version)
+    requirements: Vec<(String, String, String)>,
+}
+
+/// Handles `repos version bump <level>`.
+pub async fn handle_version_bump_command(options: VersionBumpOptions) -> Result<()> {
+    set_terminal_title("🏷️ repos");
+
+    let (_, repos) = init_command(SCANNING_MESSAGE).await;
+    if repos.is_empty() {
+        println!("\r{NO_REPOS_MESSAGE}");
+        set_terminal_title_and_flush("✅ repos");
+        return Ok(());
+    }
+
+    let manifest = WorkspaceManifest::load(Path::new("."))?;
+    let fleet = collect_packages(&repos, manifest.as_ref()).await;
+    let notes = if options.level.is_none() {
+        collect_release_notes(&fleet, &options).await?
+    } else {
+        HashMap::new()
+    };
+    let bumps = plan_bumps(&fleet, &options, &notes)?;
+    if bumps.is_empty() {
+        if !notes.is_empty() {
+            println!("\r🏷️  No unreleased features or fixes to bump for\n");
+            report_unchanged(&fleet, &options, &notes);
+            println!();
+        } else if options.targets.is_empty() {
+            println!("\r🏷️  No packages found in any repository\n");
+        } else {
+            println!(
+                "\r🏷️  No packages found matching: {}\n",
+                options.targets.join(", ")
+            );
+        }
+        set_terminal_title_and_flush("✅ repos");
+        return Ok(());
+    }
+
+    println!(
+        "\r🏷️  {} {} {} ({})\n",
+        if options.dry_run {
+            "Would bump"
+        } else {
+            "Bumping"
+        },
+        bumps.len(),
+        if bumps.len() == 1 {
+            "package"
+        } else {
+            "packages"
+        },
+        options.level.map_or("auto", BumpLevel::label)
+    );
+    for bump in &bumps {
+        println!(
+            "  {} {:<30} {} → {}{}",
+            bump.package.manager.icon(),
+            package_label(bump.package),
+            bump.package.info.version,
+            bump.new,
+            if options.level.is_none() {
+                format!(" ({})", bump.level.label())
+            } else {
+                String::new()
+            }
+        );
+    }
+    report_unchanged(&fleet, &options, &notes);
+
+    if options.dry_run {
+        let dependents = dependents_of(&fleet, &bumps);
+        if !dependents.is_empty() {
+            println!("\nRequirements that may move with them:");
+            for (dependent, dependency) in dependents {
+                println!("  ↳ {dependent} requires {dependency}");
+            }
+        }
+        println!("\nDry run - no manifests changed\n");
+        set_terminal_title_and_flush("✅ repos");
+        return Ok(());
+    }
+
+    let mut failures = Vec::new();
+    let changes = apply_bumps(&repos, &fleet, &bumps, &mut failures).await;
+
+    let requirement_count: usize = changes.values().map(|c| c.requirements.len()).sum();
+    if requirement_count > 0 {
+        println!();
+        for change in changes.values() {
+            for (dependent, dependency, version) in &change.requirements {
+                println!("  ↳ {dependent}: {dependency} requirement → {version}");
+            }
+        }
+    }
+
+    let mut committed = 0;
+    if options.commit {
+        let journal = JournalRecorder::new("version", RestoreMode::Keep);
+        for (repo, change) in &changes {
+            let result = journal
+                .track(repo, &change.path, commit_changes(change))
+                .await;
+            match result {
+                Ok(()) => committed += 1,
+                Err(e) => failures.push((repo.clone(), format!("commit failed: {e}"))),
+            }
+        }
+        journal.finish();
+    }
+
+    println!(
+        "\n✅ Bumped {} {}, updated {requirement_count} {} in {} {}{}",
+        bumps.len(),
+        if bumps.len() == 1 {
+            "package"
+        } else {
+            "packages"
+        },
+        if requirement_count == 1 {
+            "requirement"
+        } else {
+            "requirements"
+        },
+        changes.len(),
+        if changes.len() == 1 {
+            "repository"
+        } else {
+            "repositories"
+        },
+        if options.commit {
+            format!(" ({committed} committed)")
+        } else {
+            String::new()
+        }
+    );
+
+    set_terminal_title_and_flush("✅ repos");
+    if !failures.is_empty() {
+        for (repo, error) in &failures {
+            eprintln!("❌ {repo}: {error}");
+        }
+        anyhow::bail!("{} version changes failed", failures.len());
+    }
+    println!();
+    Ok(())
+}
+
+/// Reads every package in the fleet, including Cargo workspace members.
+async fn collect_packages(
+    repos: &[(String, PathBuf)],
+    manifest: Option<&WorkspaceManifest>,
+) -> Vec<FleetPackage> {
+    let mut fleet = Vec::new();
+    for (repo, repo_path) in repos {
+        let Some(manager) = detect_manager(repo_path).await else {
+            continue;
+        };
+        let tag_template = tag::template_for(manifest, repo_path, Some(manager.name()));
+        let packages = manager.list_packages(repo_path).await;
+        let workspace_member =
+            packages.len() > 1 || packages.first().is_some_and(|(path, _)| path != repo_path);
+        for (path, info) in packages {
+            fleet.push(FleetPackage {
+                repo: repo.clone(),
+                repo_path: repo_path.clone(),
+                path,
+                manager: Arc::clone(&manager),
+                info,
+                workspace_member,
+                tag_template: tag_template.clone(),
+            });
+        }
+    }
+    fleet
+}
+
+fn package_label(package: &FleetPackage) -> String {
+    if package.path == package.repo_path {
+        package.repo.clone()
+    } else {
+        format!("{}/{}", package.repo, package.info.name)
+    }
+}
+
+fn is_targeted(package: &FleetPackage, options: &VersionBumpOptions) -> bool {
+    options.targets.is_empty() || options.targets.contains(&package.repo)
+}
+
+/// Reads conventional commits since the last release tag of every targeted
+/// package, for `auto`, keyed by package label. A workspace member counts
+/// only the commits touching its directory.
+async fn collect_release_notes(
+    fleet: &[FleetPackage],
+    options: &VersionBumpOptions,
+) -> Result<HashMap<String, ReleaseNotes>> {
+    let mut notes = HashMap::new();
+    for package in fleet.iter().filter(|package| is_targeted(package, options)) {
+        let tag_match = tag::match_pattern(
+            &package.tag_template,
+            &package.info.name,
+            package.workspace_member,
+        );
+        let package_path = package
+            .path
+            .strip_prefix(&package.repo_path)
+            .ok()
+            .filter(|path| !path.as_os_str().is_empty());
+        let package_notes = release_notes(&package.repo_path, None, &tag_match, package_path)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}: {e}", package_label(package)))?;
+        notes.insert(package_label(package), package_notes);
+    }
+    Ok(notes)
+}
+
+/// Names the packages `auto` leaves alone because nothing warrants a release.
+fn report_unchanged(
+    fleet: &[FleetPackage],
+    options: &VersionBumpOptions,
+    notes: &HashMap<String, ReleaseNotes>,
+) {
+    for package in fleet.iter().filter(|package| is_targeted(package, options)) {
+        if let Some(package_notes) = notes
+            .get(&package_label(package))
+            .filter(|notes| notes.is_empty())
+        {
+            let since = package_notes
+                .since_tag
+                .as_deref()
+                .unwrap_or("the first commit");
+            println!(
+                "  · {:<30} no features or fixes since {since}",
+                package_label(package)
+            );
+        }
+    }
+}
+
+/// Computes every targeted package's next version before touching any file,
+/// so one unparseable version stops the run with nothing half-bumped.
+fn plan_bumps<'a>(
+    fleet: &'a [FleetPackage],
+    options: &VersionBumpOptions,
+    notes: &HashMap<String, ReleaseNotes>,
+) -> Result<Vec<PlannedBump<'a>>> {
+    let mut bumps = Vec::new();
+    for package in fleet.iter().filter(|package| is_targeted(package, options)) {
+        let current = Version::parse(&package.info.version)
+            .map_err(|e| anyhow::anyhow!("{}: {e}", package_label(package)))?;
+        let level = match options.level {
+            Some(level) => level,
+            None => match notes
+                .get(&package_label(package))
+                .and_then(|notes| notes.suggested_bump(Some(&current)))
+            {
+                Some(level) => level,
+                None => continue,
+            },
+        };
+        let next = current
+            .bump(level, options.pre_id.as_deref())
+            .map_err(|e| anyhow::anyhow!("{}: {e}", package_label(package)))?;
+        bumps.push(PlannedBump {
+            package,
+            level,
+            new: next.to_string(),
+        });
+    }
+    Ok(bumps)
+}
+
+/// Fleet packages declaring a dependency on a bumped package, for the dry run.
+fn dependents_of(fleet: &[FleetPackage], bumps: &[PlannedBump<'_>]) -> Vec<(String, String)> {
+    let mut dependents = Vec::new();
+    for bump in bumps {
+        for package in fleet {
+            if package.manager.name() == bump.package.manager.name()
+                && package.info.dependencies.contains(&bump.package.info.name)
+            {
+                dependents.push((package_label(package), bump.package.info.name.clone()));
+            }
+        }
+    }
+    dependents
+}
+
+fn change_for<'c>(
+    changes: &'c mut BTreeMap<String, RepoChanges>,
+    repo: &str,
+    path: &Path,
+) -> &'c mut RepoChanges {
+    let change = changes.entry(repo.to_string()).or_default();
+    change.path = path.to_path_buf();
+    change
+}
+
+/// Writes the planned versions, then moves fleet requirements that name the
+/// old versions. Failures are collected so one bad manifest does not hide
+/// the rest of the run.
+async fn apply_bumps(
+    repos: &[(String, PathBuf)],
+    fleet: &[FleetPackage],
+    bumps: &[PlannedBump<'_>],
+    failures: &mut Vec<(String, String)>,
+) -> BTreeMap<String, RepoChanges> {
+    let mut changes: BTreeMap<String, RepoChanges> = BTreeMap::new();
+
+    for bump in bumps {
+        let package = bump.package;
+        match package
+            .manager
+            .set_version(&package.path, &package.info.version, &bump.new)
+            .await
+        {
+            Ok(file) => {
+                let change = change_for(&mut changes, &package.repo, &package.repo_path);
+                change.files.insert(file);
+                change.bumped.push((
+                    package.info.name.clone(),
+                    package.info.version.clone(),
+                    bump.new.clone(),
+                ));
+            }
+            Err(e) => failures.push((package_label(package), e.to_string())),
+        }
+    }
+
+    // Requirements can live in any package manifest or in a repository root
+    // that is not itself a package (a virtual Cargo workspace).
+    let mut manifests: Vec<(&str, &Path, PathBuf, String)> = fleet
+        .iter()
+        .map(|package| {
+            (
+                package.repo.as_str(),
+                package.repo_path.as_path(),
+                package.path.clone(),
+                package_label(package),
+            )
+        })
+        .collect();
+    for (repo, repo_path) in repos {
+        if !manifests.iter().any(|(_, _, path, _)| path == repo_path) {
+            manifests.push((repo, repo_path, repo_path.clone(), repo.clone()));
+        }
+    }
+
+    for bump in bumps {
+        let dependency = &bump.package.info;
+        for (repo, repo_path, path, label) in &manifests {
+            if *path == bump.package.path {
+                continue;
+            }
+            let Some(manager) = detect_manager(path).await else {
+                continue;
+            };
+            if manager.name() != bump.package.manager.name() {
+                continue;
+            }
+            match manager
+                .update_dependency(path, &dependency.name, &dependency.version, &bump.new)
+                .await
+            {
+                Ok(Some(file)) => {
+                    let change = change_for(&mut changes, repo, repo_path);
+                    change.files.insert(file);
+                    change.requirements.push((
+                        label.clone(),
+                        dependency.name.clone(),
+                        bump.new.clone(),
+                    ));
+                }
+                Ok(None) => {}
+                Err(e) => failures.push((label.clone(), e.to_string())),
+            }
+        }
+    }
+
+    changes
+}
+
+/// Commit message naming every version and requirement change in a repository.
+fn commit_message(change: &RepoChanges) -> String {
+    let subject = match change.bumped.as_slice() {
+        [(name, _, new)] => format!("Bump {name} to {new}"),
+        [] => {
+            let dependencies: BTreeSet<&str> = change
+                .requirements
+                .iter()
+                .map(|(_, dependency, _)| dependency.as_str())
+                .collect();
+            format!(
+                "Update {} requirements",
+                dependencies.into_iter().collect::<Vec<_>>().join(", ")
+            )
+        }
+        bumped => format!("Bump {} package versions", bumped.len()),
+    };
+
+    let mut lines: Vec<String> = change
+        .bumped
+        .iter()
+        .map(|(name, old, new)| format!("- {name} {old} → {new}"))
+        .collect();
+    lines.extend(
+        change
+            .requirements
+            .iter()
+            .map(|(dependent, dependency, new)| format!("- {dependent}: {dependency} → {new}")),
+    );
+    if lines.len() <= 1 {
+        return subject;
+    }
+    format!("{subject}\n\n{}", lines.join("\n"))
+}
+
+/// Commits only the manifests this command wrote.
+async fn commit_changes(change: &RepoChanges) -> Result<()> {
+    let message = commit_message(change);
+    let mut args = vec![
+        "commit".to_string(),
+        "-m".to_string(),
+        message,
+        "--".to_string(),
+    ];
+    args.extend(change.files.iter().map(|file| {
+        file.strip_prefix(&change.path)
+            .unwrap_or(file)
+            .display()
+            .to_string()
+    }));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (success, _, stderr) = run_git(&change.path, &args).await?;
+    if !success {
+        anyhow::bail!("{}", stderr.trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_messages_name_the_bump_and_list_every_change() {
+        let mut change = RepoChanges {
+            bumped: vec![("core-lib".into(), "1.2.0".into(), "1.3.0".into())],
+            ..RepoChanges::default()
+        };
+        assert_eq!(commit_message(&change), "Bump core-lib to 1.3.0");
+
+        change
+            .requirements
+            .push(("app".into(), "core-lib".into(), "1.3.0".into()));
+        assert_eq!(
+            commit_message(&change),
+            "Bump core-lib to 1.3.0\n\n- core-lib 1.2.0 → 1.3.0\n- app: core-lib → 1.3.0"
+        );
+
+        change.bumped.clear();
+        assert_eq!(commit_message(&change), "Update core-lib requirements");
+    }
+}
@@ -20,7 +20,7 @@ pub use super::progress::{
     create_generic_processing_context, create_processing_context, GenericProcessingContext,
     ProcessingContext,
 };
-pub(crate) use super::report::{generate_sync_report, BatchOperation};
+pub(crate) use super::report::{generate_sync_report, BatchOperation, CommandCapture};
 pub use super::stats::SyncStatistics;
 
 /// Searches for Git repositories from a specific path.
@@ -29,13 +29,40 @@ pub use super::stats::SyncStatistics;
 #[allow(unused_imports)] // Used by integration tests
 pub use super::discovery::find_repos_from_path;
 
+pub(crate) use super::discovery_cache::rescan_requested;
+/// Discovery through the on-disk cache used by every fleet command (`repos discover`).
+pub use super::discovery_cache::{
+    discovery_cache_path, find_repos_cached, set_discovery_rescan, CacheStatus,
+};
+
 /// Common initialization for commands that scan repositories.
 pub use super::discovery::init_command;
 pub(crate) use super::discovery::init_command_quiet;
 
+/// Operation journal read by `repos undo`.
+pub(crate) use super::journal::{
+    short_commit, DeletedBranch, HeadState, Journal, JournalEntry, JournalRecorder, RestoreMode,
+};
+
+/// Declarative workspace manifest (`repos.toml`) used by `repos bootstrap`.
+pub use super::manifest::{ManifestRepository, WorkspaceManifest, MANIFEST_FILE_NAME};
+
+pub(crate) use super::output::{
+    emit_report, emit_repository, emit_start, is_human_output, report_no_repositories,
+    repository_records, sort_records, RepositoryRecord, UpstreamRecord,
+};
+/// Machine-readable `--format json|ndjson` output.
+pub use super::output::{set_output_format, OutputFormat, OUTPUT_SCHEMA_VERSION};
+
+pub(crate) use super::selection::apply_active_selection;
+/// Group, tag, and exclusion targeting applied after discovery.
+pub use super::selection::{set_repo_selection, RepoSelection, RepoSelector};
+
 // Configuration
 pub use super::config::GIT_CONCURRENT_CAP;
 pub use super::config::{HYGIENE_CONCURRENT_LIMIT, TRUFFLE_CONCURRENT_LIMIT};
+/// Layered `repos` configuration (system, user, workspace, env, `--set`).
+pub use super::settings::init_settings;
 
 // User-facing messages
 pub use super::config::{CONFIG_SYNCING_MESSAGE, NO_REPOS_MESSAGE};
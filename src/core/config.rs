@@ -1,5 +1,8 @@
 //! Configuration constants and settings
 //!
+//! Constants that users can override are the built-in defaults of the layered
+//! configuration in `core::settings`; read the resolved values from there.
+//!
 //! **API Stability Note**: Only items re-exported through `core::api` are part of the
 //! stable public API. Other `pub` items in this module are internal implementation details
 //! subject to change. External crates should import through `goobits_repos::core::*` rather than
@@ -21,6 +24,10 @@ pub const FETCH_CONCURRENT_CAP: usize = 24;
 #[doc(hidden)] // Internal implementation detail
 pub const SLOW_REPO_THRESHOLD_SECS: u64 = 10;
 
+// Default timeout for a single git invocation (3 minutes per repository)
+#[doc(hidden)] // Internal implementation detail
+pub const GIT_OPERATION_TIMEOUT_SECS: u64 = 180;
+
 // Default concurrency for commands that don't support --jobs flag yet
 // Increased from 12 to 32 to better utilize modern multi-core systems
 pub const GIT_CONCURRENT_CAP: usize = 32;
@@ -57,6 +64,19 @@ pub fn get_git_concurrency(jobs: Option<usize>, sequential: bool) -> usize {
 pub const TRUFFLE_CONCURRENT_LIMIT: usize = 1; // For CPU-intensive TruffleHog secret scans
 pub const HYGIENE_CONCURRENT_LIMIT: usize = 3; // For I/O-bound hygiene git operations
 
+// Large file threshold in bytes (1MB) reported by hygiene checks
+pub const LARGE_FILE_THRESHOLD: u64 = 1_048_576;
+
+// Registries `repos publish` queries to see whether a version is already published
+pub const CARGO_INDEX_URL: &str = "https://index.crates.io";
+pub const NPM_REGISTRY_URL: &str = "https://registry.npmjs.org";
+pub const PYPI_URL: &str = "https://pypi.org";
+pub const GO_PROXY_URL: &str = "https://proxy.golang.org";
+pub const RUBYGEMS_URL: &str = "https://rubygems.org";
+pub const MAVEN_REPOSITORY_URL: &str = "https://repo1.maven.org/maven2";
+pub const HEX_API_URL: &str = "https://hex.pm/api";
+pub const NUGET_URL: &str = "https://api.nuget.org/v3-flatcontainer";
+
 // Progress bar configuration
 #[doc(hidden)] // Internal UI detail
 pub const DEFAULT_PROGRESS_BAR_LENGTH: u64 = 100;
@@ -80,8 +100,6 @@ pub const PATH_DISPLAY_WIDTH: usize = 30;
 pub const ERROR_MESSAGE_MAX_LENGTH: usize = 40;
 #[doc(hidden)] // Internal formatting detail
 pub const ERROR_MESSAGE_TRUNCATE_LENGTH: usize = 37;
-#[doc(hidden)] // Internal formatting detail
-pub const TIMEOUT_SECONDS_DISPLAY: u64 = 180;
 
 // Processing limits and chunk sizes
 #[doc(hidden)] // Internal processing detail
@@ -8,9 +8,11 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use super::config::{
-    DEFAULT_REPO_NAME, ESTIMATED_REPO_COUNT, MAX_SCAN_DEPTH, SKIP_DIRECTORIES, UNKNOWN_REPO_NAME,
-};
+use super::config::{DEFAULT_REPO_NAME, ESTIMATED_REPO_COUNT, UNKNOWN_REPO_NAME};
+use super::discovery_cache::{find_repos_cached, rescan_requested};
+use super::output::is_human_output;
+use super::selection::apply_active_selection;
+use super::settings::settings;
 
 /// Check if a .git file (for submodules/worktrees) contains gitdir reference
 /// Only reads the first 5 lines for efficiency
@@ -33,11 +35,26 @@ fn is_git_file(path: &Path) -> bool {
 /// Returns a vector of (`repository_name`, path) tuples with deduplication
 ///
 /// Directory walking is parallel, while naming happens after paths are sorted so
-/// duplicate-name suffixes are stable across runs.
+/// duplicate-name suffixes are stable across runs. This always walks the tree;
+/// [`find_repos`] goes through the discovery cache instead.
 pub fn find_repos_from_path(search_path: impl AsRef<Path>) -> Vec<(String, PathBuf)> {
     let search_path = search_path.as_ref();
+    name_repositories(search_path, walk_repositories(search_path).repositories)
+}
+
+/// Repositories and directories seen by one discovery walk.
+pub(crate) struct DiscoveryWalk {
+    /// Repository paths, sorted.
+    pub repositories: Vec<PathBuf>,
+    /// Every directory whose entries were read, sorted.
+    pub directories: Vec<PathBuf>,
+}
 
+/// Walks `search_path` for repositories without naming them.
+pub(crate) fn walk_repositories(search_path: &Path) -> DiscoveryWalk {
     let repos_seen = Arc::new(DashMap::with_capacity(ESTIMATED_REPO_COUNT));
+    let dirs_seen = Arc::new(DashMap::with_capacity(ESTIMATED_REPO_COUNT));
+    let max_depth = settings().max_scan_depth.value;
 
     // Build parallel walker with optimizations
     let walker = WalkBuilder::new(search_path)
@@ -45,7 +62,7 @@ pub fn find_repos_from_path(search_path: impl AsRef<Path>) -> Vec<(String, PathB
         // that boundary must not hide repositories below it.
         .parents(false)
         .follow_links(true) // Follow symlinks to find symlinked repos
-        .max_depth(Some(MAX_SCAN_DEPTH)) // Limit depth to avoid deep recursion
+        .max_depth(Some(max_depth)) // Limit depth to avoid deep recursion
         .threads(
             std::thread::available_parallelism()
                 .map(std::num::NonZeroUsize::get)
@@ -56,7 +73,12 @@ pub fn find_repos_from_path(search_path: impl AsRef<Path>) -> Vec<(String, PathB
             let file_name = entry.file_name().to_str().unwrap_or("");
 
             // Skip common build/dependency directories
-            if SKIP_DIRECTORIES.contains(&file_name) {
+            if settings()
+                .skip_directories
+                .value
+                .iter()
+                .any(|skipped| skipped == file_name)
+            {
                 return false;
             }
 
@@ -73,6 +95,7 @@ pub fn find_repos_from_path(search_path: impl AsRef<Path>) -> Vec<(String, PathB
     // Walk the directory tree in parallel
     walker.run(|| {
         let repos_seen = Arc::clone(&repos_seen);
+        let dirs_seen = Arc::clone(&dirs_seen);
 
         Box::new(move |result| {
             use ignore::WalkState;
@@ -84,6 +107,10 @@ pub fn find_repos_from_path(search_path: impl AsRef<Path>) -> Vec<(String, PathB
                 if !entry.file_type().is_some_and(|ft| ft.is_dir()) {
                     return WalkState::Continue;
                 }
+                // Directories at the depth limit are listed but never read.
+                if entry.depth() < max_depth {
+                    dirs_seen.insert(path.to_path_buf(), ());
+                }
 
                 // Check if this directory contains a .git entry
                 let git_path = path.join(".git");
@@ -108,11 +135,23 @@ pub fn find_repos_from_path(search_path: impl AsRef<Path>) -> Vec<(String, PathB
         })
     });
 
-    let mut paths: Vec<PathBuf> = Arc::try_unwrap(repos_seen)
+    DiscoveryWalk {
+        repositories: into_sorted_paths(repos_seen),
+        directories: into_sorted_paths(dirs_seen),
+    }
+}
+
+fn into_sorted_paths(seen: Arc<DashMap<PathBuf, ()>>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Arc::try_unwrap(seen)
         .map(|map| map.into_iter().map(|(path, ())| path).collect())
         .unwrap_or_else(|arc| arc.iter().map(|entry| entry.key().clone()).collect());
     paths.sort();
+    paths
+}
 
+/// Names sorted repository paths found under `search_path`, suffixing
+/// duplicate directory names (`api`, `api-2`, ...).
+pub(crate) fn name_repositories(search_path: &Path, paths: Vec<PathBuf>) -> Vec<(String, PathBuf)> {
     let mut name_counts = HashMap::with_capacity(paths.len());
     let mut repos: Vec<(String, PathBuf)> = paths
         .into_iter()
@@ -151,15 +190,20 @@ pub fn find_repos_from_path(search_path: impl AsRef<Path>) -> Vec<(String, PathB
 /// Recursively searches for git repositories in the current directory
 /// Returns a vector of (`repository_name`, path) tuples with deduplication
 ///
-/// This is a convenience wrapper around `find_repos_from_path()` that searches
-/// from the current working directory.
+/// Searches from the current working directory through the discovery cache
+/// (see `discovery_cache`) and applies the active `--group`, `--tag`, and
+/// `--exclude` selection.
 pub fn find_repos() -> Vec<(String, PathBuf)> {
-    find_repos_from_path(".")
+    apply_active_selection(find_repos_cached(".", rescan_requested()).0)
 }
 
 /// Common initialization for commands that scan repositories
 #[must_use]
 pub async fn init_command(scanning_msg: &str) -> (std::time::Instant, Vec<(String, PathBuf)>) {
+    if !is_human_output() {
+        return init_command_quiet().await;
+    }
+
     println!();
     print!("{scanning_msg}");
     // Flush stdout - ignore errors as this is non-critical
@@ -0,0 +1,421 @@
+//! On-disk cache of discovered repository paths.
+//!
+//! Walking a large tree for `.git` entries takes seconds, so every walk is
+//! recorded per scan root in `$XDG_CACHE_HOME/repos/discovery.json` (or the
+//! directory in `REPOS_CACHE_DIR`). Alongside the repositories, the cache
+//! stores the modification time of each directory the walk read outside a
+//! repository's worktree, of every repository directory, and of every ancestor
+//! of one. Creating, removing, or renaming a directory entry changes its
+//! parent's mtime, so comparing those stamps detects added and removed
+//! repositories without reading a single directory. Editing an ignore file in
+//! place leaves its directory's mtime alone, so the `.gitignore`, `.ignore`,
+//! and `.git/info/exclude` files in recorded directories are stamped too.
+//!
+//! A repository created deeper inside another repository's worktree than any
+//! known nested repository is not noticed until `--rescan`, and neither is a
+//! change to Git's global excludes file (`core.excludesFile`).
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use super::discovery::{name_repositories, walk_repositories, DiscoveryWalk};
+use super::settings::settings;
+
+const CACHE_DIR_ENV: &str = "REPOS_CACHE_DIR";
+const CACHE_FILE_NAME: &str = "discovery.json";
+const CACHE_VERSION: u32 = 2;
+
+/// Files in a directory that change what the walk skips below it.
+const IGNORE_FILES: &[&str] = &[".gitignore", ".ignore", ".git/info/exclude"];
+
+/// Directory stamps this close to the walk are not trusted: filesystems
+/// update mtimes from a coarse clock, so a change made right after the walk
+/// can leave the stamp unchanged.
+const RACY_STAMP_WINDOW: Duration = Duration::from_secs(1);
+
+static RESCAN: AtomicBool = AtomicBool::new(false);
+
+/// Makes every discovery in this process walk the tree and refresh the cache
+/// (`--rescan`).
+pub fn set_discovery_rescan(rescan: bool) {
+    RESCAN.store(rescan, Ordering::Relaxed);
+}
+
+pub(crate) fn rescan_requested() -> bool {
+    RESCAN.load(Ordering::Relaxed)
+}
+
+/// Where a discovery result came from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CacheStatus {
+    /// Every recorded directory was unchanged; nothing was walked.
+    Hit,
+    /// The root had not been scanned yet, or with different discovery settings.
+    Miss,
+    /// A recorded directory changed, so the tree was walked again.
+    Stale,
+    /// `--rescan` forced a walk.
+    Rescan,
+    /// No cache directory could be determined.
+    Disabled,
+}
+
+impl CacheStatus {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Hit => "cached",
+            Self::Miss => "scanned (not cached yet)",
+            Self::Stale => "rescanned (tree changed)",
+            Self::Rescan => "rescanned (--rescan)",
+            Self::Disabled => "scanned (no cache directory)",
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+struct DiscoveryCache {
+    version: u32,
+    roots: BTreeMap<PathBuf, CachedScan>,
+}
+
+/// One root's walk. Paths are relative to the root; the root itself is `""`.
+#[derive(Deserialize, Serialize)]
+struct CachedScan {
+    max_scan_depth: usize,
+    skip_directories: Vec<String>,
+    started_at: SystemTime,
+    repositories: Vec<PathBuf>,
+    directories: Vec<PathStamp>,
+    /// Ignore files in the recorded directories.
+    ignore_files: Vec<PathStamp>,
+}
+
+/// A recorded directory or ignore file and its modification time.
+#[derive(Deserialize, Serialize)]
+struct PathStamp {
+    path: PathBuf,
+    modified: SystemTime,
+}
+
+/// Returns the cache file for this process, if a cache directory is known.
+#[must_use]
+pub fn discovery_cache_path() -> Option<PathBuf> {
+    let dir = std::env::var_os(CACHE_DIR_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("XDG_CACHE_HOME")
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+                .map(|cache| cache.join("repos"))
+        })?;
+    Some(dir.join(CACHE_FILE_NAME))
+}
+
+/// Like [`find_repos_from_path`](super::discovery::find_repos_from_path), but
+/// answers from the discovery cache while no recorded directory changed.
+///
+/// Walks (and refreshes the cache) when `rescan` is set, the root is not
+/// cached, or the cached walk is stale. Cache write failures are ignored:
+/// the cache only ever saves time.
+pub fn find_repos_cached(
+    search_path: impl AsRef<Path>,
+    rescan: bool,
+) -> (Vec<(String, PathBuf)>, CacheStatus) {
+    find_repos_cached_in(
+        discovery_cache_path().as_deref(),
+        search_path.as_ref(),
+        rescan,
+    )
+}
+
+fn find_repos_cached_in(
+    cache_path: Option<&Path>,
+    search_path: &Path,
+    rescan: bool,
+) -> (Vec<(String, PathBuf)>, CacheStatus) {
+    let (Some(cache_path), Ok(root)) = (cache_path, search_path.canonicalize()) else {
+        let walk = walk_repositories(search_path);
+        return (
+            name_repositories(search_path, walk.repositories),
+            CacheStatus::Disabled,
+        );
+    };
+
+    let mut cache = DiscoveryCache::load(cache_path);
+    let status = match cache.roots.get(&root) {
+        _ if rescan => CacheStatus::Rescan,
+        None => CacheStatus::Miss,
+        Some(scan) if !scan.matches_settings() => CacheStatus::Miss,
+        Some(scan) if scan.is_current(search_path) => {
+            return (
+                name_repositories(search_path, scan.repository_paths(search_path)),
+                CacheStatus::Hit,
+            );
+        }
+        Some(_) => CacheStatus::Stale,
+    };
+
+    let started_at = SystemTime::now();
+    let walk = walk_repositories(search_path);
+    cache
+        .roots
+        .insert(root, CachedScan::record(search_path, &walk, started_at));
+    // Forget roots that were deleted (temporary checkouts, old clones).
+    cache.roots.retain(|root, _| root.is_dir());
+    let _ = cache.save(cache_path);
+
+    (name_repositories(search_path, walk.repositories), status)
+}
+
+impl DiscoveryCache {
+    fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Self>(&contents).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .unwrap_or_default()
+    }
+
+    fn save(&mut self, path: &Path) -> anyhow::Result<()> {
+        self.version = CACHE_VERSION;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Concurrent runs each replace the whole file; the last one wins.
+        let temp = path.with_extension(format!("json.{}", std::process::id()));
+        fs::write(&temp, serde_json::to_vec(self)?)?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+}
+
+impl CachedScan {
+    fn record(search_path: &Path, walk: &DiscoveryWalk, started_at: SystemTime) -> Self {
+        let repositories: HashSet<&Path> = walk.repositories.iter().map(PathBuf::as_path).collect();
+        let ancestors: HashSet<&Path> = walk
+            .repositories
+            .iter()
+            .flat_map(|repo| repo.ancestors().skip(1))
+            .collect();
+        // Directories inside a worktree only matter on the way to a nested
+        // repository; recording all of them would rival the walk itself.
+        let inside_repository = |dir: &Path| {
+            dir.ancestors()
+                .skip(1)
+                .take_while(|ancestor| ancestor.starts_with(search_path))
+                .any(|ancestor| repositories.contains(ancestor))
+        };
+
+        let stamp = |path: &Path| {
+            Some(PathStamp {
+                path: relative(search_path, path),
+                modified: fs::metadata(path).ok()?.modified().ok()?,
+            })
+        };
+
+        let recorded: Vec<&PathBuf> = walk
+            .directories
+            .iter()
+            .filter(|dir| {
+                repositories.contains(dir.as_path())
+                    || ancestors.contains(dir.as_path())
+                    || !inside_repository(dir)
+            })
+            .collect();
+        let directories = recorded.iter().filter_map(|dir| stamp(dir)).collect();
+        // A missing ignore file needs no stamp: creating one changes its
+        // directory's mtime.
+        let ignore_files = recorded
+            .iter()
+            .flat_map(|dir| IGNORE_FILES.iter().map(move |file| dir.join(file)))
+            .filter_map(|file| stamp(&file))
+            .collect();
+
+        Self {
+            max_scan_depth: settings().max_scan_depth.value,
+            skip_directories: settings().skip_directories.value.clone(),
+            started_at,
+            repositories: walk
+                .repositories
+                .iter()
+                .map(|repo| relative(search_path, repo))
+                .collect(),
+            directories,
+            ignore_files,
+        }
+    }
+
+    fn matches_settings(&self) -> bool {
+        self.max_scan_depth == settings().max_scan_depth.value
+            && self.skip_directories == settings().skip_directories.value
+    }
+
+    fn is_current(&self, search_path: &Path) -> bool {
+        let trusted_before = self
+            .started_at
+            .checked_sub(RACY_STAMP_WINDOW)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        self.directories
+            .iter()
+            .chain(&self.ignore_files)
+            .all(|stamp| {
+                stamp.modified < trusted_before
+                    && fs::metadata(absolute(search_path, &stamp.path))
+                        .and_then(|metadata| metadata.modified())
+                        .is_ok_and(|modified| modified == stamp.modified)
+            })
+    }
+
+    fn repository_paths(&self, search_path: &Path) -> Vec<PathBuf> {
+        self.repositories
+            .iter()
+            .map(|repo| absolute(search_path, repo))
+            .collect()
+    }
+}
+
+fn relative(search_path: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(search_path)
+        .map_or_else(|_| path.to_path_buf(), Path::to_path_buf)
+}
+
+/// Rebuilds a path as the walker would have produced it from `search_path`.
+fn absolute(search_path: &Path, relative: &Path) -> PathBuf {
+    if relative.as_os_str().is_empty() {
+        search_path.to_path_buf()
+    } else {
+        search_path.join(relative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn init(path: &Path) {
+        fs::create_dir_all(path).unwrap();
+        let status = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// Moves every recorded stamp out of the racy window, as if the walk had
+    /// happened well after the last change.
+    fn age_cache(cache_path: &Path) {
+        let mut cache = DiscoveryCache::load(cache_path);
+        for scan in cache.roots.values_mut() {
+            scan.started_at += Duration::from_secs(60);
+        }
+        cache.save(cache_path).unwrap();
+    }
+
+    fn names(repos: &[(String, PathBuf)]) -> Vec<&str> {
+        repos.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn cache_hits_until_a_recorded_directory_changes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("fleet");
+        let cache = temp.path().join("cache").join(CACHE_FILE_NAME);
+        init(&root.join("api"));
+        init(&root.join("services").join("web"));
+        fs::create_dir_all(root.join("notes")).unwrap();
+
+        let (repos, status) = find_repos_cached_in(Some(&cache), &root, false);
+        assert_eq!(status, CacheStatus::Miss);
+        assert_eq!(names(&repos), ["api", "web"]);
+
+        // Fresh stamps are racy and force another walk.
+        let (_, status) = find_repos_cached_in(Some(&cache), &root, false);
+        assert_eq!(status, CacheStatus::Stale);
+
+        age_cache(&cache);
+        let (repos, status) = find_repos_cached_in(Some(&cache), &root, false);
+        assert_eq!(status, CacheStatus::Hit);
+        assert_eq!(names(&repos), ["api", "web"]);
+        assert_eq!(repos[1].1, root.join("services").join("web"));
+
+        // A repository in a directory that held none before is still noticed.
+        init(&root.join("notes").join("journal"));
+        let (repos, status) = find_repos_cached_in(Some(&cache), &root, false);
+        assert_eq!(status, CacheStatus::Stale);
+        assert_eq!(names(&repos), ["api", "journal", "web"]);
+
+        age_cache(&cache);
+        fs::remove_dir_all(root.join("api")).unwrap();
+        let (repos, status) = find_repos_cached_in(Some(&cache), &root, false);
+        assert_eq!(status, CacheStatus::Stale);
+        assert_eq!(names(&repos), ["journal", "web"]);
+
+        age_cache(&cache);
+        let (_, status) = find_repos_cached_in(Some(&cache), &root, true);
+        assert_eq!(status, CacheStatus::Rescan);
+    }
+
+    #[test]
+    fn editing_an_ignore_file_invalidates_the_cache() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("fleet");
+        let cache = temp.path().join("cache").join(CACHE_FILE_NAME);
+        init(&root.join("api"));
+        init(&root.join("archive").join("old"));
+        fs::write(root.join(".ignore"), "scratch/\n").unwrap();
+
+        let (repos, _) = find_repos_cached_in(Some(&cache), &root, false);
+        assert_eq!(names(&repos), ["api", "old"]);
+        age_cache(&cache);
+
+        // Rewriting the file in place leaves the root's mtime unchanged.
+        let root_modified = fs::metadata(&root).unwrap().modified().unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(root.join(".ignore"))
+            .and_then(|mut file| std::io::Write::write_all(&mut file, b"archive/\n"))
+            .unwrap();
+        assert_eq!(
+            fs::metadata(&root).unwrap().modified().unwrap(),
+            root_modified
+        );
+
+        let (repos, status) = find_repos_cached_in(Some(&cache), &root, false);
+        assert_eq!(status, CacheStatus::Stale);
+        assert_eq!(names(&repos), ["api"]);
+    }
+
+    #[test]
+    fn worktree_internals_are_not_recorded() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("fleet");
+        init(&root.join("app"));
+        fs::create_dir_all(root.join("app").join("src").join("deep")).unwrap();
+        init(&root.join("app").join("packages").join("lib"));
+
+        let walk = walk_repositories(&root);
+        let scan = CachedScan::record(&root, &walk, SystemTime::now());
+        let recorded: Vec<&Path> = scan
+            .directories
+            .iter()
+            .map(|stamp| stamp.path.as_path())
+            .collect();
+
+        assert!(recorded.contains(&Path::new("")));
+        assert!(recorded.contains(&Path::new("app")));
+        assert!(recorded.contains(&Path::new("app/packages")));
+        assert!(recorded.contains(&Path::new("app/packages/lib")));
+        assert!(!recorded.contains(&Path::new("app/src")));
+        assert!(!recorded.contains(&Path::new("app/src/deep")));
+    }
+}
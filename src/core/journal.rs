@@ -0,0 +1,416 @@
+//! Operation journal behind `repos undo`.
+//!
+//! Commands that move HEADs across the fleet (`save`, `commit`, `pull`, `sync`,
+//! `switch`, `prune`, and `nested sync`/`update`) record the branch and commit
+//! of every repository they changed, before and after the run, in
+//! `.repos/journal/<id>.json` under the workspace root:
+//!
+//! ```json
+//! {
+//!   "id": "20261017-142501-save",
+//!   "command": "save",
+//!   "created_at": "2026-10-17T14:25:01.120+02:00",
+//!   "restore": "mixed",
+//!   "repositories": [
+//!     {
+//!       "repository": "api",
+//!       "path": "/work/services/api",
+//!       "before": { "branch": "main", "commit": "4f1c2d0…" },
+//!       "after": { "branch": "main", "commit": "9a7b3e1…" }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `repos undo` reads a journal back and restores the recorded HEADs.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use super::output::is_human_output;
+use crate::git::operations::run_git;
+
+/// Journal directory, resolved from the workspace root.
+pub(crate) const JOURNAL_DIR: &str = ".repos/journal";
+
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Where HEAD pointed at one moment.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct HeadState {
+    /// Checked-out branch, or `None` on a detached HEAD.
+    pub(crate) branch: Option<String>,
+    /// Commit HEAD resolves to, or `None` before the first commit.
+    pub(crate) commit: Option<String>,
+}
+
+impl HeadState {
+    /// Reads the current HEAD of the repository at `path`.
+    pub(crate) async fn read(path: &Path) -> Result<Self> {
+        let (on_branch, branch, _) =
+            run_git(path, &["symbolic-ref", "--quiet", "--short", "HEAD"]).await?;
+        let (has_commit, commit, _) =
+            run_git(path, &["rev-parse", "--quiet", "--verify", "HEAD"]).await?;
+        Ok(Self::from_git(on_branch, &branch, has_commit, &commit))
+    }
+
+    /// Blocking variant of [`HeadState::read`] for the synchronous nested commands.
+    pub(crate) fn read_blocking(path: &Path) -> Result<Self> {
+        let git = |args: &[&str]| -> Result<(bool, String)> {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(path)
+                .args(args)
+                .output()
+                .context("Failed to read HEAD")?;
+            Ok((
+                output.status.success(),
+                String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            ))
+        };
+        let (on_branch, branch) = git(&["symbolic-ref", "--quiet", "--short", "HEAD"])?;
+        let (has_commit, commit) = git(&["rev-parse", "--quiet", "--verify", "HEAD"])?;
+        Ok(Self::from_git(on_branch, &branch, has_commit, &commit))
+    }
+
+    fn from_git(on_branch: bool, branch: &str, has_commit: bool, commit: &str) -> Self {
+        Self {
+            branch: (on_branch && !branch.is_empty()).then(|| branch.to_string()),
+            commit: (has_commit && !commit.is_empty()).then(|| commit.to_string()),
+        }
+    }
+
+    /// Short description such as `main at 4f1c2d0` or `detached at 4f1c2d0`.
+    pub(crate) fn describe(&self) -> String {
+        match (&self.branch, &self.commit) {
+            (Some(branch), Some(commit)) => format!("{branch} at {}", short_commit(commit)),
+            (None, Some(commit)) => format!("detached at {}", short_commit(commit)),
+            (Some(branch), None) => format!("{branch} (no commits)"),
+            (None, None) => "no commits".to_string(),
+        }
+    }
+}
+
+/// Returns the abbreviated form of a commit id used in messages.
+pub(crate) fn short_commit(commit: &str) -> String {
+    commit.chars().take(7).collect()
+}
+
+/// How `repos undo` moves a branch back to its recorded commit.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RestoreMode {
+    /// Keep the undone commit's changes staged (`commit`).
+    Soft,
+    /// Keep the undone commit's changes in the worktree (`save`).
+    Mixed,
+    /// Update the worktree too, refusing to overwrite local changes (`pull`).
+    Keep,
+}
+
+impl RestoreMode {
+    /// The matching `git reset` flag.
+    pub(crate) fn reset_flag(self) -> &'static str {
+        match self {
+            Self::Soft => "--soft",
+            Self::Mixed => "--mixed",
+            Self::Keep => "--keep",
+        }
+    }
+}
+
+/// A local branch deleted by the operation.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct DeletedBranch {
+    pub(crate) name: String,
+    pub(crate) commit: String,
+}
+
+/// What one operation changed in one repository.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct JournalEntry {
+    pub(crate) repository: String,
+    pub(crate) path: PathBuf,
+    pub(crate) before: HeadState,
+    pub(crate) after: HeadState,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) deleted_branches: Vec<DeletedBranch>,
+    /// Stash commit created to clear the worktree before the operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) stash: Option<String>,
+}
+
+impl JournalEntry {
+    pub(crate) fn new(repository: &str, path: &Path, before: HeadState, after: HeadState) -> Self {
+        Self {
+            repository: repository.to_string(),
+            path: path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+            before,
+            after,
+            deleted_branches: Vec::new(),
+            stash: None,
+        }
+    }
+
+    /// Returns whether the operation left anything for `repos undo` to restore.
+    pub(crate) fn is_change(&self) -> bool {
+        self.before != self.after || !self.deleted_branches.is_empty() || self.stash.is_some()
+    }
+}
+
+/// One recorded operation.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Journal {
+    pub(crate) id: String,
+    pub(crate) command: String,
+    pub(crate) created_at: String,
+    pub(crate) restore: RestoreMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) undone_at: Option<String>,
+    pub(crate) repositories: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Loads the journal with `id` from the workspace at `root`.
+    pub(crate) fn load(root: &Path, id: &str) -> Result<Self> {
+        let path = root.join(JOURNAL_DIR).join(format!("{id}.json"));
+        if !path.is_file() {
+            anyhow::bail!(
+                "No journal '{id}' in {JOURNAL_DIR}; run `repos undo --list` to see recorded operations"
+            );
+        }
+        read_journal(&path)
+    }
+
+    /// Lists every journal in the workspace at `root`, oldest first.
+    pub(crate) fn list(root: &Path) -> Result<Vec<Self>> {
+        let directory = root.join(JOURNAL_DIR);
+        if !directory.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut journals = Vec::new();
+        for entry in std::fs::read_dir(&directory)
+            .with_context(|| format!("Failed to read {}", directory.display()))?
+        {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                journals.push(read_journal(&path)?);
+            }
+        }
+        journals.sort_by(|left, right| left.created_at.cmp(&right.created_at));
+        Ok(journals)
+    }
+
+    /// Returns the most recent journal that has not been undone yet.
+    pub(crate) fn latest(root: &Path) -> Result<Option<Self>> {
+        Ok(Self::list(root)?
+            .into_iter()
+            .rev()
+            .find(|journal| journal.undone_at.is_none()))
+    }
+
+    /// Writes the journal under `root`, creating the journal directory on first use.
+    pub(crate) fn save(&self, root: &Path) -> Result<PathBuf> {
+        let directory = create_state_dir(root, JOURNAL_DIR)?;
+        let path = directory.join(format!("{}.json", self.id));
+        let json = serde_json::to_string_pretty(self).context("Failed to encode journal")?;
+        std::fs::write(&path, format!("{json}\n"))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Records that the journal was undone so `repos undo` moves on to the previous one.
+    pub(crate) fn mark_undone(&mut self, root: &Path) -> Result<()> {
+        self.undone_at = Some(chrono::Local::now().to_rfc3339());
+        self.save(root).map(|_| ())
+    }
+}
+
+/// Creates `relative` (a directory under `.repos`) in the workspace at `root`.
+pub(crate) fn create_state_dir(root: &Path, relative: &str) -> Result<PathBuf> {
+    let directory = root.join(relative);
+    if !directory.is_dir() {
+        std::fs::create_dir_all(&directory)
+            .with_context(|| format!("Failed to create {}", directory.display()))?;
+    }
+    // Keep repos state out of `repos save --include-untracked` when the
+    // workspace root is itself a repository.
+    let ignore = root.join(".repos").join(".gitignore");
+    if !ignore.exists() {
+        std::fs::write(&ignore, "*\n")
+            .with_context(|| format!("Failed to write {}", ignore.display()))?;
+    }
+    Ok(directory)
+}
+
+fn read_journal(path: &Path) -> Result<Journal> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("Invalid journal {}", path.display()))
+}
+
+/// Returns an id for a new journal of `command` that is not taken under `root`.
+fn next_journal_id(root: &Path, command: &str) -> String {
+    let base = format!("{}-{command}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let directory = root.join(JOURNAL_DIR);
+    let mut id = base.clone();
+    let mut suffix = 2;
+    while directory.join(format!("{id}.json")).exists() {
+        id = format!("{base}-{suffix}");
+        suffix += 1;
+    }
+    id
+}
+
+/// Collects journal entries while a command runs.
+pub(crate) struct JournalRecorder {
+    command: &'static str,
+    restore: RestoreMode,
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+impl JournalRecorder {
+    pub(crate) fn new(command: &'static str, restore: RestoreMode) -> Self {
+        Self {
+            command,
+            restore,
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Runs `operation` and records the repository's HEAD if it moved.
+    pub(crate) async fn track<T>(
+        &self,
+        repository: &str,
+        path: &Path,
+        operation: impl Future<Output = T>,
+    ) -> T {
+        let before = HeadState::read(path).await.ok();
+        let result = operation.await;
+        if let (Some(before), Ok(after)) = (before, HeadState::read(path).await) {
+            self.record(JournalEntry::new(repository, path, before, after));
+        }
+        result
+    }
+
+    /// Adds `entry` when it changed anything.
+    pub(crate) fn record(&self, entry: JournalEntry) {
+        if entry.is_change() {
+            self.entries
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push(entry);
+        }
+    }
+
+    /// Writes the journal under the current workspace when any repository
+    /// changed and tells the user how to undo it. Returns the journal id.
+    pub(crate) fn finish(&self) -> Option<String> {
+        let root = std::env::current_dir().ok()?;
+        self.finish_in(&root)
+    }
+
+    fn finish_in(&self, root: &Path) -> Option<String> {
+        let mut repositories = std::mem::take(
+            &mut *self
+                .entries
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+        if repositories.is_empty() {
+            return None;
+        }
+        repositories.sort_by(|left, right| left.path.cmp(&right.path));
+
+        let journal = Journal {
+            id: next_journal_id(root, self.command),
+            command: self.command.to_string(),
+            created_at: chrono::Local::now().to_rfc3339(),
+            restore: self.restore,
+            undone_at: None,
+            repositories,
+        };
+        match journal.save(root) {
+            Ok(_) => {
+                if is_human_output() {
+                    println!(
+                        "{DIM}↳ journal: {} · undo with `repos undo`{RESET}\n",
+                        journal.id
+                    );
+                }
+                Some(journal.id)
+            }
+            Err(e) => {
+                eprintln!("⚠️  Could not write the operation journal: {e:#}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(name: &str, before: &str, after: &str) -> JournalEntry {
+        let head = |commit: &str| HeadState {
+            branch: Some("main".to_string()),
+            commit: Some(commit.to_string()),
+        };
+        JournalEntry::new(name, Path::new(name), head(before), head(after))
+    }
+
+    #[test]
+    fn recorder_writes_only_changed_repositories_and_latest_skips_undone() {
+        let temp = TempDir::new().unwrap();
+
+        let recorder = JournalRecorder::new("commit", RestoreMode::Soft);
+        recorder.record(entry("unchanged", "aaaaaaa", "aaaaaaa"));
+        assert_eq!(recorder.finish_in(temp.path()), None);
+
+        recorder.record(entry("api", "aaaaaaa", "bbbbbbb"));
+        recorder.record(entry("web", "ccccccc", "ccccccc"));
+        let first = recorder.finish_in(temp.path()).unwrap();
+        assert!(first.ends_with("-commit"), "{first}");
+        assert!(temp.path().join(".repos/.gitignore").is_file());
+
+        let recorder = JournalRecorder::new("commit", RestoreMode::Soft);
+        recorder.record(entry("api", "bbbbbbb", "ddddddd"));
+        let second = recorder.finish_in(temp.path()).unwrap();
+        assert_ne!(first, second);
+
+        let journal = Journal::load(temp.path(), &first).unwrap();
+        assert_eq!(journal.restore, RestoreMode::Soft);
+        assert_eq!(journal.repositories.len(), 1);
+        assert_eq!(journal.repositories[0].repository, "api");
+
+        let mut latest = Journal::latest(temp.path()).unwrap().unwrap();
+        assert_eq!(latest.id, second);
+        latest.mark_undone(temp.path()).unwrap();
+        assert_eq!(Journal::latest(temp.path()).unwrap().unwrap().id, first);
+        assert_eq!(Journal::list(temp.path()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn describes_branch_detached_and_unborn_heads() {
+        let mut head = HeadState {
+            branch: Some("main".to_string()),
+            commit: Some("4f1c2d0e9a".to_string()),
+        };
+        assert_eq!(head.describe(), "main at 4f1c2d0");
+        head.branch = None;
+        assert_eq!(head.describe(), "detached at 4f1c2d0");
+        head.branch = Some("main".to_string());
+        head.commit = None;
+        assert_eq!(head.describe(), "main (no commits)");
+    }
+}
@@ -0,0 +1,409 @@
+//! Declarative workspace manifest (`repos.toml`).
+//!
+//! The manifest lists every repository a workspace is expected to contain so a
+//! fresh checkout can be bootstrapped without cloning each repository by hand:
+//!
+//! ```toml
+//! [[repo]]
+//! path = "services/api"
+//! url = "git@github.com:acme/api.git"
+//! branch = "main"
+//! groups = ["backend"]
+//! tags = ["rust"]
+//!
+//! [groups]
+//! legacy = ["old-*", "archive/**"]
+//!
+//! [registries.internal]
+//! groups = ["backend"]
+//! npm = "https://npm.internal.example"
+//!
+//! [tag_templates]
+//! npm = "{name}@{version}"
+//! ```
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use crate::package::tag::validate_template;
+use crate::package::PublishRegistry;
+
+/// File name of the workspace manifest, resolved from the workspace root.
+pub const MANIFEST_FILE_NAME: &str = "repos.toml";
+
+/// A single repository entry in the workspace manifest.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestRepository {
+    /// Checkout location relative to the workspace root.
+    pub path: String,
+    /// Remote URL used for the initial clone.
+    pub url: String,
+    /// Default branch to check out; the remote HEAD is used when omitted.
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Optional group names used to target subsets of the workspace.
+    #[serde(default)]
+    pub groups: Vec<String>,
+    /// Optional free-form labels used with `--tag`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Name of the `[registries]` entry `repos publish` sends this repository's packages to.
+    #[serde(default)]
+    pub registry: Option<String>,
+    /// Release tag template for this repository's packages.
+    #[serde(default)]
+    pub tag_template: Option<String>,
+}
+
+impl ManifestRepository {
+    /// Returns the display name for this entry (the last path component).
+    #[must_use]
+    pub fn name(&self) -> &str {
+        Path::new(&self.path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.path)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestFile {
+    #[serde(default, rename = "repo")]
+    repositories: Vec<ManifestRepository>,
+    #[serde(default)]
+    groups: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    registries: BTreeMap<String, PublishRegistry>,
+    #[serde(default)]
+    tag_templates: BTreeMap<String, String>,
+}
+
+/// Parsed workspace manifest anchored at the directory that contains it.
+#[derive(Clone, Debug)]
+pub struct WorkspaceManifest {
+    root: PathBuf,
+    repositories: Vec<ManifestRepository>,
+    groups: BTreeMap<String, Vec<String>>,
+    registries: BTreeMap<String, PublishRegistry>,
+    tag_templates: BTreeMap<String, String>,
+}
+
+impl WorkspaceManifest {
+    /// Loads `repos.toml` from `root`, returning `None` when the workspace has no manifest.
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let manifest_path = root.join(MANIFEST_FILE_NAME);
+        if !manifest_path.is_file() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+        Self::parse(root, &contents)
+            .with_context(|| format!("invalid manifest {}", manifest_path.display()))
+            .map(Some)
+    }
+
+    /// Parses manifest contents for a workspace rooted at `root`.
+    pub fn parse(root: &Path, contents: &str) -> Result<Self> {
+        let mut file: ManifestFile = toml::from_str(contents)?;
+        let mut seen = HashSet::with_capacity(file.repositories.len());
+
+        for (index, repository) in file.repositories.iter().enumerate() {
+            let entry = index + 1;
+            validate_entry_path(&repository.path)
+                .map_err(|error| anyhow!("repo #{entry}: {error}"))?;
+            if repository.url.trim().is_empty() {
+                bail!("repo #{entry} ({}): url must not be empty", repository.path);
+            }
+            if repository
+                .branch
+                .as_deref()
+                .is_some_and(|branch| branch.trim().is_empty())
+            {
+                bail!(
+                    "repo #{entry} ({}): branch must not be empty",
+                    repository.path
+                );
+            }
+            if let Some(registry) = &repository.registry {
+                if !file.registries.contains_key(registry) {
+                    bail!(
+                        "repo #{entry} ({}): unknown registry '{registry}'",
+                        repository.path
+                    );
+                }
+            }
+            if let Some(template) = &repository.tag_template {
+                validate_template(template)
+                    .map_err(|error| anyhow!("repo #{entry} ({}): {error}", repository.path))?;
+            }
+            if !seen.insert(normalize_relative(Path::new(&repository.path))) {
+                bail!(
+                    "repo #{entry}: duplicate repository path '{}'",
+                    repository.path
+                );
+            }
+        }
+
+        for (group, patterns) in &file.groups {
+            if group.trim().is_empty() {
+                bail!("groups: group names must not be empty");
+            }
+            if patterns.iter().any(|pattern| pattern.trim().is_empty()) {
+                bail!("groups.{group}: patterns must not be empty");
+            }
+        }
+
+        for (name, registry) in &mut file.registries {
+            if registry.cargo_index.is_some() && registry.cargo.is_none() {
+                bail!("registries.{name}: cargo_index needs a cargo registry name");
+            }
+            if registry.python_index.is_some() && registry.python.is_none() {
+                bail!("registries.{name}: python_index needs a python upload URL");
+            }
+            if let Some((key, url)) = registry.urls().find(|(_, url)| {
+                !["https://", "http://", "file://"]
+                    .iter()
+                    .any(|scheme| url.starts_with(scheme))
+            }) {
+                bail!("registries.{name}.{key}: '{url}' must be an http(s) or file URL");
+            }
+            for url in [
+                &mut registry.npm,
+                &mut registry.cargo_index,
+                &mut registry.python_index,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                url.truncate(url.trim_end_matches('/').len());
+            }
+            registry.name.clone_from(name);
+        }
+
+        for (manager, template) in &file.tag_templates {
+            validate_template(template)
+                .map_err(|error| anyhow!("tag_templates.{manager}: {error}"))?;
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            repositories: file.repositories,
+            groups: file.groups,
+            registries: file.registries,
+            tag_templates: file.tag_templates,
+        })
+    }
+
+    /// Returns the workspace root that manifest paths are resolved against.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the repository entries in manifest order.
+    #[must_use]
+    pub fn repositories(&self) -> &[ManifestRepository] {
+        &self.repositories
+    }
+
+    /// Returns the `[groups]` table: group name to repository name or path patterns.
+    #[must_use]
+    pub fn group_patterns(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.groups
+    }
+
+    /// Returns the `[registries]` table: registry name to its publish targets.
+    #[must_use]
+    pub fn registries(&self) -> &BTreeMap<String, PublishRegistry> {
+        &self.registries
+    }
+
+    /// Returns the `[tag_templates]` table: package manager name to release tag template.
+    #[must_use]
+    pub fn tag_templates(&self) -> &BTreeMap<String, String> {
+        &self.tag_templates
+    }
+
+    /// Returns the entry checked out at `path`, if the manifest lists it.
+    #[must_use]
+    pub fn repository_at(&self, path: &Path) -> Option<&ManifestRepository> {
+        let relative = normalize_relative(path.strip_prefix(&self.root).unwrap_or(path));
+        self.repositories
+            .iter()
+            .find(|repository| normalize_relative(Path::new(&repository.path)) == relative)
+    }
+
+    /// Resolves an entry's checkout location against the workspace root.
+    #[must_use]
+    pub fn checkout_path(&self, repository: &ManifestRepository) -> PathBuf {
+        self.root
+            .join(normalize_relative(Path::new(&repository.path)))
+    }
+
+    /// Returns discovered repositories that are not listed in the manifest.
+    ///
+    /// The workspace root itself is never reported: it usually holds the
+    /// manifest rather than being one of its entries.
+    #[must_use]
+    pub fn unmanaged(&self, discovered: &[(String, PathBuf)]) -> Vec<(String, PathBuf)> {
+        let managed = self
+            .repositories
+            .iter()
+            .map(|repository| normalize_relative(Path::new(&repository.path)))
+            .collect::<HashSet<_>>();
+
+        discovered
+            .iter()
+            .filter(|(_, path)| {
+                let Ok(relative) = path.strip_prefix(&self.root) else {
+                    return false;
+                };
+                let relative = normalize_relative(relative);
+                !relative.as_os_str().is_empty() && !managed.contains(&relative)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+fn validate_entry_path(path: &str) -> Result<()> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        bail!("path must not be empty");
+    }
+
+    let path = Path::new(trimmed);
+    if path.is_absolute() {
+        bail!("path '{trimmed}' must be relative to the workspace root");
+    }
+    if path
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        bail!("path '{trimmed}' must not leave the workspace root");
+    }
+    if normalize_relative(path).as_os_str().is_empty() {
+        bail!("path '{trimmed}' must name a directory below the workspace root");
+    }
+
+    Ok(())
+}
+
+pub(super) fn normalize_relative(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[[repo]]
+path = "services/api"
+url = "git@github.com:acme/api.git"
+branch = "main"
+groups = ["backend"]
+
+[[repo]]
+path = "./web"
+url = "https://github.com/acme/web.git"
+tags = ["frontend"]
+
+[groups]
+legacy = ["old-*"]
+"#;
+
+    #[test]
+    fn parses_entries_with_optional_fields() {
+        let manifest = WorkspaceManifest::parse(Path::new("/workspace"), MANIFEST).unwrap();
+        let repositories = manifest.repositories();
+
+        assert_eq!(repositories.len(), 2);
+        assert_eq!(repositories[0].name(), "api");
+        assert_eq!(repositories[0].branch.as_deref(), Some("main"));
+        assert_eq!(repositories[0].groups, vec!["backend".to_string()]);
+        assert_eq!(repositories[1].branch, None);
+        assert!(repositories[1].groups.is_empty());
+        assert_eq!(repositories[1].tags, vec!["frontend".to_string()]);
+        assert_eq!(
+            manifest.group_patterns()["legacy"],
+            vec!["old-*".to_string()]
+        );
+        assert_eq!(
+            manifest.checkout_path(&repositories[1]),
+            PathBuf::from("/workspace/web")
+        );
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_workspace() {
+        for path in ["../elsewhere", "/abs/path", ".", ""] {
+            let contents = format!("[[repo]]\npath = \"{path}\"\nurl = \"git@host:o/r.git\"\n");
+            assert!(
+                WorkspaceManifest::parse(Path::new("/workspace"), &contents).is_err(),
+                "{path} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_duplicate_paths_and_unknown_keys() {
+        let duplicate =
+            "[[repo]]\npath = \"a\"\nurl = \"u\"\n[[repo]]\npath = \"./a\"\nurl = \"v\"\n";
+        let error = WorkspaceManifest::parse(Path::new("/w"), duplicate).unwrap_err();
+        assert!(error.to_string().contains("duplicate repository path"));
+
+        let unknown = "[[repo]]\npath = \"a\"\nurl = \"u\"\nbranches = [\"main\"]\n";
+        assert!(WorkspaceManifest::parse(Path::new("/w"), unknown).is_err());
+    }
+
+    #[test]
+    fn reports_discovered_repositories_missing_from_manifest() {
+        let manifest = WorkspaceManifest::parse(Path::new("/workspace"), MANIFEST).unwrap();
+        let discovered = vec![
+            ("workspace".to_string(), PathBuf::from("/workspace")),
+            ("api".to_string(), PathBuf::from("/workspace/services/api")),
+            ("web".to_string(), PathBuf::from("/workspace/web")),
+            ("scratch".to_string(), PathBuf::from("/workspace/scratch")),
+        ];
+
+        let unmanaged = manifest.unmanaged(&discovered);
+        assert_eq!(
+            unmanaged,
+            vec![("scratch".to_string(), PathBuf::from("/workspace/scratch"))]
+        );
+    }
+
+    #[test]
+    fn parses_registries_and_rejects_dangling_references() {
+        let contents = "[[repo]]\npath = \"a\"\nurl = \"u\"\nregistry = \"internal\"\n\n[registries.internal]\nnpm = \"https://npm.internal.example/\"\ncargo = \"internal\"\ncargo_index = \"file:///srv/index/\"\n";
+        let manifest = WorkspaceManifest::parse(Path::new("/w"), contents).unwrap();
+        let registry = &manifest.registries()["internal"];
+        assert_eq!(registry.name, "internal");
+        assert_eq!(
+            registry.npm.as_deref(),
+            Some("https://npm.internal.example")
+        );
+        assert_eq!(registry.cargo_index.as_deref(), Some("file:///srv/index"));
+        assert_eq!(registry.destination("cargo"), Some("internal"));
+        assert_eq!(registry.destination("python"), None);
+
+        let dangling = contents.replace("registry = \"internal\"", "registry = \"missing\"");
+        let error = WorkspaceManifest::parse(Path::new("/w"), &dangling).unwrap_err();
+        assert!(error.to_string().contains("unknown registry 'missing'"));
+
+        let bad_url = "[registries.internal]\nnpm = \"npm.internal.example\"\n";
+        let error = WorkspaceManifest::parse(Path::new("/w"), bad_url).unwrap_err();
+        assert!(
+            error.to_string().contains("registries.internal.npm"),
+            "{error}"
+        );
+    }
+}
@@ -2,8 +2,14 @@
 pub(crate) mod attention;
 pub(crate) mod config;
 pub(crate) mod discovery;
+pub(crate) mod discovery_cache;
+pub(crate) mod journal;
+pub(crate) mod manifest;
+pub(crate) mod output;
 pub(crate) mod progress;
 pub(crate) mod report;
+pub(crate) mod selection;
+pub(crate) mod settings;
 pub(crate) mod stats;
 
 // Test modules
@@ -0,0 +1,349 @@
+//! Machine-readable command output (`--format json|ndjson`).
+//!
+//! Human reports stay the default. In `json` mode a command prints one
+//! document when it finishes; in `ndjson` mode it streams one event per line
+//! while it runs (`start`, one `repository` event per result, then `summary`).
+//! The layout is versioned by [`OUTPUT_SCHEMA_VERSION`] and documented in
+//! `docs/guides/output-format.md`.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::config::NO_REPOS_MESSAGE;
+use super::report::{CommandCapture, RepositoryOutcome};
+use super::stats::SyncStatistics;
+use crate::git::failure::{GitFailure, GitFailureKind, GitOperationPhase};
+use crate::git::remote::RemoteContext;
+use crate::git::Status;
+use crate::utils::compare_repository_locations;
+
+/// Version of the JSON/NDJSON layout; bumped on incompatible changes.
+pub const OUTPUT_SCHEMA_VERSION: u32 = 1;
+
+/// How command results are written to stdout.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// ANSI progress and reports for people.
+    #[default]
+    Human,
+    /// A single JSON document once the command finishes.
+    Json,
+    /// Newline-delimited JSON events streamed during the run.
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Returns the `--format` value for this format.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Human => "human",
+            Self::Json => "json",
+            Self::Ndjson => "ndjson",
+        }
+    }
+}
+
+static OUTPUT_FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Selects the output format for the rest of the process.
+pub fn set_output_format(format: OutputFormat) -> Result<()> {
+    if OUTPUT_FORMAT.set(format).is_err() {
+        bail!("output format was already configured");
+    }
+    Ok(())
+}
+
+/// Returns the active output format (human unless `--format` was given).
+pub(crate) fn output_format() -> OutputFormat {
+    OUTPUT_FORMAT.get().copied().unwrap_or_default()
+}
+
+/// Returns true when human progress and reports should be written.
+pub(crate) fn is_human_output() -> bool {
+    output_format() == OutputFormat::Human
+}
+
+/// Failure details attached to a repository record.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct FailureRecord {
+    pub(crate) kind: GitFailureKind,
+    pub(crate) phase: GitOperationPhase,
+    pub(crate) reason: String,
+    pub(crate) next_action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) remote: Option<RemoteContext>,
+}
+
+impl FailureRecord {
+    fn new(failure: &GitFailure, path: &str) -> Self {
+        Self {
+            kind: failure.kind,
+            phase: failure.phase,
+            reason: failure.reason(),
+            next_action: failure.next_action(path),
+            remote: failure.remote.clone(),
+        }
+    }
+}
+
+/// Upstream tracking details reported by `repos status`.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct UpstreamRecord {
+    /// `tracking`, `no_remote`, `no_upstream`, or `unknown`.
+    pub(crate) state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ahead: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) behind: Option<u32>,
+}
+
+/// One repository result, shared by `repository` events and final documents.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct RepositoryRecord {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    /// The operation that produced this result (`pull` and `push` for `repos sync`).
+    pub(crate) phase: &'static str,
+    pub(crate) status: Status,
+    pub(crate) message: String,
+    pub(crate) has_uncommitted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) failure: Option<FailureRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) upstream: Option<UpstreamRecord>,
+    /// Exit status and captured output for `repos exec`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) exec: Option<CommandCapture>,
+}
+
+impl RepositoryRecord {
+    pub(crate) fn new(
+        phase: &'static str,
+        outcome: &RepositoryOutcome,
+        failure: Option<&GitFailure>,
+    ) -> Self {
+        Self {
+            name: outcome.repository.clone(),
+            path: outcome.path.clone(),
+            phase,
+            status: outcome.status,
+            message: outcome.message.clone(),
+            has_uncommitted: outcome.has_uncommitted,
+            failure: failure.map(|failure| FailureRecord::new(failure, &outcome.path)),
+            upstream: None,
+            exec: None,
+        }
+    }
+
+    fn failed(&self) -> bool {
+        matches!(
+            self.status,
+            Status::Error
+                | Status::ConfigError
+                | Status::StagingError
+                | Status::CommitError
+                | Status::PullError
+        )
+    }
+}
+
+/// Builds sorted records for every result recorded in `stats`.
+pub(crate) fn repository_records(
+    phase: &'static str,
+    stats: &SyncStatistics,
+) -> Vec<RepositoryRecord> {
+    let failures = stats
+        .git_failures
+        .lock()
+        .map(|failures| failures.clone())
+        .unwrap_or_default();
+    let mut records = stats
+        .batch_outcomes()
+        .iter()
+        .map(|outcome| {
+            let failure = failures.get(&(outcome.repository.clone(), outcome.path.clone()));
+            let mut record = RepositoryRecord::new(phase, outcome, failure);
+            record.exec = stats.command_capture(&outcome.repository, &outcome.path);
+            record
+        })
+        .collect::<Vec<_>>();
+    sort_records(&mut records);
+    records
+}
+
+pub(crate) fn sort_records(records: &mut [RepositoryRecord]) {
+    records.sort_by(|left, right| {
+        compare_repository_locations(&left.path, &left.name, &right.path, &right.name)
+    });
+}
+
+/// Reports an empty fleet: the human notice, or an empty document/event stream.
+pub(crate) fn report_no_repositories(command: &str, start_time: std::time::Instant) {
+    if is_human_output() {
+        println!("\r{NO_REPOS_MESSAGE}");
+    } else {
+        emit_start(command, 0);
+        emit_report(command, start_time.elapsed(), &[]);
+    }
+}
+
+/// Emits the NDJSON `start` event for `command`.
+pub(crate) fn emit_start(command: &str, total_repos: usize) {
+    if output_format() == OutputFormat::Ndjson {
+        write_line(&json!({
+            "schema_version": OUTPUT_SCHEMA_VERSION,
+            "event": "start",
+            "command": command,
+            "repositories": total_repos,
+        }));
+    }
+}
+
+/// Emits an NDJSON `repository` event as soon as a result is known.
+pub(crate) fn emit_repository(record: &RepositoryRecord) {
+    if output_format() == OutputFormat::Ndjson {
+        write_line(&json!({
+            "schema_version": OUTPUT_SCHEMA_VERSION,
+            "event": "repository",
+            "repository": record,
+        }));
+    }
+}
+
+/// Emits the final result: the whole document in `json` mode, or the
+/// `summary` event in `ndjson` mode (repositories were already streamed).
+pub(crate) fn emit_report(command: &str, duration: Duration, records: &[RepositoryRecord]) {
+    let summary = summarize(records);
+    let duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+
+    match output_format() {
+        OutputFormat::Human => {}
+        OutputFormat::Json => {
+            let document = json!({
+                "schema_version": OUTPUT_SCHEMA_VERSION,
+                "command": command,
+                "duration_ms": duration_ms,
+                "summary": summary,
+                "repositories": records,
+            });
+            match serde_json::to_string_pretty(&document) {
+                Ok(text) => println!("{text}"),
+                Err(e) => eprintln!("Error: failed to serialize report: {e}"),
+            }
+        }
+        OutputFormat::Ndjson => write_line(&json!({
+            "schema_version": OUTPUT_SCHEMA_VERSION,
+            "event": "summary",
+            "command": command,
+            "duration_ms": duration_ms,
+            "summary": summary,
+        })),
+    }
+}
+
+fn summarize(records: &[RepositoryRecord]) -> serde_json::Value {
+    let checked = records
+        .iter()
+        .map(|record| record.path.as_str())
+        .collect::<BTreeSet<_>>();
+    let failed = records
+        .iter()
+        .filter(|record| record.failed())
+        .map(|record| record.path.as_str())
+        .collect::<BTreeSet<_>>();
+    let mut statuses = BTreeMap::<String, usize>::new();
+    for record in records {
+        if let Ok(serde_json::Value::String(status)) = serde_json::to_value(record.status) {
+            *statuses.entry(status).or_default() += 1;
+        }
+    }
+
+    json!({
+        "checked": checked.len(),
+        "failed": failed.len(),
+        "statuses": statuses,
+    })
+}
+
+fn write_line(event: &serde_json::Value) {
+    let mut stdout = std::io::stdout().lock();
+    // Events are best-effort: a closed pipe must not abort the fleet operation.
+    let _ = writeln!(stdout, "{event}");
+    let _ = stdout.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::remote::{RemoteDirection, RemoteTransport};
+
+    fn outcome(name: &str, status: Status, message: &str) -> RepositoryOutcome {
+        RepositoryOutcome {
+            repository: name.to_string(),
+            path: format!("./{name}"),
+            status,
+            message: message.to_string(),
+            has_uncommitted: false,
+        }
+    }
+
+    #[test]
+    fn records_serialize_status_failure_and_remote_context() {
+        let failure = GitFailure::from_message(
+            GitOperationPhase::Push,
+            "Permission denied (publickey)".to_string(),
+            Some(RemoteContext {
+                remote: "origin".to_string(),
+                direction: RemoteDirection::Push,
+                transport: RemoteTransport::Ssh,
+                identity: Some("github.com/acme/api.git".to_string()),
+                ssh_url: None,
+            }),
+        );
+        let record = RepositoryRecord::new(
+            "push",
+            &outcome("api", Status::Error, "Permission denied (publickey)"),
+            Some(&failure),
+        );
+
+        let value = serde_json::to_value(&record).unwrap();
+        assert_eq!(value["status"], "error");
+        assert_eq!(value["phase"], "push");
+        assert_eq!(value["failure"]["kind"], "authentication");
+        assert_eq!(value["failure"]["phase"], "push");
+        assert_eq!(value["failure"]["remote"]["transport"], "ssh");
+        assert_eq!(value["failure"]["remote"]["direction"], "push");
+        assert_eq!(
+            value["failure"]["remote"]["identity"],
+            "github.com/acme/api.git"
+        );
+        assert!(value.get("upstream").is_none());
+    }
+
+    #[test]
+    fn summary_counts_distinct_repositories_and_statuses() {
+        let records = [
+            RepositoryRecord::new("pull", &outcome("api", Status::Pulled, "1 commit"), None),
+            RepositoryRecord::new("push", &outcome("api", Status::Synced, "up to date"), None),
+            RepositoryRecord::new("pull", &outcome("web", Status::PullError, "conflict"), None),
+            RepositoryRecord::new(
+                "push",
+                &outcome("web", Status::NoUpstream, "no upstream"),
+                None,
+            ),
+        ];
+
+        let summary = summarize(&records);
+        assert_eq!(summary["checked"], 2);
+        assert_eq!(summary["failed"], 1);
+        assert_eq!(summary["statuses"]["pulled"], 1);
+        assert_eq!(summary["statuses"]["pull_error"], 1);
+        assert_eq!(summary["statuses"]["no_upstream"], 1);
+    }
+}
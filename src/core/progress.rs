@@ -1,11 +1,12 @@
 //! Progress bar management and processing context structures
 
 use anyhow::Result;
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 use super::config::{DEFAULT_PROGRESS_BAR_LENGTH, PROGRESS_CHARS, PROGRESS_TEMPLATE};
+use super::output::is_human_output;
 use super::stats::SyncStatistics;
 
 /// Processing context that encapsulates all parameters needed for repository processing
@@ -69,7 +70,7 @@ pub fn create_processing_context(
         .map(|(name, _)| name.len())
         .max()
         .unwrap_or(0);
-    let multi_progress = MultiProgress::new();
+    let multi_progress = create_multi_progress();
     let progress_style = create_progress_style()?;
     let statistics = Arc::new(Mutex::new(SyncStatistics::new()));
     let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrent_limit));
@@ -100,7 +101,7 @@ pub fn create_generic_processing_context<T>(
         .map(|(name, _)| name.len())
         .max()
         .unwrap_or(0);
-    let multi_progress = MultiProgress::new();
+    let multi_progress = create_multi_progress();
     let progress_style = create_progress_style()?;
     let statistics = Arc::new(Mutex::new(statistics));
     let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrent_limit));
@@ -117,6 +118,15 @@ pub fn create_generic_processing_context<T>(
     })
 }
 
+/// Creates the progress container, hidden for `--format json|ndjson`.
+fn create_multi_progress() -> MultiProgress {
+    if is_human_output() {
+        MultiProgress::new()
+    } else {
+        MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+    }
+}
+
 /// Creates and configures a progress bar for a repository
 /// Returns a configured `ProgressBar` with the specified repository name
 pub(crate) fn create_progress_bar(
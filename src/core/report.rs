@@ -1,14 +1,16 @@
 //! Shared reporting for fleet-wide repository mutations.
 
+use serde::Serialize;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use crate::git::failure::GitFailure;
 use crate::git::Status;
-use crate::utils::compare_repository_locations;
+use crate::utils::{compare_repository_locations, shell_word};
 
 use super::attention::{append_project_attention_section, AttentionKind, ProjectAttention};
+use super::output::{emit_report, is_human_output, repository_records};
 use super::stats::{
     clean_error_message, format_relative_repo_path, truncate_text, SyncStatistics, BOLD_BLUE,
     BOLD_PURPLE, DIM, GREEN, RED, RESET, YELLOW,
@@ -23,6 +25,17 @@ pub(crate) struct RepositoryOutcome {
     pub has_uncommitted: bool,
 }
 
+/// Exit status and captured output of a `repos exec` command in one repository.
+#[derive(Clone, Debug, Default, Serialize)]
+pub(crate) struct CommandCapture {
+    /// Process exit code; absent when the command timed out, was killed by a
+    /// signal, or could not be started.
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum BatchOperation {
     Save { dry_run: bool },
@@ -30,6 +43,16 @@ pub(crate) enum BatchOperation {
     Unstage,
     Commit,
     Config { dry_run: bool },
+    Bootstrap { dry_run: bool },
+    Exec,
+    Switch,
+    BranchList,
+    Prune { dry_run: bool },
+    RemoteRewrite { dry_run: bool },
+    RemoteAdd { dry_run: bool },
+    RemoteRename,
+    RemoteSetPush { dry_run: bool },
+    Undo,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -78,13 +101,23 @@ impl OutcomeCounts {
 }
 
 impl BatchOperation {
-    fn command(self) -> &'static str {
+    pub(crate) fn command(self) -> &'static str {
         match self {
             Self::Save { .. } => "save",
             Self::Stage => "stage",
             Self::Unstage => "unstage",
             Self::Commit => "commit",
             Self::Config { .. } => "config",
+            Self::Bootstrap { .. } => "bootstrap",
+            Self::Exec => "exec",
+            Self::Switch => "switch",
+            Self::BranchList => "branch",
+            Self::Prune { .. } => "prune",
+            Self::RemoteRewrite { .. } => "remote rewrite",
+            Self::RemoteAdd { .. } => "remote add",
+            Self::RemoteRename => "remote rename",
+            Self::RemoteSetPush { .. } => "remote set-push",
+            Self::Undo => "undo",
         }
     }
 
@@ -95,6 +128,16 @@ impl BatchOperation {
             Self::Unstage => "Unstaged",
             Self::Commit => "Committed",
             Self::Config { .. } => "Updated",
+            Self::Bootstrap { .. } => "Cloned",
+            Self::Exec => "Succeeded",
+            Self::Switch => "Switched",
+            Self::BranchList => "Has branch",
+            Self::Prune { .. } => "Pruned",
+            Self::RemoteRewrite { .. } => "Rewritten",
+            Self::RemoteAdd { .. } => "Added",
+            Self::RemoteRename => "Renamed",
+            Self::RemoteSetPush { .. } => "Mirrored",
+            Self::Undo => "Restored",
         }
     }
 
@@ -104,9 +147,30 @@ impl BatchOperation {
             Self::Stage | Self::Unstage => "No match",
             Self::Commit => "Nothing staged",
             Self::Config { .. } => "Already correct",
+            Self::Bootstrap { .. } => "Present",
+            Self::Exec => "No match",
+            Self::Switch => "Already on",
+            Self::BranchList => "Missing",
+            Self::Prune { .. } => "Nothing merged",
+            Self::RemoteRewrite { .. } => "Already correct",
+            Self::RemoteAdd { .. } | Self::RemoteSetPush { .. } => "Already set",
+            Self::RemoteRename => "Already renamed",
+            Self::Undo => "Already undone",
         }
     }
 
+    /// Whether outcome messages are `; `-separated lists shown item by item
+    /// instead of truncated to one line.
+    fn itemized(self) -> bool {
+        matches!(
+            self,
+            Self::RemoteRewrite { .. }
+                | Self::RemoteAdd { .. }
+                | Self::RemoteRename
+                | Self::RemoteSetPush { .. }
+        )
+    }
+
     fn classify(self, outcome: &RepositoryOutcome) -> OutcomeKind {
         match outcome.status {
             Status::Error
@@ -131,6 +195,21 @@ impl BatchOperation {
             }
             Status::NoChanges => OutcomeKind::Unchanged,
             Status::Staged if matches!(self, Self::Save { dry_run: true }) => OutcomeKind::Planned,
+            Status::Cloned if matches!(self, Self::Bootstrap { dry_run: true }) => {
+                OutcomeKind::Planned
+            }
+            Status::Pruned if matches!(self, Self::Prune { dry_run: true }) => OutcomeKind::Planned,
+            Status::ConfigUpdated
+                if matches!(
+                    self,
+                    Self::RemoteRewrite { dry_run: true }
+                        | Self::RemoteAdd { dry_run: true }
+                        | Self::RemoteSetPush { dry_run: true }
+                ) =>
+            {
+                OutcomeKind::Planned
+            }
+            Status::Synced if matches!(self, Self::BranchList) => OutcomeKind::Changed,
             Status::Synced | Status::ConfigSynced => OutcomeKind::Unchanged,
             Status::Pushed
             | Status::Pulled
@@ -138,7 +217,12 @@ impl BatchOperation {
             | Status::ConfigUpdated
             | Status::Staged
             | Status::Unstaged
-            | Status::Committed => OutcomeKind::Changed,
+            | Status::Committed
+            | Status::Cloned
+            | Status::Executed
+            | Status::Switched
+            | Status::Pruned
+            | Status::Restored => OutcomeKind::Changed,
         }
     }
 
@@ -151,6 +235,32 @@ impl BatchOperation {
             Status::NoUpstream => "set an upstream branch or exclude this repository",
             Status::Dirty => "commit or stash the local changes",
             Status::Skip if outcome.message.contains("detached HEAD") => "checkout a branch",
+            Status::Skip if matches!(self, Self::Exec) => "rerun without `--fail-fast`",
+            Status::Skip if outcome.message.contains("dirty worktree") => {
+                "commit or stash the local changes"
+            }
+            Status::Skip if matches!(self, Self::Undo) => {
+                "inspect the repository and restore it by hand"
+            }
+            Status::Skip if matches!(self, Self::Switch) => "pass `--create` to create the branch",
+            Status::Skip if matches!(self, Self::Prune { .. }) => {
+                "run `git remote set-head origin --auto`"
+            }
+            Status::Skip if matches!(self, Self::Bootstrap { .. }) => {
+                "move the existing directory aside or update repos.toml"
+            }
+            Status::Skip if matches!(self, Self::RemoteRewrite { .. }) => {
+                "set the URL by hand with `git remote set-url`"
+            }
+            Status::Skip if matches!(self, Self::RemoteAdd { .. }) => {
+                "add the remote by hand with `git remote add`"
+            }
+            Status::Skip if matches!(self, Self::RemoteRename) => {
+                "rename the remote by hand with `git remote rename`"
+            }
+            Status::Skip if matches!(self, Self::RemoteSetPush { .. }) => {
+                "add the URL by hand with `git remote set-url --add --push`"
+            }
             Status::ConfigSkipped => "rerun and approve the update, or pass `--yes`",
             Status::NoChanges if matches!(self, Self::Save { .. }) => {
                 "pass `--include-untracked` if those files should be saved"
@@ -299,6 +409,141 @@ impl SyncStatistics {
         lines.join("\n")
     }
 
+    /// Writes the final batch report in the active output format.
+    pub(crate) fn print_batch_report(&self, operation: BatchOperation, duration: Duration) {
+        if is_human_output() {
+            println!("\n{}\n", self.generate_batch_report(operation, duration));
+        } else {
+            let command = operation.command();
+            emit_report(command, duration, &repository_records(command, self));
+        }
+    }
+
+    /// Generates the `repos exec` report: captured output per repository,
+    /// exclusive outcome counts, and project-grouped attention for failures.
+    pub(crate) fn generate_exec_report(&self, command_line: &str, duration: Duration) -> String {
+        let operation = BatchOperation::Exec;
+        let mut outcomes = self.batch_outcomes();
+        outcomes.sort_by(|left, right| {
+            compare_repository_locations(
+                &left.path,
+                &left.repository,
+                &right.path,
+                &right.repository,
+            )
+        });
+        let counts = summarize(operation, &outcomes);
+        let mut lines = vec![
+            format!("{BOLD_BLUE}repos exec{RESET} {DIM}$ {command_line}{RESET}"),
+            format!(
+                "{GREEN}✓{RESET} Completed in {:.1}s",
+                duration.as_secs_f64()
+            ),
+        ];
+
+        for outcome in &outcomes {
+            let Some(capture) = self.command_capture(&outcome.repository, &outcome.path) else {
+                continue;
+            };
+            let output = [capture.stdout.trim_end(), capture.stderr.trim_end()]
+                .into_iter()
+                .filter(|stream| !stream.is_empty())
+                .collect::<Vec<_>>();
+            if output.is_empty() {
+                continue;
+            }
+
+            let color = if outcome.status == Status::Executed {
+                GREEN
+            } else {
+                RED
+            };
+            lines.push(String::new());
+            lines.push(format!(
+                "{BOLD_PURPLE}▌ {}{RESET} {DIM}{}{RESET} {color}{}{RESET}",
+                outcome.repository,
+                format_relative_repo_path(&outcome.path),
+                outcome.message
+            ));
+            for line in output.iter().flat_map(|stream| stream.lines()) {
+                lines.push(format!("  {DIM}│{RESET} {line}"));
+            }
+        }
+
+        lines.push(String::new());
+        lines.push(format!("{BOLD_PURPLE}▌ Summary{RESET}"));
+        lines.push(format!(
+            "  {GREEN}✓{RESET} {:<16}{}",
+            operation.changed_label(),
+            counts.changed
+        ));
+        if counts.unchanged > 0 {
+            lines.push(format!(
+                "  {DIM}·{RESET} {:<16}{}",
+                operation.unchanged_label(),
+                counts.unchanged
+            ));
+        }
+        if counts.failed > 0 {
+            lines.push(format!("  {RED}!{RESET} {:<16}{}", "Failed", counts.failed));
+        }
+        if counts.skipped > 0 {
+            lines.push(format!(
+                "  {DIM}·{RESET} {:<16}{}",
+                "Skipped", counts.skipped
+            ));
+        }
+        lines.push(format!(
+            "  {DIM}·{RESET} {:<16}{}",
+            "Checked",
+            counts.processed()
+        ));
+
+        let attention = outcomes
+            .iter()
+            .filter_map(|outcome| {
+                let kind = match operation.classify(outcome) {
+                    OutcomeKind::Failed => AttentionKind::Failed,
+                    // Repositories `--only-if-file` filtered out need nothing.
+                    OutcomeKind::Skipped if outcome.message.ends_with("(--only-if-file)") => {
+                        return None
+                    }
+                    OutcomeKind::Skipped => AttentionKind::Skipped,
+                    _ => return None,
+                };
+                let next = if kind == AttentionKind::Failed {
+                    format!(
+                        "cd {} && {command_line}",
+                        shell_word(&format_relative_repo_path(&outcome.path))
+                    )
+                } else {
+                    operation.next_action(outcome).to_string()
+                };
+                Some(ProjectAttention::new(
+                    kind,
+                    outcome.repository.clone(),
+                    outcome.path.clone(),
+                    outcome.message.clone(),
+                    next,
+                    None,
+                ))
+            })
+            .collect::<Vec<_>>();
+        append_project_attention_section(&mut lines, attention, false);
+
+        lines.join("\n")
+    }
+
+    /// Writes the final `repos exec` report in the active output format.
+    pub(crate) fn print_exec_report(&self, command_line: &str, duration: Duration) {
+        if is_human_output() {
+            println!("\n{}\n", self.generate_exec_report(command_line, duration));
+        } else {
+            let command = BatchOperation::Exec.command();
+            emit_report(command, duration, &repository_records(command, self));
+        }
+    }
+
     pub(super) fn batch_outcomes(&self) -> Vec<RepositoryOutcome> {
         match self.operation_outcomes.lock() {
             Ok(outcomes) => outcomes.clone(),
@@ -335,14 +580,25 @@ fn append_section(
     lines.push(String::new());
     lines.push(format!("{BOLD_PURPLE}▌ {}{RESET}", section.heading));
     for outcome in matching {
-        let message = clean_error_message(&outcome.message);
-        lines.push(format!(
+        // Itemized messages list every change in full, one per line.
+        let message = if operation.itemized() {
+            String::new()
+        } else {
+            clean_error_message(&outcome.message)
+        };
+        let headline = format!(
             "  {}{}{RESET} {:24} {message}",
             section.color,
             section.marker,
             truncate_text(&outcome.repository, 24)
-        ));
+        );
+        lines.push(headline.trim_end().to_string());
         lines.push(format!("    ↳ path: {}", outcome.path));
+        if operation.itemized() {
+            for item in outcome.message.split("; ") {
+                lines.push(format!("    ↳ {item}"));
+            }
+        }
         if section.actionable {
             lines.push(format!("    ↳ next: {}", operation.next_action(outcome)));
         }
@@ -805,6 +1061,61 @@ mod tests {
         assert!(report.contains("next: pass `--include-untracked`"));
     }
 
+    #[test]
+    fn exec_report_shows_output_and_groups_failures_for_attention() {
+        let stats = SyncStatistics::new();
+        stats.update_with_capture(
+            "api",
+            "./services/api",
+            &Status::Executed,
+            "exit 0",
+            CommandCapture {
+                exit_code: Some(0),
+                stdout: "formatted\n".to_string(),
+                ..CommandCapture::default()
+            },
+        );
+        stats.update_with_capture(
+            "web",
+            "./services/web",
+            &Status::Error,
+            "exit 1: Diff in src/main.rs",
+            CommandCapture {
+                exit_code: Some(1),
+                stderr: "Diff in src/main.rs\n".to_string(),
+                ..CommandCapture::default()
+            },
+        );
+        stats.update_with_capture(
+            "docs",
+            "./docs",
+            &Status::NoChanges,
+            "no Cargo.toml",
+            CommandCapture::default(),
+        );
+        stats.update_with_capture(
+            "cli",
+            "./cli",
+            &Status::Skip,
+            "not run after an earlier failure",
+            CommandCapture::default(),
+        );
+
+        let report = stats.generate_exec_report("cargo fmt --check", Duration::ZERO);
+
+        assert!(report.contains("repos exec"));
+        assert!(report.contains("│\x1b[0m formatted"));
+        assert!(report.contains("│\x1b[0m Diff in src/main.rs"));
+        assert!(report.contains("Succeeded       1"));
+        assert!(report.contains("No match        1"));
+        assert!(report.contains("Failed          1"));
+        assert!(report.contains("Checked         4"));
+        assert!(report.contains("▌ Needs Attention by Project"));
+        assert!(report.contains("next: cd ./services/web && cargo fmt --check"));
+        assert!(report.contains("next: rerun without `--fail-fast`"));
+        assert!(!report.contains("path: ./docs"), "{report}");
+    }
+
     #[test]
     fn stage_and_unstage_reports_use_their_own_labels() {
         let stats = SyncStatistics::new();
@@ -845,6 +1156,52 @@ mod tests {
         assert!(!report.contains("Pushed"));
     }
 
+    #[test]
+    fn prune_dry_run_plans_deletions_and_branch_list_names_matches() {
+        let stats = SyncStatistics::new();
+        stats.update(
+            "api",
+            "./api",
+            &Status::Pruned,
+            "would delete old-work (merged into origin/main)",
+            false,
+        );
+        stats.update(
+            "web",
+            "./web",
+            &Status::Skip,
+            "remote default branch unknown",
+            false,
+        );
+
+        let prune =
+            stats.generate_batch_report(BatchOperation::Prune { dry_run: true }, Duration::ZERO);
+        assert!(prune.contains("repos prune"));
+        assert!(prune.contains("▌ Planned"));
+        assert!(prune.contains("Pruned          0"));
+        assert!(prune.contains("next: run `git remote set-head origin --auto`"));
+
+        let stats = SyncStatistics::new();
+        stats.update(
+            "api",
+            "./api",
+            &Status::Synced,
+            "feature-x (current)",
+            false,
+        );
+        stats.update(
+            "web",
+            "./web",
+            &Status::NoChanges,
+            "no branch matching feature-x",
+            false,
+        );
+        let list = stats.generate_batch_report(BatchOperation::BranchList, Duration::ZERO);
+        assert!(list.contains("▌ Has branch"));
+        assert!(list.contains("Missing         1"));
+        assert!(!list.contains("path: ./web"));
+    }
+
     #[test]
     fn config_dry_run_lists_planned_repositories() {
         let stats = SyncStatistics::new();
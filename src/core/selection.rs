@@ -0,0 +1,472 @@
+//! Repository targeting by group, tag, and exclusion pattern.
+//!
+//! Commands discover the whole fleet first; the active selection then narrows
+//! that list uniformly so `repos sync --group backend` and
+//! `repos save --exclude 'legacy-*'` behave the same way for every command.
+//! Groups and tags come from the workspace manifest (`repos.toml`).
+
+use anyhow::{bail, Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::manifest::{normalize_relative, WorkspaceManifest, MANIFEST_FILE_NAME};
+use crate::package::PublishRegistry;
+
+/// Requested repository selectors as given on the command line.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RepoSelector {
+    /// Keep repositories belonging to any of these groups.
+    pub groups: Vec<String>,
+    /// Keep repositories carrying any of these tags.
+    pub tags: Vec<String>,
+    /// Drop repositories whose name or path matches any of these globs.
+    pub exclude: Vec<String>,
+}
+
+impl RepoSelector {
+    /// Returns true when no selector was requested.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty() && self.tags.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Resolves group and tag names against the manifest of the workspace at `root`.
+    ///
+    /// Unknown group or tag names are errors so a typo cannot silently turn a
+    /// targeted command into a no-op.
+    pub fn resolve(
+        &self,
+        root: &Path,
+        manifest: Option<&WorkspaceManifest>,
+    ) -> Result<RepoSelection> {
+        let groups = if self.groups.is_empty() {
+            None
+        } else {
+            let manifest = manifest.with_context(|| {
+                format!("--group requires a {MANIFEST_FILE_NAME} defining groups")
+            })?;
+            Some(resolve_groups(&self.groups, manifest)?)
+        };
+
+        let tags = if self.tags.is_empty() {
+            None
+        } else {
+            let manifest = manifest.with_context(|| {
+                format!("--tag requires a {MANIFEST_FILE_NAME} with tagged repositories")
+            })?;
+            Some(resolve_tags(&self.tags, manifest)?)
+        };
+
+        let exclude = if self.exclude.is_empty() {
+            None
+        } else {
+            Some(build_globs(&self.exclude).context("invalid --exclude pattern")?)
+        };
+
+        Ok(RepoSelection {
+            root: root.to_path_buf(),
+            groups,
+            tags,
+            exclude,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct GroupMembers {
+    paths: HashSet<PathBuf>,
+    patterns: GlobSet,
+}
+
+/// A selector resolved against a workspace, ready to filter discovered repositories.
+#[derive(Debug)]
+pub struct RepoSelection {
+    root: PathBuf,
+    groups: Option<GroupMembers>,
+    tags: Option<HashSet<PathBuf>>,
+    exclude: Option<GlobSet>,
+}
+
+impl RepoSelection {
+    /// Returns true when the repository passes every requested selector.
+    #[must_use]
+    pub fn matches(&self, name: &str, path: &Path) -> bool {
+        let relative = normalize_relative(path.strip_prefix(&self.root).unwrap_or(path));
+        let matches_pattern = |globs: &GlobSet| globs.is_match(name) || globs.is_match(&relative);
+
+        if let Some(groups) = &self.groups {
+            if !groups.paths.contains(&relative) && !matches_pattern(&groups.patterns) {
+                return false;
+            }
+        }
+        if let Some(tagged) = &self.tags {
+            if !tagged.contains(&relative) {
+                return false;
+            }
+        }
+        if let Some(exclude) = &self.exclude {
+            if matches_pattern(exclude) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Keeps only the repositories that pass every requested selector.
+    #[must_use]
+    pub fn apply(&self, repos: Vec<(String, PathBuf)>) -> Vec<(String, PathBuf)> {
+        repos
+            .into_iter()
+            .filter(|(name, path)| self.matches(name, path))
+            .collect()
+    }
+}
+
+/// Matches repositories to the `[registries]` entry they publish to: the
+/// one named on their `[[repo]]` entry, else the one listing their group.
+#[derive(Debug, Default)]
+pub struct RegistryRoutes {
+    root: PathBuf,
+    repositories: Vec<(PathBuf, PublishRegistry)>,
+    groups: Vec<(RepoSelection, PublishRegistry)>,
+}
+
+impl RegistryRoutes {
+    /// Resolves the manifest's registry assignments for the workspace at `root`.
+    pub fn resolve(root: &Path, manifest: Option<&WorkspaceManifest>) -> Result<Self> {
+        let mut routes = Self {
+            root: root.to_path_buf(),
+            ..Self::default()
+        };
+        let Some(manifest) = manifest else {
+            return Ok(routes);
+        };
+
+        for repository in manifest.repositories() {
+            if let Some(registry) = repository
+                .registry
+                .as_ref()
+                .and_then(|name| manifest.registries().get(name))
+            {
+                routes.repositories.push((
+                    normalize_relative(Path::new(&repository.path)),
+                    registry.clone(),
+                ));
+            }
+        }
+        for registry in manifest.registries().values() {
+            if registry.groups.is_empty() {
+                continue;
+            }
+            let selector = RepoSelector {
+                groups: registry.groups.clone(),
+                ..RepoSelector::default()
+            };
+            let members = selector
+                .resolve(root, Some(manifest))
+                .with_context(|| format!("registries.{}", registry.name))?;
+            routes.groups.push((members, registry.clone()));
+        }
+        Ok(routes)
+    }
+
+    /// Returns the registry the repository publishes to, or `None` for the
+    /// public registries. A repository in the groups of two registries must
+    /// name one on its `[[repo]]` entry.
+    pub fn registry_for(&self, name: &str, path: &Path) -> Result<Option<&PublishRegistry>> {
+        let relative = normalize_relative(path.strip_prefix(&self.root).unwrap_or(path));
+        if let Some((_, registry)) = self
+            .repositories
+            .iter()
+            .find(|(repository, _)| *repository == relative)
+        {
+            return Ok(Some(registry));
+        }
+
+        let mut matching = self
+            .groups
+            .iter()
+            .filter(|(members, _)| members.matches(name, path))
+            .map(|(_, registry)| registry);
+        let registry = matching.next();
+        if let (Some(first), Some(second)) = (registry, matching.next()) {
+            bail!(
+                "{name} is in groups published to both '{}' and '{}'; set registry on its [[repo]] entry in {MANIFEST_FILE_NAME}",
+                first.name,
+                second.name
+            );
+        }
+        Ok(registry)
+    }
+}
+
+static ACTIVE_SELECTION: OnceLock<RepoSelection> = OnceLock::new();
+
+/// Resolves `selector` against the current workspace and applies it to every
+/// subsequent repository discovery in this process.
+pub fn set_repo_selection(selector: &RepoSelector) -> Result<()> {
+    if selector.is_empty() {
+        return Ok(());
+    }
+
+    let root = Path::new(".");
+    let manifest = WorkspaceManifest::load(root)?;
+    let selection = selector.resolve(root, manifest.as_ref())?;
+    if ACTIVE_SELECTION.set(selection).is_err() {
+        bail!("repository selection was already configured");
+    }
+    Ok(())
+}
+
+/// Narrows discovered repositories with the process-wide selection, if any.
+pub(crate) fn apply_active_selection(repos: Vec<(String, PathBuf)>) -> Vec<(String, PathBuf)> {
+    match ACTIVE_SELECTION.get() {
+        Some(selection) => selection.apply(repos),
+        None => repos,
+    }
+}
+
+fn resolve_groups(requested: &[String], manifest: &WorkspaceManifest) -> Result<GroupMembers> {
+    let mut known = manifest
+        .group_patterns()
+        .keys()
+        .cloned()
+        .collect::<HashSet<_>>();
+    known.extend(
+        manifest
+            .repositories()
+            .iter()
+            .flat_map(|repository| repository.groups.iter().cloned()),
+    );
+
+    for group in requested {
+        if !known.contains(group) {
+            let mut defined = known.iter().map(String::as_str).collect::<Vec<_>>();
+            defined.sort_unstable();
+            let defined = if defined.is_empty() {
+                "none".to_string()
+            } else {
+                defined.join(", ")
+            };
+            bail!("unknown group '{group}' (defined in {MANIFEST_FILE_NAME}: {defined})");
+        }
+    }
+
+    let paths = manifest
+        .repositories()
+        .iter()
+        .filter(|repository| {
+            repository
+                .groups
+                .iter()
+                .any(|group| requested.contains(group))
+        })
+        .map(|repository| normalize_relative(Path::new(&repository.path)))
+        .collect();
+    let patterns = requested
+        .iter()
+        .filter_map(|group| manifest.group_patterns().get(group))
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>();
+    let patterns = build_globs(&patterns)
+        .with_context(|| format!("invalid pattern in {MANIFEST_FILE_NAME} [groups]"))?;
+
+    Ok(GroupMembers { paths, patterns })
+}
+
+fn resolve_tags(requested: &[String], manifest: &WorkspaceManifest) -> Result<HashSet<PathBuf>> {
+    for tag in requested {
+        let known = manifest
+            .repositories()
+            .iter()
+            .any(|repository| repository.tags.contains(tag));
+        if !known {
+            bail!("unknown tag '{tag}'; no repository in {MANIFEST_FILE_NAME} carries it");
+        }
+    }
+
+    Ok(manifest
+        .repositories()
+        .iter()
+        .filter(|repository| repository.tags.iter().any(|tag| requested.contains(tag)))
+        .map(|repository| normalize_relative(Path::new(&repository.path)))
+        .collect())
+}
+
+fn build_globs(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern
+            .trim()
+            .trim_start_matches("./")
+            .trim_end_matches('/');
+        builder.add(Glob::new(pattern).with_context(|| format!("'{pattern}'"))?);
+    }
+    Ok(builder.build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[[repo]]
+path = "services/api"
+url = "git@github.com:acme/api.git"
+groups = ["backend"]
+tags = ["rust"]
+
+[[repo]]
+path = "services/worker"
+url = "git@github.com:acme/worker.git"
+groups = ["backend"]
+
+[[repo]]
+path = "web"
+url = "git@github.com:acme/web.git"
+tags = ["node"]
+
+[groups]
+legacy = ["legacy-*", "archive/**"]
+"#;
+
+    fn fleet() -> Vec<(String, PathBuf)> {
+        [
+            ("api", "./services/api"),
+            ("worker", "./services/worker"),
+            ("web", "./web"),
+            ("legacy-billing", "./legacy-billing"),
+            ("old", "./archive/old"),
+        ]
+        .into_iter()
+        .map(|(name, path)| (name.to_string(), PathBuf::from(path)))
+        .collect()
+    }
+
+    fn names(repos: &[(String, PathBuf)]) -> Vec<&str> {
+        repos.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    fn select(selector: RepoSelector) -> Result<Vec<(String, PathBuf)>> {
+        let root = Path::new(".");
+        let manifest = WorkspaceManifest::parse(root, MANIFEST).unwrap();
+        Ok(selector.resolve(root, Some(&manifest))?.apply(fleet()))
+    }
+
+    #[test]
+    fn selects_groups_from_entries_and_pattern_table() {
+        let backend = select(RepoSelector {
+            groups: vec!["backend".to_string()],
+            ..RepoSelector::default()
+        })
+        .unwrap();
+        assert_eq!(names(&backend), vec!["api", "worker"]);
+
+        let legacy = select(RepoSelector {
+            groups: vec!["legacy".to_string()],
+            ..RepoSelector::default()
+        })
+        .unwrap();
+        assert_eq!(names(&legacy), vec!["legacy-billing", "old"]);
+    }
+
+    #[test]
+    fn combines_group_tag_and_exclude_selectors() {
+        let selected = select(RepoSelector {
+            groups: vec!["backend".to_string()],
+            tags: vec!["rust".to_string()],
+            exclude: Vec::new(),
+        })
+        .unwrap();
+        assert_eq!(names(&selected), vec!["api"]);
+
+        let selected = select(RepoSelector {
+            exclude: vec!["legacy-*".to_string(), "services/*".to_string()],
+            ..RepoSelector::default()
+        })
+        .unwrap();
+        assert_eq!(names(&selected), vec!["web", "old"]);
+    }
+
+    #[test]
+    fn rejects_unknown_groups_and_tags() {
+        let error = select(RepoSelector {
+            groups: vec!["frontend".to_string()],
+            ..RepoSelector::default()
+        })
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown group 'frontend'"));
+        assert!(error.to_string().contains("backend, legacy"));
+
+        let error = select(RepoSelector {
+            tags: vec!["go".to_string()],
+            ..RepoSelector::default()
+        })
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown tag 'go'"));
+    }
+
+    #[test]
+    fn exclude_works_without_a_manifest() {
+        let selection = RepoSelector {
+            exclude: vec!["web".to_string()],
+            ..RepoSelector::default()
+        }
+        .resolve(Path::new("."), None)
+        .unwrap();
+
+        assert_eq!(selection.apply(fleet()).len(), 4);
+        assert!(RepoSelector {
+            groups: vec!["backend".to_string()],
+            ..RepoSelector::default()
+        }
+        .resolve(Path::new("."), None)
+        .is_err());
+    }
+
+    #[test]
+    fn routes_repositories_to_their_own_or_their_groups_registry() {
+        let manifest = format!(
+            "{MANIFEST}\n[registries.internal]\ngroups = [\"backend\"]\nnpm = \"https://npm.internal\"\n\n[registries.archive]\ngroups = [\"legacy\"]\ncargo = \"archive\"\n"
+        )
+        .replace(
+            "path = \"services/worker\"",
+            "path = \"services/worker\"\nregistry = \"archive\"",
+        );
+        let root = Path::new(".");
+        let manifest = WorkspaceManifest::parse(root, &manifest).unwrap();
+        let routes = RegistryRoutes::resolve(root, Some(&manifest)).unwrap();
+        let registry = |name: &str, path: &str| {
+            routes
+                .registry_for(name, Path::new(path))
+                .unwrap()
+                .map(|registry| registry.name.clone())
+        };
+
+        assert_eq!(
+            registry("api", "./services/api").as_deref(),
+            Some("internal")
+        );
+        assert_eq!(
+            registry("worker", "./services/worker").as_deref(),
+            Some("archive")
+        );
+        assert_eq!(registry("old", "./archive/old").as_deref(), Some("archive"));
+        assert_eq!(registry("web", "./web"), None);
+
+        let overlapping = WorkspaceManifest::parse(
+            root,
+            &format!("{MANIFEST}\n[registries.a]\ngroups = [\"backend\"]\n\n[registries.b]\ngroups = [\"backend\"]\n"),
+        )
+        .unwrap();
+        let routes = RegistryRoutes::resolve(root, Some(&overlapping)).unwrap();
+        let error = routes
+            .registry_for("api", Path::new("./services/api"))
+            .unwrap_err();
+        assert!(error.to_string().contains("both 'a' and 'b'"), "{error}");
+    }
+}
@@ -0,0 +1,849 @@
+//! Layered `repos` configuration.
+//!
+//! Settings are resolved from, in increasing precedence:
+//!
+//! 1. built-in defaults (`core::config`)
+//! 2. the system file (`/etc/repos/config.toml`, or `REPOS_SYSTEM_CONFIG`)
+//! 3. the user file (`$XDG_CONFIG_HOME/repos/config.toml`, usually `~/.config/repos/config.toml`)
+//! 4. the workspace file (`.repos.toml` in the current directory)
+//! 5. environment variables (`REPOS_<SECTION>_<KEY>`, e.g. `REPOS_DISCOVERY_MAX_SCAN_DEPTH`)
+//! 6. command-line overrides (`--set key=value`)
+//!
+//! Every resolved value remembers the layer it came from so
+//! `repos config show --effective` can explain the result.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::config::{
+    CARGO_INDEX_URL, FETCH_CONCURRENT_CAP, GIT_CONCURRENT_CAP, GIT_OPERATION_TIMEOUT_SECS,
+    GO_PROXY_URL, HEX_API_URL, LARGE_FILE_THRESHOLD, MAVEN_REPOSITORY_URL, MAX_SCAN_DEPTH,
+    NPM_REGISTRY_URL, NUGET_URL, PYPI_URL, RUBYGEMS_URL, SKIP_DIRECTORIES,
+    SLOW_REPO_THRESHOLD_SECS,
+};
+use crate::git::operations::GitBackendKind;
+use crate::git::remote::TransportPolicy;
+use crate::package::tag::{validate_template, DEFAULT_TAG_TEMPLATE};
+
+/// Workspace configuration file name, resolved from the current directory.
+pub const WORKSPACE_CONFIG_FILE: &str = ".repos.toml";
+
+const SYSTEM_CONFIG_PATH: &str = "/etc/repos/config.toml";
+const SYSTEM_CONFIG_ENV: &str = "REPOS_SYSTEM_CONFIG";
+const LEGACY_TRANSPORT_POLICY_ENV: &str = "REPOS_TRANSPORT_POLICY";
+
+/// Every supported key, in display order.
+pub const SETTING_KEYS: &[&str] = &[
+    "discovery.skip_directories",
+    "discovery.max_scan_depth",
+    "concurrency.git",
+    "concurrency.fetch",
+    "git.operation_timeout_secs",
+    "git.slow_repo_threshold_secs",
+    "git.transport_policy",
+    "git.allowed_hosts",
+    "git.backend",
+    "audit.large_file_threshold",
+    "publish.cargo_index_url",
+    "publish.npm_registry_url",
+    "publish.pypi_url",
+    "publish.go_proxy_url",
+    "publish.rubygems_url",
+    "publish.maven_repository_url",
+    "publish.hex_api_url",
+    "publish.nuget_url",
+    "publish.tag_template",
+];
+
+/// The configuration layer a value was resolved from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigOrigin {
+    Default,
+    System(PathBuf),
+    User(PathBuf),
+    Workspace(PathBuf),
+    Env(String),
+    Cli,
+    /// Legacy `repos.transportPolicy` in global git config.
+    GitConfig,
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::System(path) => write!(f, "system ({})", path.display()),
+            Self::User(path) => write!(f, "user ({})", path.display()),
+            Self::Workspace(path) => write!(f, "workspace ({})", path.display()),
+            Self::Env(name) => write!(f, "env {name}"),
+            Self::Cli => write!(f, "cli --set"),
+            Self::GitConfig => write!(f, "git config repos.transportPolicy"),
+        }
+    }
+}
+
+/// A resolved value together with the layer that set it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub origin: ConfigOrigin,
+}
+
+impl<T> Setting<T> {
+    fn default_value(value: T) -> Self {
+        Self {
+            value,
+            origin: ConfigOrigin::Default,
+        }
+    }
+}
+
+/// Typed, fully resolved configuration.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settings {
+    /// Directory names never descended into during discovery.
+    pub skip_directories: Setting<Vec<String>>,
+    /// Maximum directory depth walked during discovery.
+    pub max_scan_depth: Setting<usize>,
+    /// Concurrency for commands without `--jobs`.
+    pub git_concurrency: Setting<usize>,
+    /// Upper bound on concurrent fetches during push/pull analysis.
+    pub fetch_concurrency: Setting<usize>,
+    /// Timeout for a single git invocation.
+    pub git_timeout_secs: Setting<u64>,
+    /// Elapsed time after which a running repository is named in progress output.
+    pub slow_repo_threshold_secs: Setting<u64>,
+    /// Remote transport policy; `None` defers to global git config.
+    pub transport_policy: Setting<Option<TransportPolicy>>,
+    /// Hosts remotes may point at; empty allows any host.
+    pub allowed_hosts: Setting<Vec<String>>,
+    /// Implementation answering read-only status queries.
+    pub git_backend: Setting<GitBackendKind>,
+    /// Size in bytes above which tracked files are reported by `repos audit`.
+    pub large_file_threshold: Setting<u64>,
+    /// Cargo sparse index checked for already-published crate versions.
+    pub cargo_index_url: Setting<String>,
+    /// npm registry checked for already-published package versions.
+    pub npm_registry_url: Setting<String>,
+    /// PyPI JSON API checked for already-published releases.
+    pub pypi_url: Setting<String>,
+    /// Go module proxy checked for already-published module versions.
+    pub go_proxy_url: Setting<String>,
+    /// RubyGems API checked for already-published gem versions.
+    pub rubygems_url: Setting<String>,
+    /// Maven repository checked for already-deployed artifacts.
+    pub maven_repository_url: Setting<String>,
+    /// Hex API checked for already-published releases.
+    pub hex_api_url: Setting<String>,
+    /// NuGet flat container checked for already-pushed package versions.
+    pub nuget_url: Setting<String>,
+    /// Release tag template for `repos publish --tag`.
+    pub tag_template: Setting<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            skip_directories: Setting::default_value(
+                SKIP_DIRECTORIES.iter().map(ToString::to_string).collect(),
+            ),
+            max_scan_depth: Setting::default_value(MAX_SCAN_DEPTH),
+            git_concurrency: Setting::default_value(GIT_CONCURRENT_CAP),
+            fetch_concurrency: Setting::default_value(FETCH_CONCURRENT_CAP),
+            git_timeout_secs: Setting::default_value(GIT_OPERATION_TIMEOUT_SECS),
+            slow_repo_threshold_secs: Setting::default_value(SLOW_REPO_THRESHOLD_SECS),
+            transport_policy: Setting::default_value(None),
+            allowed_hosts: Setting::default_value(Vec::new()),
+            git_backend: Setting::default_value(GitBackendKind::Cli),
+            large_file_threshold: Setting::default_value(LARGE_FILE_THRESHOLD),
+            cargo_index_url: Setting::default_value(CARGO_INDEX_URL.to_string()),
+            npm_registry_url: Setting::default_value(NPM_REGISTRY_URL.to_string()),
+            pypi_url: Setting::default_value(PYPI_URL.to_string()),
+            go_proxy_url: Setting::default_value(GO_PROXY_URL.to_string()),
+            rubygems_url: Setting::default_value(RUBYGEMS_URL.to_string()),
+            maven_repository_url: Setting::default_value(MAVEN_REPOSITORY_URL.to_string()),
+            hex_api_url: Setting::default_value(HEX_API_URL.to_string()),
+            nuget_url: Setting::default_value(NUGET_URL.to_string()),
+            tag_template: Setting::default_value(DEFAULT_TAG_TEMPLATE.to_string()),
+        }
+    }
+}
+
+/// Locations of the file-backed configuration layers.
+#[derive(Clone, Debug)]
+pub struct ConfigPaths {
+    pub system: PathBuf,
+    pub user: Option<PathBuf>,
+    pub workspace: PathBuf,
+}
+
+impl ConfigPaths {
+    /// Resolves the standard layer locations for the current process.
+    #[must_use]
+    pub fn discover() -> Self {
+        let system = std::env::var_os(SYSTEM_CONFIG_ENV)
+            .map_or_else(|| PathBuf::from(SYSTEM_CONFIG_PATH), PathBuf::from);
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+        Self {
+            system,
+            user: config_home.map(|home| home.join("repos").join("config.toml")),
+            workspace: PathBuf::from(WORKSPACE_CONFIG_FILE),
+        }
+    }
+
+    /// Returns each file layer with the origin it assigns, in precedence order.
+    #[must_use]
+    pub fn layers(&self) -> Vec<(PathBuf, ConfigOrigin)> {
+        let mut layers = vec![(
+            self.system.clone(),
+            ConfigOrigin::System(self.system.clone()),
+        )];
+        if let Some(user) = &self.user {
+            layers.push((user.clone(), ConfigOrigin::User(user.clone())));
+        }
+        layers.push((
+            self.workspace.clone(),
+            ConfigOrigin::Workspace(self.workspace.clone()),
+        ));
+        layers
+    }
+}
+
+enum RawValue<'a> {
+    Toml(&'a toml::Value),
+    Text(&'a str),
+}
+
+impl Settings {
+    /// Resolves every layer. `env` looks up environment variables and
+    /// `overrides` holds `key=value` pairs from the command line.
+    pub fn load(
+        paths: &ConfigPaths,
+        env: &dyn Fn(&str) -> Option<String>,
+        overrides: &[String],
+    ) -> Result<Self> {
+        let mut settings = Self::default();
+
+        for (path, origin) in paths.layers() {
+            settings.apply_file(&path, &origin)?;
+        }
+
+        // Applied first so REPOS_GIT_TRANSPORT_POLICY wins when both are set.
+        if let Some(value) = env(LEGACY_TRANSPORT_POLICY_ENV) {
+            settings
+                .set(
+                    "git.transport_policy",
+                    RawValue::Text(&value),
+                    ConfigOrigin::Env(LEGACY_TRANSPORT_POLICY_ENV.to_string()),
+                )
+                .with_context(|| format!("{LEGACY_TRANSPORT_POLICY_ENV}: invalid value"))?;
+        }
+        for key in SETTING_KEYS {
+            let name = env_var_name(key);
+            if let Some(value) = env(&name) {
+                settings
+                    .set(key, RawValue::Text(&value), ConfigOrigin::Env(name.clone()))
+                    .with_context(|| format!("{name}: invalid value for {key}"))?;
+            }
+        }
+
+        for assignment in overrides {
+            let (key, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| anyhow!("--set {assignment}: expected KEY=VALUE"))?;
+            let key = key.trim();
+            settings
+                .set(key, RawValue::Text(value.trim()), ConfigOrigin::Cli)
+                .with_context(|| format!("--set {key}: invalid value"))?;
+        }
+
+        Ok(settings)
+    }
+
+    fn apply_file(&mut self, path: &Path, origin: &ConfigOrigin) -> Result<()> {
+        if !path.is_file() {
+            return Ok(());
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let table: toml::Table = toml::from_str(&contents)
+            .with_context(|| format!("{}: invalid TOML", path.display()))?;
+
+        for (section, entries) in &table {
+            let Some(entries) = entries.as_table() else {
+                bail!(
+                    "{}: unknown key '{section}' (expected a section such as [discovery])",
+                    path.display()
+                );
+            };
+            for (name, value) in entries {
+                let key = format!("{section}.{name}");
+                self.set(&key, RawValue::Toml(value), origin.clone())
+                    .with_context(|| format!("{}: {key}", path.display()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, key: &str, raw: RawValue<'_>, origin: ConfigOrigin) -> Result<()> {
+        match key {
+            "discovery.skip_directories" => {
+                self.skip_directories = Setting {
+                    value: parse_string_list(&raw)?,
+                    origin,
+                };
+            }
+            "discovery.max_scan_depth" => {
+                self.max_scan_depth = Setting {
+                    value: parse_positive(&raw)?,
+                    origin,
+                };
+            }
+            "concurrency.git" => {
+                self.git_concurrency = Setting {
+                    value: parse_positive(&raw)?,
+                    origin,
+                };
+            }
+            "concurrency.fetch" => {
+                self.fetch_concurrency = Setting {
+                    value: parse_positive(&raw)?,
+                    origin,
+                };
+            }
+            "git.operation_timeout_secs" => {
+                self.git_timeout_secs = Setting {
+                    value: parse_positive(&raw)?,
+                    origin,
+                };
+            }
+            "git.slow_repo_threshold_secs" => {
+                self.slow_repo_threshold_secs = Setting {
+                    value: parse_positive(&raw)?,
+                    origin,
+                };
+            }
+            "git.transport_policy" => {
+                self.transport_policy = Setting {
+                    value: Some(TransportPolicy::parse(&parse_string(&raw)?)?),
+                    origin,
+                };
+            }
+            "git.allowed_hosts" => {
+                self.allowed_hosts = Setting {
+                    value: parse_string_list(&raw)?,
+                    origin,
+                };
+            }
+            "git.backend" => {
+                self.git_backend = Setting {
+                    value: GitBackendKind::parse(&parse_string(&raw)?)?,
+                    origin,
+                };
+            }
+            "audit.large_file_threshold" => {
+                self.large_file_threshold = Setting {
+                    value: parse_positive(&raw)?,
+                    origin,
+                };
+            }
+            "publish.cargo_index_url" => {
+                self.cargo_index_url = Setting {
+                    value: parse_url(&raw)?,
+                    origin,
+                };
+            }
+            "publish.npm_registry_url" => {
+                self.npm_registry_url = Setting {
+                    value: parse_url(&raw)?,
+                    origin,
+                };
+            }
+            "publish.pypi_url" => {
+                self.pypi_url = Setting {
+                    value: parse_url(&raw)?,
+                    origin,
+                };
+            }
+            "publish.go_proxy_url" => {
+                self.go_proxy_url = Setting {
+                    value: parse_url(&raw)?,
+                    origin,
+                };
+            }
+            "publish.rubygems_url" => {
+                self.rubygems_url = Setting {
+                    value: parse_url(&raw)?,
+                    origin,
+                };
+            }
+            "publish.maven_repository_url" => {
+                self.maven_repository_url = Setting {
+                    value: parse_url(&raw)?,
+                    origin,
+                };
+            }
+            "publish.hex_api_url" => {
+                self.hex_api_url = Setting {
+                    value: parse_url(&raw)?,
+                    origin,
+                };
+            }
+            "publish.nuget_url" => {
+                self.nuget_url = Setting {
+                    value: parse_url(&raw)?,
+                    origin,
+                };
+            }
+            "publish.tag_template" => {
+                let template = parse_string(&raw)?;
+                validate_template(&template)?;
+                self.tag_template = Setting {
+                    value: template,
+                    origin,
+                };
+            }
+            _ => bail!(
+                "unknown key '{key}'; supported keys: {}",
+                SETTING_KEYS.join(", ")
+            ),
+        }
+        Ok(())
+    }
+
+    /// Returns `(key, rendered value, origin)` for every setting, in display order.
+    #[must_use]
+    pub fn entries(&self) -> Vec<(&'static str, String, &ConfigOrigin)> {
+        let list = |values: &[String]| {
+            toml::Value::Array(values.iter().cloned().map(toml::Value::String).collect())
+                .to_string()
+        };
+        vec![
+            (
+                "discovery.skip_directories",
+                list(&self.skip_directories.value),
+                &self.skip_directories.origin,
+            ),
+            (
+                "discovery.max_scan_depth",
+                self.max_scan_depth.value.to_string(),
+                &self.max_scan_depth.origin,
+            ),
+            (
+                "concurrency.git",
+                self.git_concurrency.value.to_string(),
+                &self.git_concurrency.origin,
+            ),
+            (
+                "concurrency.fetch",
+                self.fetch_concurrency.value.to_string(),
+                &self.fetch_concurrency.origin,
+            ),
+            (
+                "git.operation_timeout_secs",
+                self.git_timeout_secs.value.to_string(),
+                &self.git_timeout_secs.origin,
+            ),
+            (
+                "git.slow_repo_threshold_secs",
+                self.slow_repo_threshold_secs.value.to_string(),
+                &self.slow_repo_threshold_secs.origin,
+            ),
+            (
+                "git.transport_policy",
+                format!(
+                    "\"{}\"",
+                    self.transport_policy
+                        .value
+                        .unwrap_or(TransportPolicy::Preserve)
+                        .label()
+                ),
+                &self.transport_policy.origin,
+            ),
+            (
+                "git.allowed_hosts",
+                list(&self.allowed_hosts.value),
+                &self.allowed_hosts.origin,
+            ),
+            (
+                "git.backend",
+                format!("\"{}\"", self.git_backend.value.label()),
+                &self.git_backend.origin,
+            ),
+            (
+                "audit.large_file_threshold",
+                self.large_file_threshold.value.to_string(),
+                &self.large_file_threshold.origin,
+            ),
+            (
+                "publish.cargo_index_url",
+                format!("\"{}\"", self.cargo_index_url.value),
+                &self.cargo_index_url.origin,
+            ),
+            (
+                "publish.npm_registry_url",
+                format!("\"{}\"", self.npm_registry_url.value),
+                &self.npm_registry_url.origin,
+            ),
+            (
+                "publish.pypi_url",
+                format!("\"{}\"", self.pypi_url.value),
+                &self.pypi_url.origin,
+            ),
+            (
+                "publish.go_proxy_url",
+                format!("\"{}\"", self.go_proxy_url.value),
+                &self.go_proxy_url.origin,
+            ),
+            (
+                "publish.rubygems_url",
+                format!("\"{}\"", self.rubygems_url.value),
+                &self.rubygems_url.origin,
+            ),
+            (
+                "publish.maven_repository_url",
+                format!("\"{}\"", self.maven_repository_url.value),
+                &self.maven_repository_url.origin,
+            ),
+            (
+                "publish.hex_api_url",
+                format!("\"{}\"", self.hex_api_url.value),
+                &self.hex_api_url.origin,
+            ),
+            (
+                "publish.nuget_url",
+                format!("\"{}\"", self.nuget_url.value),
+                &self.nuget_url.origin,
+            ),
+            (
+                "publish.tag_template",
+                format!("\"{}\"", self.tag_template.value),
+                &self.tag_template.origin,
+            ),
+        ]
+    }
+}
+
+/// Maps `section.key` to its environment variable, e.g. `REPOS_CONCURRENCY_GIT`.
+#[must_use]
+pub fn env_var_name(key: &str) -> String {
+    format!("REPOS_{}", key.replace('.', "_").to_ascii_uppercase())
+}
+
+fn parse_positive<T: TryFrom<u64>>(raw: &RawValue<'_>) -> Result<T> {
+    let value = match raw {
+        RawValue::Toml(toml::Value::Integer(value)) => u64::try_from(*value).ok(),
+        RawValue::Toml(_) => None,
+        RawValue::Text(text) => text.trim().parse::<u64>().ok(),
+    };
+    value
+        .filter(|value| *value > 0)
+        .and_then(|value| T::try_from(value).ok())
+        .ok_or_else(|| anyhow!("expected a positive integer"))
+}
+
+fn parse_string(raw: &RawValue<'_>) -> Result<String> {
+    match raw {
+        RawValue::Toml(toml::Value::String(value)) => Ok(value.clone()),
+        RawValue::Toml(_) => Err(anyhow!("expected a string")),
+        RawValue::Text(text) => Ok((*text).to_string()),
+    }
+}
+
+/// Registry base URL without a trailing slash. `file://` URLs let a local
+/// directory stand in for a registry.
+fn parse_url(raw: &RawValue<'_>) -> Result<String> {
+    let value = parse_string(raw)?;
+    let value = value.trim().trim_end_matches('/');
+    let scheme_ok = ["https://", "http://", "file://"]
+        .iter()
+        .any(|scheme| value.len() > scheme.len() && value.starts_with(scheme));
+    if !scheme_ok {
+        bail!("expected an https://, http://, or file:// URL");
+    }
+    Ok(value.to_string())
+}
+
+fn parse_string_list(raw: &RawValue<'_>) -> Result<Vec<String>> {
+    let values = match raw {
+        RawValue::Toml(toml::Value::Array(items)) => items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow!("expected an array of strings"))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        RawValue::Toml(_) => bail!("expected an array of strings"),
+        RawValue::Text(text) => text
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect(),
+    };
+
+    if values.iter().any(|value| value.trim().is_empty()) {
+        bail!("entries must not be empty");
+    }
+    Ok(values)
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Loads the layered configuration with command-line overrides and installs it
+/// for the rest of the process. Errors name the offending file or variable and key.
+pub fn init_settings(overrides: &[String]) -> Result<()> {
+    let settings = Settings::load(
+        &ConfigPaths::discover(),
+        &|name| std::env::var(name).ok(),
+        overrides,
+    )?;
+    if SETTINGS.set(settings).is_err() {
+        bail!("repos configuration was already loaded");
+    }
+    Ok(())
+}
+
+/// Returns the process configuration, loading it without overrides on first use.
+///
+/// The CLI validates configuration up front through [`init_settings`]; library
+/// callers that skip that step fall back to defaults if a layer is invalid.
+pub fn settings() -> &'static Settings {
+    SETTINGS.get_or_init(|| {
+        Settings::load(
+            &ConfigPaths::discover(),
+            &|name| std::env::var(name).ok(),
+            &[],
+        )
+        .unwrap_or_else(|error| {
+            eprintln!("Warning: {error:#}; using built-in defaults");
+            Settings::default()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    struct Layers {
+        _directory: TempDir,
+        paths: ConfigPaths,
+    }
+
+    fn layers(system: &str, user: &str, workspace: &str) -> Layers {
+        let directory = TempDir::new().unwrap();
+        let paths = ConfigPaths {
+            system: directory.path().join("system.toml"),
+            user: Some(directory.path().join("user.toml")),
+            workspace: directory.path().join(".repos.toml"),
+        };
+        for (path, contents) in [
+            (&paths.system, system),
+            (paths.user.as_ref().unwrap(), user),
+            (&paths.workspace, workspace),
+        ] {
+            if !contents.is_empty() {
+                std::fs::write(path, contents).unwrap();
+            }
+        }
+        Layers {
+            _directory: directory,
+            paths,
+        }
+    }
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn defaults_match_core_constants() {
+        let settings = Settings::load(&layers("", "", "").paths, &no_env, &[]).unwrap();
+
+        assert_eq!(settings, Settings::default());
+        assert_eq!(settings.max_scan_depth.value, MAX_SCAN_DEPTH);
+        assert!(settings
+            .entries()
+            .iter()
+            .all(|(_, _, origin)| **origin == ConfigOrigin::Default));
+    }
+
+    #[test]
+    fn later_layers_override_earlier_ones_and_record_origin() {
+        let layers = layers(
+            "[discovery]\nmax_scan_depth = 4\n[concurrency]\ngit = 8\n",
+            "[discovery]\nmax_scan_depth = 6\n",
+            "[discovery]\nskip_directories = [\"node_modules\", \"third_party\"]\n",
+        );
+        let env = HashMap::from([
+            ("REPOS_CONCURRENCY_FETCH".to_string(), "3".to_string()),
+            ("REPOS_TRANSPORT_POLICY".to_string(), "ssh-only".to_string()),
+        ]);
+        let settings = Settings::load(
+            &layers.paths,
+            &|name| env.get(name).cloned(),
+            &["concurrency.git=2".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(settings.max_scan_depth.value, 6);
+        assert_eq!(
+            settings.max_scan_depth.origin,
+            ConfigOrigin::User(layers.paths.user.clone().unwrap())
+        );
+        assert_eq!(
+            settings.skip_directories.value,
+            vec!["node_modules".to_string(), "third_party".to_string()]
+        );
+        assert_eq!(
+            settings.skip_directories.origin,
+            ConfigOrigin::Workspace(layers.paths.workspace.clone())
+        );
+        assert_eq!(settings.fetch_concurrency.value, 3);
+        assert_eq!(
+            settings.fetch_concurrency.origin,
+            ConfigOrigin::Env("REPOS_CONCURRENCY_FETCH".to_string())
+        );
+        assert_eq!(settings.git_concurrency.value, 2);
+        assert_eq!(settings.git_concurrency.origin, ConfigOrigin::Cli);
+        assert_eq!(
+            settings.transport_policy.value,
+            Some(TransportPolicy::SshOnly)
+        );
+    }
+
+    #[test]
+    fn new_transport_policy_variable_wins_over_the_legacy_one() {
+        let env = HashMap::from([
+            ("REPOS_TRANSPORT_POLICY".to_string(), "ssh-only".to_string()),
+            (
+                "REPOS_GIT_TRANSPORT_POLICY".to_string(),
+                "https-only".to_string(),
+            ),
+        ]);
+        let settings = Settings::load(
+            &layers("", "", "").paths,
+            &|name| env.get(name).cloned(),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            settings.transport_policy.value,
+            Some(TransportPolicy::HttpsOnly)
+        );
+        assert_eq!(
+            settings.transport_policy.origin,
+            ConfigOrigin::Env("REPOS_GIT_TRANSPORT_POLICY".to_string())
+        );
+    }
+
+    #[test]
+    fn validation_errors_name_the_file_and_key() {
+        let workspace = layers("", "", "[discovery]\nmax_scan_depth = 0\n");
+        let error = Settings::load(&workspace.paths, &no_env, &[]).unwrap_err();
+        let message = format!("{error:#}");
+        assert!(message.contains(".repos.toml"), "{message}");
+        assert!(message.contains("discovery.max_scan_depth"), "{message}");
+        assert!(message.contains("positive integer"), "{message}");
+
+        let system = layers("[discovery]\nmax_depth = 3\n", "", "");
+        let message = format!(
+            "{:#}",
+            Settings::load(&system.paths, &no_env, &[]).unwrap_err()
+        );
+        assert!(message.contains("system.toml"), "{message}");
+        assert!(
+            message.contains("unknown key 'discovery.max_depth'"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn env_and_cli_errors_name_their_source() {
+        let empty = layers("", "", "");
+        let message = format!(
+            "{:#}",
+            Settings::load(
+                &empty.paths,
+                &|name| (name == "REPOS_GIT_TRANSPORT_POLICY").then(|| "ftp".to_string()),
+                &[],
+            )
+            .unwrap_err()
+        );
+        assert!(message.contains("REPOS_GIT_TRANSPORT_POLICY"), "{message}");
+
+        let message = format!(
+            "{:#}",
+            Settings::load(&empty.paths, &no_env, &["concurrency.git".to_string()]).unwrap_err()
+        );
+        assert!(message.contains("expected KEY=VALUE"), "{message}");
+    }
+
+    #[test]
+    fn git_backend_accepts_cli_and_gix() {
+        let empty = layers("", "", "");
+        let settings = Settings::load(&empty.paths, &no_env, &[]).unwrap();
+        assert_eq!(settings.git_backend.value, GitBackendKind::Cli);
+
+        let settings =
+            Settings::load(&empty.paths, &no_env, &["git.backend=gix".to_string()]).unwrap();
+        assert_eq!(settings.git_backend.value, GitBackendKind::Gix);
+
+        let message = format!(
+            "{:#}",
+            Settings::load(&empty.paths, &no_env, &["git.backend=libgit2".to_string()])
+                .unwrap_err()
+        );
+        assert!(message.contains("expected 'cli' or 'gix'"), "{message}");
+    }
+
+    #[test]
+    fn registry_urls_drop_trailing_slashes_and_require_a_scheme() {
+        let empty = layers("", "", "");
+        let settings = Settings::load(
+            &empty.paths,
+            &|name| {
+                (name == "REPOS_PUBLISH_NPM_REGISTRY_URL")
+                    .then(|| "file:///srv/registry/".to_string())
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(settings.npm_registry_url.value, "file:///srv/registry");
+        assert_eq!(settings.cargo_index_url.value, CARGO_INDEX_URL);
+
+        let message = format!(
+            "{:#}",
+            Settings::load(
+                &empty.paths,
+                &no_env,
+                &["publish.pypi_url=pypi.internal".to_string()]
+            )
+            .unwrap_err()
+        );
+        assert!(message.contains("publish.pypi_url"), "{message}");
+        assert!(message.contains("file:// URL"), "{message}");
+    }
+
+    #[test]
+    fn env_var_names_follow_section_and_key() {
+        assert_eq!(
+            env_var_name("discovery.max_scan_depth"),
+            "REPOS_DISCOVERY_MAX_SCAN_DEPTH"
+        );
+        assert_eq!(env_var_name("concurrency.git"), "REPOS_CONCURRENCY_GIT");
+    }
+}
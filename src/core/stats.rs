@@ -1,17 +1,17 @@
 //! Statistics tracking for repository operations
 
 use super::attention::{append_project_attention_section, AttentionKind, ProjectAttention};
-use super::report::RepositoryOutcome;
-use crate::core::config::{
-    ERROR_MESSAGE_MAX_LENGTH, ERROR_MESSAGE_TRUNCATE_LENGTH, TIMEOUT_SECONDS_DISPLAY,
-};
+use super::output::{emit_repository, RepositoryRecord};
+use super::report::{CommandCapture, RepositoryOutcome};
+use crate::core::config::{ERROR_MESSAGE_MAX_LENGTH, ERROR_MESSAGE_TRUNCATE_LENGTH};
+use crate::core::settings::settings;
 use crate::git::failure::GitFailure;
 use crate::git::Status;
 use crate::utils::compare_repository_locations;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 
 pub(super) const RESET: &str = "\x1b[0m";
@@ -103,6 +103,9 @@ pub struct SyncStatistics {
     pub fetched_repo_details: Mutex<Vec<(String, String, u64)>>, // (repo_name, repo_path, refs)
     pub(crate) operation_outcomes: Mutex<Vec<RepositoryOutcome>>,
     pub(crate) git_failures: Mutex<HashMap<(String, String), GitFailure>>,
+    pub(crate) command_captures: Mutex<HashMap<(String, String), CommandCapture>>,
+    // Operation name used for `--format ndjson` repository events
+    pub(crate) event_phase: OnceLock<&'static str>,
 }
 
 impl Default for SyncStatistics {
@@ -133,9 +136,17 @@ impl SyncStatistics {
             fetched_repo_details: Mutex::new(Vec::new()),
             operation_outcomes: Mutex::new(Vec::new()),
             git_failures: Mutex::new(HashMap::new()),
+            command_captures: Mutex::new(HashMap::new()),
+            event_phase: OnceLock::new(),
         }
     }
 
+    /// Names the operation whose results are streamed as `--format ndjson`
+    /// repository events; without it, results are only kept for the final report.
+    pub(crate) fn stream_events_as(&self, phase: &'static str) {
+        let _ = self.event_phase.set(phase);
+    }
+
     /// Updates statistics based on the synchronization result
     pub fn update(
         &self,
@@ -145,14 +156,33 @@ impl SyncStatistics {
         message: &str,
         has_uncommitted: bool,
     ) {
+        self.record(repo_name, repo_path, status, message, has_uncommitted, None);
+    }
+
+    fn record(
+        &self,
+        repo_name: &str,
+        repo_path: &str,
+        status: &Status,
+        message: &str,
+        has_uncommitted: bool,
+        failure: Option<&GitFailure>,
+    ) {
+        let outcome = RepositoryOutcome {
+            repository: repo_name.to_string(),
+            path: repo_path.to_string(),
+            status: *status,
+            message: message.to_string(),
+            has_uncommitted,
+        };
+        if let Some(phase) = self.event_phase.get() {
+            let mut record = RepositoryRecord::new(phase, &outcome, failure);
+            record.exec = self.command_capture(repo_name, repo_path);
+            emit_repository(&record);
+        }
+
         if let Ok(mut outcomes) = self.operation_outcomes.lock() {
-            outcomes.push(RepositoryOutcome {
-                repository: repo_name.to_string(),
-                path: repo_path.to_string(),
-                status: *status,
-                message: message.to_string(),
-                has_uncommitted,
-            });
+            outcomes.push(outcome);
         } else {
             eprintln!("Warning: Failed to record operation result for repo: {repo_name}");
         }
@@ -204,7 +234,12 @@ impl SyncStatistics {
             | Status::ConfigUpdated
             | Status::Staged
             | Status::Unstaged
-            | Status::Committed => {
+            | Status::Committed
+            | Status::Cloned
+            | Status::Executed
+            | Status::Switched
+            | Status::Pruned
+            | Status::Restored => {
                 self.synced_repos.fetch_add(1, Ordering::Relaxed);
             }
             Status::Skip
@@ -265,7 +300,14 @@ impl SyncStatistics {
         has_uncommitted: bool,
         failure: Option<&GitFailure>,
     ) {
-        self.update(repo_name, repo_path, status, message, has_uncommitted);
+        self.record(
+            repo_name,
+            repo_path,
+            status,
+            message,
+            has_uncommitted,
+            failure,
+        );
 
         if let Some(failure) = failure {
             if let Ok(mut failures) = self.git_failures.lock() {
@@ -279,6 +321,36 @@ impl SyncStatistics {
         }
     }
 
+    /// Updates statistics and retains the exit status and output of `repos exec`.
+    pub(crate) fn update_with_capture(
+        &self,
+        repo_name: &str,
+        repo_path: &str,
+        status: &Status,
+        message: &str,
+        capture: CommandCapture,
+    ) {
+        if let Ok(mut captures) = self.command_captures.lock() {
+            captures.insert((repo_name.to_string(), repo_path.to_string()), capture);
+        } else {
+            eprintln!("Warning: Failed to retain command output for repo: {repo_name}");
+        }
+
+        self.record(repo_name, repo_path, status, message, false, None);
+    }
+
+    pub(crate) fn command_capture(
+        &self,
+        repo_name: &str,
+        repo_path: &str,
+    ) -> Option<CommandCapture> {
+        self.command_captures.lock().ok().and_then(|captures| {
+            captures
+                .get(&(repo_name.to_string(), repo_path.to_string()))
+                .cloned()
+        })
+    }
+
     /// Generates a push-specific completion summary.
     pub fn generate_push_summary(&self, duration: Duration) -> String {
         self.generate_transfer_summary(Transfer::Push, duration)
@@ -781,8 +853,9 @@ pub(crate) fn clean_error_message(error: &str) -> String {
         "email privacy restriction".to_string()
     } else if cleaned.contains("timed out") {
         // Extract timeout duration if present
-        if cleaned.contains(&TIMEOUT_SECONDS_DISPLAY.to_string()) {
-            format!("timeout ({TIMEOUT_SECONDS_DISPLAY}s)")
+        let timeout_secs = settings().git_timeout_secs.value;
+        if cleaned.contains(&timeout_secs.to_string()) {
+            format!("timeout ({timeout_secs}s)")
         } else {
             "timeout".to_string()
         }
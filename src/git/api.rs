@@ -24,6 +24,10 @@ pub use super::operations::RepoVisibility;
 pub use super::operations::{fetch_and_analyze, push_if_needed};
 pub use super::operations::{fetch_and_analyze_for_pull, pull_if_needed};
 
+// Read-only query backends (`git.backend`)
+pub use super::gix_backend::GixBackend;
+pub use super::operations::{CliBackend, GitBackend};
+
 // Status
 pub use super::status::Status;
 
@@ -35,9 +39,9 @@ pub use super::config::{
 
 // Additional operations for command modules and tests
 pub use super::operations::{
-    commit_changes, create_and_push_tag, get_repo_visibility, get_staging_status,
-    has_staged_changes, has_uncommitted_changes, is_detached_head, stage_all_changes, stage_files,
-    stage_tracked_changes, unstage_files,
+    commit_changes, create_and_push_tag, create_tag, get_repo_visibility, get_staging_status,
+    has_staged_changes, has_uncommitted_changes, is_detached_head, push_tag, stage_all_changes,
+    stage_files, stage_tracked_changes, unstage_files,
 };
 
 // LFS functions - used internally by push_if_needed, exported for integration tests
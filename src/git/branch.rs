@@ -0,0 +1,199 @@
+//! Branch inspection and mutation used by `repos branch`, `switch`, and `prune`.
+
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+use super::operations::run_git;
+
+/// Local and remote-tracking branches matching a name or glob.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct BranchMatches {
+    pub(crate) local: Vec<String>,
+    pub(crate) remote: Vec<String>,
+}
+
+impl BranchMatches {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.local.is_empty() && self.remote.is_empty()
+    }
+}
+
+/// Returns the checked-out branch, or `None` on a detached HEAD.
+pub(crate) async fn current_branch(path: &Path) -> Result<Option<String>> {
+    match run_git(path, &["symbolic-ref", "--quiet", "--short", "HEAD"]).await? {
+        (true, branch, _) => Ok(Some(branch)),
+        (false, _, stderr) if stderr.is_empty() => Ok(None),
+        (false, _, stderr) => Err(anyhow!(stderr)),
+    }
+}
+
+/// Lists local branches and remote-tracking branches (on any remote) whose
+/// name matches `pattern`, which may be a literal name or a glob.
+pub(crate) async fn find_branches(path: &Path, pattern: &str) -> Result<BranchMatches> {
+    let local_pattern = format!("refs/heads/{pattern}");
+    let remote_pattern = format!("refs/remotes/*/{pattern}");
+    let (success, stdout, stderr) = run_git(
+        path,
+        &[
+            "for-each-ref",
+            "--format=%(refname)",
+            &local_pattern,
+            &remote_pattern,
+        ],
+    )
+    .await?;
+    if !success {
+        return Err(anyhow!(stderr));
+    }
+
+    let mut matches = BranchMatches::default();
+    for refname in stdout.lines() {
+        if let Some(branch) = refname.strip_prefix("refs/heads/") {
+            matches.local.push(branch.to_string());
+        } else if let Some(branch) = refname.strip_prefix("refs/remotes/") {
+            if !branch.ends_with("/HEAD") {
+                matches.remote.push(branch.to_string());
+            }
+        }
+    }
+    Ok(matches)
+}
+
+/// Returns the remote default branch (e.g. `origin/main`) that `repos prune`
+/// compares against, preferring `origin` and falling back to other remotes.
+///
+/// Uses the remote's `HEAD` when it has been recorded, otherwise the first of
+/// `main` or `master` that exists on the remote. Returns `Ok(None)` when the
+/// repository has remotes but none of them has a known default branch.
+pub(crate) async fn remote_default_branch(
+    path: &Path,
+    remotes: &[String],
+) -> Result<Option<String>> {
+    let mut ordered = remotes.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|remote| remote.as_str() != "origin");
+
+    for remote in ordered {
+        let head = format!("refs/remotes/{remote}/HEAD");
+        if let (true, branch, _) =
+            run_git(path, &["symbolic-ref", "--quiet", "--short", &head]).await?
+        {
+            return Ok(Some(branch));
+        }
+        for candidate in ["main", "master"] {
+            let refname = format!("refs/remotes/{remote}/{candidate}");
+            if run_git(path, &["show-ref", "--verify", "--quiet", &refname])
+                .await?
+                .0
+            {
+                return Ok(Some(format!("{remote}/{candidate}")));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Lists configured remotes.
+pub(crate) async fn list_remotes(path: &Path) -> Result<Vec<String>> {
+    match run_git(path, &["remote"]).await? {
+        (true, stdout, _) => Ok(stdout.lines().map(str::to_string).collect()),
+        (false, _, stderr) => Err(anyhow!(stderr)),
+    }
+}
+
+/// Lists local branches fully merged into `base`, excluding the branch named
+/// like `base` itself and branches checked out in any worktree.
+pub(crate) async fn merged_branches(path: &Path, base: &str) -> Result<Vec<String>> {
+    let (success, stdout, stderr) = run_git(
+        path,
+        &[
+            "for-each-ref",
+            "--merged",
+            base,
+            "--format=%(refname:short)\t%(worktreepath)",
+            "refs/heads",
+        ],
+    )
+    .await?;
+    if !success {
+        return Err(anyhow!(stderr));
+    }
+
+    let base_name = base.split_once('/').map_or(base, |(_, name)| name);
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let (branch, worktree) = line.split_once('\t').unwrap_or((line, ""));
+            (branch != base_name && worktree.is_empty()).then(|| branch.to_string())
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(path)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn repository_with_remote() -> (TempDir, std::path::PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let remote = temp.path().join("remote.git");
+        let local = temp.path().join("local");
+        git(
+            temp.path(),
+            &["init", "--quiet", "--bare", "-b", "main", "remote.git"],
+        );
+        git(temp.path(), &["init", "--quiet", "-b", "main", "local"]);
+        git(
+            &local,
+            &["commit", "--quiet", "--allow-empty", "-m", "init"],
+        );
+        git(
+            &local,
+            &["remote", "add", "origin", &remote.to_string_lossy()],
+        );
+        git(&local, &["push", "--quiet", "-u", "origin", "main"]);
+        (temp, local)
+    }
+
+    #[tokio::test]
+    async fn merged_branches_skip_the_default_branch_and_unmerged_work() {
+        let (_temp, local) = repository_with_remote();
+        git(&local, &["branch", "merged-feature"]);
+        git(&local, &["switch", "--quiet", "-c", "open-feature"]);
+        git(&local, &["commit", "--quiet", "--allow-empty", "-m", "wip"]);
+        git(&local, &["switch", "--quiet", "main"]);
+
+        let remotes = list_remotes(&local).await.unwrap();
+        let base = remote_default_branch(&local, &remotes).await.unwrap();
+        assert_eq!(base.as_deref(), Some("origin/main"));
+
+        let merged = merged_branches(&local, "origin/main").await.unwrap();
+        assert_eq!(merged, vec!["merged-feature".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn find_branches_reports_local_and_remote_tracking_matches() {
+        let (_temp, local) = repository_with_remote();
+        git(&local, &["switch", "--quiet", "-c", "feature/login"]);
+        git(&local, &["push", "--quiet", "origin", "feature/login"]);
+
+        let matches = find_branches(&local, "feature/*").await.unwrap();
+        assert_eq!(matches.local, vec!["feature/login".to_string()]);
+        assert_eq!(matches.remote, vec!["origin/feature/login".to_string()]);
+        assert_eq!(
+            current_branch(&local).await.unwrap().as_deref(),
+            Some("feature/login")
+        );
+        assert!(find_branches(&local, "missing").await.unwrap().is_empty());
+    }
+}
@@ -1,19 +1,27 @@
 //! Structured Git operation failures and actionable remediation.
 
-use super::remote::{RemoteContext, RemoteDirection, RemotePolicyViolation, RemoteTransport};
+use serde::Serialize;
+
+use super::remote::{
+    PolicyRule, RemoteContext, RemoteDirection, RemotePolicyViolation, RemoteTransport,
+    TransportPolicy,
+};
 use super::status::Status;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub(crate) enum GitFailureKind {
     Authentication,
     Diverged,
     Network,
     Timeout,
     TransportPolicy,
+    HostPolicy,
     Other,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub(crate) enum GitOperationPhase {
     Fetch,
     LfsPush,
@@ -40,6 +48,8 @@ pub(crate) struct GitFailure {
     pub(crate) phase: GitOperationPhase,
     pub(crate) remote: Option<RemoteContext>,
     pub(crate) message: String,
+    /// Transport policy that blocked the remote, when known.
+    pub(crate) policy: Option<TransportPolicy>,
 }
 
 impl GitFailure {
@@ -49,12 +59,17 @@ impl GitFailure {
             RemoteDirection::Push => GitOperationPhase::Push,
         };
         let message = violation.message();
+        let (kind, policy) = match violation.rule {
+            PolicyRule::Transport(policy) => (GitFailureKind::TransportPolicy, Some(policy)),
+            PolicyRule::Host => (GitFailureKind::HostPolicy, None),
+        };
 
         Self {
-            kind: GitFailureKind::TransportPolicy,
+            kind,
             phase,
             remote: Some(violation.context),
             message,
+            policy,
         }
     }
 
@@ -64,8 +79,10 @@ impl GitFailure {
         remote: Option<RemoteContext>,
     ) -> Self {
         let lower = message.to_ascii_lowercase();
-        let kind = if lower.contains("ssh-only policy") {
+        let kind = if contains_any(&lower, &["ssh-only policy", "https-only policy"]) {
             GitFailureKind::TransportPolicy
+        } else if lower.contains("host allowlist") {
+            GitFailureKind::HostPolicy
         } else if contains_any(
             &lower,
             &[
@@ -100,6 +117,7 @@ impl GitFailure {
             phase,
             remote,
             message,
+            policy: None,
         }
     }
 
@@ -108,16 +126,29 @@ impl GitFailure {
             GitFailureKind::Authentication => {
                 format!("authentication failed during {}", self.phase.label())
             }
-            GitFailureKind::TransportPolicy => self.remote.as_ref().map_or_else(
-                || format!("SSH-only policy blocked {}", self.phase.label()),
-                |remote| {
-                    format!(
-                        "SSH-only policy blocked {} ({})",
-                        self.phase.label(),
-                        remote.transport.label()
-                    )
-                },
-            ),
+            GitFailureKind::TransportPolicy => {
+                let policy = if self.policy == Some(TransportPolicy::HttpsOnly) {
+                    "HTTPS-only"
+                } else {
+                    "SSH-only"
+                };
+                self.remote.as_ref().map_or_else(
+                    || format!("{policy} policy blocked {}", self.phase.label()),
+                    |remote| {
+                        format!(
+                            "{policy} policy blocked {} ({})",
+                            self.phase.label(),
+                            remote.transport.label()
+                        )
+                    },
+                )
+            }
+            GitFailureKind::HostPolicy => {
+                match self.remote.as_ref().and_then(RemoteContext::host) {
+                    Some(host) => format!("host allowlist blocked {} ({host})", self.phase.label()),
+                    None => format!("host allowlist blocked {}", self.phase.label()),
+                }
+            }
             GitFailureKind::Diverged => self.message.clone(),
             GitFailureKind::Network => format!("network error during {}", self.phase.label()),
             GitFailureKind::Timeout => format!("timeout during {}", self.phase.label()),
@@ -129,7 +160,17 @@ impl GitFailure {
         match self.kind {
             GitFailureKind::TransportPolicy => self.remote.as_ref().map_or_else(
                 || "inspect the remote transport".to_string(),
-                |remote| ssh_remote_fix(repo_path, remote),
+                |remote| {
+                    if self.policy == Some(TransportPolicy::HttpsOnly) {
+                        https_remote_fix(repo_path, remote)
+                    } else {
+                        ssh_remote_fix(repo_path, remote)
+                    }
+                },
+            ),
+            GitFailureKind::HostPolicy => self.remote.as_ref().and_then(RemoteContext::host).map_or_else(
+                || "point the remote at an allowed host".to_string(),
+                |host| format!("point the remote at an allowed host or add {host} to git.allowed_hosts"),
             ),
             GitFailureKind::Authentication => self.remote.as_ref().map_or_else(
                 || "inspect authentication and remote access".to_string(),
@@ -158,17 +199,28 @@ impl GitFailure {
 }
 
 fn ssh_remote_fix(repo_path: &str, remote: &RemoteContext) -> String {
+    let ssh_url = remote.ssh_url.as_deref().unwrap_or("<SSH clone URL>");
+    set_url_fix(repo_path, remote, ssh_url)
+}
+
+fn https_remote_fix(repo_path: &str, remote: &RemoteContext) -> String {
+    let https_url = remote
+        .https_url()
+        .unwrap_or_else(|| "<HTTPS clone URL>".to_string());
+    set_url_fix(repo_path, remote, &https_url)
+}
+
+fn set_url_fix(repo_path: &str, remote: &RemoteContext, url: &str) -> String {
     let push_flag = if remote.direction == RemoteDirection::Push {
         " --push"
     } else {
         ""
     };
-    let ssh_url = remote.ssh_url.as_deref().unwrap_or("<SSH clone URL>");
     format!(
         "git -C {} remote set-url{push_flag} {} {}",
         shell_quote(repo_path),
         shell_quote(&remote.remote),
-        shell_quote(ssh_url)
+        shell_quote(url)
     )
 }
 
@@ -216,7 +268,8 @@ fn shell_quote(value: &str) -> String {
 mod tests {
     use super::{GitFailure, GitFailureKind, GitOperationPhase};
     use crate::git::remote::{
-        RemoteContext, RemoteDirection, RemotePolicyViolation, RemoteTransport,
+        PolicyRule, RemoteContext, RemoteDirection, RemotePolicyViolation, RemoteTransport,
+        TransportPolicy,
     };
 
     #[test]
@@ -273,6 +326,7 @@ mod tests {
                 identity: Some("github.com/goobits/aw.git".to_string()),
                 ssh_url: Some("git@github.com:goobits/aw.git".to_string()),
             },
+            rule: PolicyRule::Transport(TransportPolicy::SshOnly),
         });
 
         assert_eq!(failure.kind, GitFailureKind::TransportPolicy);
@@ -280,6 +334,37 @@ mod tests {
         assert_eq!(failure.reason(), "SSH-only policy blocked push (HTTPS)");
     }
 
+    #[test]
+    fn maps_https_only_and_host_violations_to_https_fixes() {
+        let context = RemoteContext {
+            remote: "origin".to_string(),
+            direction: RemoteDirection::Fetch,
+            transport: RemoteTransport::Ssh,
+            identity: Some("github.com/goobits/aw.git".to_string()),
+            ssh_url: None,
+        };
+        let failure = GitFailure::from_policy(RemotePolicyViolation {
+            context: context.clone(),
+            rule: PolicyRule::Transport(TransportPolicy::HttpsOnly),
+        });
+
+        assert_eq!(failure.reason(), "HTTPS-only policy blocked fetch (SSH)");
+        assert_eq!(
+            failure.next_action("./aw"),
+            "git -C './aw' remote set-url 'origin' 'https://github.com/goobits/aw.git'"
+        );
+
+        let failure = GitFailure::from_policy(RemotePolicyViolation {
+            context,
+            rule: PolicyRule::Host,
+        });
+        assert_eq!(failure.kind, GitFailureKind::HostPolicy);
+        assert_eq!(
+            failure.reason(),
+            "host allowlist blocked fetch (github.com)"
+        );
+    }
+
     #[test]
     fn builds_shell_safe_push_fix_for_known_host() {
         let failure = GitFailure {
@@ -293,6 +378,7 @@ mod tests {
                 ssh_url: Some("git@github.com:goobits/aw.git".to_string()),
             }),
             message: "blocked".to_string(),
+            policy: None,
         };
 
         assert_eq!(
@@ -314,6 +400,7 @@ mod tests {
                 ssh_url: None,
             }),
             message: "authentication failed".to_string(),
+            policy: None,
         };
 
         assert_eq!(
@@ -335,6 +422,7 @@ mod tests {
                 ssh_url: None,
             }),
             message: "authentication failed".to_string(),
+            policy: None,
         };
 
         assert_eq!(
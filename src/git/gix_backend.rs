@@ -0,0 +1,340 @@
+//! In-process [`GitBackend`] built on gitoxide.
+//!
+//! Every query opens the repository on a blocking thread and answers from the
+//! object database, index, and config directly. Any gitoxide error falls back
+//! to [`CliBackend`], so an unusual repository layout degrades to the old
+//! process-per-query cost instead of a wrong answer.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::operations::{CliBackend, GitBackend, GitBackendKind};
+
+/// Answers read-only queries in-process with gitoxide.
+pub struct GixBackend;
+
+#[async_trait]
+impl GitBackend for GixBackend {
+    fn name(&self) -> &'static str {
+        GitBackendKind::Gix.label()
+    }
+
+    async fn head_name(&self, path: &Path) -> Result<String> {
+        match query(path, head_name).await {
+            Ok(name) => Ok(name),
+            Err(_) => CliBackend.head_name(path).await,
+        }
+    }
+
+    async fn upstream(&self, path: &Path) -> Result<Option<String>> {
+        match query(path, |repo| {
+            Ok(upstream_ref(repo)?.map(|name| name.shorten().to_string()))
+        })
+        .await
+        {
+            Ok(upstream) => Ok(upstream),
+            Err(_) => CliBackend.upstream(path).await,
+        }
+    }
+
+    async fn ahead_behind(&self, path: &Path) -> Result<(u32, u32)> {
+        match query(path, ahead_behind).await {
+            Ok(counts) => Ok(counts),
+            Err(_) => CliBackend.ahead_behind(path).await,
+        }
+    }
+
+    async fn porcelain_status(&self, path: &Path) -> Result<String> {
+        match query(path, porcelain_status).await {
+            Ok(status) => Ok(status),
+            Err(_) => CliBackend.porcelain_status(path).await,
+        }
+    }
+
+    async fn remote_urls(&self, path: &Path) -> Result<Vec<(String, String)>> {
+        match query(path, remote_urls).await {
+            Ok(remotes) => Ok(remotes),
+            Err(_) => CliBackend.remote_urls(path).await,
+        }
+    }
+}
+
+/// Opens the repository at `path` on a blocking thread and runs `f` on it.
+async fn query<T, F>(path: &Path, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&gix::Repository) -> Result<T> + Send + 'static,
+{
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || f(&gix::open(path)?)).await?
+}
+
+fn head_name(repo: &gix::Repository) -> Result<String> {
+    let head = repo.head()?;
+    // `git rev-parse --abbrev-ref HEAD` fails without a commit; let the CLI
+    // report that in its own words.
+    if head.is_unborn() {
+        return Err(anyhow!("HEAD has no commits"));
+    }
+    Ok(head
+        .referent_name()
+        .map_or_else(|| "HEAD".to_string(), |name| name.shorten().to_string()))
+}
+
+/// Full name of the current branch's upstream, when it is configured and
+/// its remote-tracking ref exists (the same cases in which `@{upstream}`
+/// resolves).
+fn upstream_ref(repo: &gix::Repository) -> Result<Option<gix::refs::FullName>> {
+    let Some(branch) = repo.head_name()? else {
+        return Ok(None);
+    };
+    let tracking = match repo
+        .branch_remote_tracking_ref_name(branch.as_ref(), gix::remote::Direction::Fetch)
+    {
+        Some(tracking) => tracking?.into_owned(),
+        None => return Ok(None),
+    };
+    Ok(repo
+        .try_find_reference(tracking.as_ref())?
+        .map(|_| tracking))
+}
+
+fn ahead_behind(repo: &gix::Repository) -> Result<(u32, u32)> {
+    let upstream = upstream_ref(repo)?.ok_or_else(|| anyhow!("no upstream configured"))?;
+    let head = repo.head_id()?.detach();
+    let upstream = repo
+        .find_reference(upstream.as_ref())?
+        .peel_to_id()?
+        .detach();
+
+    let count = |tip: gix::ObjectId, hidden: gix::ObjectId| -> Result<u32> {
+        let mut count = 0;
+        for commit in repo.rev_walk([tip]).with_hidden([hidden]).all()? {
+            commit?;
+            count += 1;
+        }
+        Ok(count)
+    };
+    Ok((count(head, upstream)?, count(upstream, head)?))
+}
+
+/// Renders status in porcelain v1 order: tracked changes sorted by path,
+/// then untracked entries, with untracked directories collapsed to `dir/`.
+fn porcelain_status(repo: &gix::Repository) -> Result<String> {
+    use gix::status::index_worktree::iter::Summary;
+
+    let mut tracked: BTreeMap<String, [char; 2]> = BTreeMap::new();
+    let mut untracked = Vec::new();
+
+    let items = repo
+        .status(gix::progress::Discard)?
+        .untracked_files(gix::status::UntrackedFiles::Collapsed)
+        .index_worktree_submodules(gix::status::Submodule::Given {
+            ignore: gix::submodule::config::Ignore::Dirty,
+            check_dirty: false,
+        })
+        .into_iter(None)?;
+    for item in items {
+        match item? {
+            gix::status::Item::TreeIndex(change) => {
+                let (state, location) = match &change {
+                    gix::diff::index::Change::Addition { location, .. } => ('A', location),
+                    gix::diff::index::Change::Deletion { location, .. } => ('D', location),
+                    gix::diff::index::Change::Modification { location, .. } => ('M', location),
+                    gix::diff::index::Change::Rewrite {
+                        source_location,
+                        location,
+                        copy,
+                        ..
+                    } => {
+                        let state = if *copy { 'C' } else { 'R' };
+                        let path = format!("{source_location} -> {location}");
+                        tracked.entry(path).or_insert([' ', ' '])[0] = state;
+                        continue;
+                    }
+                };
+                tracked.entry(location.to_string()).or_insert([' ', ' '])[0] = state;
+            }
+            gix::status::Item::IndexWorktree(item) => {
+                let state = match item.summary() {
+                    None => continue,
+                    Some(Summary::Added) => {
+                        let mut path = item.rela_path().to_string();
+                        if let gix::status::index_worktree::Item::DirectoryContents {
+                            entry, ..
+                        } = &item
+                        {
+                            if matches!(
+                                entry.disk_kind,
+                                Some(
+                                    gix::dir::entry::Kind::Directory
+                                        | gix::dir::entry::Kind::Repository
+                                )
+                            ) {
+                                path.push('/');
+                            }
+                        }
+                        untracked.push(path);
+                        continue;
+                    }
+                    Some(Summary::Removed) => 'D',
+                    Some(Summary::Modified) => 'M',
+                    Some(Summary::TypeChange) => 'T',
+                    Some(Summary::IntentToAdd) => 'A',
+                    Some(Summary::Conflict) => {
+                        tracked.insert(item.rela_path().to_string(), ['U', 'U']);
+                        continue;
+                    }
+                    Some(Summary::Renamed | Summary::Copied) => 'R',
+                };
+                tracked
+                    .entry(item.rela_path().to_string())
+                    .or_insert([' ', ' '])[1] = state;
+            }
+        }
+    }
+
+    untracked.sort();
+    let lines: Vec<String> = tracked
+        .into_iter()
+        .map(|(path, [x, y])| format!("{x}{y} {path}"))
+        .chain(untracked.into_iter().map(|path| format!("?? {path}")))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn remote_urls(repo: &gix::Repository) -> Result<Vec<(String, String)>> {
+    let mut remotes = Vec::new();
+    for name in repo.remote_names() {
+        let remote = repo.find_remote(name.as_ref())?;
+        if let Some(url) = remote.url(gix::remote::Direction::Fetch) {
+            remotes.push((name.to_string(), url.to_bstring().to_string()));
+        }
+    }
+    Ok(remotes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(path)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// Compares gitoxide's answers with the CLI's, calling the in-process
+    /// queries directly so a silent fallback cannot hide a difference.
+    async fn assert_backends_agree(path: &Path) {
+        let repo = gix::open(path).unwrap();
+        assert_eq!(
+            head_name(&repo).unwrap(),
+            CliBackend.head_name(path).await.unwrap()
+        );
+        assert_eq!(
+            upstream_ref(&repo)
+                .unwrap()
+                .map(|name| name.shorten().to_string()),
+            CliBackend.upstream(path).await.unwrap()
+        );
+        assert_eq!(
+            porcelain_status(&repo).unwrap(),
+            CliBackend.porcelain_status(path).await.unwrap()
+        );
+        assert_eq!(
+            remote_urls(&repo).unwrap(),
+            CliBackend.remote_urls(path).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn gix_answers_match_the_git_cli() {
+        let temp = TempDir::new().unwrap();
+        let remote = temp.path().join("remote.git");
+        let local = temp.path().join("local");
+        let other = temp.path().join("other");
+        git(
+            temp.path(),
+            &["init", "--quiet", "--bare", "-b", "main", "remote.git"],
+        );
+        git(temp.path(), &["init", "--quiet", "-b", "main", "local"]);
+        std::fs::write(local.join("tracked.txt"), "one\n").unwrap();
+        std::fs::write(local.join("removed.txt"), "gone\n").unwrap();
+        git(&local, &["add", "."]);
+        git(&local, &["commit", "--quiet", "-m", "init"]);
+        git(
+            &local,
+            &["remote", "add", "origin", &remote.to_string_lossy()],
+        );
+        git(&local, &["push", "--quiet", "-u", "origin", "main"]);
+        assert_backends_agree(&local).await;
+
+        // Diverge: one commit only upstream, two only local.
+        git(
+            temp.path(),
+            &["clone", "--quiet", &remote.to_string_lossy(), "other"],
+        );
+        git(
+            &other,
+            &["commit", "--quiet", "--allow-empty", "-m", "theirs"],
+        );
+        git(&other, &["push", "--quiet", "origin", "main"]);
+        git(&local, &["fetch", "--quiet"]);
+        git(
+            &local,
+            &["commit", "--quiet", "--allow-empty", "-m", "ours 1"],
+        );
+        git(
+            &local,
+            &["commit", "--quiet", "--allow-empty", "-m", "ours 2"],
+        );
+        assert_eq!(ahead_behind(&gix::open(&local).unwrap()).unwrap(), (2, 1));
+        assert_eq!(CliBackend.ahead_behind(&local).await.unwrap(), (2, 1));
+
+        // Staged, unstaged, deleted, and untracked files and directories.
+        std::fs::write(local.join("staged.txt"), "new\n").unwrap();
+        git(&local, &["add", "staged.txt"]);
+        std::fs::write(local.join("tracked.txt"), "two\n").unwrap();
+        std::fs::remove_file(local.join("removed.txt")).unwrap();
+        std::fs::write(local.join("untracked.txt"), "?\n").unwrap();
+        std::fs::create_dir(local.join("scratch")).unwrap();
+        std::fs::write(local.join("scratch/notes.txt"), "?\n").unwrap();
+        assert_backends_agree(&local).await;
+        let status = GixBackend.porcelain_status(&local).await.unwrap();
+        assert!(status.contains("A  staged.txt"), "{status}");
+        assert!(status.contains("?? scratch/"), "{status}");
+
+        // Detached HEAD has neither a branch nor an upstream.
+        git(&local, &["switch", "--quiet", "--detach", "HEAD~1"]);
+        assert_backends_agree(&local).await;
+        assert_eq!(GixBackend.head_name(&local).await.unwrap(), "HEAD");
+        assert_eq!(GixBackend.upstream(&local).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn gix_reports_no_upstream_or_remotes_like_the_cli() {
+        let temp = TempDir::new().unwrap();
+        git(temp.path(), &["init", "--quiet", "-b", "main"]);
+        git(
+            temp.path(),
+            &["commit", "--quiet", "--allow-empty", "-m", "init"],
+        );
+
+        assert_backends_agree(temp.path()).await;
+        assert_eq!(GixBackend.upstream(temp.path()).await.unwrap(), None);
+        assert!(GixBackend
+            .remote_urls(temp.path())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
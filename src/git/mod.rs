@@ -1,6 +1,8 @@
 // Internal modules - not part of public API
+pub(crate) mod branch;
 pub(crate) mod config;
 pub(crate) mod failure;
+pub(crate) mod gix_backend;
 pub(crate) mod operations;
 pub(crate) mod remote;
 pub(crate) mod status;
@@ -1,6 +1,7 @@
 //! Basic git operations and command execution
 
 use anyhow::Result;
+use async_trait::async_trait;
 use dashmap::DashMap;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -14,9 +15,7 @@ use super::remote::{
     TransportPolicy,
 };
 use super::status::Status;
-
-// Timeout constants
-const GIT_OPERATION_TIMEOUT_SECS: u64 = 180; // 3 minutes per repository
+use crate::core::settings::settings;
 
 // Git command arguments
 const GIT_REMOTE_ARGS: &[&str] = &["remote"];
@@ -59,7 +58,8 @@ const STATUS_SYNCED: &str = "up to date";
 /// - `stdout`: standard output with trailing line endings removed
 /// - `stderr`: trimmed standard error as String
 ///
-/// Includes a 180-second timeout to prevent hanging on network operations.
+/// Includes a timeout (`git.operation_timeout_secs`, 180 seconds by default) to
+/// prevent hanging on network operations.
 #[doc(hidden)]
 pub async fn run_git(path: &Path, args: &[&str]) -> Result<(bool, String, String)> {
     let mut last_error = None;
@@ -67,7 +67,8 @@ pub async fn run_git(path: &Path, args: &[&str]) -> Result<(bool, String, String
     let max_retries = if is_network { 3 } else { 1 };
 
     for attempt in 1..=max_retries {
-        let timeout_duration = Duration::from_secs(GIT_OPERATION_TIMEOUT_SECS);
+        let timeout_secs = settings().git_timeout_secs.value;
+        let timeout_duration = Duration::from_secs(timeout_secs);
         let mut command = Command::new("git");
 
         command.kill_on_drop(true);
@@ -128,7 +129,7 @@ pub async fn run_git(path: &Path, args: &[&str]) -> Result<(bool, String, String
             Err(_) => {
                 if attempt == max_retries {
                     return Err(anyhow::anyhow!(
-                        "Git operation timed out after {GIT_OPERATION_TIMEOUT_SECS} seconds"
+                        "Git operation timed out after {timeout_secs} seconds"
                     ));
                 }
                 last_error = Some(anyhow::anyhow!("Git operation timed out"));
@@ -1034,18 +1035,62 @@ pub async fn is_detached_head(path: &Path) -> Result<bool> {
 /// Creates a git tag and pushes it to the remote
 /// Returns (success, message)
 pub async fn create_and_push_tag(path: &Path, tag_name: &str) -> (bool, String) {
-    let tag_result = run_git(path, &["tag", tag_name]).await;
-
-    let (success, _, stderr) = match tag_result {
-        Ok(result) => result,
-        Err(e) => return (false, format!("failed to create tag: {e}")),
+    let existed = match create_tag(path, tag_name, None).await {
+        Ok(existed) => existed,
+        Err(message) => return (false, message),
     };
 
+    match push_tag(path, tag_name).await {
+        (true, _) if existed => (true, format!("existing tag pushed {tag_name}")),
+        (true, _) => (true, format!("tagged & pushed {tag_name}")),
+        failed => failed,
+    }
+}
+
+/// Creates a tag at HEAD: lightweight, or signed and annotated with
+/// `signed_message` (`git tag -s`, using the configured GPG or SSH key).
+/// An existing tag is kept only when it already points at HEAD.
+/// Returns whether the tag already existed, or the error message.
+pub async fn create_tag(
+    path: &Path,
+    tag_name: &str,
+    signed_message: Option<&str>,
+) -> Result<bool, String> {
+    let mut args = vec!["tag"];
+    if let Some(message) = signed_message {
+        args.extend(["-s", "--cleanup=verbatim", "-m", message]);
+    }
+    args.push(tag_name);
+    let (success, _, stderr) = run_git(path, &args)
+        .await
+        .map_err(|e| format!("failed to create tag: {e}"))?;
+
     let existed = !success && stderr.contains("already exists");
     if !success && !existed {
-        return (false, format!("failed to create tag: {stderr}"));
+        return Err(format!("failed to create tag: {stderr}"));
+    }
+    if existed {
+        let tagged = run_git(path, &["rev-parse", &format!("{tag_name}^{{commit}}")]).await;
+        let head = run_git(path, &["rev-parse", "HEAD"]).await;
+        match (tagged, head) {
+            (Ok((true, tagged, _)), Ok((true, head, _))) if tagged == head => {}
+            (Ok((true, tagged, _)), Ok((true, head, _))) => {
+                return Err(format!(
+                    "tag {tag_name} already exists at {} instead of HEAD ({})",
+                    &tagged[..tagged.len().min(7)],
+                    &head[..head.len().min(7)]
+                ));
+            }
+            _ => return Err(format!("could not resolve existing tag {tag_name}")),
+        }
     }
+    Ok(existed)
+}
 
+/// Pushes an existing local tag to the branch's upstream remote, or to
+/// `origin` (else the first remote) when there is none.
+/// Returns (success, message)
+pub async fn push_tag(path: &Path, tag_name: &str) -> (bool, String) {
     let current_branch = match run_git(path, GIT_REV_PARSE_HEAD_ARGS).await {
         Ok((true, branch, _)) if branch != DETACHED_HEAD_BRANCH => Some(branch),
         _ => None,
@@ -1106,8 +1151,7 @@ pub async fn create_and_push_tag(path: &Path, tag_name: &str) -> (bool, String)
     let push_result = run_git(path, &["push", &remote_name, tag_name]).await;
 
     match push_result {
-        Ok((true, _, _)) if existed => (true, format!("existing tag pushed {tag_name}")),
-        Ok((true, _, _)) => (true, format!("tagged & pushed {tag_name}")),
+        Ok((true, _, _)) => (true, format!("pushed {tag_name}")),
         Ok((false, _, stderr)) => (
             false,
             format!(
@@ -1436,6 +1480,118 @@ pub(crate) async fn pull_if_needed_with_context(
     }
 }
 
+/// Implementation that answers read-only repository queries (`git.backend`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum GitBackendKind {
+    /// Spawn the `git` CLI for every query.
+    Cli,
+    /// Answer queries in-process with gitoxide, falling back to the CLI.
+    Gix,
+}
+
+impl GitBackendKind {
+    pub(crate) fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "cli" => Ok(Self::Cli),
+            "gix" => Ok(Self::Gix),
+            value => Err(anyhow::anyhow!(
+                "invalid git backend '{value}'; expected 'cli' or 'gix'"
+            )),
+        }
+    }
+
+    pub(crate) const fn label(self) -> &'static str {
+        match self {
+            Self::Cli => "cli",
+            Self::Gix => "gix",
+        }
+    }
+}
+
+/// Read-only repository queries used by status inspection.
+///
+/// Network, LFS, and mutating operations always go through [`run_git`]; only
+/// these local reads can be answered without spawning a process.
+#[async_trait]
+pub trait GitBackend: Send + Sync {
+    /// Name used for `git.backend`.
+    fn name(&self) -> &'static str;
+
+    /// Current branch name, or `HEAD` when detached (`git rev-parse --abbrev-ref HEAD`).
+    async fn head_name(&self, path: &Path) -> Result<String>;
+
+    /// Upstream of the current branch as `remote/branch`, if one is configured
+    /// and its remote-tracking ref exists.
+    async fn upstream(&self, path: &Path) -> Result<Option<String>>;
+
+    /// Commits on HEAD missing from the upstream, and the reverse.
+    async fn ahead_behind(&self, path: &Path) -> Result<(u32, u32)>;
+
+    /// Worktree status in `git status --porcelain=v1` format.
+    async fn porcelain_status(&self, path: &Path) -> Result<String>;
+
+    /// Configured remotes with their fetch URLs.
+    async fn remote_urls(&self, path: &Path) -> Result<Vec<(String, String)>>;
+}
+
+/// Answers every query by spawning `git`.
+pub struct CliBackend;
+
+#[async_trait]
+impl GitBackend for CliBackend {
+    fn name(&self) -> &'static str {
+        GitBackendKind::Cli.label()
+    }
+
+    async fn head_name(&self, path: &Path) -> Result<String> {
+        match run_git(path, GIT_REV_PARSE_HEAD_ARGS).await? {
+            (true, branch, _) => Ok(branch),
+            (false, _, stderr) => Err(anyhow::anyhow!(command_error(
+                &stderr,
+                "HEAD inspection failed"
+            ))),
+        }
+    }
+
+    async fn upstream(&self, path: &Path) -> Result<Option<String>> {
+        let (success, upstream, _) =
+            run_git(path, &["rev-parse", "--abbrev-ref", "@{upstream}"]).await?;
+        Ok(success.then_some(upstream))
+    }
+
+    async fn ahead_behind(&self, path: &Path) -> Result<(u32, u32)> {
+        let ahead = count_revisions(path, "HEAD", "^@{upstream}").await?;
+        let behind = count_revisions(path, "@{upstream}", "^HEAD").await?;
+        Ok((ahead, behind))
+    }
+
+    async fn porcelain_status(&self, path: &Path) -> Result<String> {
+        get_staging_status(path).await.map(|(stdout, _)| stdout)
+    }
+
+    async fn remote_urls(&self, path: &Path) -> Result<Vec<(String, String)>> {
+        // Exit code 1 means no remote has a URL.
+        let (_, output, _) =
+            run_git(path, &["config", "--get-regexp", r"^remote\..*\.url$"]).await?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let (key, url) = line.split_once(' ')?;
+                let name = key.strip_prefix("remote.")?.strip_suffix(".url")?;
+                Some((name.to_string(), url.to_string()))
+            })
+            .collect())
+    }
+}
+
+/// Returns the backend selected by `git.backend`.
+pub(crate) fn git_backend() -> &'static dyn GitBackend {
+    match settings().git_backend.value {
+        GitBackendKind::Cli => &CliBackend,
+        GitBackendKind::Gix => &super::gix_backend::GixBackend,
+    }
+}
+
 /// Repository visibility status
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RepoVisibility {
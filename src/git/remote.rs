@@ -1,34 +1,60 @@
 //! Remote transport inspection and fleet-wide transport policy.
 
 use anyhow::{anyhow, Result};
+use serde::Serialize;
 use std::path::Path;
 use std::process::Command;
 use std::sync::OnceLock;
 
 use super::operations::run_git;
+use crate::core::settings::settings;
 
-const TRANSPORT_POLICY_ENV: &str = "REPOS_TRANSPORT_POLICY";
 const TRANSPORT_POLICY_CONFIG: &str = "repos.transportPolicy";
 
+/// Hosts whose SSH and HTTPS clone URLs share a path, so one form can be
+/// derived from the other.
+const KNOWN_HOSTS: &[&str] = &["github.com", "gitlab.com", "bitbucket.org"];
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum TransportPolicy {
     Preserve,
     SshOnly,
+    HttpsOnly,
 }
 
 impl TransportPolicy {
-    fn parse(value: &str) -> Result<Self> {
+    pub(crate) fn parse(value: &str) -> Result<Self> {
         match value.trim().to_ascii_lowercase().as_str() {
             "preserve" => Ok(Self::Preserve),
             "ssh-only" => Ok(Self::SshOnly),
+            "https-only" => Ok(Self::HttpsOnly),
             value => Err(anyhow!(
-                "invalid transport policy '{value}'; expected 'preserve' or 'ssh-only'"
+                "invalid transport policy '{value}'; expected 'preserve', 'ssh-only' or 'https-only'"
             )),
         }
     }
+
+    pub(crate) const fn label(self) -> &'static str {
+        match self {
+            Self::Preserve => "preserve",
+            Self::SshOnly => "ssh-only",
+            Self::HttpsOnly => "https-only",
+        }
+    }
+
+    /// Whether remotes using `transport` may be contacted. Local and
+    /// unrecognized transports are never blocked.
+    pub(crate) const fn allows(self, transport: RemoteTransport) -> bool {
+        match self {
+            Self::Preserve => true,
+            Self::SshOnly => !transport.is_http(),
+            Self::HttpsOnly => !matches!(transport, RemoteTransport::Http | RemoteTransport::Ssh),
+        }
+    }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub(crate) enum RemoteDirection {
     Fetch,
     Push,
@@ -43,7 +69,8 @@ impl RemoteDirection {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub(crate) enum RemoteTransport {
     Http,
     Https,
@@ -94,7 +121,7 @@ impl RemoteTransport {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub(crate) struct RemoteContext {
     pub(crate) remote: String,
     pub(crate) direction: RemoteDirection,
@@ -110,21 +137,55 @@ impl RemoteContext {
             |identity| format!("{} ({}, {identity})", self.remote, self.transport.label()),
         )
     }
+
+    /// Host the remote points at, without credentials or port.
+    pub(crate) fn host(&self) -> Option<&str> {
+        let authority = self.identity.as_deref()?.split('/').next()?;
+        let (host, _) = host_and_port(authority);
+        (!host.is_empty()).then_some(host)
+    }
+
+    /// HTTPS form of an SSH or HTTP remote on a known host.
+    pub(crate) fn https_url(&self) -> Option<String> {
+        if !matches!(self.transport, RemoteTransport::Ssh | RemoteTransport::Http) {
+            return None;
+        }
+        let identity = self.identity.as_deref()?;
+        let (authority, _) = identity.split_once('/')?;
+        is_known_host(authority).then(|| format!("https://{identity}"))
+    }
+}
+
+/// The rule a remote broke.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PolicyRule {
+    Transport(TransportPolicy),
+    Host,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct RemotePolicyViolation {
     pub(crate) context: RemoteContext,
+    pub(crate) rule: PolicyRule,
 }
 
 impl RemotePolicyViolation {
     pub(crate) fn message(&self) -> String {
-        format!(
-            "ssh-only policy blocked {}: remote {} uses {}",
-            self.context.direction.label(),
-            self.context.remote,
-            self.context.transport.label()
-        )
+        match self.rule {
+            PolicyRule::Transport(policy) => format!(
+                "{} policy blocked {}: remote {} uses {}",
+                policy.label(),
+                self.context.direction.label(),
+                self.context.remote,
+                self.context.transport.label()
+            ),
+            PolicyRule::Host => format!(
+                "host allowlist blocked {}: remote {} uses host {}",
+                self.context.direction.label(),
+                self.context.remote,
+                self.context.host().unwrap_or("unknown")
+            ),
+        }
     }
 }
 
@@ -138,24 +199,31 @@ pub(crate) fn transport_policy() -> Result<TransportPolicy> {
 }
 
 fn resolve_transport_policy() -> std::result::Result<TransportPolicy, String> {
-    if let Some(value) = std::env::var_os(TRANSPORT_POLICY_ENV) {
-        return TransportPolicy::parse(&value.to_string_lossy()).map_err(|error| error.to_string());
+    if let Some(policy) = settings().transport_policy.value {
+        return Ok(policy);
     }
 
-    let output = match Command::new("git")
+    git_config_transport_policy().unwrap_or(Ok(TransportPolicy::Preserve))
+}
+
+/// Reads the legacy `repos.transportPolicy` key from global git config.
+///
+/// Used only when no `repos` configuration layer sets `git.transport_policy`.
+pub(crate) fn git_config_transport_policy() -> Option<std::result::Result<TransportPolicy, String>>
+{
+    let output = Command::new("git")
         .args(["config", "--global", "--get", TRANSPORT_POLICY_CONFIG])
         .output()
-    {
-        Ok(output) => output,
-        Err(_) => return Ok(TransportPolicy::Preserve),
-    };
+        .ok()?;
 
     if !output.status.success() {
-        return Ok(TransportPolicy::Preserve);
+        return None;
     }
 
-    TransportPolicy::parse(&String::from_utf8_lossy(&output.stdout))
-        .map_err(|error| error.to_string())
+    Some(
+        TransportPolicy::parse(&String::from_utf8_lossy(&output.stdout))
+            .map_err(|error| error.to_string()),
+    )
 }
 
 pub(crate) async fn inspect_remote(
@@ -173,6 +241,21 @@ pub(crate) async fn inspect_remote(
         }]);
     }
 
+    Ok(effective_urls(path, remote, direction)
+        .await?
+        .iter()
+        .map(|url| context_from_url(remote, direction, url))
+        .collect())
+}
+
+/// Reads the URLs git uses for `remote` in one direction, after `insteadOf`
+/// rewriting and with push falling back to the fetch URLs. The URLs may
+/// carry credentials; only sanitized contexts should reach output.
+pub(crate) async fn effective_urls(
+    path: &Path,
+    remote: &str,
+    direction: RemoteDirection,
+) -> Result<Vec<String>> {
     let mut args = vec!["remote", "get-url"];
     if direction == RemoteDirection::Push {
         args.push("--push");
@@ -192,10 +275,48 @@ pub(crate) async fn inspect_remote(
         ));
     }
 
-    Ok(urls
-        .lines()
-        .map(|url| context_from_url(remote, direction, url))
-        .collect())
+    Ok(urls.lines().map(str::to_string).collect())
+}
+
+/// Reads the URLs configured for `remote` in one direction, without
+/// `insteadOf` rewriting. Push URLs are only those set explicitly; an empty
+/// list means pushes use the fetch URLs.
+pub(crate) async fn configured_urls(
+    path: &Path,
+    remote: &str,
+    direction: RemoteDirection,
+) -> Result<Vec<String>> {
+    let key = format!("remote.{remote}.{}", url_key(direction));
+    match run_git(path, &["config", "--get-all", &key]).await? {
+        (true, urls, _) => Ok(urls.lines().map(str::to_string).collect()),
+        (false, _, stderr) if stderr.trim().is_empty() => Ok(Vec::new()),
+        (false, _, stderr) => Err(anyhow!("could not read {key}: {}", stderr.trim())),
+    }
+}
+
+/// Replaces the URLs configured for `remote` in one direction, keeping their order.
+pub(crate) async fn set_configured_urls(
+    path: &Path,
+    remote: &str,
+    direction: RemoteDirection,
+    urls: &[String],
+) -> Result<()> {
+    let key = format!("remote.{remote}.{}", url_key(direction));
+    for (index, url) in urls.iter().enumerate() {
+        let mode = if index == 0 { "--replace-all" } else { "--add" };
+        let (success, _, stderr) = run_git(path, &["config", mode, &key, url]).await?;
+        if !success {
+            return Err(anyhow!("could not set {key}: {}", stderr.trim()));
+        }
+    }
+    Ok(())
+}
+
+const fn url_key(direction: RemoteDirection) -> &'static str {
+    match direction {
+        RemoteDirection::Fetch => "url",
+        RemoteDirection::Push => "pushurl",
+    }
 }
 
 /// Builds a sanitized remote context without retaining credentials or query data.
@@ -215,27 +336,63 @@ pub(crate) fn context_from_url(
     }
 }
 
+/// Checks `contexts` against the transport policy, then the host allowlist.
 pub(crate) fn policy_violation(
     contexts: &[RemoteContext],
 ) -> Result<Option<RemotePolicyViolation>> {
-    Ok(policy_violation_for(transport_policy()?, contexts))
+    Ok(policy_violation_for(transport_policy()?, contexts)
+        .or_else(|| host_violation_for(&settings().allowed_hosts.value, contexts)))
 }
 
 fn policy_violation_for(
     policy: TransportPolicy,
     contexts: &[RemoteContext],
 ) -> Option<RemotePolicyViolation> {
-    if policy == TransportPolicy::Preserve {
+    contexts.iter().find_map(|context| {
+        (!policy.allows(context.transport)).then(|| RemotePolicyViolation {
+            context: context.clone(),
+            rule: PolicyRule::Transport(policy),
+        })
+    })
+}
+
+/// Finds the first remote whose host is not in `allowed`. An empty allowlist
+/// allows every host, and remotes without a host (local paths) always pass.
+fn host_violation_for(
+    allowed: &[String],
+    contexts: &[RemoteContext],
+) -> Option<RemotePolicyViolation> {
+    if allowed.is_empty() {
         return None;
     }
 
     contexts.iter().find_map(|context| {
-        context.transport.is_http().then(|| RemotePolicyViolation {
-            context: context.clone(),
+        let host = context.host()?;
+        (!allowed.iter().any(|pattern| host_matches(pattern, host))).then(|| {
+            RemotePolicyViolation {
+                context: context.clone(),
+                rule: PolicyRule::Host,
+            }
         })
     })
 }
 
+/// Matches a host case-insensitively; `*.example.com` matches any subdomain.
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.') && prefix.len() > 1),
+        None => host == pattern,
+    }
+}
+
+fn is_known_host(host: &str) -> bool {
+    KNOWN_HOSTS.contains(&host.to_ascii_lowercase().as_str())
+}
+
 fn safe_remote_details(url: &str, transport: RemoteTransport) -> (Option<String>, Option<String>) {
     match transport {
         RemoteTransport::Http | RemoteTransport::Https => safe_http_details(url),
@@ -262,11 +419,7 @@ fn safe_http_details(url: &str) -> (Option<String>, Option<String>) {
     }
 
     let identity = format!("{safe_authority}/{path}");
-    let standard_ssh_host = !has_explicit_port
-        && matches!(
-            host.to_ascii_lowercase().as_str(),
-            "github.com" | "gitlab.com" | "bitbucket.org"
-        );
+    let standard_ssh_host = !has_explicit_port && is_known_host(host);
     let ssh_url = standard_ssh_host.then(|| format!("git@{host}:{path}"));
 
     (Some(identity), ssh_url)
@@ -304,8 +457,9 @@ fn safe_ssh_identity(url: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::{
-        policy_violation_for, safe_http_details, safe_ssh_identity, RemoteContext, RemoteDirection,
-        RemoteTransport, TransportPolicy,
+        context_from_url, host_violation_for, policy_violation_for, safe_http_details,
+        safe_ssh_identity, PolicyRule, RemoteContext, RemoteDirection, RemoteTransport,
+        TransportPolicy,
     };
 
     fn context(transport: RemoteTransport) -> RemoteContext {
@@ -328,9 +482,88 @@ mod tests {
             TransportPolicy::parse("SSH-ONLY").unwrap(),
             TransportPolicy::SshOnly
         );
+        assert_eq!(
+            TransportPolicy::parse("https-only").unwrap(),
+            TransportPolicy::HttpsOnly
+        );
         assert!(TransportPolicy::parse("automatic").is_err());
     }
 
+    #[test]
+    fn https_only_blocks_ssh_and_plain_http() {
+        for (transport, allowed) in [
+            (RemoteTransport::Http, false),
+            (RemoteTransport::Https, true),
+            (RemoteTransport::Ssh, false),
+            (RemoteTransport::Local, true),
+            (RemoteTransport::Other, true),
+        ] {
+            let violation = policy_violation_for(TransportPolicy::HttpsOnly, &[context(transport)]);
+            assert_eq!(violation.is_none(), allowed, "{transport:?}");
+        }
+
+        let violation =
+            policy_violation_for(TransportPolicy::HttpsOnly, &[context(RemoteTransport::Ssh)])
+                .unwrap();
+        assert_eq!(
+            violation.rule,
+            PolicyRule::Transport(TransportPolicy::HttpsOnly)
+        );
+        assert_eq!(
+            violation.message(),
+            "https-only policy blocked fetch: remote origin uses SSH"
+        );
+    }
+
+    #[test]
+    fn host_allowlist_matches_exact_hosts_and_subdomain_wildcards() {
+        let allowed = vec!["GitHub.com".to_string(), "*.corp.example".to_string()];
+        let remote = |url: &str| [context_from_url("origin", RemoteDirection::Push, url)];
+
+        for url in [
+            "git@github.com:goobits/repos.git",
+            "https://token@github.com/goobits/repos.git",
+            "ssh://git@git.corp.example:2222/team/repo.git",
+            "https://code.corp.example:8443/team/repo.git",
+            "../local.git",
+        ] {
+            assert!(
+                host_violation_for(&allowed, &remote(url)).is_none(),
+                "{url}"
+            );
+        }
+
+        let violation =
+            host_violation_for(&allowed, &remote("https://gitlab.com/goobits/repos.git")).unwrap();
+        assert_eq!(violation.rule, PolicyRule::Host);
+        assert_eq!(
+            violation.message(),
+            "host allowlist blocked push: remote origin uses host gitlab.com"
+        );
+        assert!(host_violation_for(&allowed, &remote("git@corp.example:team/repo.git")).is_some());
+        assert!(host_violation_for(&[], &remote("git@gitlab.com:team/repo.git")).is_none());
+    }
+
+    #[test]
+    fn builds_known_host_https_urls() {
+        let https = |url: &str| context_from_url("origin", RemoteDirection::Fetch, url).https_url();
+
+        assert_eq!(
+            https("git@github.com:goobits/repos.git").as_deref(),
+            Some("https://github.com/goobits/repos.git")
+        );
+        assert_eq!(
+            https("ssh://git@gitlab.com/goobits/repos.git").as_deref(),
+            Some("https://gitlab.com/goobits/repos.git")
+        );
+        assert_eq!(
+            https("http://user@bitbucket.org/goobits/repos.git").as_deref(),
+            Some("https://bitbucket.org/goobits/repos.git")
+        );
+        assert_eq!(https("git@code.example.com:team/repo.git"), None);
+        assert_eq!(https("https://github.com/goobits/repos.git"), None);
+    }
+
     #[test]
     fn classifies_remote_transports_without_exposing_urls() {
         let cases = [
@@ -1,7 +1,12 @@
 //! Git status enumeration and utilities
 
+use serde::Serialize;
+
 /// Status enum representing the result of git operations
-#[derive(Clone, Copy, Debug, PartialEq)]
+///
+/// Serialized in snake case (`no_upstream`, `pull_error`) for `--format json`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Status {
     /// Repository is already up to date with remote
     Synced,
@@ -43,6 +48,16 @@ pub enum Status {
     PullError,
     /// Repository has local worktree changes
     Dirty,
+    /// Repository was cloned from its manifest entry
+    Cloned,
+    /// `repos exec` command exited successfully
+    Executed,
+    /// Repository was switched to the requested branch
+    Switched,
+    /// Merged local branches were deleted
+    Pruned,
+    /// `repos undo` restored the journaled HEAD
+    Restored,
 }
 
 impl Status {
@@ -58,7 +73,12 @@ impl Status {
             | Status::Unstaged
             | Status::Committed
             | Status::Pulled
-            | Status::Fetched => "🟢",
+            | Status::Fetched
+            | Status::Cloned
+            | Status::Executed
+            | Status::Switched
+            | Status::Pruned
+            | Status::Restored => "🟢",
             Status::Skip | Status::NoRemote | Status::ConfigSkipped | Status::NoChanges => "🟠",
             Status::NoUpstream | Status::Dirty => "🟡",
             Status::Error
@@ -93,6 +113,11 @@ impl Status {
             Status::Fetched => "fetched",
             Status::PullError => "pull-failed",
             Status::Dirty => "dirty",
+            Status::Cloned => "cloned",
+            Status::Executed => "executed",
+            Status::Switched => "switched",
+            Status::Pruned => "pruned",
+            Status::Restored => "restored",
         }
     }
 }
@@ -6,12 +6,27 @@
 #![allow(clippy::large_enum_variant)]
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
+use goobits_repos::audit::formats::AuditFormat;
+use goobits_repos::audit::scanner::SecretEngine;
 use goobits_repos::commands::audit::handle_audit_command;
-use goobits_repos::commands::config::{handle_config_command, parse_config_command};
+use goobits_repos::commands::bootstrap::handle_bootstrap_command;
+use goobits_repos::commands::branch::{
+    handle_branch_list_command, handle_prune_command, handle_switch_command,
+};
+use goobits_repos::commands::changelog::{handle_changelog_command, ChangelogOptions};
+use goobits_repos::commands::config::{
+    handle_config_command, handle_config_show_command, parse_config_command,
+};
+use goobits_repos::commands::discover::handle_discover_command;
 use goobits_repos::commands::doctor::handle_doctor_command;
+use goobits_repos::commands::exec::handle_exec_command;
 use goobits_repos::commands::publish::handle_publish_command;
+use goobits_repos::commands::remote::{
+    handle_remote_add_command, handle_remote_list_command, handle_remote_rename_command,
+    handle_remote_rewrite_command, handle_remote_set_push_command, RewriteTarget,
+};
 use goobits_repos::commands::save::handle_save_command;
 use goobits_repos::commands::staging::{
     handle_commit_command, handle_stage_command, handle_staging_status_command,
@@ -20,7 +35,14 @@ use goobits_repos::commands::staging::{
 use goobits_repos::commands::sync::{
     handle_fetch_command, handle_pull_command, handle_push_command, handle_sync_command,
 };
+use goobits_repos::commands::undo::handle_undo_command;
+use goobits_repos::commands::version::{handle_version_bump_command, VersionBumpOptions};
+use goobits_repos::core::{
+    init_settings, set_discovery_rescan, set_output_format, set_repo_selection, OutputFormat,
+    RepoSelector,
+};
 use goobits_repos::git::ConfigArgs;
+use goobits_repos::package::version::BumpLevel;
 use goobits_repos::subrepo;
 
 #[derive(Subcommand, Clone)]
@@ -41,6 +63,8 @@ enum Commands {
         /// Print the save plan without mutating repositories
         #[arg(long)]
         dry_run: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Pull safe remote changes, push local commits, and report nested drift
     Sync {
@@ -62,6 +86,8 @@ enum Commands {
         /// Run one operation at a time (advanced)
         #[arg(long, hide = true)]
         sequential: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Fetch remote references without changing local branches or worktrees
     Fetch {
@@ -74,6 +100,8 @@ enum Commands {
         /// Run one operation at a time (advanced)
         #[arg(long, hide = true)]
         sequential: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Push unpushed commits to remotes across all repositories
     Push {
@@ -95,6 +123,8 @@ enum Commands {
         /// Run one operation at a time (advanced)
         #[arg(long, hide = true)]
         sequential: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Pull changes from remotes across all repositories
     Pull {
@@ -116,9 +146,99 @@ enum Commands {
         /// Run one operation at a time (advanced)
         #[arg(long, hide = true)]
         sequential: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Clone repositories listed in repos.toml that are missing on disk
+    #[command(visible_alias = "clone")]
+    Bootstrap {
+        /// Show what would be cloned without cloning
+        #[arg(long)]
+        dry_run: bool,
+        /// Number of concurrent operations (advanced)
+        #[arg(long, short = 'j', conflicts_with = "sequential", hide = true)]
+        jobs: Option<usize>,
+        /// Run one operation at a time (advanced)
+        #[arg(long, hide = true)]
+        sequential: bool,
+    },
+    /// List discovered repositories from the discovery cache (refresh with --rescan)
+    Discover {
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Switch every repository to a branch, skipping dirty worktrees
+    Switch {
+        /// Branch to check out
+        branch: String,
+        /// Create the branch from the current HEAD where it does not exist
+        #[arg(long, short = 'c')]
+        create: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Show which repositories have a branch
+    Branch {
+        /// Branch name or glob to look for (e.g. "feature/*")
+        #[arg(long, value_name = "PATTERN")]
+        list: String,
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Delete local branches already merged into the remote default branch
+    Prune {
+        /// Show which branches would be deleted without deleting them
+        #[arg(long)]
+        dry_run: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Manage remotes across repositories
+    Remote {
+        #[command(subcommand)]
+        action: RemoteAction,
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Restore the HEADs recorded before the last save, commit, pull, sync, switch, prune or version bump
+    Undo {
+        /// Journal to undo (see `--list`); defaults to the most recent one
+        #[arg(value_name = "ID", conflicts_with_all = ["last", "list"])]
+        id: Option<String>,
+        /// Undo the most recent operation that has not been undone (default)
+        #[arg(long)]
+        last: bool,
+        /// List recorded operations instead of undoing one
+        #[arg(long, conflicts_with = "last")]
+        list: bool,
+    },
+    /// Run a command in every repository (e.g. `repos exec -- cargo fmt --check`)
+    Exec {
+        /// Command to run; a single argument is run through the shell
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+        /// Only run in repositories containing this file (repeatable)
+        #[arg(long = "only-if-file", value_name = "PATH")]
+        only_if_files: Vec<String>,
+        /// Start no further repositories after the first failure
+        #[arg(long)]
+        fail_fast: bool,
+        /// Kill the command in a repository after this many seconds
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+        /// Number of concurrent operations (advanced)
+        #[arg(long, short = 'j', conflicts_with = "sequential", hide = true)]
+        jobs: Option<usize>,
+        /// Run one operation at a time (advanced)
+        #[arg(long, hide = true)]
+        sequential: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Manage git configuration across repositories
     Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
         /// User name to set across all repositories
         #[arg(long)]
         name: Option<String>,
@@ -137,16 +257,22 @@ enum Commands {
         /// Show what would be changed without making changes
         #[arg(long)]
         dry_run: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Stage files matching pattern across all repositories
     Stage {
         /// Pattern to match files (e.g., "*.md", "README.md")
         pattern: String,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Unstage files matching pattern across all repositories
     Unstage {
         /// Pattern to match files (e.g., "*.md", "README.md", "*")
         pattern: String,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Show staging status across all repositories, or only specific repositories/paths
     Status {
@@ -170,6 +296,8 @@ enum Commands {
         skipped: bool,
         /// Repository names or paths to inspect
         targets: Vec<String>,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Commit staged changes across all repositories
     Commit {
@@ -178,6 +306,8 @@ enum Commands {
         /// Include repositories with no staged changes (create empty commits)
         #[arg(long)]
         include_empty: bool,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Publish packages to their registries (npm, cargo, `PyPI`)
     Publish {
@@ -189,9 +319,15 @@ enum Commands {
         /// Create and push git tags after successful publish (e.g., v1.2.3)
         #[arg(long)]
         tag: bool,
+        /// Sign the tags and annotate them with the version's CHANGELOG.md section
+        #[arg(long, requires = "tag")]
+        sign: bool,
         /// Allow publishing with uncommitted changes (not recommended)
         #[arg(long)]
         allow_dirty: bool,
+        /// Skip the pre-flight build and pack checks (not recommended)
+        #[arg(long)]
+        skip_preflight: bool,
         /// Publish all repositories regardless of visibility
         #[arg(long, conflicts_with_all = ["public_only", "private_only"])]
         all: bool,
@@ -201,9 +337,34 @@ enum Commands {
         /// Only publish private repositories
         #[arg(long, conflicts_with_all = ["all", "public_only"])]
         private_only: bool,
+        /// Continue the last unfinished publish run with its original options
+        #[arg(
+            long,
+            conflicts_with_all = ["repos", "dry_run", "tag", "sign", "all", "public_only", "private_only"]
+        )]
+        resume: bool,
+    },
+    /// Write CHANGELOG.md sections from conventional commits since the last version tag
+    Changelog {
+        /// Specific repositories to summarize (by name)
+        repos: Vec<String>,
+        /// Start from this tag instead of each repository's last vX.Y.Z tag
+        #[arg(long, value_name = "TAG")]
+        since_tag: Option<String>,
+        /// Print the sections without writing CHANGELOG.md
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Bump package versions and the fleet requirements that pin them
+    Version {
+        #[command(subcommand)]
+        action: VersionAction,
     },
     /// Audit repositories for security vulnerabilities and secrets
     Audit {
+        /// Secret scanner: `TruffleHog`, or built-in rules that need no install or network
+        #[arg(long, value_enum, default_value_t = EngineArg::Trufflehog)]
+        engine: EngineArg,
         /// Install required tools (`TruffleHog`) without prompting
         #[arg(long)]
         install_tools: bool,
@@ -231,17 +392,282 @@ enum Commands {
         /// Preview changes without applying them
         #[arg(long)]
         dry_run: bool,
+        /// Accept the current secret findings in .repos-audit-baseline
+        #[arg(long)]
+        update_baseline: bool,
         /// Only fix specific repositories (comma-separated)
         #[arg(long, value_delimiter = ',')]
         repos: Option<Vec<String>>,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Manage nested repository synchronization
     Nested {
         #[command(subcommand)]
         subcommand: NestedCommand,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Diagnose auth, remotes, nested state, and common blockers
-    Doctor,
+    Doctor {
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+}
+
+/// Repository targeting shared by every fleet command.
+#[derive(Args, Clone, Debug, Default)]
+struct SelectionArgs {
+    /// Only include repositories in these repos.toml groups
+    #[arg(
+        long = "group",
+        global = true,
+        value_delimiter = ',',
+        value_name = "GROUP"
+    )]
+    groups: Vec<String>,
+    /// Only include repositories carrying these repos.toml tags
+    #[arg(long = "tag", global = true, value_delimiter = ',', value_name = "TAG")]
+    tags: Vec<String>,
+    /// Skip repositories whose name or path matches these globs
+    #[arg(long, global = true, value_delimiter = ',', value_name = "PATTERN")]
+    exclude: Vec<String>,
+}
+
+impl SelectionArgs {
+    fn to_selector(&self) -> RepoSelector {
+        RepoSelector {
+            groups: self.groups.clone(),
+            tags: self.tags.clone(),
+            exclude: self.exclude.clone(),
+        }
+    }
+}
+
+/// Output format selected with `--format`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+enum FormatArg {
+    /// Progress bars and colored reports
+    #[default]
+    Human,
+    /// One JSON document when the command finishes
+    Json,
+    /// One JSON event per line while the command runs
+    Ndjson,
+    /// SARIF 2.1.0 for code-scanning dashboards (audit only)
+    Sarif,
+    /// `JUnit` XML with one test case per repository (audit only)
+    Junit,
+}
+
+impl FormatArg {
+    /// Returns the event format, or `None` for the audit-only report formats.
+    const fn to_output_format(self) -> Option<OutputFormat> {
+        match self {
+            Self::Human => Some(OutputFormat::Human),
+            Self::Json => Some(OutputFormat::Json),
+            Self::Ndjson => Some(OutputFormat::Ndjson),
+            Self::Sarif | Self::Junit => None,
+        }
+    }
+
+    const fn to_audit_format(self) -> Option<AuditFormat> {
+        match self {
+            Self::Sarif => Some(AuditFormat::Sarif),
+            Self::Junit => Some(AuditFormat::Junit),
+            Self::Human | Self::Json | Self::Ndjson => None,
+        }
+    }
+}
+
+/// Secret scanner selected with `repos audit --engine`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+enum EngineArg {
+    /// `TruffleHog` detectors, with optional live verification
+    #[default]
+    Trufflehog,
+    /// Built-in regex and entropy rules
+    Native,
+}
+
+impl EngineArg {
+    const fn to_secret_engine(self) -> SecretEngine {
+        match self {
+            Self::Trufflehog => SecretEngine::Trufflehog,
+            Self::Native => SecretEngine::Native,
+        }
+    }
+}
+
+impl Commands {
+    /// Returns true for commands that can emit `--format json|ndjson` output.
+    fn supports_structured_output(&self) -> bool {
+        matches!(
+            self,
+            Self::Status { .. }
+                | Self::Sync { .. }
+                | Self::Fetch { .. }
+                | Self::Push { .. }
+                | Self::Pull { .. }
+                | Self::Save { .. }
+                | Self::Commit { .. }
+                | Self::Exec { .. }
+        )
+    }
+
+    /// Returns the repository selection flags for commands that operate on the fleet.
+    fn selection(&self) -> Option<&SelectionArgs> {
+        match self {
+            Self::Save { selection, .. }
+            | Self::Sync { selection, .. }
+            | Self::Fetch { selection, .. }
+            | Self::Push { selection, .. }
+            | Self::Pull { selection, .. }
+            | Self::Config { selection, .. }
+            | Self::Stage { selection, .. }
+            | Self::Unstage { selection, .. }
+            | Self::Status { selection, .. }
+            | Self::Commit { selection, .. }
+            | Self::Exec { selection, .. }
+            | Self::Switch { selection, .. }
+            | Self::Branch { selection, .. }
+            | Self::Prune { selection, .. }
+            | Self::Remote { selection, .. }
+            | Self::Audit { selection, .. }
+            | Self::Nested { selection, .. }
+            | Self::Discover { selection }
+            | Self::Doctor { selection } => Some(selection),
+            Self::Bootstrap { .. }
+            | Self::Publish { .. }
+            | Self::Changelog { .. }
+            | Self::Version { .. }
+            | Self::Undo { .. } => None,
+        }
+    }
+}
+
+#[derive(Subcommand, Clone)]
+enum ConfigAction {
+    /// Show the resolved repos configuration
+    Show {
+        /// Annotate each value with the file, variable, or flag that set it
+        #[arg(long)]
+        effective: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum RemoteAction {
+    /// Show every repository's remotes grouped by host and owner
+    List,
+    /// Add a remote whose URL is built from an existing one
+    Add {
+        /// Name of the new remote
+        name: String,
+        /// URL template with {host}, {owner} and {repo} (e.g. 'git@git.internal:{owner}/{repo}.git')
+        #[arg(long)]
+        template: String,
+        /// Remote whose URL fills the template placeholders
+        #[arg(long, default_value = "origin", value_name = "REMOTE")]
+        from: String,
+        /// Show the remotes that would be added without adding them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rename a remote wherever it exists
+    Rename {
+        /// Current remote name
+        old: String,
+        /// New remote name
+        new: String,
+    },
+    /// Add a mirror push URL to a remote, keeping its existing push targets
+    SetPush {
+        /// Remote to add the push URL to
+        remote: String,
+        /// URL template with {host}, {owner} and {repo}, filled from the remote's fetch URL
+        #[arg(long)]
+        template: String,
+        /// Show the push URLs that would be added without adding them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rewrite fetch and push URLs to one transport
+    Rewrite {
+        /// Transport to convert remote URLs to
+        #[arg(long, value_enum)]
+        to: TransportArg,
+        /// Show the URLs that would change without rewriting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Transport selected with `repos remote rewrite --to`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum TransportArg {
+    /// git@host:owner/repo.git
+    Ssh,
+    /// https://host/owner/repo.git
+    Https,
+}
+
+impl TransportArg {
+    const fn to_rewrite_target(self) -> RewriteTarget {
+        match self {
+            Self::Ssh => RewriteTarget::Ssh,
+            Self::Https => RewriteTarget::Https,
+        }
+    }
+}
+
+#[derive(Subcommand, Clone)]
+enum VersionAction {
+    /// Bump versions in manifests, update pinned requirements, and commit
+    Bump {
+        /// Version part to increment
+        #[arg(value_enum)]
+        level: BumpArg,
+        /// Specific repositories to bump (by name)
+        repos: Vec<String>,
+        /// Start or continue a pre-release with this identifier (e.g. rc, beta)
+        #[arg(long, value_name = "ID")]
+        pre: Option<String>,
+        /// Show the new versions without changing any manifest
+        #[arg(long)]
+        dry_run: bool,
+        /// Leave the changed manifests uncommitted
+        #[arg(long)]
+        no_commit: bool,
+    },
+}
+
+/// Version part selected with `repos version bump`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum BumpArg {
+    /// 1.2.3 → 1.2.4
+    Patch,
+    /// 1.2.3 → 1.3.0
+    Minor,
+    /// 1.2.3 → 2.0.0
+    Major,
+    /// 1.2.3 → 1.2.4-rc.0, 1.2.4-rc.0 → 1.2.4-rc.1
+    Prerelease,
+    /// Level suggested by conventional commits since the last version tag
+    Auto,
+}
+
+impl BumpArg {
+    /// Returns the fixed level, or `None` for `auto`.
+    const fn to_bump_level(self) -> Option<BumpLevel> {
+        match self {
+            Self::Patch => Some(BumpLevel::Patch),
+            Self::Minor => Some(BumpLevel::Minor),
+            Self::Major => Some(BumpLevel::Major),
+            Self::Prerelease => Some(BumpLevel::Prerelease),
+            Self::Auto => None,
+        }
+    }
 }
 
 #[derive(Subcommand, Clone)]
@@ -280,6 +706,15 @@ enum NestedCommand {
 #[command(about = "Fleet-scale Git orchestration for humans")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
 struct Cli {
+    /// Override a configuration value for this run (e.g. --set concurrency.git=8)
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    overrides: Vec<String>,
+    /// Walk the directory tree instead of trusting the discovery cache
+    #[arg(long, global = true)]
+    rescan: bool,
+    /// Output format for status, sync, fetch, push, pull, save, commit and exec; sarif or junit for audit
+    #[arg(long, global = true, value_enum, default_value_t = FormatArg::Human)]
+    format: FormatArg,
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -307,6 +742,37 @@ fn handle_nested_command(subcommand: NestedCommand) -> Result<()> {
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    init_settings(&cli.overrides)?;
+    set_discovery_rescan(cli.rescan);
+
+    match (cli.format.to_output_format(), cli.format.to_audit_format()) {
+        (Some(OutputFormat::Human), _) => {}
+        (Some(format), _) => {
+            if !cli
+                .command
+                .as_ref()
+                .is_some_and(Commands::supports_structured_output)
+            {
+                anyhow::bail!(
+                    "--format {} is supported by status, sync, fetch, push, pull, save, commit and exec",
+                    format.label()
+                );
+            }
+            set_output_format(format)?;
+        }
+        (None, audit_format) => {
+            if !matches!(cli.command, Some(Commands::Audit { .. })) {
+                anyhow::bail!(
+                    "--format {} is supported by audit",
+                    audit_format.unwrap_or_default().label()
+                );
+            }
+        }
+    }
+
+    if let Some(selection) = cli.command.as_ref().and_then(Commands::selection) {
+        set_repo_selection(&selection.to_selector())?;
+    }
 
     // Determine the operation mode and handle commands
     match &cli.command {
@@ -316,6 +782,7 @@ async fn main() -> Result<()> {
             all,
             auto_upstream,
             dry_run,
+            ..
         }) => {
             handle_save_command(
                 message.clone(),
@@ -333,6 +800,7 @@ async fn main() -> Result<()> {
             no_drift_check,
             jobs,
             sequential,
+            ..
         }) => {
             handle_sync_command(
                 *auto_upstream,
@@ -348,6 +816,7 @@ async fn main() -> Result<()> {
             verbose,
             jobs,
             sequential,
+            ..
         }) => handle_fetch_command(*verbose, *jobs, *sequential).await,
         Some(Commands::Push {
             auto_upstream,
@@ -356,6 +825,7 @@ async fn main() -> Result<()> {
             no_drift_check,
             jobs,
             sequential,
+            ..
         }) => {
             handle_push_command(
                 *auto_upstream,
@@ -374,6 +844,7 @@ async fn main() -> Result<()> {
             no_drift_check,
             jobs,
             sequential,
+            ..
         }) => {
             handle_pull_command(
                 *rebase,
@@ -385,8 +856,62 @@ async fn main() -> Result<()> {
             )
             .await
         }
-        Some(Commands::Stage { pattern }) => handle_stage_command(pattern.clone()).await,
-        Some(Commands::Unstage { pattern }) => handle_unstage_command(pattern.clone()).await,
+        Some(Commands::Bootstrap {
+            dry_run,
+            jobs,
+            sequential,
+        }) => handle_bootstrap_command(*dry_run, *jobs, *sequential).await,
+        Some(Commands::Discover { .. }) => handle_discover_command().await,
+        Some(Commands::Switch { branch, create, .. }) => {
+            handle_switch_command(branch.clone(), *create).await
+        }
+        Some(Commands::Branch { list, .. }) => handle_branch_list_command(list.clone()).await,
+        Some(Commands::Prune { dry_run, .. }) => handle_prune_command(*dry_run).await,
+        Some(Commands::Remote { action, .. }) => match action {
+            RemoteAction::List => handle_remote_list_command().await,
+            RemoteAction::Add {
+                name,
+                template,
+                from,
+                dry_run,
+            } => {
+                handle_remote_add_command(name.clone(), template.clone(), from.clone(), *dry_run)
+                    .await
+            }
+            RemoteAction::Rename { old, new } => {
+                handle_remote_rename_command(old.clone(), new.clone()).await
+            }
+            RemoteAction::SetPush {
+                remote,
+                template,
+                dry_run,
+            } => handle_remote_set_push_command(remote.clone(), template.clone(), *dry_run).await,
+            RemoteAction::Rewrite { to, dry_run } => {
+                handle_remote_rewrite_command(to.to_rewrite_target(), *dry_run).await
+            }
+        },
+        Some(Commands::Undo { id, list, .. }) => handle_undo_command(id.clone(), *list).await,
+        Some(Commands::Exec {
+            command,
+            only_if_files,
+            fail_fast,
+            timeout,
+            jobs,
+            sequential,
+            ..
+        }) => {
+            handle_exec_command(
+                command.clone(),
+                only_if_files.clone(),
+                *fail_fast,
+                *timeout,
+                *jobs,
+                *sequential,
+            )
+            .await
+        }
+        Some(Commands::Stage { pattern, .. }) => handle_stage_command(pattern.clone()).await,
+        Some(Commands::Unstage { pattern, .. }) => handle_unstage_command(pattern.clone()).await,
         Some(Commands::Status {
             needs_work,
             dirty,
@@ -395,6 +920,7 @@ async fn main() -> Result<()> {
             failed,
             skipped,
             targets,
+            ..
         }) => {
             let filters = StatusFilters {
                 needs_work: *needs_work,
@@ -409,26 +935,68 @@ async fn main() -> Result<()> {
         Some(Commands::Commit {
             message,
             include_empty,
+            ..
         }) => handle_commit_command(message.clone(), *include_empty).await,
         Some(Commands::Publish {
             repos,
             dry_run,
             tag,
+            sign,
             allow_dirty,
+            skip_preflight,
             all,
             public_only: _,
             private_only,
+            resume,
         }) => {
             handle_publish_command(
                 repos.clone(),
                 *dry_run,
                 *tag,
+                *sign,
                 *allow_dirty,
+                *skip_preflight,
                 *all,
                 *private_only,
+                *resume,
             )
             .await
         }
+        Some(Commands::Changelog {
+            repos,
+            since_tag,
+            dry_run,
+        }) => {
+            handle_changelog_command(ChangelogOptions {
+                targets: repos.clone(),
+                since_tag: since_tag.clone(),
+                dry_run: *dry_run,
+            })
+            .await
+        }
+        Some(Commands::Version {
+            action:
+                VersionAction::Bump {
+                    level,
+                    repos,
+                    pre,
+                    dry_run,
+                    no_commit,
+                },
+        }) => {
+            handle_version_bump_command(VersionBumpOptions {
+                targets: repos.clone(),
+                level: level.to_bump_level(),
+                pre_id: pre.clone(),
+                dry_run: *dry_run,
+                commit: !*no_commit,
+            })
+            .await
+        }
+        Some(Commands::Config {
+            action: Some(ConfigAction::Show { effective }),
+            ..
+        }) => handle_config_show_command(*effective),
         Some(Commands::Config {
             name,
             email,
@@ -436,6 +1004,7 @@ async fn main() -> Result<()> {
             from_current,
             yes,
             dry_run,
+            ..
         }) => {
             let config_args = ConfigArgs {
                 command: parse_config_command(
@@ -450,6 +1019,7 @@ async fn main() -> Result<()> {
             handle_config_command(config_args).await
         }
         Some(Commands::Audit {
+            engine,
             install_tools,
             verify,
             json,
@@ -459,24 +1029,35 @@ async fn main() -> Result<()> {
             fix_secrets,
             fix_all,
             dry_run,
+            update_baseline,
             repos,
+            ..
         }) => {
+            let format = match (*json, cli.format.to_audit_format()) {
+                (true, Some(format)) => {
+                    anyhow::bail!("--json cannot be combined with --format {}", format.label())
+                }
+                (true, None) => AuditFormat::Json,
+                (false, format) => format.unwrap_or_default(),
+            };
             handle_audit_command(
+                engine.to_secret_engine(),
                 *install_tools,
                 *verify,
-                *json,
+                format,
                 *interactive,
                 *fix_gitignore,
                 *fix_large,
                 *fix_secrets,
                 *fix_all,
                 *dry_run,
+                *update_baseline,
                 repos.clone(),
             )
             .await
         }
-        Some(Commands::Nested { subcommand }) => handle_nested_command(subcommand.clone()),
-        Some(Commands::Doctor) => handle_doctor_command().await,
+        Some(Commands::Nested { subcommand, .. }) => handle_nested_command(subcommand.clone()),
+        Some(Commands::Doctor { .. }) => handle_doctor_command().await,
         None => {
             // Default behavior - show help
             use clap::CommandFactory;
@@ -2,11 +2,14 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::process::Command;
 
-use super::{PackageInfo, PackageManager};
+use super::version::{edit_toml_lines, replace_version_token, toml_key};
+use super::{registry, PackSummary, PackageInfo, PackageManager, PublishRegistry};
+use crate::core::settings::settings;
 
 const CARGO_OPERATION_TIMEOUT_SECS: u64 = 600; // 10 minutes for cargo operations (can be slow)
 
@@ -30,45 +33,434 @@ impl PackageManager for Cargo {
         get_package_info_internal(path).await
     }
 
-    async fn publish(&self, path: &Path, dry_run: bool) -> (bool, String) {
-        publish_internal(path, dry_run).await
+    async fn list_packages(&self, path: &Path) -> Vec<(PathBuf, PackageInfo)> {
+        list_packages_internal(path).await
+    }
+
+    async fn set_version(&self, path: &Path, old: &str, new: &str) -> anyhow::Result<PathBuf> {
+        set_version_internal(path, old, new).await
+    }
+
+    async fn update_dependency(
+        &self,
+        path: &Path,
+        dependency: &str,
+        old: &str,
+        new: &str,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        update_dependency_internal(path, dependency, old, new).await
+    }
+
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool> {
+        let base = match registry.filter(|registry| registry.cargo.is_some()) {
+            Some(registry) => registry.cargo_index.as_deref().ok_or_else(|| {
+                anyhow::anyhow!("no cargo_index set for registry '{}'", registry.name)
+            })?,
+            None => &settings().cargo_index_url.value,
+        };
+        let url = format!("{base}/{}", index_path(name));
+        Ok(registry::fetch(&url)
+            .await?
+            .is_some_and(|index| index_contains_version(&index, version)))
+    }
+
+    fn packs_against_registry(&self) -> bool {
+        true
+    }
+
+    async fn preflight(
+        &self,
+        path: &Path,
+        registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<Option<PackSummary>> {
+        let registry_name = registry.and_then(|registry| registry.cargo.as_deref());
+        preflight_internal(path, registry_name).await.map(Some)
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String) {
+        let registry_name = registry.and_then(|registry| registry.cargo.as_deref());
+        publish_internal(path, registry_name, dry_run).await
     }
 }
 
-/// Cargo.toml package section (partial)
+/// Cargo.toml (partial): a package, a workspace root, or both
 #[derive(Deserialize)]
 struct CargoToml {
-    package: CargoPackage,
+    package: Option<CargoPackage>,
+    workspace: Option<CargoWorkspace>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default, rename = "build-dependencies")]
+    build_dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    target: BTreeMap<String, CargoTarget>,
+}
+
+/// `[target.'cfg(...)'.dependencies]` tables
+#[derive(Deserialize)]
+struct CargoTarget {
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default, rename = "build-dependencies")]
+    build_dependencies: BTreeMap<String, toml::Value>,
 }
 
 #[derive(Deserialize)]
 struct CargoPackage {
     name: String,
-    version: String,
+    version: Option<Inheritable>,
+    publish: Option<toml::Value>,
+}
+
+impl CargoPackage {
+    /// `publish = false` and `publish = []` keep a crate off every registry.
+    fn is_publishable(&self) -> bool {
+        match &self.publish {
+            Some(toml::Value::Boolean(publish)) => *publish,
+            Some(toml::Value::Array(registries)) => !registries.is_empty(),
+            _ => true,
+        }
+    }
+}
+
+/// A field that is either set or inherited with `field.workspace = true`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Inheritable {
+    Value(String),
+    Workspace { workspace: bool },
+}
+
+#[derive(Deserialize)]
+struct CargoWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    package: Option<WorkspacePackage>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+}
+
+/// `[workspace.package]` values members inherit
+#[derive(Deserialize)]
+struct WorkspacePackage {
+    version: Option<String>,
+}
+
+async fn read_manifest(dir: &Path) -> Option<CargoToml> {
+    let content = tokio::fs::read_to_string(dir.join("Cargo.toml"))
+        .await
+        .ok()?;
+    toml::from_str(&content).ok()
+}
+
+/// Finds the workspace a member belongs to by walking up from its directory.
+async fn find_workspace(member_dir: &Path) -> Option<CargoWorkspace> {
+    for dir in member_dir.ancestors().skip(1) {
+        if let Some(workspace) = read_manifest(dir).await.and_then(|root| root.workspace) {
+            return Some(workspace);
+        }
+    }
+    None
 }
 
-/// Gets package information from Cargo.toml
+/// Gets package information from Cargo.toml, resolving fields inherited from
+/// the enclosing workspace. Returns None for a virtual workspace root.
 async fn get_package_info_internal(repo_path: &Path) -> Option<PackageInfo> {
-    let cargo_toml_path = repo_path.join("Cargo.toml");
+    let mut cargo = read_manifest(repo_path).await?;
+    let workspace = match cargo.workspace.take() {
+        Some(workspace) => Some(workspace),
+        None => find_workspace(repo_path).await,
+    };
+    resolve_info(cargo, workspace.as_ref())
+}
+
+/// Lists the publishable crates in a repository: the crate itself, or the
+/// root package and every member of a workspace except `publish = false` ones.
+async fn list_packages_internal(repo_path: &Path) -> Vec<(PathBuf, PackageInfo)> {
+    let Some(mut root) = read_manifest(repo_path).await else {
+        return Vec::new();
+    };
+    let Some(workspace) = root.workspace.take() else {
+        return resolve_info(root, None)
+            .map(|info| (repo_path.to_path_buf(), info))
+            .into_iter()
+            .collect();
+    };
+
+    let mut manifests = Vec::new();
+    if root.package.is_some() {
+        manifests.push((repo_path.to_path_buf(), root));
+    }
+    for dir in member_dirs(repo_path, &workspace) {
+        if let Some(member) = read_manifest(&dir).await {
+            manifests.push((dir, member));
+        }
+    }
+
+    manifests
+        .into_iter()
+        .filter(|(_, manifest)| {
+            manifest
+                .package
+                .as_ref()
+                .is_some_and(CargoPackage::is_publishable)
+        })
+        .filter_map(|(dir, manifest)| Some((dir, resolve_info(manifest, Some(&workspace))?)))
+        .collect()
+}
+
+/// Expands `[workspace].members` globs (one glob per path component, as
+/// Cargo does) and drops `exclude`d directories.
+fn member_dirs(root: &Path, workspace: &CargoWorkspace) -> Vec<PathBuf> {
+    let excluded: Vec<PathBuf> = workspace
+        .exclude
+        .iter()
+        .map(|path| root.join(path))
+        .collect();
+    let mut dirs = Vec::new();
+    for pattern in &workspace.members {
+        let mut matches = vec![root.to_path_buf()];
+        for component in pattern.split('/').filter(|c| !c.is_empty() && *c != ".") {
+            let Ok(glob) = globset::Glob::new(component) else {
+                matches.clear();
+                break;
+            };
+            let matcher = glob.compile_matcher();
+            matches = matches
+                .iter()
+                .flat_map(|dir| match std::fs::read_dir(dir) {
+                    Ok(entries) => entries
+                        .filter_map(Result::ok)
+                        .filter(|entry| entry.path().is_dir())
+                        .filter(|entry| matcher.is_match(entry.file_name()))
+                        .map(|entry| entry.path())
+                        .collect(),
+                    Err(_) => Vec::new(),
+                })
+                .collect();
+        }
+        for dir in matches {
+            if dir != root
+                && dir.join("Cargo.toml").is_file()
+                && !excluded.contains(&dir)
+                && !dirs.contains(&dir)
+            {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs.sort();
+    dirs
+}
 
-    let content = tokio::fs::read_to_string(&cargo_toml_path).await.ok()?;
-    let cargo: CargoToml = toml::from_str(&content).ok()?;
+fn resolve_info(cargo: CargoToml, workspace: Option<&CargoWorkspace>) -> Option<PackageInfo> {
+    let package = cargo.package?;
+    let version = match package.version {
+        Some(Inheritable::Value(version)) => version,
+        Some(Inheritable::Workspace { workspace: true }) => {
+            workspace?.package.as_ref()?.version.clone()?
+        }
+        Some(Inheritable::Workspace { workspace: false }) => return None,
+        // Cargo defaults a missing version to 0.0.0.
+        None => "0.0.0".to_string(),
+    };
+
+    let tables = [&cargo.dependencies, &cargo.build_dependencies]
+        .into_iter()
+        .chain(
+            cargo
+                .target
+                .values()
+                .flat_map(|target| [&target.dependencies, &target.build_dependencies]),
+        );
+    let mut dependencies: Vec<String> = tables
+        .flat_map(|table| table.iter())
+        .map(|(key, spec)| {
+            let inherited = spec
+                .get("workspace")
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(false);
+            match workspace.and_then(|ws| ws.dependencies.get(key)) {
+                Some(workspace_spec) if inherited => dependency_name(key, workspace_spec),
+                _ => dependency_name(key, spec),
+            }
+        })
+        .collect();
+    dependencies.sort();
+    dependencies.dedup();
 
     Some(PackageInfo {
         manager_name: "cargo".to_string(),
-        name: cargo.package.name,
-        version: cargo.package.version,
+        name: package.name,
+        version,
+        dependencies,
     })
 }
 
-/// Publishes a cargo package
-/// Returns (success, message)
-async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
-    let mut args = vec!["publish"];
+/// Rewrites `version = "old"` in the table named `table` of `manifest_path`.
+/// Returns false when the table does not hold that version.
+async fn replace_table_version(
+    manifest_path: &Path,
+    table: &str,
+    old: &str,
+    new: &str,
+) -> anyhow::Result<bool> {
+    let content = tokio::fs::read_to_string(manifest_path).await?;
+    let edited = edit_toml_lines(&content, |current, line| {
+        (current == table && toml_key(line) == Some("version"))
+            .then(|| replace_version_token(line, old, new))
+            .flatten()
+    });
+    match edited {
+        Some(edited) => {
+            tokio::fs::write(manifest_path, edited).await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Bumps `[package].version`, or `[workspace.package].version` in the
+/// workspace root when the crate inherits it. Members sharing an inherited
+/// version may each ask for the same bump; later requests find it done.
+async fn set_version_internal(dir: &Path, old: &str, new: &str) -> anyhow::Result<PathBuf> {
+    let manifest_path = dir.join("Cargo.toml");
+    if replace_table_version(&manifest_path, "package", old, new).await? {
+        return Ok(manifest_path);
+    }
+
+    let inherits = read_manifest(dir).await.is_some_and(|cargo| {
+        matches!(
+            cargo.package.and_then(|package| package.version),
+            Some(Inheritable::Workspace { workspace: true })
+        )
+    });
+    if inherits {
+        for root in dir.ancestors() {
+            let Some(workspace) = read_manifest(root).await.and_then(|cargo| cargo.workspace)
+            else {
+                continue;
+            };
+            let root_manifest = root.join("Cargo.toml");
+            let current = workspace.package.and_then(|package| package.version);
+            if current.as_deref() == Some(new)
+                || replace_table_version(&root_manifest, "workspace.package", old, new).await?
+            {
+                return Ok(root_manifest);
+            }
+            break;
+        }
+    }
 
+    anyhow::bail!(
+        "no version \"{old}\" in the [package] table of {}",
+        manifest_path.display()
+    )
+}
+
+/// Updates requirements on `dependency` in every dependency table of the
+/// manifest, including `[workspace.dependencies]` and `[dependencies.name]`
+/// tables, and renamed entries (`alias = { package = "name" }`).
+async fn update_dependency_internal(
+    dir: &Path,
+    dependency: &str,
+    old: &str,
+    new: &str,
+) -> anyhow::Result<Option<PathBuf>> {
+    let manifest_path = dir.join("Cargo.toml");
+    let Ok(content) = tokio::fs::read_to_string(&manifest_path).await else {
+        return Ok(None);
+    };
+    let renamed = format!("package = \"{dependency}\"");
+    let edited = edit_toml_lines(&content, |table, line| {
+        let (parent, last) = table.rsplit_once('.').unwrap_or(("", table));
+        let names_dependency = if is_dependency_table(table) {
+            toml_key(line) == Some(dependency) || line.contains(&renamed)
+        } else {
+            // `[dependencies.name]` tables hold one dependency's fields.
+            is_dependency_table(parent) && last == dependency && toml_key(line) == Some("version")
+        };
+        names_dependency
+            .then(|| replace_version_token(line, old, new))
+            .flatten()
+    });
+    match edited {
+        Some(edited) => {
+            tokio::fs::write(&manifest_path, edited).await?;
+            Ok(Some(manifest_path))
+        }
+        None => Ok(None),
+    }
+}
+
+fn is_dependency_table(table: &str) -> bool {
+    let last = table.rsplit('.').next().unwrap_or(table);
+    matches!(
+        last,
+        "dependencies" | "dev-dependencies" | "build-dependencies"
+    )
+}
+
+/// Resolves a dependency key to the crate it names (`foo = { package = "bar" }` is `bar`).
+fn dependency_name(key: &str, spec: &toml::Value) -> String {
+    spec.get("package")
+        .and_then(toml::Value::as_str)
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Path of a crate's file in the sparse registry index.
+fn index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    }
+}
+
+/// Sparse index files hold one JSON object per published version.
+fn index_contains_version(index: &str, version: &str) -> bool {
+    index
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .any(|entry| entry.get("vers").and_then(serde_json::Value::as_str) == Some(version))
+}
+
+/// Arguments for `cargo publish`, pointed at the named alternate registry
+/// when given.
+fn publish_args(registry_name: Option<&str>, dry_run: bool) -> Vec<&str> {
+    let mut args = vec!["publish"];
+    if let Some(name) = registry_name {
+        args.extend(["--registry", name]);
+    }
     if dry_run {
         args.push("--dry-run");
     }
+    args
+}
+
+/// Publishes a cargo package
+/// Returns (success, message)
+async fn publish_internal(
+    repo_path: &Path,
+    registry_name: Option<&str>,
+    dry_run: bool,
+) -> (bool, String) {
+    let args = publish_args(registry_name, dry_run);
 
     let timeout_duration = Duration::from_secs(CARGO_OPERATION_TIMEOUT_SECS);
 
@@ -108,6 +500,55 @@ async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
     }
 }
 
+/// Lists the files `cargo package` includes, then runs `cargo publish
+/// --dry-run`, which packs the crate and verifies it by building the packed
+/// sources.
+async fn preflight_internal(
+    repo_path: &Path,
+    registry_name: Option<&str>,
+) -> anyhow::Result<PackSummary> {
+    let mut list_args = vec!["package", "--list"];
+    if let Some(name) = registry_name {
+        list_args.extend(["--registry", name]);
+    }
+    let listed = run_cargo(repo_path, &list_args).await?;
+    let verified = run_cargo(repo_path, &publish_args(registry_name, true)).await?;
+    Ok(PackSummary {
+        files: String::from_utf8_lossy(&listed.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect(),
+        size: packaged_size(&String::from_utf8_lossy(&verified.stderr)),
+    })
+}
+
+/// Runs cargo in the package directory, failing with the cleaned-up error.
+async fn run_cargo(repo_path: &Path, args: &[&str]) -> anyhow::Result<std::process::Output> {
+    let output = tokio::time::timeout(
+        Duration::from_secs(CARGO_OPERATION_TIMEOUT_SECS),
+        Command::new("cargo")
+            .args(args)
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("cargo {} timed out", args[0]))?
+    .map_err(|e| anyhow::anyhow!("cargo command failed: {e}"))?;
+    if !output.status.success() {
+        anyhow::bail!(clean_cargo_error(&String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(output)
+}
+
+/// Size from cargo's `Packaged 6 files, 1.2KiB (930B compressed)` line.
+fn packaged_size(stderr: &str) -> Option<String> {
+    stderr
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Packaged "))
+        .and_then(|summary| summary.split_once(", "))
+        .map(|(_, size)| size.trim().to_string())
+}
+
 /// Cleans up cargo error messages to be more user-friendly
 fn clean_cargo_error(error: &str) -> String {
     if error.contains("already uploaded") || error.contains("already exists") {
@@ -135,3 +576,188 @@ fn clean_cargo_error(error: &str) -> String {
             .map_or_else(|| error.trim().to_string(), |line| line.trim().to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dependencies_include_renames_and_target_tables_but_not_dev_dependencies() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            r#"[package]
+name = "app"
+version = "0.4.0"
+
+[dependencies]
+core-lib = { path = "../core-lib", version = "1.2.0" }
+log = "0.4"
+utils = { package = "fleet-utils", version = "2" }
+
+[dev-dependencies]
+test-kit = { path = "../test-kit" }
+
+[target.'cfg(unix)'.build-dependencies]
+codegen = "1"
+"#,
+        )
+        .unwrap();
+
+        let info = get_package_info_internal(temp_dir.path()).await.unwrap();
+        assert_eq!(
+            info.dependencies,
+            vec!["codegen", "core-lib", "fleet-utils", "log"]
+        );
+    }
+
+    #[tokio::test]
+    async fn workspace_members_are_listed_with_inherited_versions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "Cargo.toml",
+            r#"[workspace]
+members = ["crates/*", "tools/cli"]
+exclude = ["crates/experimental"]
+
+[workspace.package]
+version = "1.4.0"
+
+[workspace.dependencies]
+core = { package = "fleet-core", path = "crates/core", version = "1.4.0" }
+"#,
+        );
+        write(
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"fleet-core\"\nversion.workspace = true\n",
+        );
+        write(
+            "crates/http/Cargo.toml",
+            "[package]\nname = \"fleet-http\"\nversion.workspace = true\n\n[dependencies]\ncore = { workspace = true }\n",
+        );
+        write(
+            "crates/experimental/Cargo.toml",
+            "[package]\nname = \"fleet-experimental\"\nversion = \"0.1.0\"\n",
+        );
+        write(
+            "tools/cli/Cargo.toml",
+            "[package]\nname = \"fleet-cli\"\nversion = \"0.9.0\"\npublish = false\n",
+        );
+
+        let packages = list_packages_internal(root).await;
+        let listed: Vec<(String, &str, &str)> = packages
+            .iter()
+            .map(|(dir, info)| {
+                (
+                    dir.strip_prefix(root).unwrap().display().to_string(),
+                    info.name.as_str(),
+                    info.version.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("crates/core".to_string(), "fleet-core", "1.4.0"),
+                ("crates/http".to_string(), "fleet-http", "1.4.0"),
+            ]
+        );
+        assert_eq!(packages[1].1.dependencies, vec!["fleet-core"]);
+
+        // A member read on its own still resolves the inherited version.
+        let member = get_package_info_internal(&root.join("crates/http"))
+            .await
+            .unwrap();
+        assert_eq!(member.version, "1.4.0");
+        assert!(get_package_info_internal(root).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn version_edits_keep_formatting_and_follow_workspace_inheritance() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("core")).unwrap();
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"core\", \"app\"]\n\n[workspace.package]\nversion = \"1.2.0\"  # shared\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("core/Cargo.toml"),
+            "[package]\nname = \"core-lib\"\nversion.workspace = true\n",
+        )
+        .unwrap();
+        let app = "[package]\nname = \"app\"\nversion = \"0.4.0\"\n\n[dependencies]\ncore = { package = \"core-lib\", path = \"../core\", version = \"^1.2.0\" }\nother = \"1.2.0\"\n\n[dev-dependencies.core-lib]\npath = \"../core\"\nversion = \"1.2.0\"\n";
+        std::fs::write(root.join("app/Cargo.toml"), app).unwrap();
+
+        let written = set_version_internal(&root.join("core"), "1.2.0", "1.3.0")
+            .await
+            .unwrap();
+        assert_eq!(written, root.join("Cargo.toml"));
+        let workspace = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(workspace.contains("version = \"1.3.0\"  # shared"));
+        // A second member sharing the version finds the bump already made.
+        assert!(set_version_internal(&root.join("core"), "1.2.0", "1.3.0")
+            .await
+            .is_ok());
+
+        update_dependency_internal(&root.join("app"), "core-lib", "1.2.0", "1.3.0")
+            .await
+            .unwrap()
+            .unwrap();
+        let app = std::fs::read_to_string(root.join("app/Cargo.toml")).unwrap();
+        assert!(app.contains("path = \"../core\", version = \"^1.3.0\" }"));
+        assert!(app.contains("other = \"1.2.0\""));
+        assert!(app.ends_with("path = \"../core\"\nversion = \"1.3.0\"\n"));
+        assert!(set_version_internal(&root.join("app"), "9.9.9", "9.9.10")
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn sparse_index_paths_follow_the_crates_io_layout() {
+        assert_eq!(index_path("a"), "1/a");
+        assert_eq!(index_path("ab"), "2/ab");
+        assert_eq!(index_path("abc"), "3/a/abc");
+        assert_eq!(index_path("Serde_JSON"), "se/rd/serde_json");
+    }
+
+    #[test]
+    fn sparse_index_lookup_matches_exact_versions() {
+        let index = concat!(
+            "{\"name\":\"core-lib\",\"vers\":\"1.1.0\"}\n",
+            "{\"name\":\"core-lib\",\"vers\":\"1.2.0\"}\n"
+        );
+        assert!(index_contains_version(index, "1.2.0"));
+        assert!(!index_contains_version(index, "1.2"));
+    }
+
+    #[test]
+    fn packaged_size_comes_from_the_packaging_summary() {
+        let stderr = "   Packaging core-lib v1.2.0 (/src/core-lib)\n    Packaged 6 files, 1.2KiB (930B compressed)\n   Verifying core-lib v1.2.0 (/src/core-lib)\n";
+        assert_eq!(
+            packaged_size(stderr).as_deref(),
+            Some("1.2KiB (930B compressed)")
+        );
+        assert_eq!(
+            packaged_size("error: failed to verify package tarball"),
+            None
+        );
+    }
+
+    #[test]
+    fn publish_args_name_an_alternate_registry() {
+        assert_eq!(publish_args(None, false), vec!["publish"]);
+        assert_eq!(
+            publish_args(Some("internal"), true),
+            vec!["publish", "--registry", "internal", "--dry-run"]
+        );
+    }
+}
@@ -0,0 +1,433 @@
+//! Release notes from conventional commits
+//!
+//! Commits since a package's last release tag are read as conventional
+//! commits (`feat(api)!: drop v1 routes`). Features, fixes, and breaking
+//! changes become a `CHANGELOG.md` section, and together they suggest the
+//! next semantic version bump for `repos changelog` and
+//! `repos version bump auto`.
+
+use anyhow::Result;
+use std::path::Path;
+
+use super::version::{BumpLevel, Version};
+use crate::git::operations::run_git;
+
+/// File `repos changelog` writes in each repository.
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+const FIELD_SEPARATOR: char = '\u{1f}';
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// One parsed `type(scope)!: description` commit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConventionalCommit {
+    pub hash: String,
+    pub kind: String,
+    pub scope: Option<String>,
+    pub description: String,
+    pub breaking: bool,
+}
+
+impl ConventionalCommit {
+    /// Parses a commit subject and body; returns `None` for commits that do
+    /// not follow the convention.
+    pub fn parse(hash: &str, subject: &str, body: &str) -> Option<Self> {
+        let (header, description) = subject.split_once(':')?;
+        let description = description.trim();
+        let (header, bang) = match header.strip_suffix('!') {
+            Some(header) => (header, true),
+            None => (header, false),
+        };
+        let (kind, scope) = match header.split_once('(') {
+            Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?.trim().to_string())),
+            None => (header, None),
+        };
+        if kind.is_empty()
+            || !kind.chars().all(|c| c.is_ascii_alphabetic())
+            || description.is_empty()
+        {
+            return None;
+        }
+        let breaking = bang
+            || body.lines().any(|line| {
+                line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+            });
+        Some(Self {
+            hash: hash.to_string(),
+            kind: kind.to_ascii_lowercase(),
+            scope: scope.filter(|scope| !scope.is_empty()),
+            description: description.to_string(),
+            breaking,
+        })
+    }
+
+    fn entry(&self) -> String {
+        let short: String = self.hash.chars().take(7).collect();
+        match &self.scope {
+            Some(scope) => format!("- **{scope}:** {} ({short})", self.description),
+            None => format!("- {} ({short})", self.description),
+        }
+    }
+}
+
+/// Conventional commits since a repository's last version tag.
+#[derive(Debug, Default)]
+pub struct ReleaseNotes {
+    /// Tag the notes start from; `None` covers the whole history.
+    pub since_tag: Option<String>,
+    pub breaking: Vec<ConventionalCommit>,
+    pub features: Vec<ConventionalCommit>,
+    pub fixes: Vec<ConventionalCommit>,
+}
+
+impl ReleaseNotes {
+    /// Groups commits; a breaking `feat` or `fix` is listed only as breaking,
+    /// and other types (`docs`, `chore`, ...) are left out.
+    #[must_use]
+    pub fn from_commits(since_tag: Option<String>, commits: Vec<ConventionalCommit>) -> Self {
+        let mut notes = Self {
+            since_tag,
+            ..Self::default()
+        };
+        for commit in commits {
+            if commit.breaking {
+                notes.breaking.push(commit);
+            } else if commit.kind == "feat" {
+                notes.features.push(commit);
+            } else if commit.kind == "fix" {
+                notes.fixes.push(commit);
+            }
+        }
+        notes
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.breaking.is_empty() && self.features.is_empty() && self.fixes.is_empty()
+    }
+
+    /// Suggested bump for a release after `current`: breaking changes are
+    /// major (minor before 1.0.0), features minor, fixes patch.
+    #[must_use]
+    pub fn suggested_bump(&self, current: Option<&Version>) -> Option<BumpLevel> {
+        let pre_1_0 = current.is_some_and(|version| version.major == 0);
+        if !self.breaking.is_empty() {
+            Some(if pre_1_0 {
+                BumpLevel::Minor
+            } else {
+                BumpLevel::Major
+            })
+        } else if !self.features.is_empty() {
+            Some(BumpLevel::Minor)
+        } else if !self.fixes.is_empty() {
+            Some(BumpLevel::Patch)
+        } else {
+            None
+        }
+    }
+
+    /// Markdown section headed `heading` (`1.3.0 - 2026-10-17`).
+    #[must_use]
+    pub fn render(&self, heading: &str) -> String {
+        let mut lines = vec![format!("## {heading}")];
+        for (title, commits) in [
+            ("⚠ Breaking Changes", &self.breaking),
+            ("Features", &self.features),
+            ("Fixes", &self.fixes),
+        ] {
+            if commits.is_empty() {
+                continue;
+            }
+            lines.push(String::new());
+            lines.push(format!("### {title}"));
+            lines.push(String::new());
+            lines.extend(commits.iter().map(ConventionalCommit::entry));
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Returns the most recent release tag matching `tag_match` (a
+/// `git describe --match` glob such as `v[0-9]*`) reachable from HEAD.
+pub async fn latest_version_tag(repo_path: &Path, tag_match: &str) -> Result<Option<String>> {
+    let (success, stdout, stderr) = run_git(
+        repo_path,
+        &["describe", "--tags", "--abbrev=0", "--match", tag_match],
+    )
+    .await?;
+    if success {
+        return Ok(Some(stdout.trim().to_string()));
+    }
+    // No matching tag, or no commits at all.
+    if stderr.contains("No names found")
+        || stderr.contains("No tags can describe")
+        || stderr.contains("cannot describe")
+        || stderr.contains("bad revision")
+        || stderr.contains("Not a valid object name")
+    {
+        return Ok(None);
+    }
+    anyhow::bail!("failed to find the last version tag: {}", stderr.trim())
+}
+
+/// Reads the conventional commits since `since_tag` (or the last tag matching
+/// `tag_match` when `None`) on the current branch. A workspace member passes
+/// its directory, relative to the repository, as `package_path` so only
+/// commits touching it count.
+pub async fn release_notes(
+    repo_path: &Path,
+    since_tag: Option<&str>,
+    tag_match: &str,
+    package_path: Option<&Path>,
+) -> Result<ReleaseNotes> {
+    let since_tag = match since_tag {
+        Some(tag) => Some(tag.to_string()),
+        None => latest_version_tag(repo_path, tag_match).await?,
+    };
+    let range = since_tag
+        .as_ref()
+        .map_or_else(|| "HEAD".to_string(), |tag| format!("{tag}..HEAD"));
+    let format = format!("--format=%H{FIELD_SEPARATOR}%s{FIELD_SEPARATOR}%b{RECORD_SEPARATOR}");
+    let pathspec = package_path.map(|path| path.to_string_lossy().into_owned());
+    let mut args = vec!["log", "--no-merges", &format, &range];
+    if let Some(pathspec) = &pathspec {
+        args.extend(["--", pathspec.as_str()]);
+    }
+    let (success, stdout, stderr) = run_git(repo_path, &args).await?;
+    if !success {
+        // A repository without commits has nothing to report.
+        if since_tag.is_none() && stderr.contains("does not have any commits") {
+            return Ok(ReleaseNotes::default());
+        }
+        anyhow::bail!("failed to read commits in {range}: {}", stderr.trim());
+    }
+
+    let commits = stdout
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, FIELD_SEPARATOR);
+            let hash = fields.next()?;
+            let subject = fields.next()?;
+            let body = fields.next().unwrap_or_default();
+            ConventionalCommit::parse(hash, subject, body)
+        })
+        .collect();
+    Ok(ReleaseNotes::from_commits(since_tag, commits))
+}
+
+/// Inserts `section` above the newest release in an existing changelog,
+/// keeping its title and preamble, or starts a new one.
+#[must_use]
+pub fn prepend_section(existing: Option<&str>, section: &str) -> String {
+    let Some(existing) = existing.filter(|existing| !existing.trim().is_empty()) else {
+        return format!("# Changelog\n\n{section}");
+    };
+    let mut offset = 0;
+    for line in existing.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            return format!("{}{section}\n{}", &existing[..offset], &existing[offset..]);
+        }
+        offset += line.len();
+    }
+    let separator = if existing.ends_with("\n\n") {
+        ""
+    } else if existing.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!("{existing}{separator}{section}")
+}
+
+/// Replaces the section for `version` when the changelog already has one,
+/// so rerunning `repos changelog` rewrites that release instead of adding a
+/// duplicate; otherwise inserts `section` as `prepend_section` does.
+#[must_use]
+pub fn write_section(existing: Option<&str>, version: &str, section: &str) -> String {
+    let Some(existing) = existing else {
+        return prepend_section(None, section);
+    };
+    let mut start = None;
+    let mut offset = 0;
+    for line in existing.split_inclusive('\n') {
+        if start.is_some() && line.starts_with("## ") {
+            break;
+        }
+        if start.is_none() && is_heading_for(line.trim_end(), version) {
+            start = Some(offset);
+        }
+        offset += line.len();
+    }
+    let Some(start) = start else {
+        return prepend_section(Some(existing), section);
+    };
+    let separator = if offset < existing.len() { "\n" } else { "" };
+    format!(
+        "{}{section}{separator}{}",
+        &existing[..start],
+        &existing[offset..]
+    )
+}
+
+/// Whether the changelog has a section for `version`.
+#[must_use]
+pub fn has_section(changelog: &str, version: &str) -> bool {
+    changelog.lines().any(|line| is_heading_for(line, version))
+}
+
+/// Whether `line` heads the section for `version` (`## 1.3.0 - 2026-10-17`,
+/// `## [1.3.0]`, or `## v1.3.0`).
+fn is_heading_for(line: &str, version: &str) -> bool {
+    line.strip_prefix("## ").is_some_and(|heading| {
+        heading
+            .split_whitespace()
+            .next()
+            .map(|first| first.trim_matches(['[', ']']).trim_start_matches('v'))
+            == Some(version)
+    })
+}
+
+/// Body of the changelog section for `version` (`## 1.3.0 - 2026-10-17`,
+/// `## [1.3.0]`, or `## v1.3.0`), without its heading.
+#[must_use]
+pub fn section_for(changelog: &str, version: &str) -> Option<String> {
+    let mut lines = changelog.lines();
+    lines.find(|line| is_heading_for(line, version))?;
+    let body = lines
+        .take_while(|line| !line.starts_with("## "))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = body.trim();
+    (!body.is_empty()).then(|| body.to_string())
+}
+
+/// Message for the release tag of `version`: a title line, then the
+/// version's section from the package's CHANGELOG.md when it has one.
+pub async fn tag_message(path: &Path, name: &str, version: &str) -> String {
+    let title = format!("{name} {version}");
+    let section = tokio::fs::read_to_string(path.join(CHANGELOG_FILE))
+        .await
+        .ok()
+        .and_then(|changelog| section_for(&changelog, version));
+    match section {
+        Some(section) => format!("{title}\n\n{section}\n"),
+        None => title,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(subject: &str, body: &str) -> Option<ConventionalCommit> {
+        ConventionalCommit::parse("0123456789abcdef", subject, body)
+    }
+
+    #[test]
+    fn conventional_subjects_are_parsed_and_others_skipped() {
+        let parsed = commit("feat(api)!: drop v1 routes", "").unwrap();
+        assert_eq!(parsed.kind, "feat");
+        assert_eq!(parsed.scope.as_deref(), Some("api"));
+        assert_eq!(parsed.description, "drop v1 routes");
+        assert!(parsed.breaking);
+
+        assert!(
+            commit(
+                "fix: handle empty input",
+                "Details\n\nBREAKING CHANGE: errors now typed"
+            )
+            .unwrap()
+            .breaking
+        );
+        assert!(!commit("Fix: typo", "").unwrap().breaking);
+        assert!(commit("Update README", "").is_none());
+        assert!(commit("Merge branch 'main': sync", "").is_none());
+        assert!(commit("feat(api: missing paren", "").is_none());
+    }
+
+    #[test]
+    fn notes_group_commits_and_suggest_a_bump() {
+        let commits = [
+            commit("feat: add export", ""),
+            commit("fix(cli): exit code", ""),
+            commit("docs: typo", ""),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let notes = ReleaseNotes::from_commits(Some("v1.2.0".into()), commits);
+        let current = Version::parse("1.2.0").unwrap();
+        assert_eq!(notes.suggested_bump(Some(&current)), Some(BumpLevel::Minor));
+
+        let rendered = notes.render("1.3.0 - 2026-10-17");
+        assert_eq!(
+            rendered,
+            "## 1.3.0 - 2026-10-17\n\n### Features\n\n- add export (0123456)\n\n### Fixes\n\n- **cli:** exit code (0123456)\n"
+        );
+
+        let breaking =
+            ReleaseNotes::from_commits(None, commit("feat!: new API", "").into_iter().collect());
+        assert_eq!(
+            breaking.suggested_bump(Some(&current)),
+            Some(BumpLevel::Major)
+        );
+        let early = Version::parse("0.4.0").unwrap();
+        assert_eq!(
+            breaking.suggested_bump(Some(&early)),
+            Some(BumpLevel::Minor)
+        );
+        assert_eq!(ReleaseNotes::default().suggested_bump(None), None);
+    }
+
+    #[test]
+    fn sections_go_above_the_newest_release_and_keep_the_preamble() {
+        let section = "## 1.3.0\n\n### Fixes\n\n- a (0123456)\n";
+        let existing = "# Changelog\n\nAll notable changes.\n\n## 1.2.0\n\n- old\n";
+        assert_eq!(
+            prepend_section(Some(existing), section),
+            "# Changelog\n\nAll notable changes.\n\n## 1.3.0\n\n### Fixes\n\n- a (0123456)\n\n## 1.2.0\n\n- old\n"
+        );
+        assert_eq!(
+            prepend_section(None, section),
+            "# Changelog\n\n## 1.3.0\n\n### Fixes\n\n- a (0123456)\n"
+        );
+        assert_eq!(
+            prepend_section(Some("# Changelog\n"), section),
+            "# Changelog\n\n## 1.3.0\n\n### Fixes\n\n- a (0123456)\n"
+        );
+    }
+
+    #[test]
+    fn rewriting_a_release_replaces_its_section() {
+        let section = "## 1.3.0 - 2026-10-18\n\n### Fixes\n\n- a (0123456)\n- b (89abcde)\n";
+        let existing = "# Changelog\n\n## 1.3.0 - 2026-10-17\n\n### Fixes\n\n- a (0123456)\n\n## 1.2.0\n\n- old\n";
+        let written = write_section(Some(existing), "1.3.0", section);
+        assert_eq!(
+            written,
+            "# Changelog\n\n## 1.3.0 - 2026-10-18\n\n### Fixes\n\n- a (0123456)\n- b (89abcde)\n\n## 1.2.0\n\n- old\n"
+        );
+        assert_eq!(write_section(Some(&written), "1.3.0", section), written);
+
+        let latest = "# Changelog\n\n## [1.3.0]\n\n- stale\n";
+        assert_eq!(
+            write_section(Some(latest), "1.3.0", section),
+            format!("# Changelog\n\n{section}")
+        );
+        assert_eq!(
+            write_section(Some(existing), "1.4.0", "## 1.4.0\n"),
+            prepend_section(Some(existing), "## 1.4.0\n")
+        );
+    }
+
+    #[test]
+    fn version_sections_are_found_under_any_heading_style() {
+        let changelog = "# Changelog\n\n## [1.3.0] - 2026-10-17\n\n### Fixes\n\n- a (0123456)\n\n## v1.2.0\n\n- old\n";
+        assert_eq!(
+            section_for(changelog, "1.3.0").as_deref(),
+            Some("### Fixes\n\n- a (0123456)")
+        );
+        assert_eq!(section_for(changelog, "1.2.0").as_deref(), Some("- old"));
+        assert_eq!(section_for(changelog, "1.2"), None);
+    }
+}
@@ -0,0 +1,293 @@
+//! Go module publishing functionality
+//!
+//! Go modules have no registry upload: a version is published by pushing its
+//! semver tag, after which the module proxy serves it. The version is the
+//! newest `vX.Y.Z` tag reachable from HEAD.
+
+use async_trait::async_trait;
+use std::path::Path;
+
+use super::version::Version;
+use super::{registry, PackageInfo, PackageManager, PublishRegistry};
+use crate::core::settings::settings;
+use crate::git::operations::run_git;
+use crate::git::push_tag;
+
+pub struct GoModule;
+
+#[async_trait]
+impl PackageManager for GoModule {
+    fn name(&self) -> &str {
+        "go"
+    }
+
+    fn icon(&self) -> &str {
+        "🐹"
+    }
+
+    async fn detect(&self, path: &Path) -> bool {
+        tokio::fs::metadata(path.join("go.mod")).await.is_ok()
+    }
+
+    async fn get_info(&self, path: &Path) -> Option<PackageInfo> {
+        get_package_info_internal(path).await
+    }
+
+    fn publishes_by_tag(&self) -> bool {
+        true
+    }
+
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        _registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool> {
+        let base = &settings().go_proxy_url.value;
+        let url = format!(
+            "{base}/{}/@v/{}.info",
+            escape_module_path(name),
+            escape_module_path(&format!("v{version}"))
+        );
+        Ok(registry::fetch(&url).await?.is_some())
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        _registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String) {
+        publish_internal(path, dry_run).await
+    }
+}
+
+/// The parts of go.mod publishing needs.
+struct GoMod {
+    module: String,
+    requires: Vec<String>,
+}
+
+fn parse_go_mod(content: &str) -> Option<GoMod> {
+    let mut module = None;
+    let mut requires = Vec::new();
+    let mut in_require_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_require_block {
+            if line == ")" {
+                in_require_block = false;
+            } else if let Some(path) = line.split_whitespace().next() {
+                requires.push(path.to_string());
+            }
+        } else if let Some(path) = line.strip_prefix("module ") {
+            module = Some(path.trim().trim_matches('"').to_string());
+        } else if let Some(rest) = line.strip_prefix("require") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_require_block = true;
+            } else if let Some(path) = rest.split_whitespace().next() {
+                requires.push(path.to_string());
+            }
+        }
+    }
+    Some(GoMod {
+        module: module.filter(|module| !module.is_empty())?,
+        requires,
+    })
+}
+
+/// Gets the module path from go.mod and the version from the newest tag
+async fn get_package_info_internal(repo_path: &Path) -> Option<PackageInfo> {
+    let content = tokio::fs::read_to_string(repo_path.join("go.mod"))
+        .await
+        .ok()?;
+    let go_mod = parse_go_mod(&content)?;
+    let tag = latest_semver_tag(repo_path).await?;
+
+    Some(PackageInfo {
+        manager_name: "go".to_string(),
+        name: go_mod.module,
+        version: tag.trim_start_matches('v').to_string(),
+        dependencies: go_mod.requires,
+    })
+}
+
+async fn latest_semver_tag(repo_path: &Path) -> Option<String> {
+    let (success, stdout, _) = run_git(
+        repo_path,
+        &["describe", "--tags", "--abbrev=0", "--match", "v[0-9]*"],
+    )
+    .await
+    .ok()?;
+    let tag = stdout.trim();
+    (success && Version::parse(tag.trim_start_matches('v')).is_ok()).then(|| tag.to_string())
+}
+
+/// Module proxies spell upper-case letters as `!` plus the lower-case letter.
+fn escape_module_path(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c.is_ascii_uppercase() {
+            escaped.push('!');
+            escaped.push(c.to_ascii_lowercase());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Checks that the module path can carry `version`: v2 and later need a
+/// `/vN` suffix, and v0/v1 must not have one.
+fn validate_module_path(module: &str, version: &Version) -> Result<(), String> {
+    let suffix = module
+        .rsplit_once('/')
+        .map(|(_, last)| last)
+        .and_then(|last| last.strip_prefix('v'))
+        .and_then(|major| major.parse::<u64>().ok())
+        .filter(|major| *major >= 2);
+    match (version.major, suffix) {
+        (0 | 1, None) => Ok(()),
+        (0 | 1, Some(major)) => Err(format!(
+            "module path {module} ends in /v{major} but the version is v{version}"
+        )),
+        (major, Some(suffix)) if major == suffix => Ok(()),
+        (major, _) => Err(format!(
+            "v{version} needs the module path to end in /v{major} (go.mod has {module})"
+        )),
+    }
+}
+
+/// Checks that the module path names the repository's remote, so the proxy
+/// can fetch the tag (`github.com/org/repo` for `git@github.com:org/repo.git`).
+async fn validate_module_remote(repo_path: &Path, module: &str) -> Result<(), String> {
+    let Ok((true, url, _)) = run_git(repo_path, &["remote", "get-url", "origin"]).await else {
+        return Ok(());
+    };
+    let Some(location) = remote_location(url.trim()) else {
+        return Ok(());
+    };
+    let lowered = module.to_ascii_lowercase();
+    if lowered == location || lowered.starts_with(&format!("{location}/")) {
+        Ok(())
+    } else {
+        Err(format!(
+            "module path {module} does not match the origin remote ({location})"
+        ))
+    }
+}
+
+/// `host/path` of a remote URL, lower-cased and without `.git`.
+fn remote_location(url: &str) -> Option<String> {
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map(|(host, path)| {
+            let host = host.rsplit('@').next().unwrap_or(host);
+            let host = host.split(':').next().unwrap_or(host);
+            format!("{host}/{path}")
+        })?,
+        None => {
+            let (host, path) = url.split_once(':')?;
+            format!("{}/{path}", host.rsplit('@').next().unwrap_or(host))
+        }
+    };
+    let rest = rest.trim_end_matches('/');
+    Some(rest.trim_end_matches(".git").to_ascii_lowercase())
+}
+
+/// Publishes a Go module by pushing its version tag
+/// Returns (success, message)
+async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
+    let Some(info) = get_package_info_internal(repo_path).await else {
+        return (
+            false,
+            "no module path in go.mod or no vX.Y.Z tag to publish".to_string(),
+        );
+    };
+    let Ok(version) = Version::parse(&info.version) else {
+        return (false, format!("v{} is not a semver tag", info.version));
+    };
+    if let Err(message) = validate_module_path(&info.name, &version) {
+        return (false, message);
+    }
+    if let Err(message) = validate_module_remote(repo_path, &info.name).await {
+        return (false, message);
+    }
+    if dry_run {
+        return (true, "dry-run ok".to_string());
+    }
+
+    // The version is read from an existing tag, so it only needs pushing.
+    let (success, message) = push_tag(repo_path, &format!("v{version}")).await;
+    if success {
+        (true, "published (tag pushed)".to_string())
+    } else {
+        (false, clean_go_error(&message))
+    }
+}
+
+/// Cleans up tag push errors to be more user-friendly
+fn clean_go_error(error: &str) -> String {
+    if error.contains("Permission denied") || error.contains("Authentication failed") {
+        "not authenticated (check git push access)".to_string()
+    } else if error.contains("no remote configured") {
+        "no remote configured to push the tag to".to_string()
+    } else {
+        error
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .map_or_else(|| error.trim().to_string(), |line| line.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn go_mod_lists_module_and_requirements() {
+        let content = "module github.com/acme/widget/v2 // main module\n\ngo 1.22\n\nrequire github.com/acme/core v1.4.0\n\nrequire (\n\tgolang.org/x/text v0.14.0 // indirect\n\tgithub.com/acme/log v0.3.1\n)\n";
+        let go_mod = parse_go_mod(content).unwrap();
+        assert_eq!(go_mod.module, "github.com/acme/widget/v2");
+        assert_eq!(
+            go_mod.requires,
+            vec![
+                "github.com/acme/core",
+                "golang.org/x/text",
+                "github.com/acme/log"
+            ]
+        );
+        assert!(parse_go_mod("go 1.22\n").is_none());
+    }
+
+    #[test]
+    fn module_paths_must_match_the_major_version_and_remote() {
+        let v1 = Version::parse("1.4.0").unwrap();
+        let v2 = Version::parse("2.0.0").unwrap();
+        assert!(validate_module_path("github.com/acme/widget", &v1).is_ok());
+        assert!(validate_module_path("github.com/acme/widget/v2", &v2).is_ok());
+        assert!(validate_module_path("github.com/acme/widget", &v2).is_err());
+        assert!(validate_module_path("github.com/acme/widget/v2", &v1).is_err());
+
+        assert_eq!(
+            remote_location("git@github.com:Acme/widget.git").as_deref(),
+            Some("github.com/acme/widget")
+        );
+        assert_eq!(
+            remote_location("https://user@github.com/acme/widget/").as_deref(),
+            Some("github.com/acme/widget")
+        );
+        assert_eq!(
+            remote_location("ssh://git@github.com:22/acme/widget.git").as_deref(),
+            Some("github.com/acme/widget")
+        );
+    }
+
+    #[test]
+    fn proxy_paths_escape_upper_case_letters() {
+        assert_eq!(
+            escape_module_path("github.com/Azure/azure-sdk"),
+            "github.com/!azure/azure-sdk"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Hex (Elixir) package publishing functionality
+
+use async_trait::async_trait;
+use regex::Regex;
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::{registry, PackageInfo, PackageManager, PublishRegistry};
+use crate::core::settings::settings;
+
+const MIX_OPERATION_TIMEOUT_SECS: u64 = 600; // 10 minutes for mix (compiles and builds docs)
+
+pub struct Hex;
+
+#[async_trait]
+impl PackageManager for Hex {
+    fn name(&self) -> &str {
+        "hex"
+    }
+
+    fn icon(&self) -> &str {
+        "💧"
+    }
+
+    async fn detect(&self, path: &Path) -> bool {
+        tokio::fs::metadata(path.join("mix.exs")).await.is_ok()
+    }
+
+    async fn get_info(&self, path: &Path) -> Option<PackageInfo> {
+        let content = tokio::fs::read_to_string(path.join("mix.exs")).await.ok()?;
+        parse_mix_exs(&content)
+    }
+
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        _registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool> {
+        let base = &settings().hex_api_url.value;
+        let url = format!("{base}/packages/{name}/releases/{version}");
+        Ok(registry::fetch(&url).await?.is_some())
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        _registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String) {
+        publish_internal(path, dry_run).await
+    }
+}
+
+/// Reads the project's `app:` (or `package: [name: ...]`) and `version:`
+/// (literal or `@version` attribute) from mix.exs, plus dependencies that
+/// are not limited to `:dev` or `:test`.
+fn parse_mix_exs(content: &str) -> Option<PackageInfo> {
+    let app = capture(content, r"\bapp:\s*:([A-Za-z0-9_]+)")?;
+    let name = capture(content, r#"package:\s*\[[^\]]*?\bname:\s*"([^"]+)""#).unwrap_or(app);
+    let version = match capture(content, r#"\bversion:\s*"([^"]+)""#) {
+        Some(version) => version,
+        None => capture(content, r#"@version\s+"([^"]+)""#)?,
+    };
+
+    let dependencies = Regex::new(r"\{\s*:([A-Za-z0-9_]+)\s*,([^}]*)\}")
+        .map(|pattern| {
+            let deps = content.split_once("defp deps").map_or("", |(_, rest)| rest);
+            pattern
+                .captures_iter(deps)
+                .filter(|captures| {
+                    let options = &captures[2];
+                    !options.contains("only:") || options.contains(":prod")
+                })
+                .map(|captures| captures[1].to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(PackageInfo {
+        manager_name: "hex".to_string(),
+        name,
+        version,
+        dependencies,
+    })
+}
+
+fn capture(content: &str, pattern: &str) -> Option<String> {
+    Regex::new(pattern)
+        .ok()?
+        .captures(content)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Publishes a Hex package with `mix hex.publish`
+/// Returns (success, message)
+async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
+    let mut args = vec!["hex.publish", "--yes"];
+    if dry_run {
+        args.push("--dry-run");
+    }
+
+    let timeout_duration = Duration::from_secs(MIX_OPERATION_TIMEOUT_SECS);
+    let result = tokio::time::timeout(
+        timeout_duration,
+        Command::new("mix")
+            .args(&args)
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(output)) => {
+            if output.status.success() {
+                if dry_run {
+                    (true, "dry-run ok".to_string())
+                } else {
+                    (true, "published".to_string())
+                }
+            } else {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let error_message = clean_hex_error(&format!("{stdout}{stderr}"));
+                if error_message == "already published" {
+                    (true, error_message)
+                } else {
+                    (false, error_message)
+                }
+            }
+        }
+        Ok(Err(e)) => (false, format!("mix command failed: {e}")),
+        Err(_) => (false, "mix operation timed out".to_string()),
+    }
+}
+
+/// Cleans up Hex error messages to be more user-friendly
+fn clean_hex_error(error: &str) -> String {
+    if error.contains("must include the --replace flag") || error.contains("already exists") {
+        "already published".to_string()
+    } else if error.contains("No authenticated user found") || error.contains("mix hex.user auth") {
+        "not authenticated (run: mix hex.user auth)".to_string()
+    } else if error.contains("403") || error.contains("not authorized") {
+        "permission denied (check package ownership)".to_string()
+    } else {
+        error
+            .lines()
+            .find(|line| line.contains("**") || line.contains("error"))
+            .or_else(|| error.lines().find(|line| !line.trim().is_empty()))
+            .map_or_else(
+                || error.trim().to_string(),
+                |line| line.replace("**", "").trim().to_string(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_exs_project_and_runtime_deps_are_read() {
+        let mix = r#"defmodule Widget.MixProject do
+  use Mix.Project
+
+  @version "0.6.2"
+
+  def project do
+    [app: :widget, version: @version, deps: deps()]
+  end
+
+  defp deps do
+    [
+      {:core_lib, "~> 1.2"},
+      {:jason, "~> 1.4", optional: true},
+      {:ex_doc, "~> 0.31", only: :dev, runtime: false},
+      {:telemetry, "~> 1.0", only: [:dev, :prod]}
+    ]
+  end
+end
+"#;
+        let info = parse_mix_exs(mix).unwrap();
+        assert_eq!(info.name, "widget");
+        assert_eq!(info.version, "0.6.2");
+        assert_eq!(info.dependencies, vec!["core_lib", "jason", "telemetry"]);
+    }
+
+    #[test]
+    fn hex_errors_are_summarized() {
+        assert_eq!(
+            clean_hex_error("** (Mix) Publishing failed: inserted_at must include the --replace flag to update an existing package version"),
+            "already published"
+        );
+        assert_eq!(
+            clean_hex_error("No authenticated user found. Run `mix hex.user auth`"),
+            "not authenticated (run: mix hex.user auth)"
+        );
+    }
+}
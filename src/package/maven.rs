@@ -0,0 +1,302 @@
+//! Maven artifact publishing functionality
+//!
+//! `pom.xml` is read as text: the project's own coordinates are the
+//! top-level `groupId`/`artifactId`/`version` outside `<parent>`,
+//! `<dependencies>` and the other nested sections.
+
+use async_trait::async_trait;
+use regex::Regex;
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::{registry, PackageInfo, PackageManager, PublishRegistry};
+use crate::core::settings::settings;
+
+const MAVEN_OPERATION_TIMEOUT_SECS: u64 = 900; // 15 minutes for mvn deploy (builds and signs)
+
+/// Sections whose `groupId`/`artifactId`/`version` do not describe the project.
+const NESTED_SECTIONS: &[&str] = &[
+    "parent",
+    "dependencies",
+    "dependencyManagement",
+    "build",
+    "reporting",
+    "profiles",
+    "distributionManagement",
+    "repositories",
+    "pluginRepositories",
+    "modules",
+];
+
+pub struct Maven;
+
+#[async_trait]
+impl PackageManager for Maven {
+    fn name(&self) -> &str {
+        "maven"
+    }
+
+    fn icon(&self) -> &str {
+        "☕"
+    }
+
+    async fn detect(&self, path: &Path) -> bool {
+        tokio::fs::metadata(path.join("pom.xml")).await.is_ok()
+    }
+
+    async fn get_info(&self, path: &Path) -> Option<PackageInfo> {
+        let content = tokio::fs::read_to_string(path.join("pom.xml")).await.ok()?;
+        parse_pom(&content)
+    }
+
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        _registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool> {
+        let Some((group, artifact)) = name.split_once(':') else {
+            anyhow::bail!("'{name}' is not a groupId:artifactId coordinate");
+        };
+        let base = &settings().maven_repository_url.value;
+        let url = format!(
+            "{base}/{}/{artifact}/{version}/{artifact}-{version}.pom",
+            group.replace('.', "/")
+        );
+        Ok(registry::fetch(&url).await?.is_some())
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        _registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String) {
+        publish_internal(path, dry_run).await
+    }
+}
+
+/// Reads the project coordinates (`groupId:artifactId` as the name) and its
+/// non-test dependencies. `${property}` versions resolve from `<properties>`.
+fn parse_pom(content: &str) -> Option<PackageInfo> {
+    let content = strip_comments(content);
+    let project = content
+        .split_once("<project")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map_or(content.as_str(), |(_, body)| body);
+    let top_level = strip_sections(project, NESTED_SECTIONS);
+    let parent = section(project, "parent").unwrap_or_default();
+
+    let artifact = element(&top_level, "artifactId")?;
+    let group = element(&top_level, "groupId").or_else(|| element(parent, "groupId"))?;
+    let version = element(&top_level, "version").or_else(|| element(parent, "version"))?;
+    let properties = section(project, "properties").unwrap_or_default();
+    let version = resolve_property(&version, properties);
+
+    let dependencies = section(project, "dependencies")
+        .map(|dependencies| {
+            sections(dependencies, "dependency")
+                .into_iter()
+                .filter(|dependency| {
+                    !matches!(
+                        element(dependency, "scope").as_deref(),
+                        Some("test" | "provided")
+                    )
+                })
+                .filter_map(|dependency| {
+                    Some(format!(
+                        "{}:{}",
+                        element(dependency, "groupId")?,
+                        element(dependency, "artifactId")?
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(PackageInfo {
+        manager_name: "maven".to_string(),
+        name: format!("{group}:{artifact}"),
+        version,
+        dependencies,
+    })
+}
+
+fn strip_comments(content: &str) -> String {
+    Regex::new(r"(?s)<!--.*?-->").map_or_else(
+        |_| content.to_string(),
+        |pattern| pattern.replace_all(content, "").into_owned(),
+    )
+}
+
+/// Inner text of the first `<tag>...</tag>` in `content`.
+fn section<'a>(content: &'a str, tag: &str) -> Option<&'a str> {
+    let start = content.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = content[start..].find(&format!("</{tag}>"))? + start;
+    Some(&content[start..end])
+}
+
+/// Inner text of every `<tag>...</tag>` in `content`.
+fn sections<'a>(content: &'a str, tag: &str) -> Vec<&'a str> {
+    let (open, close) = (format!("<{tag}>"), format!("</{tag}>"));
+    let mut found = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find(&open).map(|index| index + open.len()) {
+        let Some(length) = rest[start..].find(&close) else {
+            break;
+        };
+        found.push(&rest[start..start + length]);
+        rest = &rest[start + length + close.len()..];
+    }
+    found
+}
+
+/// Trimmed text of the first `<tag>` element.
+fn element(content: &str, tag: &str) -> Option<String> {
+    section(content, tag)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Removes the named sections (and everything in them) from `content`.
+fn strip_sections(content: &str, tags: &[&str]) -> String {
+    let mut stripped = content.to_string();
+    for tag in tags {
+        while let Some(start) = stripped.find(&format!("<{tag}>")) {
+            let close = format!("</{tag}>");
+            let Some(end) = stripped[start..].find(&close) else {
+                break;
+            };
+            stripped.replace_range(start..start + end + close.len(), "");
+        }
+    }
+    stripped
+}
+
+fn resolve_property(value: &str, properties: &str) -> String {
+    value
+        .strip_prefix("${")
+        .and_then(|rest| rest.strip_suffix('}'))
+        .and_then(|name| element(properties, name))
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Deploys the artifact with `mvn deploy` (or the Maven wrapper when present)
+/// Returns (success, message)
+async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
+    let program = if repo_path.join("mvnw").is_file() {
+        "./mvnw"
+    } else {
+        "mvn"
+    };
+    let goal = if dry_run { "verify" } else { "deploy" };
+
+    let timeout_duration = Duration::from_secs(MAVEN_OPERATION_TIMEOUT_SECS);
+    let result = tokio::time::timeout(
+        timeout_duration,
+        Command::new(program)
+            .args(["--batch-mode", "--no-transfer-progress", goal])
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(output)) => {
+            if output.status.success() {
+                if dry_run {
+                    (true, "dry-run ok".to_string())
+                } else {
+                    (true, "published".to_string())
+                }
+            } else {
+                // Maven reports failures on stdout.
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let combined = format!("{stdout}{stderr}");
+                let error_message = clean_maven_error(&combined);
+                if error_message == "already published" {
+                    (true, error_message)
+                } else {
+                    (false, error_message)
+                }
+            }
+        }
+        Ok(Err(e)) => (false, format!("mvn command failed: {e}")),
+        Err(_) => (false, "maven operation timed out".to_string()),
+    }
+}
+
+/// Cleans up Maven error messages to be more user-friendly
+fn clean_maven_error(error: &str) -> String {
+    if error.contains("409 Conflict") || error.contains("status code: 409") {
+        "already published".to_string()
+    } else if error.contains("401 Unauthorized") || error.contains("status code: 401") {
+        "not authenticated (configure ~/.m2/settings.xml)".to_string()
+    } else if error.contains("403 Forbidden") || error.contains("status code: 403") {
+        "permission denied (check repository permissions)".to_string()
+    } else {
+        // Return the first [ERROR] line, cleaned up
+        error
+            .lines()
+            .find(|line| line.starts_with("[ERROR]") && line.len() > "[ERROR] ".len())
+            .map_or_else(
+                || error.trim().to_string(),
+                |line| line.trim_start_matches("[ERROR]").trim().to_string(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_coordinates_skip_parent_and_dependency_sections() {
+        let pom = r#"<?xml version="1.0"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <parent>
+    <groupId>com.acme</groupId>
+    <artifactId>acme-parent</artifactId>
+    <version>7</version>
+  </parent>
+  <!-- <artifactId>commented-out</artifactId> -->
+  <artifactId>widget</artifactId>
+  <version>${revision}</version>
+  <properties>
+    <revision>1.4.0</revision>
+  </properties>
+  <dependencies>
+    <dependency>
+      <groupId>com.acme</groupId>
+      <artifactId>core-lib</artifactId>
+      <version>2.1.0</version>
+    </dependency>
+    <dependency>
+      <groupId>org.junit.jupiter</groupId>
+      <artifactId>junit-jupiter</artifactId>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+        let info = parse_pom(pom).unwrap();
+        assert_eq!(info.name, "com.acme:widget");
+        assert_eq!(info.version, "1.4.0");
+        assert_eq!(info.dependencies, vec!["com.acme:core-lib"]);
+    }
+
+    #[test]
+    fn maven_errors_are_summarized() {
+        assert_eq!(
+            clean_maven_error("[INFO] Uploading\n[ERROR] Failed to deploy: status code: 409, reason phrase: Conflict"),
+            "already published"
+        );
+        assert_eq!(
+            clean_maven_error("[INFO] Scanning\n[ERROR] \n[ERROR] Could not resolve plugin"),
+            "Could not resolve plugin"
+        );
+    }
+}
@@ -1,18 +1,29 @@
 //! Package management and publishing functionality
 //!
 //! This module provides an extensible system for detecting and publishing packages
-//! across different ecosystems (npm, Cargo, PyPI).
+//! across different ecosystems (npm, Cargo, PyPI, Go modules, RubyGems, Maven,
+//! Hex, NuGet).
 //!
 //! This is command plumbing for `repos publish`. It is public for integration
 //! tests and advanced automation, but the CLI remains the primary supported
 //! interface for publishing.
 
 pub mod cargo;
+pub mod changelog;
+pub mod go;
+pub mod hex;
+pub mod maven;
 pub mod npm;
+pub mod nuget;
 pub mod pypi;
+mod registry;
+pub mod rubygems;
+pub mod tag;
+pub mod version;
 
 use async_trait::async_trait;
-use std::path::Path;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Trait for package managers to implement.
@@ -30,13 +41,89 @@ pub trait PackageManager: Send + Sync {
     /// Detects if this package manager manages the repository at the given path.
     async fn detect(&self, path: &Path) -> bool;
 
-    /// Gets package information (name, version) from the repository.
+    /// Gets package information (name, version, dependencies) from the repository.
     async fn get_info(&self, path: &Path) -> Option<PackageInfo>;
 
-    /// Publishes the package to its respective registry.
+    /// Lists the packages to publish from the repository with their
+    /// directories. Most repositories hold one package; a Cargo workspace
+    /// lists each publishable member.
+    async fn list_packages(&self, path: &Path) -> Vec<(PathBuf, PackageInfo)> {
+        self.get_info(path)
+            .await
+            .map(|info| (path.to_path_buf(), info))
+            .into_iter()
+            .collect()
+    }
+
+    /// Checks whether the registry already serves `version` of the package
+    /// `name`. `registry` is the private registry the package publishes to,
+    /// or `None` for the ecosystem's public one.
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool>;
+
+    /// Changes the package's own version from `old` to `new` in its manifest,
+    /// keeping the rest of the file intact. Returns the file written.
+    async fn set_version(&self, path: &Path, old: &str, new: &str) -> anyhow::Result<PathBuf> {
+        let _ = (path, old, new);
+        anyhow::bail!(
+            "version bumps are not supported for {} packages",
+            self.name()
+        )
+    }
+
+    /// Moves requirements on the package `dependency` that name version `old`
+    /// to `new` (`^1.2.0` becomes `^1.3.0`). Returns the file written, or
+    /// `None` when the manifest has no such requirement.
+    async fn update_dependency(
+        &self,
+        path: &Path,
+        dependency: &str,
+        old: &str,
+        new: &str,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        let _ = (path, dependency, old, new);
+        Ok(None)
+    }
+
+    /// True when publishing pushes the release tag itself (Go modules), so
+    /// `--tag` has nothing to add.
+    fn publishes_by_tag(&self) -> bool {
+        false
+    }
+
+    /// True when packing resolves dependencies against the registry (Cargo
+    /// verifies the packed crate by building it), so the pre-flight check of
+    /// a package waits until the fleet packages it depends on are published.
+    fn packs_against_registry(&self) -> bool {
+        false
+    }
+
+    /// Builds and packs the package the way `publish` would, without
+    /// uploading, so a broken package is caught before anything is published.
+    /// Returns `None` for ecosystems without a pre-flight check.
+    async fn preflight(
+        &self,
+        path: &Path,
+        registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<Option<PackSummary>> {
+        let _ = (path, registry);
+        Ok(None)
+    }
+
+    /// Publishes the package to its respective registry, or to `registry`
+    /// when it overrides this ecosystem.
     ///
     /// Returns `(success, message)`.
-    async fn publish(&self, path: &Path, dry_run: bool) -> (bool, String);
+    async fn publish(
+        &self,
+        path: &Path,
+        registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String);
 }
 
 /// Information about a detected package.
@@ -49,6 +136,102 @@ pub struct PackageInfo {
     pub name: String,
     /// The version of the package.
     pub version: String,
+    /// Names of the packages this one needs at build or run time, as they
+    /// appear in its registry (dev-only dependencies are left out).
+    pub dependencies: Vec<String>,
+}
+
+/// What a pre-flight check packed: the files the registry would receive
+/// and their size as the package manager reports it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PackSummary {
+    pub files: Vec<String>,
+    pub size: Option<String>,
+}
+
+impl PackSummary {
+    /// One-line description such as `6 files, 1.2KiB (930B compressed)`.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let count = self.files.len();
+        let files = if count == 1 { "file" } else { "files" };
+        match &self.size {
+            Some(size) => format!("{count} {files}, {size}"),
+            None => format!("{count} {files}"),
+        }
+    }
+}
+
+/// Formats a byte count the way Cargo reports package sizes (`930B`, `1.2KiB`).
+#[must_use]
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{size:.1}{}", UNITS[unit])
+    }
+}
+
+/// A private registry from the workspace manifest's `[registries]` table.
+///
+/// Each key points one ecosystem somewhere other than its public registry;
+/// ecosystems without a key keep publishing to the public one.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PublishRegistry {
+    /// The table name, as shown in the publish plan.
+    #[serde(skip)]
+    pub name: String,
+    /// Repositories in these groups publish here unless they name a registry.
+    #[serde(default)]
+    pub groups: Vec<String>,
+    /// npm registry URL (`npm publish --registry`).
+    #[serde(default)]
+    pub npm: Option<String>,
+    /// Cargo registry name from `.cargo/config.toml` (`cargo publish --registry`).
+    #[serde(default)]
+    pub cargo: Option<String>,
+    /// Sparse index URL of the Cargo registry, for the already-published check.
+    #[serde(default)]
+    pub cargo_index: Option<String>,
+    /// Upload URL for Python packages (`twine upload --repository-url`).
+    #[serde(default)]
+    pub python: Option<String>,
+    /// Base URL serving the PyPI JSON API, for the already-published check.
+    #[serde(default)]
+    pub python_index: Option<String>,
+}
+
+impl PublishRegistry {
+    /// Where packages of the `manager` ecosystem go, when this registry
+    /// overrides it.
+    #[must_use]
+    pub fn destination(&self, manager: &str) -> Option<&str> {
+        match manager {
+            "npm" => self.npm.as_deref(),
+            "cargo" => self.cargo.as_deref(),
+            "python" => self.python.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn urls(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("npm", &self.npm),
+            ("cargo_index", &self.cargo_index),
+            ("python", &self.python),
+            ("python_index", &self.python_index),
+        ]
+        .into_iter()
+        .filter_map(|(key, url)| Some((key, url.as_deref()?)))
+    }
 }
 
 /// Returns a list of all supported package managers
@@ -57,12 +240,17 @@ pub fn get_all_managers() -> Vec<Arc<dyn PackageManager>> {
         Arc::new(npm::Npm),
         Arc::new(cargo::Cargo),
         Arc::new(pypi::PyPI),
+        Arc::new(go::GoModule),
+        Arc::new(rubygems::RubyGems),
+        Arc::new(maven::Maven),
+        Arc::new(hex::Hex),
+        Arc::new(nuget::NuGet),
     ]
 }
 
 /// Helper to detect package manager for a path (returns the first match)
 pub async fn detect_manager(path: &Path) -> Option<Arc<dyn PackageManager>> {
-    // Check in order of priority: Npm, Cargo, PyPI
+    // Check in order of priority: Npm, Cargo, PyPI, then the other ecosystems
     // (Npm first because it's common to have package.json alongside others)
     let managers = get_all_managers();
 
@@ -149,6 +337,18 @@ mod tests {
         assert_eq!(manager.unwrap().name(), "python");
     }
 
+    #[test]
+    fn pack_summaries_describe_file_count_and_size() {
+        assert_eq!(format_size(930), "930B");
+        assert_eq!(format_size(1229), "1.2KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0MiB");
+        let summary = PackSummary {
+            files: vec!["Cargo.toml".to_string(), "src/lib.rs".to_string()],
+            size: Some(format_size(2048)),
+        };
+        assert_eq!(summary.describe(), "2 files, 2.0KiB");
+    }
+
     #[tokio::test]
     async fn test_detect_none() {
         use tempfile::TempDir;
@@ -2,11 +2,15 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::process::Command;
 
-use super::{PackageInfo, PackageManager};
+use super::version::replace_version_token;
+use super::{format_size, registry, PackSummary, PackageInfo, PackageManager, PublishRegistry};
+use crate::core::settings::settings;
 
 const NPM_OPERATION_TIMEOUT_SECS: u64 = 300; // 5 minutes for npm operations
 
@@ -30,8 +34,68 @@ impl PackageManager for Npm {
         get_package_info_internal(path).await
     }
 
-    async fn publish(&self, path: &Path, dry_run: bool) -> (bool, String) {
-        publish_internal(path, dry_run).await
+    async fn set_version(&self, path: &Path, old: &str, new: &str) -> anyhow::Result<PathBuf> {
+        let manifest_path = path.join("package.json");
+        let content = tokio::fs::read_to_string(&manifest_path).await?;
+        let Some(edited) = set_version_in_package_json(&content, old, new) else {
+            anyhow::bail!("no \"version\": \"{old}\" in {}", manifest_path.display());
+        };
+        tokio::fs::write(&manifest_path, edited).await?;
+        Ok(manifest_path)
+    }
+
+    async fn update_dependency(
+        &self,
+        path: &Path,
+        dependency: &str,
+        old: &str,
+        new: &str,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        let manifest_path = path.join("package.json");
+        let Ok(content) = tokio::fs::read_to_string(&manifest_path).await else {
+            return Ok(None);
+        };
+        match update_dependency_in_package_json(&content, dependency, old, new) {
+            Some(edited) => {
+                tokio::fs::write(&manifest_path, edited).await?;
+                Ok(Some(manifest_path))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool> {
+        // Scoped names keep their `@` but escape the slash: `@scope%2fname`.
+        let base = registry
+            .and_then(|registry| registry.npm.as_deref())
+            .unwrap_or(&settings().npm_registry_url.value);
+        let url = format!("{base}/{}", name.replace('/', "%2f"));
+        Ok(registry::fetch(&url)
+            .await?
+            .is_some_and(|document| packument_contains_version(&document, version)))
+    }
+
+    async fn preflight(
+        &self,
+        path: &Path,
+        _registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<Option<PackSummary>> {
+        preflight_internal(path).await.map(Some)
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String) {
+        let registry_url = registry.and_then(|registry| registry.npm.as_deref());
+        publish_internal(path, registry_url, dry_run).await
     }
 }
 
@@ -40,6 +104,25 @@ impl PackageManager for Npm {
 struct PackageJson {
     name: String,
     version: String,
+    #[serde(default)]
+    dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    main: Option<String>,
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+/// One entry of `npm pack --json` output (partial).
+#[derive(Deserialize)]
+struct PackReport {
+    size: Option<u64>,
+    #[serde(default)]
+    files: Vec<PackedFile>,
+}
+
+#[derive(Deserialize)]
+struct PackedFile {
+    path: String,
 }
 
 /// Gets package information from package.json
@@ -53,17 +136,189 @@ async fn get_package_info_internal(repo_path: &Path) -> Option<PackageInfo> {
         manager_name: "npm".to_string(),
         name: package.name,
         version: package.version,
+        dependencies: package.dependencies.into_keys().collect(),
     })
 }
 
-/// Publishes an npm package
-/// Returns (success, message)
-async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
-    let mut args = vec!["publish"];
+/// Dependency maps `update_dependency_in_package_json` edits.
+const DEPENDENCY_MAPS: &[&str] = &[
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// Rewrites the package's own top-level `"version": "old"` entry as text so
+/// the file keeps its indentation and key order.
+fn set_version_in_package_json(content: &str, old: &str, new: &str) -> Option<String> {
+    let root: serde_json::Value = serde_json::from_str(content).ok()?;
+    if root.get("version").and_then(serde_json::Value::as_str) != Some(old) {
+        return None;
+    }
+    let span = object_members(content, root_object(content)?)?
+        .into_iter()
+        .find_map(|(key, span)| (key == "version").then_some(span))?;
+    Some(format!(
+        "{}\"{new}\"{}",
+        &content[..span.start],
+        &content[span.end..]
+    ))
+}
+
+/// Rewrites `"dependency": "<range>"` entries in the dependency maps whose
+/// range names `old`.
+fn update_dependency_in_package_json(
+    content: &str,
+    dependency: &str,
+    old: &str,
+    new: &str,
+) -> Option<String> {
+    let mut edits = Vec::new();
+    for (key, map) in object_members(content, root_object(content)?)? {
+        if !DEPENDENCY_MAPS.contains(&key.as_str()) || !content[map.clone()].starts_with('{') {
+            continue;
+        }
+        for (name, span) in object_members(content, map.start)? {
+            let Ok(range) = serde_json::from_str::<String>(&content[span.clone()]) else {
+                continue;
+            };
+            if name != dependency || range.contains(['"', '\\']) {
+                continue;
+            }
+            if let Some(range) = replace_version_token(&range, old, new) {
+                edits.push((span, range));
+            }
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    let mut edited = content.to_string();
+    for (span, range) in edits.into_iter().rev() {
+        edited.replace_range(span, &format!("\"{range}\""));
+    }
+    Some(edited)
+}
+
+/// Offset of the `{` opening the document's root object.
+fn root_object(content: &str) -> Option<usize> {
+    let offset = content.len() - content.trim_start().len();
+    content[offset..].starts_with('{').then_some(offset)
+}
+
+/// Keys and value spans of the members of the JSON object opening at `open`,
+/// without descending into nested values.
+fn object_members(content: &str, open: usize) -> Option<Vec<(String, Range<usize>)>> {
+    let bytes = content.as_bytes();
+    let mut members = Vec::new();
+    let mut i = skip_whitespace(bytes, open + 1);
+    if bytes.get(i) == Some(&b'}') {
+        return Some(members);
+    }
+    loop {
+        let key_end = skip_string(bytes, i)?;
+        let key = serde_json::from_str::<String>(&content[i..key_end]).ok()?;
+        i = skip_whitespace(bytes, key_end);
+        if bytes.get(i) != Some(&b':') {
+            return None;
+        }
+        let value_start = skip_whitespace(bytes, i + 1);
+        let value_end = skip_value(bytes, value_start)?;
+        members.push((key, value_start..value_end));
+        i = skip_whitespace(bytes, value_end);
+        match bytes.get(i) {
+            Some(b',') => i = skip_whitespace(bytes, i + 1),
+            Some(b'}') => return Some(members),
+            _ => return None,
+        }
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+        i += 1;
+    }
+    i
+}
+
+/// End of the string literal starting at `i`, past its closing quote.
+fn skip_string(bytes: &[u8], mut i: usize) -> Option<usize> {
+    if bytes.get(i) != Some(&b'"') {
+        return None;
+    }
+    i += 1;
+    loop {
+        match bytes.get(i)? {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+}
+
+/// End of the JSON value starting at `i`.
+fn skip_value(bytes: &[u8], mut i: usize) -> Option<usize> {
+    match bytes.get(i)? {
+        b'"' => skip_string(bytes, i),
+        b'{' | b'[' => {
+            let mut depth = 0usize;
+            loop {
+                match bytes.get(i)? {
+                    b'"' => {
+                        i = skip_string(bytes, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+        }
+        _ => {
+            while bytes.get(i).is_some_and(|byte| {
+                !matches!(byte, b',' | b'}' | b']') && !byte.is_ascii_whitespace()
+            }) {
+                i += 1;
+            }
+            Some(i)
+        }
+    }
+}
+
+/// A registry package document lists every published version under `versions`.
+fn packument_contains_version(document: &str, version: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(document)
+        .ok()
+        .and_then(|packument| packument.get("versions").cloned())
+        .is_some_and(|versions| versions.get(version).is_some())
+}
 
+/// Arguments for `npm publish`, pointed at `registry_url` when given.
+fn publish_args(registry_url: Option<&str>, dry_run: bool) -> Vec<&str> {
+    let mut args = vec!["publish"];
+    if let Some(url) = registry_url {
+        args.extend(["--registry", url]);
+    }
     if dry_run {
         args.push("--dry-run");
     }
+    args
+}
+
+/// Publishes an npm package
+/// Returns (success, message)
+async fn publish_internal(
+    repo_path: &Path,
+    registry_url: Option<&str>,
+    dry_run: bool,
+) -> (bool, String) {
+    let args = publish_args(registry_url, dry_run);
 
     let timeout_duration = Duration::from_secs(NPM_OPERATION_TIMEOUT_SECS);
 
@@ -103,6 +358,91 @@ async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
     }
 }
 
+/// Runs `npm pack --dry-run` (including `prepack` and `prepare` scripts)
+/// and checks that the packed files include `main` and every `files` entry.
+async fn preflight_internal(repo_path: &Path) -> anyhow::Result<PackSummary> {
+    let manifest = tokio::fs::read_to_string(repo_path.join("package.json")).await?;
+    let manifest: PackageJson = serde_json::from_str(&manifest)?;
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(NPM_OPERATION_TIMEOUT_SECS),
+        Command::new("npm")
+            .args(["pack", "--dry-run", "--json"])
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("npm pack timed out"))?
+    .map_err(|e| anyhow::anyhow!("npm command failed: {e}"))?;
+    if !output.status.success() {
+        anyhow::bail!(clean_npm_error(&String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let report = parse_pack_report(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| anyhow::anyhow!("could not read the npm pack --json output"))?;
+    let files: Vec<String> = report.files.into_iter().map(|file| file.path).collect();
+    check_packed_files(&manifest, &files)?;
+    Ok(PackSummary {
+        files,
+        size: report.size.map(format_size),
+    })
+}
+
+/// Reads the pack report, skipping script output printed before the JSON.
+fn parse_pack_report(stdout: &str) -> Option<PackReport> {
+    let start = stdout
+        .lines()
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len() + 1;
+            Some((start, line))
+        })
+        .find(|(_, line)| line.starts_with('['))
+        .map(|(start, _)| start)?;
+    let reports: Vec<PackReport> = serde_json::from_str(&stdout[start..]).ok()?;
+    reports.into_iter().next()
+}
+
+/// Fails when `main` or a `files` entry of the manifest is missing from the
+/// packed `files`, such as a `dist/` that was never built.
+fn check_packed_files(manifest: &PackageJson, files: &[String]) -> anyhow::Result<()> {
+    let normalize = |path: &str| {
+        path.trim_start_matches("./")
+            .trim_end_matches('/')
+            .to_string()
+    };
+    let mut missing = Vec::new();
+    if let Some(main) = &manifest.main {
+        let main = normalize(main);
+        if !files.contains(&main) {
+            missing.push(format!("main {main}"));
+        }
+    }
+    for entry in manifest
+        .files
+        .iter()
+        .filter(|entry| !entry.starts_with('!'))
+    {
+        let entry = normalize(entry);
+        let matched = match globset::Glob::new(&entry) {
+            Ok(glob) if entry.contains(['*', '?', '[', '{']) => {
+                let matcher = glob.compile_matcher();
+                files.iter().any(|file| matcher.is_match(file))
+            }
+            _ => files
+                .iter()
+                .any(|file| *file == entry || file.starts_with(&format!("{entry}/"))),
+        };
+        if !matched {
+            missing.push(format!("files entry {entry}"));
+        }
+    }
+    if !missing.is_empty() {
+        anyhow::bail!("not in the package: {}", missing.join(", "));
+    }
+    Ok(())
+}
+
 /// Cleans up npm error messages to be more user-friendly
 fn clean_npm_error(error: &str) -> String {
     // Extract the most relevant error message
@@ -125,3 +465,101 @@ fn clean_npm_error(error: &str) -> String {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_json_edits_touch_only_matching_versions() {
+        let content = "{\n  \"name\": \"app\",\n  \"version\": \"1.2.0\",\n  \"dependencies\": {\n    \"core-lib\": \"^1.2.0\",\n    \"left-pad\": \"1.2.0\"\n  },\n  \"devDependencies\": { \"core-lib\": \"workspace:*\" }\n}\n";
+
+        let bumped = set_version_in_package_json(content, "1.2.0", "1.3.0").unwrap();
+        assert!(bumped.contains("\"version\": \"1.3.0\""));
+        assert!(bumped.contains("\"left-pad\": \"1.2.0\""));
+        assert!(set_version_in_package_json(content, "9.0.0", "9.0.1").is_none());
+
+        let updated =
+            update_dependency_in_package_json(content, "core-lib", "1.2.0", "1.3.0").unwrap();
+        assert!(updated.contains("\"core-lib\": \"^1.3.0\""));
+        assert!(updated.contains("\"version\": \"1.2.0\""));
+        assert!(updated.contains("\"core-lib\": \"workspace:*\""));
+    }
+
+    #[test]
+    fn package_json_edits_skip_nested_and_non_dependency_keys() {
+        let content = "{\n  \"name\": \"app\",\n  \"publishConfig\": { \"version\": \"1.2.0\" },\n  \"version\": \"1.2.0\",\n  \"scripts\": { \"core-lib\": \"1.2.0\" },\n  \"overrides\": { \"core-lib\": \"1.2.0\" },\n  \"peerDependencies\": { \"core-lib\": \"~1.2.0\" }\n}\n";
+
+        let bumped = set_version_in_package_json(content, "1.2.0", "1.3.0").unwrap();
+        assert!(bumped.contains("\"publishConfig\": { \"version\": \"1.2.0\" }"));
+        assert!(bumped.contains("\n  \"version\": \"1.3.0\",\n"));
+
+        let updated =
+            update_dependency_in_package_json(content, "core-lib", "1.2.0", "1.3.0").unwrap();
+        assert!(updated.contains("\"scripts\": { \"core-lib\": \"1.2.0\" }"));
+        assert!(updated.contains("\"overrides\": { \"core-lib\": \"1.2.0\" }"));
+        assert!(updated.contains("\"peerDependencies\": { \"core-lib\": \"~1.3.0\" }"));
+        assert_eq!(
+            update_dependency_in_package_json(
+                "{\"scripts\": {\"core-lib\": \"1.2.0\"}}",
+                "core-lib",
+                "1.2.0",
+                "1.3.0"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn packument_lookup_matches_published_versions() {
+        let document = r#"{"name":"core-lib","versions":{"1.1.0":{},"1.2.0":{}}}"#;
+        assert!(packument_contains_version(document, "1.2.0"));
+        assert!(!packument_contains_version(document, "1.3.0"));
+        assert!(!packument_contains_version("not json", "1.2.0"));
+    }
+
+    #[test]
+    fn pack_check_requires_main_and_files_entries() {
+        let manifest: PackageJson = serde_json::from_str(
+            r#"{"name": "ui", "version": "1.0.0", "main": "./dist/index.js", "files": ["dist/", "types/*.d.ts", "!dist/test"]}"#,
+        )
+        .unwrap();
+        let packed = |files: &[&str]| {
+            files
+                .iter()
+                .map(|file| file.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(check_packed_files(
+            &manifest,
+            &packed(&["package.json", "dist/index.js", "types/index.d.ts"])
+        )
+        .is_ok());
+        let error = check_packed_files(&manifest, &packed(&["package.json", "README.md"]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "not in the package: main dist/index.js, files entry dist, files entry types/*.d.ts"
+        );
+    }
+
+    #[test]
+    fn pack_report_is_read_after_script_output() {
+        let stdout = "\n> ui@1.0.0 prepack\n> tsc\n\n[\n  {\"size\": 930, \"files\": [{\"path\": \"package.json\"}, {\"path\": \"dist/index.js\"}]}\n]\n";
+        let report = parse_pack_report(stdout).unwrap();
+        assert_eq!(report.size, Some(930));
+        assert_eq!(report.files.len(), 2);
+        assert!(parse_pack_report("npm ERR! missing script").is_none());
+    }
+
+    #[test]
+    fn publish_args_point_at_a_private_registry() {
+        assert_eq!(publish_args(None, true), vec!["publish", "--dry-run"]);
+        assert_eq!(
+            publish_args(Some("https://npm.internal.example"), false),
+            vec!["publish", "--registry", "https://npm.internal.example"]
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! NuGet package publishing functionality
+
+use async_trait::async_trait;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::{registry, PackageInfo, PackageManager, PublishRegistry};
+use crate::core::settings::settings;
+
+const DOTNET_OPERATION_TIMEOUT_SECS: u64 = 600; // 10 minutes for dotnet pack and push
+const NUGET_PUSH_SOURCE: &str = "https://api.nuget.org/v3/index.json";
+/// Version `dotnet pack` uses when the project file sets none.
+const DEFAULT_PACKAGE_VERSION: &str = "1.0.0";
+
+pub struct NuGet;
+
+#[async_trait]
+impl PackageManager for NuGet {
+    fn name(&self) -> &str {
+        "nuget"
+    }
+
+    fn icon(&self) -> &str {
+        "🔷"
+    }
+
+    async fn detect(&self, path: &Path) -> bool {
+        find_project(path).await.is_some()
+    }
+
+    async fn get_info(&self, path: &Path) -> Option<PackageInfo> {
+        let project = find_project(path).await?;
+        let content = tokio::fs::read_to_string(&project).await.ok()?;
+        let stem = project.file_stem()?.to_string_lossy().into_owned();
+        Some(parse_project(&content, &stem))
+    }
+
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        _registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool> {
+        // The flat container lists lower-cased versions under a lower-cased id.
+        let base = &settings().nuget_url.value;
+        let url = format!("{base}/{}/index.json", name.to_ascii_lowercase());
+        Ok(registry::fetch(&url)
+            .await?
+            .is_some_and(|document| versions_contain(&document, version)))
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        _registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String) {
+        publish_internal(path, dry_run).await
+    }
+}
+
+/// Returns the first `.csproj`/`.fsproj`/`.vbproj` in the repository root.
+async fn find_project(repo_path: &Path) -> Option<PathBuf> {
+    let mut entries = tokio::fs::read_dir(repo_path).await.ok()?;
+    let mut projects = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_some_and(|extension| {
+            matches!(extension.to_str(), Some("csproj" | "fsproj" | "vbproj"))
+        }) {
+            projects.push(path);
+        }
+    }
+    projects.sort();
+    projects.into_iter().next()
+}
+
+/// Reads `PackageId` (the project name by default), `Version` or
+/// `PackageVersion`, and `PackageReference` dependencies that flow to
+/// consumers (not `PrivateAssets="all"`).
+fn parse_project(content: &str, project_name: &str) -> PackageInfo {
+    let property = |name: &str| {
+        Regex::new(&format!(r"<{name}>\s*([^<]+?)\s*</{name}>"))
+            .ok()?
+            .captures(content)
+            .map(|captures| captures[1].to_string())
+    };
+    let name = property("PackageId").unwrap_or_else(|| project_name.to_string());
+    let version = property("PackageVersion")
+        .or_else(|| property("Version"))
+        .unwrap_or_else(|| DEFAULT_PACKAGE_VERSION.to_string());
+
+    let dependencies = Regex::new(r#"<PackageReference\s+([^>]*?)/?>"#)
+        .map(|pattern| {
+            pattern
+                .captures_iter(content)
+                .filter(|captures| !captures[1].contains(r#"PrivateAssets="all""#))
+                .filter_map(|captures| {
+                    Regex::new(r#"Include="([^"]+)""#)
+                        .ok()?
+                        .captures(&captures[1])
+                        .map(|include| include[1].to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    PackageInfo {
+        manager_name: "nuget".to_string(),
+        name,
+        version,
+        dependencies,
+    }
+}
+
+fn versions_contain(document: &str, version: &str) -> bool {
+    let version = version.to_ascii_lowercase();
+    serde_json::from_str::<serde_json::Value>(document)
+        .ok()
+        .and_then(|index| index.get("versions").cloned())
+        .and_then(|versions| versions.as_array().cloned())
+        .is_some_and(|versions| {
+            versions
+                .iter()
+                .any(|entry| entry.as_str() == Some(version.as_str()))
+        })
+}
+
+/// Packs the project and pushes the `.nupkg` to nuget.org
+/// Returns (success, message)
+async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
+    let output_dir = repo_path.join("bin").join("repos-publish");
+    let output_arg = output_dir.to_string_lossy().into_owned();
+    let timeout_duration = Duration::from_secs(DOTNET_OPERATION_TIMEOUT_SECS);
+
+    let pack = tokio::time::timeout(
+        timeout_duration,
+        Command::new("dotnet")
+            .args([
+                "pack",
+                "--configuration",
+                "Release",
+                "--output",
+                &output_arg,
+            ])
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await;
+    match pack {
+        Ok(Ok(output)) if output.status.success() => {}
+        Ok(Ok(output)) => {
+            let _ = tokio::fs::remove_dir_all(&output_dir).await;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return (
+                false,
+                format!("dotnet pack failed: {}", clean_nuget_error(&stdout)),
+            );
+        }
+        Ok(Err(e)) => return (false, format!("dotnet command failed: {e}")),
+        Err(_) => return (false, "dotnet pack timed out".to_string()),
+    }
+
+    if dry_run {
+        let _ = tokio::fs::remove_dir_all(&output_dir).await;
+        return (true, "dry-run ok".to_string());
+    }
+
+    let result = tokio::time::timeout(
+        timeout_duration,
+        Command::new("dotnet")
+            .args([
+                "nuget",
+                "push",
+                &format!("{output_arg}/*.nupkg"),
+                "--source",
+                NUGET_PUSH_SOURCE,
+            ])
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await;
+    let _ = tokio::fs::remove_dir_all(&output_dir).await;
+
+    match result {
+        Ok(Ok(output)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let error_message = clean_nuget_error(&format!("{stdout}{stderr}"));
+            if output.status.success() {
+                (true, "published".to_string())
+            } else if error_message == "already published" {
+                (true, error_message)
+            } else {
+                (false, error_message)
+            }
+        }
+        Ok(Err(e)) => (false, format!("dotnet command failed: {e}")),
+        Err(_) => (false, "nuget operation timed out".to_string()),
+    }
+}
+
+/// Cleans up dotnet/NuGet error messages to be more user-friendly
+fn clean_nuget_error(error: &str) -> String {
+    if error.contains("409 (Conflict") || error.contains("already exists") {
+        "already published".to_string()
+    } else if error.contains("401") || error.contains("API key") {
+        "not authenticated (run: dotnet nuget setapikey)".to_string()
+    } else if error.contains("403") {
+        "permission denied (check the API key's package scope)".to_string()
+    } else {
+        error
+            .lines()
+            .find(|line| line.contains("error"))
+            .or_else(|| error.lines().find(|line| !line.trim().is_empty()))
+            .map_or_else(|| error.trim().to_string(), |line| line.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_properties_and_references_are_read() {
+        let project = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <PackageId>Acme.Widget</PackageId>
+    <Version>2.3.0</Version>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Acme.Core" Version="1.4.0" />
+    <PackageReference Include="Microsoft.SourceLink.GitHub" Version="8.0.0" PrivateAssets="all" />
+  </ItemGroup>
+</Project>
+"#;
+        let info = parse_project(project, "Widget");
+        assert_eq!(info.name, "Acme.Widget");
+        assert_eq!(info.version, "2.3.0");
+        assert_eq!(info.dependencies, vec!["Acme.Core"]);
+
+        let bare = parse_project("<Project Sdk=\"Microsoft.NET.Sdk\" />", "Widget");
+        assert_eq!(bare.name, "Widget");
+        assert_eq!(bare.version, DEFAULT_PACKAGE_VERSION);
+    }
+
+    #[test]
+    fn flat_container_versions_compare_case_insensitively() {
+        let document = r#"{"versions":["1.0.0","2.3.0-beta.1"]}"#;
+        assert!(versions_contain(document, "2.3.0-Beta.1"));
+        assert!(!versions_contain(document, "2.3.0"));
+        assert_eq!(
+            clean_nuget_error("Pushing Acme.Widget.2.3.0.nupkg\nResponse status code does not indicate success: 409 (Conflict - The feed already contains 'Acme.Widget 2.3.0'.)"),
+            "already published"
+        );
+    }
+}
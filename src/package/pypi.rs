@@ -2,12 +2,15 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use regex::Regex;
 use serde::Deserialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::process::Command;
 
-use super::{PackageInfo, PackageManager};
+use super::version::{edit_toml_lines, replace_version_token, toml_key};
+use super::{format_size, registry, PackSummary, PackageInfo, PackageManager, PublishRegistry};
+use crate::core::settings::settings;
 
 const PYTHON_OPERATION_TIMEOUT_SECS: u64 = 300; // 5 minutes for python operations
 
@@ -34,8 +37,76 @@ impl PackageManager for PyPI {
         get_package_info_internal(path).await
     }
 
-    async fn publish(&self, path: &Path, dry_run: bool) -> (bool, String) {
-        publish_internal(path, dry_run).await
+    async fn set_version(&self, path: &Path, old: &str, new: &str) -> anyhow::Result<PathBuf> {
+        let manifest_path = path.join("pyproject.toml");
+        let content = tokio::fs::read_to_string(&manifest_path).await?;
+        let edited = edit_toml_lines(&content, |table, line| {
+            (table == "project" && toml_key(line) == Some("version"))
+                .then(|| replace_version_token(line, old, new))
+                .flatten()
+        });
+        let Some(edited) = edited else {
+            anyhow::bail!(
+                "no version \"{old}\" in the [project] table of {}",
+                manifest_path.display()
+            );
+        };
+        tokio::fs::write(&manifest_path, edited).await?;
+        Ok(manifest_path)
+    }
+
+    async fn update_dependency(
+        &self,
+        path: &Path,
+        dependency: &str,
+        old: &str,
+        new: &str,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        let manifest_path = path.join("pyproject.toml");
+        let Ok(content) = tokio::fs::read_to_string(&manifest_path).await else {
+            return Ok(None);
+        };
+        match update_requirements(&content, dependency, old, new) {
+            Some(edited) => {
+                tokio::fs::write(&manifest_path, edited).await?;
+                Ok(Some(manifest_path))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool> {
+        let base = match registry.filter(|registry| registry.python.is_some()) {
+            Some(registry) => registry.python_index.as_deref().ok_or_else(|| {
+                anyhow::anyhow!("no python_index set for registry '{}'", registry.name)
+            })?,
+            None => &settings().pypi_url.value,
+        };
+        let url = format!("{base}/pypi/{}/{version}/json", normalize_name(name));
+        Ok(registry::fetch(&url).await?.is_some())
+    }
+
+    async fn preflight(
+        &self,
+        path: &Path,
+        _registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<Option<PackSummary>> {
+        preflight_internal(path).await.map(Some)
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String) {
+        let repository_url = registry.and_then(|registry| registry.python.as_deref());
+        publish_internal(path, repository_url, dry_run).await
     }
 }
 
@@ -49,6 +120,60 @@ struct PyProjectToml {
 struct PyProject {
     name: String,
     version: String,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Normalizes a distribution name as PyPI compares them (PEP 503).
+pub(crate) fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Extracts the normalized distribution name from a PEP 508 requirement
+/// such as `core-lib[extra]>=1.2; python_version >= "3.9"`.
+fn requirement_name(requirement: &str) -> Option<String> {
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let name = requirement[..end].trim();
+    (!name.is_empty()).then(|| normalize_name(name))
+}
+
+/// Rewrites quoted requirement strings for `dependency` that name `old`
+/// (`"core-lib>=1.2.0"` becomes `"core-lib>=1.3.0"`).
+fn update_requirements(content: &str, dependency: &str, old: &str, new: &str) -> Option<String> {
+    let pattern = Regex::new(r#""([^"\n]*)"|'([^'\n]*)'"#).ok()?;
+    let wanted = normalize_name(dependency);
+    let mut changed = false;
+    let edited = pattern.replace_all(content, |captures: &regex::Captures<'_>| {
+        let quoted = &captures[0];
+        let requirement = &quoted[1..quoted.len() - 1];
+        if requirement_name(requirement.trim()).as_deref() != Some(wanted.as_str()) {
+            return quoted.to_string();
+        }
+        match replace_version_token(requirement, old, new) {
+            Some(requirement) => {
+                changed = true;
+                format!(
+                    "{}{requirement}{}",
+                    &quoted[..1],
+                    &quoted[quoted.len() - 1..]
+                )
+            }
+            None => quoted.to_string(),
+        }
+    });
+    changed.then(|| edited.into_owned())
 }
 
 /// Gets package information from pyproject.toml or setup.py
@@ -63,6 +188,11 @@ async fn get_package_info_internal(repo_path: &Path) -> Option<PackageInfo> {
                         manager_name: "python".to_string(),
                         name: project.name,
                         version: project.version,
+                        dependencies: project
+                            .dependencies
+                            .iter()
+                            .filter_map(|requirement| requirement_name(requirement.trim()))
+                            .collect(),
                     });
                 }
             }
@@ -85,6 +215,7 @@ async fn get_package_info_internal(repo_path: &Path) -> Option<PackageInfo> {
                     manager_name: "python".to_string(),
                     name: "unknown".to_string(),
                     version: "unknown".to_string(),
+                    dependencies: Vec::new(),
                 });
             }
         }
@@ -95,7 +226,11 @@ async fn get_package_info_internal(repo_path: &Path) -> Option<PackageInfo> {
 
 /// Publishes a Python package
 /// Returns (success, message)
-async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
+async fn publish_internal(
+    repo_path: &Path,
+    repository_url: Option<&str>,
+    dry_run: bool,
+) -> (bool, String) {
     // First, check if twine is available
     let twine_check = Command::new("twine").arg("--version").output().await;
 
@@ -107,7 +242,7 @@ async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
     }
 
     // Build the package first
-    let build_result = build_package(repo_path).await;
+    let build_result = build_package(repo_path, None).await;
     if let Err(e) = build_result {
         return (false, format!("build failed: {e}"));
     }
@@ -122,6 +257,9 @@ async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
     }
 
     args.push("dist/*");
+    if let Some(url) = repository_url {
+        args.extend(["--repository-url", url]);
+    }
 
     if dry_run {
         // For dry-run, just check the packages
@@ -168,14 +306,109 @@ async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
     }
 }
 
-/// Builds a Python package
-async fn build_package(repo_path: &Path) -> Result<()> {
-    // Try using build module (modern approach)
-    let build_result = Command::new("python")
-        .args(["-m", "build"])
-        .current_dir(repo_path)
+/// Builds and checks the distributions without uploading: `python -m build`
+/// into a scratch directory, then `twine check` on the result.
+async fn preflight_internal(repo_path: &Path) -> Result<PackSummary> {
+    if Command::new("twine")
+        .arg("--version")
         .output()
-        .await;
+        .await
+        .is_err()
+    {
+        anyhow::bail!("twine not installed (run: pip install twine)");
+    }
+
+    let out_dir = std::env::temp_dir().join(format!(
+        "repos-preflight-{}-{}",
+        std::process::id(),
+        repo_path
+            .canonicalize()
+            .unwrap_or_else(|_| repo_path.to_path_buf())
+            .file_name()
+            .map_or_else(|| "package".into(), |name| name.to_string_lossy())
+    ));
+    let result = build_and_check(repo_path, &out_dir).await;
+    let _ = tokio::fs::remove_dir_all(&out_dir).await;
+    result
+}
+
+async fn build_and_check(repo_path: &Path, out_dir: &Path) -> Result<PackSummary> {
+    tokio::time::timeout(
+        Duration::from_secs(PYTHON_OPERATION_TIMEOUT_SECS),
+        build_package(repo_path, Some(out_dir)),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("python build timed out"))?
+    .map_err(|e| anyhow::anyhow!("build failed: {e}"))?;
+
+    let mut distributions = Vec::new();
+    let mut entries = tokio::fs::read_dir(out_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        distributions.push((entry.path(), entry.metadata().await?.len()));
+    }
+    distributions.sort();
+    if distributions.is_empty() {
+        anyhow::bail!("build produced no distributions");
+    }
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(PYTHON_OPERATION_TIMEOUT_SECS),
+        Command::new("twine")
+            .arg("check")
+            .args(distributions.iter().map(|(path, _)| path))
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("twine check timed out"))??;
+    if !output.status.success() {
+        let combined = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        anyhow::bail!("twine check failed: {}", twine_check_error(&combined));
+    }
+
+    Ok(PackSummary {
+        files: distributions
+            .iter()
+            .filter_map(|(path, _)| Some(path.file_name()?.to_string_lossy().into_owned()))
+            .collect(),
+        size: Some(format_size(
+            distributions.iter().map(|(_, size)| size).sum(),
+        )),
+    })
+}
+
+/// The distribution `twine check` failed and the first error it gave.
+fn twine_check_error(output: &str) -> String {
+    let failed = output.lines().find_map(|line| {
+        let checked = line.trim().strip_prefix("Checking ")?;
+        let path = checked.strip_suffix(": FAILED")?;
+        Some(Path::new(path).file_name()?.to_string_lossy().into_owned())
+    });
+    let error = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("ERROR"))
+        .map(str::trim);
+    match (failed, error) {
+        (Some(failed), Some(error)) => format!("{failed}: {error}"),
+        (None, Some(error)) => error.to_string(),
+        (Some(failed), None) => format!("{failed} failed"),
+        (None, None) => output.trim().to_string(),
+    }
+}
+
+/// Builds a Python package into `dist/`, or into `out_dir` when given
+async fn build_package(repo_path: &Path, out_dir: Option<&Path>) -> Result<()> {
+    // Try using build module (modern approach)
+    let mut build = Command::new("python");
+    build.args(["-m", "build"]).current_dir(repo_path);
+    if let Some(out_dir) = out_dir {
+        build.arg("--outdir").arg(out_dir);
+    }
+    let build_result = build.output().await;
 
     if let Ok(output) = build_result {
         if output.status.success() {
@@ -184,11 +417,21 @@ async fn build_package(repo_path: &Path) -> Result<()> {
     }
 
     // Fallback to setup.py
-    let setup_result = Command::new("python")
-        .args(["setup.py", "sdist", "bdist_wheel"])
-        .current_dir(repo_path)
-        .output()
-        .await?;
+    let mut setup = Command::new("python");
+    setup.current_dir(repo_path);
+    match out_dir {
+        Some(out_dir) => {
+            setup
+                .args(["setup.py", "sdist", "--dist-dir"])
+                .arg(out_dir)
+                .args(["bdist_wheel", "--dist-dir"])
+                .arg(out_dir);
+        }
+        None => {
+            setup.args(["setup.py", "sdist", "bdist_wheel"]);
+        }
+    }
+    let setup_result = setup.output().await?;
 
     if setup_result.status.success() {
         Ok(())
@@ -213,3 +456,40 @@ fn clean_python_error(error: &str) -> String {
             .map_or_else(|| error.trim().to_string(), |line| line.trim().to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requirement_updates_match_normalized_names_only() {
+        let content = "[project]\nname = \"tool\"\nversion = \"1.2.0\"\ndependencies = [\n  \"Core_Lib>=1.2.0\",\n  'other==1.2.0',\n]\n";
+        let updated = update_requirements(content, "core-lib", "1.2.0", "1.3.0").unwrap();
+        assert!(updated.contains("\"Core_Lib>=1.3.0\""));
+        assert!(updated.contains("'other==1.2.0'"));
+        assert!(updated.contains("version = \"1.2.0\""));
+    }
+
+    #[test]
+    fn twine_check_errors_name_the_failing_distribution() {
+        let output = "Checking dist/tool-1.2.0-py3-none-any.whl: PASSED\nChecking dist/tool-1.2.0.tar.gz: FAILED\nERROR    `long_description` has syntax errors in markup\n";
+        assert_eq!(
+            twine_check_error(output),
+            "tool-1.2.0.tar.gz: `long_description` has syntax errors in markup"
+        );
+    }
+
+    #[test]
+    fn requirement_names_are_normalized_and_stripped_of_specifiers() {
+        assert_eq!(
+            requirement_name("Core_Lib[extra]>=1.2; python_version >= '3.9'"),
+            Some("core-lib".to_string())
+        );
+        assert_eq!(requirement_name("requests"), Some("requests".to_string()));
+        assert_eq!(
+            requirement_name("zope.interface (>=5)"),
+            Some("zope-interface".to_string())
+        );
+        assert_eq!(requirement_name(">=1.0"), None);
+    }
+}
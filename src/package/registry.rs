@@ -0,0 +1,72 @@
+//! Registry queries shared by the package managers
+//!
+//! Registries are read over HTTPS with `curl`, the same tool the audit
+//! installer uses, so no HTTP client is linked into the binary. A `file://`
+//! URL reads a local directory laid out like the registry instead, which
+//! stands in for a registry in tests and air-gapped mirrors.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::time::Duration;
+use tokio::process::Command;
+
+const REGISTRY_QUERY_TIMEOUT_SECS: u64 = 30;
+
+/// Fetches `url` and returns its body, or `None` when the registry answers
+/// 404/410 (the package or version does not exist).
+pub(crate) async fn fetch(url: &str) -> Result<Option<String>> {
+    if let Some(path) = url.strip_prefix("file://") {
+        return match tokio::fs::read_to_string(path).await {
+            Ok(body) => Ok(Some(body)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("failed to read {path}")),
+        };
+    }
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(REGISTRY_QUERY_TIMEOUT_SECS),
+        Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--location",
+                "--write-out",
+                "\n%{http_code}",
+                url,
+            ])
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow!("registry query timed out: {url}"))?
+    .context("failed to run curl")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("registry query failed: {}", stderr.trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    match status.trim() {
+        "200" => Ok(Some(body.to_string())),
+        "404" | "410" => Ok(None),
+        code => bail!("registry returned HTTP {code} for {url}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_urls_read_a_local_stand_in_registry() {
+        let directory = tempfile::TempDir::new().unwrap();
+        std::fs::write(directory.path().join("core-lib"), "{}").unwrap();
+        let base = format!("file://{}", directory.path().display());
+
+        assert_eq!(
+            fetch(&format!("{base}/core-lib")).await.unwrap().as_deref(),
+            Some("{}")
+        );
+        assert_eq!(fetch(&format!("{base}/missing")).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,282 @@
+//! RubyGems package publishing functionality
+
+use async_trait::async_trait;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::{registry, PackageInfo, PackageManager, PublishRegistry};
+use crate::core::settings::settings;
+
+const GEM_OPERATION_TIMEOUT_SECS: u64 = 300; // 5 minutes for gem build and push
+
+pub struct RubyGems;
+
+#[async_trait]
+impl PackageManager for RubyGems {
+    fn name(&self) -> &str {
+        "gem"
+    }
+
+    fn icon(&self) -> &str {
+        "💎"
+    }
+
+    async fn detect(&self, path: &Path) -> bool {
+        find_gemspec(path).await.is_some()
+    }
+
+    async fn get_info(&self, path: &Path) -> Option<PackageInfo> {
+        get_package_info_internal(path).await
+    }
+
+    async fn is_version_published(
+        &self,
+        name: &str,
+        version: &str,
+        _registry: Option<&PublishRegistry>,
+    ) -> anyhow::Result<bool> {
+        let base = &settings().rubygems_url.value;
+        let url = format!("{base}/api/v1/versions/{name}.json");
+        Ok(registry::fetch(&url)
+            .await?
+            .is_some_and(|document| versions_contain(&document, version)))
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        _registry: Option<&PublishRegistry>,
+        dry_run: bool,
+    ) -> (bool, String) {
+        publish_internal(path, dry_run).await
+    }
+}
+
+/// Returns the first `*.gemspec` in the repository root.
+async fn find_gemspec(repo_path: &Path) -> Option<PathBuf> {
+    let mut entries = tokio::fs::read_dir(repo_path).await.ok()?;
+    let mut gemspecs = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "gemspec")
+        {
+            gemspecs.push(path);
+        }
+    }
+    gemspecs.sort();
+    gemspecs.into_iter().next()
+}
+
+/// Reads `spec.name`, `spec.version` and runtime dependencies from a gemspec.
+/// A version given as a constant (`Widget::VERSION`) is looked up in
+/// `lib/**/version.rb`.
+async fn get_package_info_internal(repo_path: &Path) -> Option<PackageInfo> {
+    let gemspec = tokio::fs::read_to_string(find_gemspec(repo_path).await?)
+        .await
+        .ok()?;
+    let name = string_assignment(&gemspec, "name")?;
+    let version = match string_assignment(&gemspec, "version") {
+        Some(version) => version,
+        None => version_constant(&repo_path.join("lib")).await?,
+    };
+
+    Some(PackageInfo {
+        manager_name: "gem".to_string(),
+        name,
+        version,
+        dependencies: runtime_dependencies(&gemspec),
+    })
+}
+
+/// Value of `<spec>.<field> = "value"` in a gemspec.
+fn string_assignment(gemspec: &str, field: &str) -> Option<String> {
+    let pattern = Regex::new(&format!(r#"\.{field}\s*=\s*["']([^"']+)["']"#)).ok()?;
+    pattern
+        .captures(gemspec)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Finds `VERSION = "x.y.z"` in the gem's `version.rb`.
+async fn version_constant(lib: &Path) -> Option<String> {
+    let pattern = Regex::new(r#"VERSION\s*=\s*["']([^"']+)["']"#).ok()?;
+    let mut pending = vec![lib.to_path_buf()];
+    while let Some(directory) = pending.pop() {
+        let Ok(mut entries) = tokio::fs::read_dir(&directory).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.file_name().is_some_and(|name| name == "version.rb") {
+                let content = tokio::fs::read_to_string(&path).await.ok()?;
+                if let Some(captures) = pattern.captures(&content) {
+                    return Some(captures[1].to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Names from `add_dependency` and `add_runtime_dependency`; development
+/// dependencies are left out.
+fn runtime_dependencies(gemspec: &str) -> Vec<String> {
+    let Ok(pattern) = Regex::new(r#"\.add_(?:runtime_)?dependency\s*\(?\s*["']([^"']+)["']"#)
+    else {
+        return Vec::new();
+    };
+    pattern
+        .captures_iter(gemspec)
+        .map(|captures| captures[1].to_string())
+        .collect()
+}
+
+/// The versions API lists every published version as `{"number": "1.2.3"}`.
+fn versions_contain(document: &str, version: &str) -> bool {
+    serde_json::from_str::<Vec<serde_json::Value>>(document).is_ok_and(|versions| {
+        versions
+            .iter()
+            .any(|entry| entry.get("number").and_then(serde_json::Value::as_str) == Some(version))
+    })
+}
+
+/// Builds the gem and pushes it to RubyGems
+/// Returns (success, message)
+async fn publish_internal(repo_path: &Path, dry_run: bool) -> (bool, String) {
+    let Some(gemspec) = find_gemspec(repo_path).await else {
+        return (false, "no .gemspec found".to_string());
+    };
+    let Some(info) = get_package_info_internal(repo_path).await else {
+        return (
+            false,
+            "could not read name and version from the gemspec".to_string(),
+        );
+    };
+    let gemspec_name = gemspec
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let gem_file = format!("{}-{}.gem", info.name, info.version);
+
+    let timeout_duration = Duration::from_secs(GEM_OPERATION_TIMEOUT_SECS);
+    let build = tokio::time::timeout(
+        timeout_duration,
+        Command::new("gem")
+            .args(["build", &gemspec_name, "--output", &gem_file])
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await;
+    match build {
+        Ok(Ok(output)) if output.status.success() => {}
+        Ok(Ok(output)) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return (
+                false,
+                format!("gem build failed: {}", clean_gem_error(&stderr)),
+            );
+        }
+        Ok(Err(e)) => return (false, format!("gem command failed: {e}")),
+        Err(_) => return (false, "gem build timed out".to_string()),
+    }
+
+    if dry_run {
+        let _ = tokio::fs::remove_file(repo_path.join(&gem_file)).await;
+        return (true, "dry-run ok".to_string());
+    }
+
+    let result = tokio::time::timeout(
+        timeout_duration,
+        Command::new("gem")
+            .args(["push", &gem_file])
+            .current_dir(repo_path)
+            .output(),
+    )
+    .await;
+    let _ = tokio::fs::remove_file(repo_path.join(&gem_file)).await;
+
+    match result {
+        Ok(Ok(output)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let combined = format!("{stdout}{stderr}");
+            if combined.contains("Repushing of gem versions") {
+                (true, "already published".to_string())
+            } else if output.status.success() {
+                (true, "published".to_string())
+            } else {
+                (false, clean_gem_error(&combined))
+            }
+        }
+        Ok(Err(e)) => (false, format!("gem command failed: {e}")),
+        Err(_) => (false, "gem operation timed out".to_string()),
+    }
+}
+
+/// Cleans up gem error messages to be more user-friendly
+fn clean_gem_error(error: &str) -> String {
+    if error.contains("Repushing of gem versions is not allowed") {
+        "already published".to_string()
+    } else if error.contains("Access Denied")
+        || error.contains("You must be logged in")
+        || error.contains("API key")
+    {
+        "not authenticated (run: gem signin)".to_string()
+    } else if error.contains("You do not have permission") {
+        "permission denied (check gem ownership)".to_string()
+    } else {
+        error
+            .lines()
+            .find(|line| !line.trim().is_empty() && !line.starts_with("Pushing gem"))
+            .map_or_else(
+                || error.trim().to_string(),
+                |line| line.replace("ERROR:", "").trim().to_string(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gemspec_fields_and_runtime_dependencies_are_read() {
+        let gemspec = r#"Gem::Specification.new do |spec|
+  spec.name          = "widget"
+  spec.version       = Widget::VERSION
+  spec.add_dependency "core-lib", "~> 1.2"
+  spec.add_runtime_dependency('http-kit', '>= 0.4')
+  spec.add_development_dependency "rspec", "~> 3.0"
+end
+"#;
+        assert_eq!(
+            string_assignment(gemspec, "name").as_deref(),
+            Some("widget")
+        );
+        assert_eq!(string_assignment(gemspec, "version"), None);
+        assert_eq!(runtime_dependencies(gemspec), vec!["core-lib", "http-kit"]);
+    }
+
+    #[test]
+    fn versions_api_lookup_and_error_cleaning() {
+        let document = r#"[{"number":"1.3.0"},{"number":"1.2.0"}]"#;
+        assert!(versions_contain(document, "1.2.0"));
+        assert!(!versions_contain(document, "1.4.0"));
+
+        assert_eq!(
+            clean_gem_error("Repushing of gem versions is not allowed.\nPlease bump"),
+            "already published"
+        );
+        assert_eq!(
+            clean_gem_error(
+                "Pushing gem to https://rubygems.org...\nAccess Denied. Please sign up"
+            ),
+            "not authenticated (run: gem signin)"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Release tag names.
+//!
+//! A tag template spells a release tag with `{name}` (the package name) and
+//! `{version}`: `v{version}` gives `v1.2.0`, `{name}@{version}` gives
+//! `core-lib@1.2.0`.
+
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use crate::core::manifest::WorkspaceManifest;
+use crate::core::settings::settings;
+
+/// Template used when neither the settings nor `repos.toml` name one.
+pub const DEFAULT_TAG_TEMPLATE: &str = "v{version}";
+
+const PLACEHOLDERS: &[&str] = &["{name}", "{version}"];
+
+/// Checks that `template` names the version and renders to a valid tag.
+pub fn validate_template(template: &str) -> Result<()> {
+    if !template.contains("{version}") {
+        bail!("tag template '{template}' must contain {{version}}");
+    }
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let placeholder = rest[start..]
+            .find('}')
+            .map_or(&rest[start..], |end| &rest[start..=start + end]);
+        if !PLACEHOLDERS.contains(&placeholder) {
+            bail!(
+                "tag template '{template}' has unknown placeholder {placeholder} (use {})",
+                PLACEHOLDERS.join(" or ")
+            );
+        }
+        rest = &rest[start + placeholder.len()..];
+    }
+    if validate_name(&render(template, "name", "1.0.0", false)).is_err() {
+        bail!("tag template '{template}' would not be a valid git tag name");
+    }
+    Ok(())
+}
+
+/// Checks a rendered tag against git's ref name rules
+/// (`git check-ref-format`), so a package name such as Maven's
+/// `group:artifact` is caught before anything is published.
+pub fn validate_name(tag: &str) -> Result<()> {
+    let forbidden = tag
+        .chars()
+        .find(|c| c.is_whitespace() || c.is_control() || "~^:?*[\\".contains(*c));
+    let reason = if tag.is_empty() {
+        "it is empty".to_string()
+    } else if let Some(c) = forbidden {
+        format!("it contains {c:?}")
+    } else if tag.contains("..") || tag.contains("@{") || tag.contains("//") {
+        "it contains '..', '@{' or '//'".to_string()
+    } else if tag == "@" || tag.starts_with('-') {
+        "it is '@' or starts with '-'".to_string()
+    } else if tag.starts_with('/') || tag.ends_with('/') || tag.ends_with('.') {
+        "it starts or ends with '/' or ends with '.'".to_string()
+    } else if tag
+        .split('/')
+        .any(|part| part.starts_with('.') || part.ends_with(".lock"))
+    {
+        "a path component starts with '.' or ends with '.lock'".to_string()
+    } else {
+        return Ok(());
+    };
+    bail!("'{tag}' is not a valid git tag name: {reason}")
+}
+
+/// Renders the tag for `version` of `name`. Members of a multi-package
+/// workspace share a repository, so a template without `{name}` gets a
+/// `{name}-` prefix to keep their tags apart.
+#[must_use]
+pub fn render(template: &str, name: &str, version: &str, workspace_member: bool) -> String {
+    let tag = template
+        .replace("{name}", name)
+        .replace("{version}", version);
+    if workspace_member && !template.contains("{name}") {
+        format!("{name}-{tag}")
+    } else {
+        tag
+    }
+}
+
+/// Tag template for a repository's packages: its own `repos.toml` entry wins
+/// over its ecosystem's `[tag_templates]` entry, then the
+/// `publish.tag_template` setting.
+#[must_use]
+pub fn template_for(
+    manifest: Option<&WorkspaceManifest>,
+    repo_path: &Path,
+    manager: Option<&str>,
+) -> String {
+    manifest
+        .and_then(|manifest| {
+            manifest
+                .repository_at(repo_path)
+                .and_then(|repository| repository.tag_template.clone())
+                .or_else(|| manager.and_then(|name| manifest.tag_templates().get(name).cloned()))
+        })
+        .unwrap_or_else(|| settings().tag_template.value.clone())
+}
+
+/// `git describe --match` glob for the release tags `render` gives `name`.
+#[must_use]
+pub fn match_pattern(template: &str, name: &str, workspace_member: bool) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        if "*?[\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    render(template, &escaped, "[0-9]*", workspace_member)
+}
+
+/// The version in a release tag that `render` gave `name`, if `tag` has the
+/// template's shape.
+#[must_use]
+pub fn version_in<'a>(
+    template: &str,
+    name: &str,
+    workspace_member: bool,
+    tag: &'a str,
+) -> Option<&'a str> {
+    let shape = render(template, name, "{version}", workspace_member);
+    let (prefix, suffix) = shape.split_once("{version}")?;
+    tag.strip_prefix(prefix)?.strip_suffix(suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates_render_names_and_versions() {
+        assert_eq!(
+            render(DEFAULT_TAG_TEMPLATE, "app", "1.2.0", false),
+            "v1.2.0"
+        );
+        assert_eq!(
+            render(DEFAULT_TAG_TEMPLATE, "core-lib", "1.2.0", true),
+            "core-lib-v1.2.0"
+        );
+        assert_eq!(
+            render("{name}@{version}", "@acme/ui", "3.0.0", true),
+            "@acme/ui@3.0.0"
+        );
+    }
+
+    #[test]
+    fn match_patterns_follow_the_rendered_tag() {
+        assert_eq!(match_pattern(DEFAULT_TAG_TEMPLATE, "app", false), "v[0-9]*");
+        assert_eq!(
+            match_pattern(DEFAULT_TAG_TEMPLATE, "core-lib", true),
+            "core-lib-v[0-9]*"
+        );
+        assert_eq!(
+            match_pattern("{name}@{version}", "@acme/ui", false),
+            "@acme/ui@[0-9]*"
+        );
+        assert_eq!(
+            match_pattern("{name}-{version}", "a*b", false),
+            "a\\*b-[0-9]*"
+        );
+
+        assert_eq!(
+            version_in("{name}@{version}", "@acme/ui", false, "@acme/ui@3.0.0"),
+            Some("3.0.0")
+        );
+        assert_eq!(
+            version_in(DEFAULT_TAG_TEMPLATE, "core-lib", true, "core-lib-v1.2.3"),
+            Some("1.2.3")
+        );
+        assert_eq!(
+            version_in(DEFAULT_TAG_TEMPLATE, "app", false, "1.2.3"),
+            None
+        );
+    }
+
+    #[test]
+    fn templates_need_the_version_and_known_placeholders() {
+        assert!(validate_template("{name}-v{version}").is_ok());
+        assert!(validate_template("release-{name}").is_err());
+        assert!(validate_template("{crate}-{version}").is_err());
+        assert!(validate_template("v{version} final").is_err());
+        assert!(validate_template("v{version}^").is_err());
+    }
+
+    #[test]
+    fn rendered_names_follow_git_ref_rules() {
+        assert!(validate_name("core-lib-v1.2.0").is_ok());
+        assert!(validate_name("@acme/ui@3.0.0").is_ok());
+        let maven = render(DEFAULT_TAG_TEMPLATE, "com.acme:core", "1.0.0", true);
+        assert!(validate_name(&maven)
+            .unwrap_err()
+            .to_string()
+            .contains("contains ':'"));
+        assert!(validate_name("v1.0.0.lock").is_err());
+        assert!(validate_name("scope/.hidden-1.0.0").is_err());
+        assert!(validate_name("-v1.0.0").is_err());
+    }
+}
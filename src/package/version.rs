@@ -0,0 +1,311 @@
+//! Semantic version bumps and formatting-preserving manifest edits
+//!
+//! `repos version bump` rewrites only the version strings it changes, line by
+//! line, so comments, key order, and indentation in manifests survive.
+
+use anyhow::{bail, Result};
+use std::fmt;
+
+/// Which part of a version `repos version bump` increments.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum BumpLevel {
+    Patch,
+    Minor,
+    Major,
+    /// Next pre-release of the current or next patch version (`1.2.4-rc.1`)
+    Prerelease,
+}
+
+impl BumpLevel {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+            Self::Prerelease => "prerelease",
+        }
+    }
+}
+
+/// A `MAJOR.MINOR.PATCH[-PRE][+BUILD]` version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Dot-separated pre-release identifiers (`rc.1` is `["rc", "1"]`)
+    pub pre: Vec<String>,
+    pub build: Option<String>,
+}
+
+impl Version {
+    pub fn parse(value: &str) -> Result<Self> {
+        let invalid = || anyhow::anyhow!("'{value}' is not a semantic version (MAJOR.MINOR.PATCH)");
+        let (rest, build) = match value.split_once('+') {
+            Some((rest, build)) if !build.is_empty() => (rest, Some(build.to_string())),
+            Some(_) => return Err(invalid()),
+            None => (value, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => {
+                let pre: Vec<String> = pre.split('.').map(str::to_string).collect();
+                if pre.iter().any(|id| {
+                    id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                }) {
+                    return Err(invalid());
+                }
+                (core, pre)
+            }
+            None => (rest, Vec::new()),
+        };
+        let numbers: Vec<u64> = core
+            .split('.')
+            .map(|part| {
+                if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+                part.parse().map_err(|_| invalid())
+            })
+            .collect::<Result<_>>()?;
+        let [major, minor, patch] = numbers[..] else {
+            return Err(invalid());
+        };
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        })
+    }
+
+    /// Returns the next version. Without `pre_id`, bumping a pre-release to
+    /// the level it anticipates releases it (`1.3.0-rc.2` minor is `1.3.0`);
+    /// with `pre_id`, the bumped version starts a pre-release (`1.3.0-rc.0`).
+    pub fn bump(&self, level: BumpLevel, pre_id: Option<&str>) -> Result<Self> {
+        if let Some(id) = pre_id {
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                bail!("invalid pre-release identifier '{id}'; use letters, digits and '-'");
+            }
+        }
+        let is_pre = !self.pre.is_empty();
+        let mut next = Self {
+            pre: Vec::new(),
+            build: None,
+            ..self.clone()
+        };
+        match level {
+            BumpLevel::Major => {
+                if !(is_pre && pre_id.is_none() && self.minor == 0 && self.patch == 0) {
+                    next.major += 1;
+                    next.minor = 0;
+                    next.patch = 0;
+                }
+            }
+            BumpLevel::Minor => {
+                if !(is_pre && pre_id.is_none() && self.patch == 0) {
+                    next.minor += 1;
+                    next.patch = 0;
+                }
+            }
+            BumpLevel::Patch => {
+                if !(is_pre && pre_id.is_none()) {
+                    next.patch += 1;
+                }
+            }
+            BumpLevel::Prerelease => {
+                let same_id =
+                    pre_id.is_none_or(|id| self.pre.first().map(String::as_str) == Some(id));
+                if is_pre && same_id {
+                    next.pre = self.pre.clone();
+                    match next
+                        .pre
+                        .last_mut()
+                        .and_then(|last| last.parse::<u64>().ok().map(|number| (last, number)))
+                    {
+                        Some((last, number)) => *last = (number + 1).to_string(),
+                        None => next.pre.push("0".to_string()),
+                    }
+                    return Ok(next);
+                }
+                if !is_pre {
+                    next.patch += 1;
+                }
+                next.pre = vec![pre_id.unwrap_or("rc").to_string(), "0".to_string()];
+                return Ok(next);
+            }
+        }
+        if let Some(id) = pre_id {
+            next.pre = vec![id.to_string(), "0".to_string()];
+        }
+        Ok(next)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{build}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces `old` with `new` in `text` where `old` stands alone as a version,
+/// so `1.2.0` inside `^1.2.0` or `>=1.2.0,<2` changes but `11.2.0` and
+/// `1.2.0-rc.1` do not. Returns `None` when nothing matched.
+pub(crate) fn replace_version_token(text: &str, old: &str, new: &str) -> Option<String> {
+    let is_part = |c: char| c.is_ascii_alphanumeric() || c == '.';
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut replaced = false;
+    while let Some(index) = rest.find(old) {
+        let before = rest[..index]
+            .chars()
+            .last()
+            .or_else(|| result.chars().last());
+        let after = rest[index + old.len()..].chars().next();
+        let standalone = !before.is_some_and(is_part)
+            && !after.is_some_and(|c| is_part(c) || c == '-' || c == '+');
+        result.push_str(&rest[..index]);
+        result.push_str(if standalone { new } else { old });
+        replaced |= standalone;
+        rest = &rest[index + old.len()..];
+    }
+    result.push_str(rest);
+    replaced.then_some(result)
+}
+
+/// Table name of a TOML header line (`[package]` is `package`,
+/// `[target.'cfg(unix)'.dependencies]` is `target.'cfg(unix)'.dependencies`).
+pub(crate) fn toml_table(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let inner = line
+        .strip_prefix("[[")
+        .and_then(|rest| rest.split("]]").next())
+        .or_else(|| {
+            line.strip_prefix('[')
+                .and_then(|rest| rest.split(']').next())
+        })?;
+    Some(inner.trim())
+}
+
+/// Returns the key a TOML line assigns (`version = "1"` and
+/// `version.workspace = true` both assign under `version`).
+pub(crate) fn toml_key(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() || key.starts_with('#') || key.starts_with('[') {
+        return None;
+    }
+    let key = key.split('.').next()?.trim();
+    Some(key.trim_matches('"').trim_matches('\''))
+}
+
+/// Rewrites the lines of `content` that `edit` changes, keeping every other
+/// byte. `edit` receives the current table name and the line.
+pub(crate) fn edit_toml_lines(
+    content: &str,
+    mut edit: impl FnMut(&str, &str) -> Option<String>,
+) -> Option<String> {
+    let mut table = String::new();
+    let mut changed = false;
+    let mut result = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        if let Some(name) = toml_table(line) {
+            table = name.to_string();
+        } else if let Some(edited) = edit(&table, line) {
+            result.push_str(&edited);
+            changed = true;
+            continue;
+        }
+        result.push_str(line);
+    }
+    changed.then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bump(version: &str, level: BumpLevel, pre_id: Option<&str>) -> String {
+        Version::parse(version)
+            .unwrap()
+            .bump(level, pre_id)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn releases_increment_one_level_and_reset_the_lower_ones() {
+        assert_eq!(bump("1.2.3", BumpLevel::Patch, None), "1.2.4");
+        assert_eq!(bump("1.2.3", BumpLevel::Minor, None), "1.3.0");
+        assert_eq!(bump("1.2.3+build.5", BumpLevel::Major, None), "2.0.0");
+    }
+
+    #[test]
+    fn pre_releases_graduate_or_advance() {
+        assert_eq!(bump("1.3.0-rc.2", BumpLevel::Minor, None), "1.3.0");
+        assert_eq!(bump("1.2.4-rc.2", BumpLevel::Minor, None), "1.3.0");
+        assert_eq!(bump("1.2.4-rc.2", BumpLevel::Patch, None), "1.2.4");
+        assert_eq!(
+            bump("1.2.3", BumpLevel::Minor, Some("beta")),
+            "1.3.0-beta.0"
+        );
+        assert_eq!(bump("1.2.3", BumpLevel::Prerelease, None), "1.2.4-rc.0");
+        assert_eq!(
+            bump("1.2.4-rc.0", BumpLevel::Prerelease, None),
+            "1.2.4-rc.1"
+        );
+        assert_eq!(
+            bump("1.2.4-alpha.3", BumpLevel::Prerelease, Some("beta")),
+            "1.2.4-beta.0"
+        );
+        assert_eq!(
+            bump("1.2.4-beta", BumpLevel::Prerelease, None),
+            "1.2.4-beta.0"
+        );
+    }
+
+    #[test]
+    fn invalid_versions_and_identifiers_are_rejected() {
+        assert!(Version::parse("1.2").is_err());
+        assert!(Version::parse("1.2.x").is_err());
+        assert!(Version::parse("1.2.0rc1").is_err());
+        let version = Version::parse("1.2.3").unwrap();
+        assert!(version.bump(BumpLevel::Patch, Some("rc.1")).is_err());
+    }
+
+    #[test]
+    fn version_tokens_are_replaced_only_where_they_stand_alone() {
+        assert_eq!(
+            replace_version_token(">=1.2.0,<2", "1.2.0", "1.3.0").as_deref(),
+            Some(">=1.3.0,<2")
+        );
+        assert_eq!(
+            replace_version_token("^1.2.0", "1.2.0", "1.3.0").as_deref(),
+            Some("^1.3.0")
+        );
+        assert_eq!(replace_version_token("11.2.0", "1.2.0", "1.3.0"), None);
+        assert_eq!(replace_version_token("1.2.0-rc.1", "1.2.0", "1.3.0"), None);
+    }
+
+    #[test]
+    fn toml_line_edits_keep_comments_and_other_tables() {
+        let content = "[package]\nname = \"a\"\nversion = \"1.0.0\" # release\n\n[dependencies]\nversion = \"1.0.0\"\n";
+        let edited = edit_toml_lines(content, |table, line| {
+            (table == "package" && toml_key(line) == Some("version"))
+                .then(|| replace_version_token(line, "1.0.0", "1.1.0"))
+                .flatten()
+        })
+        .unwrap();
+        assert_eq!(
+            edited,
+            "[package]\nname = \"a\"\nversion = \"1.1.0\" # release\n\n[dependencies]\nversion = \"1.0.0\"\n"
+        );
+    }
+}
@@ -101,9 +101,12 @@ fn get_remote_url(path: &Path) -> Result<String> {
     Ok(normalize_remote_url(&url))
 }
 
-/// Normalize remote URLs to group equivalent URLs together
-fn normalize_remote_url(url: &str) -> String {
-    let trimmed = url.trim().trim_end_matches('/');
+/// Normalize remote URLs to group equivalent URLs together: `host/path`,
+/// without credentials, query strings, or a `.git` suffix.
+pub(crate) fn normalize_remote_url(url: &str) -> String {
+    let trimmed = url.trim();
+    let trimmed = trimmed.split(['?', '#']).next().unwrap_or(trimmed);
+    let trimmed = trimmed.trim_end_matches('/');
     let trimmed = trimmed.strip_suffix(".git").unwrap_or(trimmed);
 
     if let Some((authority, path)) = trimmed.split_once(':') {
@@ -207,6 +210,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn drops_credentials_and_query_strings() {
+        assert_eq!(
+            normalize_remote_url("https://token@git.example.com/team/repo.git?private=1"),
+            "git.example.com/team/repo"
+        );
+    }
+
     #[test]
     fn preserves_case_for_case_sensitive_remote_paths() {
         assert_eq!(
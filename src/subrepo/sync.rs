@@ -1,7 +1,10 @@
 //! Subrepo synchronization operations
 
 use super::{SubrepoInstance, ValidationReport};
-use crate::core::{clean_error_message, format_relative_repo_path, truncate_text};
+use crate::core::{
+    clean_error_message, format_relative_repo_path, truncate_text, HeadState, JournalEntry,
+    JournalRecorder, RestoreMode,
+};
 use crate::utils::compare_repository_locations;
 use anyhow::{Context, Result};
 use std::path::Path;
@@ -239,8 +242,8 @@ fn has_uncommitted_changes(path: &Path) -> Result<bool> {
     Ok(!output.stdout.is_empty())
 }
 
-/// Stash uncommitted changes in a repository
-fn stash_changes(path: &Path) -> Result<()> {
+/// Stash uncommitted changes in a repository, returning the stash commit
+fn stash_changes(path: &Path) -> Result<String> {
     let output = Command::new("git")
         .args([
             "-C",
@@ -259,7 +262,40 @@ fn stash_changes(path: &Path) -> Result<()> {
         anyhow::bail!("git stash failed: {stderr}");
     }
 
-    Ok(())
+    let output = Command::new("git")
+        .args([
+            "-C",
+            path_to_str(path)?,
+            "rev-parse",
+            "--verify",
+            "refs/stash",
+        ])
+        .output()
+        .context("Failed to resolve the stash")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git rev-parse failed: {stderr}");
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Records `instance`'s HEAD change (and any stash) for `repos undo`.
+fn record_nested_change(
+    journal: &JournalRecorder,
+    instance: &SubrepoInstance,
+    before: Option<HeadState>,
+    stash: Option<String>,
+) {
+    let Some(before) = before else {
+        return;
+    };
+    if let Ok(after) = HeadState::read_blocking(&instance.subrepo_path) {
+        let mut entry =
+            JournalEntry::new(&instance.parent_repo, &instance.subrepo_path, before, after);
+        entry.stash = stash;
+        journal.record(entry);
+    }
 }
 
 /// Checkout a specific commit in a git repository
@@ -336,7 +372,10 @@ fn fetch_latest_commit(path: &Path) -> Result<String> {
 /// Sync a subrepo to a specific commit across all parent repositories
 pub fn sync_subrepo(name: &str, target_commit: &str, stash: bool, force: bool) -> Result<()> {
     let report = super::validation::validate_subrepos()?;
-    sync_subrepo_with_report(name, target_commit, stash, force, &report)
+    let journal = JournalRecorder::new("nested-sync", RestoreMode::Keep);
+    let result = run_sync(name, target_commit, stash, force, &report, &journal);
+    journal.finish();
+    result
 }
 
 /// Sync logic that accepts a report (useful for testing)
@@ -346,6 +385,18 @@ pub fn sync_subrepo_with_report(
     stash: bool,
     force: bool,
     report: &ValidationReport,
+) -> Result<()> {
+    let journal = JournalRecorder::new("nested-sync", RestoreMode::Keep);
+    run_sync(name, target_commit, stash, force, report, &journal)
+}
+
+fn run_sync(
+    name: &str,
+    target_commit: &str,
+    stash: bool,
+    force: bool,
+    report: &ValidationReport,
+    journal: &JournalRecorder,
 ) -> Result<()> {
     let instances = find_instances_by_name(report, name)?;
 
@@ -369,15 +420,16 @@ pub fn sync_subrepo_with_report(
                 continue;
             }
         };
-        let mut stashed = false;
+        let before = HeadState::read_blocking(&instance.subrepo_path).ok();
+        let mut stashed = None;
 
         // Handle uncommitted changes
         if has_changes {
             if stash {
                 // Stash changes before syncing
                 match stash_changes(&instance.subrepo_path) {
-                    Ok(()) => {
-                        stashed = true;
+                    Ok(commit) => {
+                        stashed = Some(commit);
                     }
                     Err(e) => {
                         let error = clean_error_message(&e.to_string());
@@ -411,14 +463,17 @@ pub fn sync_subrepo_with_report(
         }
 
         // Checkout the commit
-        match checkout_commit(&instance.subrepo_path, target_commit) {
+        let checkout = checkout_commit(&instance.subrepo_path, target_commit);
+        let stashed_changes = stashed.is_some();
+        record_nested_change(journal, instance, before, stashed);
+        match checkout {
             Ok(()) => {
                 println!("  ✅ {}", instance.parent_repo);
-                let (message, next) = if stashed {
+                let (message, next) = if stashed_changes {
                     (
                         format!("checked out {short_commit}; local changes stashed"),
                         Some(format!(
-                            "run `git -C '{}' stash pop` when ready to restore them",
+                            "run `repos undo` or `git -C '{}' stash pop` when ready to restore them",
                             format_relative_repo_path(&instance.subrepo_path.to_string_lossy())
                         )),
                     )
@@ -464,7 +519,10 @@ pub fn sync_subrepo_with_report(
 /// Update a subrepo to the latest commit from remote
 pub fn update_subrepo(name: &str, force: bool) -> Result<()> {
     let report = super::validation::validate_subrepos()?;
-    update_subrepo_with_report(name, force, &report)
+    let journal = JournalRecorder::new("nested-update", RestoreMode::Keep);
+    let result = run_update(name, force, &report, &journal);
+    journal.finish();
+    result
 }
 
 /// Update logic that accepts a report (useful for testing)
@@ -472,6 +530,16 @@ pub fn update_subrepo_with_report(
     name: &str,
     force: bool,
     report: &ValidationReport,
+) -> Result<()> {
+    let journal = JournalRecorder::new("nested-update", RestoreMode::Keep);
+    run_update(name, force, report, &journal)
+}
+
+fn run_update(
+    name: &str,
+    force: bool,
+    report: &ValidationReport,
+    journal: &JournalRecorder,
 ) -> Result<()> {
     let instances = find_instances_by_name(report, name)?;
 
@@ -564,7 +632,10 @@ pub fn update_subrepo_with_report(
                     }
                 }
 
-                match checkout_commit(&instance.subrepo_path, &commit) {
+                let before = HeadState::read_blocking(&instance.subrepo_path).ok();
+                let checkout = checkout_commit(&instance.subrepo_path, &commit);
+                record_nested_change(journal, instance, before, None);
+                match checkout {
                     Ok(()) => {
                         let old_short = instance.short_hash.clone();
                         println!(
@@ -4,7 +4,7 @@ use super::{
     get_commit_timestamp, get_current_commit, get_remote_url, has_uncommitted_changes,
     SubrepoInstance, ValidationReport,
 };
-use crate::core::config::SKIP_DIRECTORIES;
+use crate::core::settings::settings;
 use anyhow::Result;
 use ignore::WalkBuilder;
 use std::collections::HashMap;
@@ -76,7 +76,12 @@ fn find_nested_in_parent(parent_name: &str, parent_path: &Path) -> Result<Vec<Su
             let file_name = entry.file_name().to_str().unwrap_or("");
 
             // Skip build/dependency directories
-            if SKIP_DIRECTORIES.contains(&file_name) {
+            if settings()
+                .skip_directories
+                .value
+                .iter()
+                .any(|skipped| skipped == file_name)
+            {
                 return false;
             }
 
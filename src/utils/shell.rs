@@ -0,0 +1,47 @@
+//! Shell quoting for commands shown in reports and next steps.
+
+use std::borrow::Cow;
+
+/// Quotes `value` for a POSIX shell only when it contains special characters.
+pub(crate) fn shell_word(value: &str) -> Cow<'_, str> {
+    let is_plain = !value.is_empty()
+        && value.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '-' | '_' | '.' | '/' | ':' | '=' | '@' | '+' | ',')
+        });
+    if is_plain {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(format!("'{}'", value.replace('\'', "'\\''")))
+    }
+}
+
+/// Joins command arguments into one line a user can paste into a shell.
+pub(crate) fn shell_join(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| shell_word(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_only_words_that_need_it() {
+        assert_eq!(shell_word("./services/api"), "./services/api");
+        assert_eq!(shell_word("my repo"), "'my repo'");
+        assert_eq!(shell_word("it's"), "'it'\\''s'");
+        assert_eq!(shell_word(""), "''");
+    }
+
+    #[test]
+    fn joins_arguments_into_a_pasteable_line() {
+        let args = ["cargo", "fmt", "--check"].map(String::from);
+        assert_eq!(shell_join(&args), "cargo fmt --check");
+
+        let args = ["git", "commit", "-m", "fix typo"].map(String::from);
+        assert_eq!(shell_join(&args), "git commit -m 'fix typo'");
+    }
+}
@@ -2,14 +2,24 @@
 
 use std::io::Write;
 
+use crate::core::is_human_output;
+
 /// Sets the terminal title to the specified text
+///
+/// Skipped for `--format json|ndjson` so stdout stays machine-readable.
 pub fn set_terminal_title(title: &str) {
+    if !is_human_output() {
+        return;
+    }
     // ANSI escape sequence to set terminal title
     print!("\x1b]0;{title}\x07");
 }
 
 /// Sets the terminal title and ensures it's flushed to the terminal
 pub fn set_terminal_title_and_flush(title: &str) {
+    if !is_human_output() {
+        return;
+    }
     set_terminal_title(title);
     std::io::stdout().flush().expect("Failed to flush stdout when setting terminal title - this indicates a terminal or I/O issue");
 }
@@ -679,6 +679,16 @@ async fn test_create_and_push_tag() {
         "Should indicate existing tag was pushed: {}",
         message
     );
+
+    // Once HEAD moves on, the existing tag no longer marks this release.
+    std::process::Command::new("git")
+        .args(["commit", "--allow-empty", "-m", "Next commit"])
+        .current_dir(repo_path)
+        .output()
+        .expect("Failed to commit");
+    let (success, message) = create_and_push_tag(repo_path, "v1.0.0").await;
+    assert!(!success, "Should refuse a tag away from HEAD: {message}");
+    assert!(message.contains("instead of HEAD"), "{message}");
 }
 
 // ============================================================================
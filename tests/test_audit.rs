@@ -82,11 +82,13 @@ fn test_truffle_statistics_add_repo_result_with_unverified_secrets() {
             detector_name: "AWS".to_string(),
             verified: false,
             file_path: "config/aws.yml".to_string(),
+            fingerprint: String::new(),
         },
         SecretFinding {
             detector_name: "GitHub".to_string(),
             verified: false,
             file_path: ".env".to_string(),
+            fingerprint: String::new(),
         },
     ];
 
@@ -138,11 +140,13 @@ fn test_truffle_statistics_add_repo_result_with_verified_secrets() {
             detector_name: "AWS".to_string(),
             verified: true,
             file_path: "config/aws.yml".to_string(),
+            fingerprint: String::new(),
         },
         SecretFinding {
             detector_name: "AWS".to_string(),
             verified: false,
             file_path: ".env".to_string(),
+            fingerprint: String::new(),
         },
     ];
 
@@ -190,6 +194,7 @@ fn test_truffle_statistics_accumulation_across_repos() {
         detector_name: "AWS".to_string(),
         verified: true,
         file_path: "config.yml".to_string(),
+        fingerprint: String::new(),
     }];
     stats.add_repo_result("repo1", &secrets1);
 
@@ -199,11 +204,13 @@ fn test_truffle_statistics_accumulation_across_repos() {
             detector_name: "GitHub".to_string(),
             verified: false,
             file_path: ".env".to_string(),
+            fingerprint: String::new(),
         },
         SecretFinding {
             detector_name: "AWS".to_string(),
             verified: false,
             file_path: "creds.json".to_string(),
+            fingerprint: String::new(),
         },
     ];
     stats.add_repo_result("repo2", &secrets2);
@@ -304,6 +311,7 @@ fn test_truffle_statistics_generate_detailed_report_text() {
             detector_name: "AWS".to_string(),
             verified: true,
             file_path: "config/credentials.yml".to_string(),
+            fingerprint: String::new(),
         }],
     );
     stats.verified_secrets = 2;
@@ -349,6 +357,7 @@ fn test_truffle_statistics_generate_detailed_report_json() {
             detector_name: "AWS".to_string(),
             verified: true,
             file_path: "config/credentials.yml".to_string(),
+            fingerprint: String::new(),
         }],
     );
     stats.total_repos_scanned = 5;
@@ -426,6 +435,7 @@ fn test_secret_finding_structure() {
         detector_name: "AWS".to_string(),
         verified: true,
         file_path: "config/aws.yml".to_string(),
+        fingerprint: String::new(),
     };
 
     assert_eq!(finding.detector_name, "AWS", "Should store detector name");
@@ -483,16 +493,19 @@ fn test_hygiene_statistics_update_with_violations() {
             file_path: "node_modules/package/index.js".to_string(),
             violation_type: ViolationType::UniversalBadPattern,
             size_bytes: None,
+            rule: None,
         },
         HygieneViolation {
             file_path: ".env".to_string(),
             violation_type: ViolationType::GitignoreViolation,
             size_bytes: None,
+            rule: None,
         },
         HygieneViolation {
             file_path: "large_file.bin".to_string(),
             violation_type: ViolationType::LargeFile,
             size_bytes: Some(5_000_000),
+            rule: None,
         },
     ];
 
@@ -560,6 +573,7 @@ fn test_hygiene_statistics_multiple_repos() {
         file_path: ".env".to_string(),
         violation_type: ViolationType::GitignoreViolation,
         size_bytes: None,
+        rule: None,
     }];
     stats.update(
         "repo2",
@@ -605,16 +619,19 @@ fn test_hygiene_statistics_generate_detailed_summary_with_violations() {
             file_path: "node_modules/pkg/index.js".to_string(),
             violation_type: ViolationType::UniversalBadPattern,
             size_bytes: None,
+            rule: None,
         },
         HygieneViolation {
             file_path: ".env".to_string(),
             violation_type: ViolationType::GitignoreViolation,
             size_bytes: None,
+            rule: None,
         },
         HygieneViolation {
             file_path: "big.bin".to_string(),
             violation_type: ViolationType::LargeFile,
             size_bytes: Some(2_000_000),
+            rule: None,
         },
     ];
 
@@ -691,6 +708,7 @@ fn test_hygiene_json_retains_repository_and_violation_details() {
             file_path: ".env".to_string(),
             violation_type: ViolationType::GitignoreViolation,
             size_bytes: None,
+            rule: None,
         }],
     );
 
@@ -714,18 +732,21 @@ fn test_hygiene_violation_types() {
         file_path: ".env".to_string(),
         violation_type: ViolationType::GitignoreViolation,
         size_bytes: None,
+        rule: None,
     };
 
     let pattern_violation = HygieneViolation {
         file_path: "node_modules/pkg/file.js".to_string(),
         violation_type: ViolationType::UniversalBadPattern,
         size_bytes: None,
+        rule: None,
     };
 
     let large_violation = HygieneViolation {
         file_path: "huge.bin".to_string(),
         violation_type: ViolationType::LargeFile,
         size_bytes: Some(10_000_000),
+        rule: None,
     };
 
     assert_eq!(
@@ -830,6 +851,7 @@ async fn test_hygiene_statistics_with_git_repo() {
         file_path: ".env".to_string(),
         violation_type: ViolationType::GitignoreViolation,
         size_bytes: None,
+        rule: None,
     }];
 
     stats.update(
@@ -860,6 +882,7 @@ async fn test_truffle_statistics_with_multiple_detectors() {
             detector_name: detector.to_string(),
             verified: false,
             file_path: format!("config/{}.yml", detector.to_lowercase()),
+            fingerprint: String::new(),
         }];
         stats.add_repo_result(&format!("{}-repo", detector), &secrets);
     }
@@ -958,6 +981,7 @@ fn test_comprehensive_audit_workflow() {
         detector_name: "AWS".to_string(),
         verified: true,
         file_path: ".env".to_string(),
+        fingerprint: String::new(),
     }];
     truffle_stats.add_repo_result("dirty-repo", &secrets);
 
@@ -966,11 +990,13 @@ fn test_comprehensive_audit_workflow() {
             file_path: ".env".to_string(),
             violation_type: ViolationType::GitignoreViolation,
             size_bytes: None,
+            rule: None,
         },
         HygieneViolation {
             file_path: "node_modules/pkg/index.js".to_string(),
             violation_type: ViolationType::UniversalBadPattern,
             size_bytes: None,
+            rule: None,
         },
     ];
     hygiene_stats.update(
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use goobits_repos::audit::fixes::{apply_fixes, FixOptions};
 use goobits_repos::audit::hygiene::{check_repo_hygiene, HygieneStatistics};
+use goobits_repos::audit::scanner::SecretEngine;
 use std::process::Command;
 use tempfile::TempDir;
 
@@ -14,7 +15,7 @@ async fn test_fix_gitignore_violations() -> Result<()> {
     let repo_path = temp_dir.path();
     setup_git_repo(repo_path)?;
 
-    // Create a violation file (.log files are in UNIVERSAL_BAD_PATTERNS)
+    // Create a violation file (.log files match the built-in logs-and-temp rule)
     create_test_commit(repo_path, "app.log", "some logs", "Add log file")?;
 
     // 2. Scan
@@ -43,6 +44,7 @@ async fn test_fix_gitignore_violations() -> Result<()> {
         dry_run: false,
         skip_confirm: true,
         target_repos: None,
+        engine: SecretEngine::Trufflehog,
     };
 
     let results = apply_fixes(&stats, options).await?;
@@ -124,6 +126,7 @@ async fn test_fix_large_files_dry_run() -> Result<()> {
         dry_run: true,
         skip_confirm: true,
         target_repos: None,
+        engine: SecretEngine::Trufflehog,
     };
 
     let results = apply_fixes(&stats, options).await?;
@@ -192,6 +195,7 @@ async fn test_history_rewrite_fails_when_upstream_is_unreachable() -> Result<()>
             dry_run: false,
             skip_confirm: true,
             target_repos: None,
+            engine: SecretEngine::Trufflehog,
         },
     )
     .await;
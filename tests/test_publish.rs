@@ -1,6 +1,8 @@
 use goobits_repos::commands::publish::handle_publish_command;
 use std::env;
 use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
 
 mod common;
 use common::fixtures::TestRepo;
@@ -40,9 +42,12 @@ version = "0.1.0"
         vec![], // target_repos
         true,   // dry_run
         false,  // tag
+        false,  // sign
         false,  // allow_dirty
+        false,  // skip_preflight
         true,   // all (to ignore visibility check since test repo might be private/unknown)
         false,  // private_only
+        false,  // resume
     )
     .await;
 
@@ -56,6 +61,71 @@ version = "0.1.0"
     );
 }
 
+#[tokio::test]
+async fn test_publish_dry_run_cargo_workspace() {
+    let _lock = common::lock_test().await;
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let original_dir = env::current_dir().expect("Failed to get current dir");
+
+    let repo = match TestRepo::new() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Failed to create test repo: {}, skipping", e);
+            return;
+        }
+    };
+
+    // Virtual workspace: no [package] table at the root
+    let workspace_toml = r#"[workspace]
+members = ["crates/*"]
+
+[workspace.package]
+version = "0.2.0"
+"#;
+    fs::write(repo.path().join("Cargo.toml"), workspace_toml).expect("Failed to write Cargo.toml");
+    for (member, manifest) in [
+        (
+            "core",
+            "[package]\nname = \"ws-core\"\nversion.workspace = true\n",
+        ),
+        (
+            "app",
+            "[package]\nname = \"ws-app\"\nversion.workspace = true\n\n[dependencies]\nws-core = { path = \"../core\", version = \"0.2.0\" }\n",
+        ),
+    ] {
+        let dir = repo.path().join("crates").join(member);
+        fs::create_dir_all(&dir).expect("Failed to create member dir");
+        fs::write(dir.join("Cargo.toml"), manifest).expect("Failed to write member Cargo.toml");
+    }
+
+    env::set_current_dir(repo.path()).expect("Failed to change dir");
+
+    let result = handle_publish_command(
+        vec![], // target_repos
+        true,   // dry_run
+        false,  // tag
+        false,  // sign
+        false,  // allow_dirty
+        false,  // skip_preflight
+        true,   // all
+        false,  // private_only
+        false,  // resume
+    )
+    .await;
+
+    let _ = env::set_current_dir(&original_dir);
+
+    assert!(
+        result.is_ok(),
+        "Workspace publish dry-run should succeed: {:?}",
+        result
+    );
+}
+
 #[tokio::test]
 async fn test_publish_dry_run_npm() {
     let _lock = common::lock_test().await;
@@ -91,9 +161,12 @@ async fn test_publish_dry_run_npm() {
         vec![], // target_repos
         true,   // dry_run
         false,  // tag
+        false,  // sign
         false,  // allow_dirty
+        false,  // skip_preflight
         true,   // all
         false,  // private_only
+        false,  // resume
     )
     .await;
 
@@ -106,3 +179,530 @@ async fn test_publish_dry_run_npm() {
         result
     );
 }
+
+#[test]
+fn test_publish_dry_run_marks_versions_already_on_the_registry() {
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let workspace = TempDir::new().expect("Failed to create workspace");
+    for (name, version) in [("core-lib", "1.0.0"), ("app", "0.1.0")] {
+        let dir = workspace.path().join(name);
+        fs::create_dir(&dir).expect("Failed to create repo dir");
+        common::git::setup_git_repo(&dir).expect("Failed to init repo");
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"{version}\"\n"),
+        )
+        .expect("Failed to write Cargo.toml");
+    }
+
+    // Sparse-index stand-in: core-lib 1.0.0 is published, app is not.
+    let index = TempDir::new().expect("Failed to create index");
+    let entry = index.path().join("co").join("re");
+    fs::create_dir_all(&entry).expect("Failed to create index dir");
+    fs::write(
+        entry.join("core-lib"),
+        "{\"name\":\"core-lib\",\"vers\":\"1.0.0\",\"deps\":[],\"cksum\":\"\",\"features\":{},\"yanked\":false}\n",
+    )
+    .expect("Failed to write index entry");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_repos"))
+        .args(["publish", "--dry-run", "--all"])
+        .env(
+            "REPOS_PUBLISH_CARGO_INDEX_URL",
+            format!("file://{}", index.path().display()),
+        )
+        .current_dir(workspace.path())
+        .output()
+        .expect("Failed to run repos");
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = |name: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(name))
+            .unwrap_or_default()
+            .to_string()
+    };
+    assert!(line("core-lib").contains("already published"), "{stdout}");
+    assert!(!line("app ").contains("already published"), "{stdout}");
+    assert!(
+        stdout.contains("Would publish 1 packages, skip 1 already published"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_publish_dry_run_uses_registries_from_the_manifest() {
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let workspace = TempDir::new().expect("Failed to create workspace");
+    for (name, manifest, contents) in [
+        (
+            "core-lib",
+            "Cargo.toml",
+            "[package]\nname = \"core-lib\"\nversion = \"1.0.0\"\n",
+        ),
+        (
+            "app",
+            "Cargo.toml",
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        ),
+        (
+            "ui",
+            "package.json",
+            "{\"name\": \"ui\", \"version\": \"2.0.0\"}",
+        ),
+    ] {
+        let dir = workspace.path().join(name);
+        fs::create_dir(&dir).expect("Failed to create repo dir");
+        common::git::setup_git_repo(&dir).expect("Failed to init repo");
+        fs::write(dir.join(manifest), contents).expect("Failed to write manifest");
+    }
+
+    // Private registry stand-ins: core-lib 1.0.0 is on the internal Cargo
+    // index and ui 2.0.0 on the internal npm registry. The public index is empty.
+    let cargo_index = TempDir::new().expect("Failed to create index");
+    let entry = cargo_index.path().join("co").join("re");
+    fs::create_dir_all(&entry).expect("Failed to create index dir");
+    fs::write(
+        entry.join("core-lib"),
+        "{\"name\":\"core-lib\",\"vers\":\"1.0.0\",\"deps\":[],\"cksum\":\"\",\"features\":{},\"yanked\":false}\n",
+    )
+    .expect("Failed to write index entry");
+    let npm_registry = TempDir::new().expect("Failed to create npm registry");
+    fs::write(
+        npm_registry.path().join("ui"),
+        "{\"name\":\"ui\",\"versions\":{\"2.0.0\":{}}}",
+    )
+    .expect("Failed to write packument");
+    let public_index = TempDir::new().expect("Failed to create public index");
+
+    fs::write(
+        workspace.path().join("repos.toml"),
+        format!(
+            r#"[[repo]]
+path = "core-lib"
+url = "git@example.com:acme/core-lib.git"
+groups = ["platform"]
+
+[[repo]]
+path = "ui"
+url = "git@example.com:acme/ui.git"
+registry = "verdaccio"
+
+[registries.internal]
+groups = ["platform"]
+cargo = "internal"
+cargo_index = "file://{}"
+
+[registries.verdaccio]
+npm = "file://{}/"
+"#,
+            cargo_index.path().display(),
+            npm_registry.path().display()
+        ),
+    )
+    .expect("Failed to write repos.toml");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_repos"))
+        .args(["publish", "--dry-run", "--all"])
+        .env(
+            "REPOS_PUBLISH_CARGO_INDEX_URL",
+            format!("file://{}", public_index.path().display()),
+        )
+        .current_dir(workspace.path())
+        .output()
+        .expect("Failed to run repos");
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = |name: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(name))
+            .unwrap_or_default()
+            .to_string()
+    };
+    let core_lib = line("core-lib");
+    assert!(core_lib.contains("to internal (internal)"), "{stdout}");
+    assert!(core_lib.contains("already published"), "{stdout}");
+    let ui = line("ui ");
+    assert!(ui.contains("to verdaccio (file://"), "{stdout}");
+    assert!(ui.contains("already published"), "{stdout}");
+    let app = line("app ");
+    assert!(!app.contains(" to "), "{stdout}");
+    assert!(!app.contains("already published"), "{stdout}");
+}
+
+#[test]
+fn test_publish_dry_run_names_tags_from_templates() {
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let workspace = TempDir::new().expect("Failed to create workspace");
+    for (name, manifest, contents) in [
+        (
+            "core-lib",
+            "Cargo.toml",
+            "[package]\nname = \"core-lib\"\nversion = \"1.0.0\"\n",
+        ),
+        (
+            "app",
+            "Cargo.toml",
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        ),
+        (
+            "ui",
+            "package.json",
+            "{\"name\": \"ui\", \"version\": \"2.0.0\"}",
+        ),
+    ] {
+        let dir = workspace.path().join(name);
+        fs::create_dir(&dir).expect("Failed to create repo dir");
+        common::git::setup_git_repo(&dir).expect("Failed to init repo");
+        fs::write(dir.join(manifest), contents).expect("Failed to write manifest");
+    }
+    fs::write(
+        workspace.path().join("repos.toml"),
+        r#"[[repo]]
+path = "core-lib"
+url = "git@example.com:acme/core-lib.git"
+tag_template = "{name}-v{version}"
+
+[tag_templates]
+npm = "{name}@{version}"
+"#,
+    )
+    .expect("Failed to write repos.toml");
+    let empty_index = TempDir::new().expect("Failed to create index");
+    let empty_registry = TempDir::new().expect("Failed to create npm registry");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_repos"))
+        .args(["publish", "--dry-run", "--all", "--tag"])
+        .env(
+            "REPOS_PUBLISH_CARGO_INDEX_URL",
+            format!("file://{}", empty_index.path().display()),
+        )
+        .env(
+            "REPOS_PUBLISH_NPM_REGISTRY_URL",
+            format!("file://{}", empty_registry.path().display()),
+        )
+        .env("REPOS_PUBLISH_TAG_TEMPLATE", "release-{version}")
+        .current_dir(workspace.path())
+        .output()
+        .expect("Failed to run repos");
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("tag core-lib-v1.0.0"), "{stdout}");
+    assert!(stdout.contains("tag ui@2.0.0"), "{stdout}");
+    assert!(stdout.contains("tag release-0.1.0"), "{stdout}");
+}
+
+/// Creates `name` under `workspace` as a committed crate; `lib` adds a
+/// `src/lib.rs` so the crate packs cleanly.
+fn committed_crate(workspace: &std::path::Path, name: &str, dependencies: &str, lib: bool) {
+    let repo = workspace.join(name);
+    fs::create_dir(&repo).expect("Failed to create repo dir");
+    common::git::setup_git_repo(&repo).expect("Failed to init repo");
+    if lib {
+        fs::create_dir(repo.join("src")).expect("Failed to create src");
+        fs::write(repo.join("src").join("lib.rs"), "").expect("Failed to write lib.rs");
+        common::git::run_git_ok(&repo, &["add", "src"]);
+    }
+    common::git::create_test_commit(
+        &repo,
+        "Cargo.toml",
+        &format!(
+            "[package]\nname = \"{name}\"\nversion = \"1.0.0\"\nedition = \"2021\"\nlicense = \"MIT\"\ndescription = \"{name}\"\n\n[dependencies]\n{dependencies}"
+        ),
+        "Release 1.0.0",
+    )
+    .expect("Failed to commit");
+}
+
+#[test]
+fn test_publish_refuses_release_tags_git_would_reject() {
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let workspace = TempDir::new().expect("Failed to create workspace");
+    let repo = workspace.path().join("widget");
+    fs::create_dir(&repo).expect("Failed to create repo dir");
+    common::git::setup_git_repo(&repo).expect("Failed to init repo");
+    common::git::create_test_commit(
+        &repo,
+        "pom.xml",
+        "<project>\n  <groupId>com.acme</groupId>\n  <artifactId>widget</artifactId>\n  <version>1.0.0</version>\n</project>\n",
+        "Add pom.xml",
+    )
+    .expect("commit");
+    let empty_repository = TempDir::new().expect("Failed to create maven repository");
+
+    let publish = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_repos"))
+            .args(args)
+            .env(
+                "REPOS_PUBLISH_MAVEN_REPOSITORY_URL",
+                format!("file://{}", empty_repository.path().display()),
+            )
+            .env("REPOS_PUBLISH_TAG_TEMPLATE", "{name}-{version}")
+            .current_dir(workspace.path())
+            .output()
+            .expect("Failed to run repos")
+    };
+
+    let dry_run = publish(&["publish", "--dry-run", "--all", "--tag", "--skip-preflight"]);
+    assert!(dry_run.status.success(), "{dry_run:?}");
+    let stdout = String::from_utf8_lossy(&dry_run.stdout);
+    assert!(
+        stdout.contains("tag com.acme:widget-1.0.0 (invalid tag name)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("1 with invalid tag names"), "{stdout}");
+
+    let output = publish(&["publish", "--all", "--tag", "--skip-preflight"]);
+    assert!(!output.status.success(), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("'com.acme:widget-1.0.0' is not a valid git tag name"),
+        "{stderr}"
+    );
+    assert!(!workspace.path().join(".repos/publish").exists());
+}
+
+#[test]
+fn test_publish_dry_run_shows_pre_flight_results() {
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let workspace = TempDir::new().expect("Failed to create workspace");
+    committed_crate(workspace.path(), "widget", "", true);
+    // No src/: cargo cannot package a crate without targets.
+    committed_crate(workspace.path(), "broken", "", false);
+    let index = TempDir::new().expect("Failed to create index");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_repos"))
+        .args(["publish", "--dry-run", "--all"])
+        .env(
+            "REPOS_PUBLISH_CARGO_INDEX_URL",
+            format!("file://{}", index.path().display()),
+        )
+        .current_dir(workspace.path())
+        .output()
+        .expect("Failed to run repos");
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("packed "), "{stdout}");
+    assert!(stdout.contains("        src/lib.rs"), "{stdout}");
+    assert!(stdout.contains("❌ pre-flight failed:"), "{stdout}");
+    assert!(
+        stdout.contains("Would publish 2 packages; 1 blocked by failing pre-flight checks"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_publish_blocks_packages_that_fail_pre_flight_and_their_dependents() {
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let workspace = TempDir::new().expect("Failed to create workspace");
+    committed_crate(workspace.path(), "core-lib", "", false);
+    committed_crate(workspace.path(), "app", "core-lib = \"1.0.0\"\n", true);
+    let index = TempDir::new().expect("Failed to create index");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_repos"))
+        .args(["publish", "--all"])
+        .env(
+            "REPOS_PUBLISH_CARGO_INDEX_URL",
+            format!("file://{}", index.path().display()),
+        )
+        .current_dir(workspace.path())
+        .output()
+        .expect("Failed to run repos");
+
+    assert!(!output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pre-flight failed:"), "{stdout}");
+    assert!(
+        stdout.contains("next: fix the package build so it packs cleanly"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("not published: dependency core-lib"),
+        "{stdout}"
+    );
+    assert!(workspace
+        .path()
+        .join(".repos")
+        .join("publish")
+        .join("state.json")
+        .is_file());
+}
+
+#[test]
+fn test_publish_resume_pushes_the_tag_left_behind_without_republishing() {
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let workspace = TempDir::new().expect("Failed to create workspace");
+    let repo = workspace.path().join("core-lib");
+    fs::create_dir(&repo).expect("Failed to create repo dir");
+    common::git::setup_git_repo(&repo).expect("Failed to init repo");
+    common::git::create_test_commit(
+        &repo,
+        "Cargo.toml",
+        "[package]\nname = \"core-lib\"\nversion = \"1.0.0\"\n",
+        "Release 1.0.0",
+    )
+    .expect("Failed to commit");
+    let remote = common::git::add_bare_remote(&repo, true).expect("Failed to add remote");
+    // The interrupted run published and tagged, then failed to push the tag;
+    // work continued on top of the release commit since.
+    let released = common::git::get_head_commit(&repo).expect("Failed to read HEAD");
+    common::git::run_git_ok(&repo, &["tag", "v1.0.0"]);
+    common::git::create_test_commit(&repo, "NOTES.md", "next\n", "Start 1.1.0")
+        .expect("Failed to commit");
+
+    let state_dir = workspace.path().join(".repos").join("publish");
+    fs::create_dir_all(&state_dir).expect("Failed to create state dir");
+    fs::write(
+        state_dir.join("state.json"),
+        r#"{
+  "started_at": "2026-10-17T14:25:01+02:00",
+  "options": { "targets": [], "all": true, "private_only": false, "tag": true },
+  "packages": [
+    { "package": "core-lib", "version": "1.0.0", "tag_name": "v1.0.0",
+      "published": true, "tagged": true, "pushed_tag": false }
+  ]
+}
+"#,
+    )
+    .expect("Failed to write state");
+
+    let index = TempDir::new().expect("Failed to create index");
+    let entry = index.path().join("co").join("re");
+    fs::create_dir_all(&entry).expect("Failed to create index dir");
+    fs::write(
+        entry.join("core-lib"),
+        "{\"name\":\"core-lib\",\"vers\":\"1.0.0\",\"deps\":[],\"cksum\":\"\",\"features\":{},\"yanked\":false}\n",
+    )
+    .expect("Failed to write index entry");
+
+    let resume = || {
+        Command::new(env!("CARGO_BIN_EXE_repos"))
+            .args(["publish", "--resume"])
+            .env(
+                "REPOS_PUBLISH_CARGO_INDEX_URL",
+                format!("file://{}", index.path().display()),
+            )
+            .current_dir(workspace.path())
+            .output()
+            .expect("Failed to run repos")
+    };
+
+    let output = resume();
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("published by the interrupted run"),
+        "{stdout}"
+    );
+
+    let remote_tags = Command::new("git")
+        .args(["ls-remote", "--tags"])
+        .arg(remote.path().join("remote.git"))
+        .output()
+        .expect("Failed to list remote tags");
+    let remote_tags = String::from_utf8_lossy(&remote_tags.stdout);
+    assert!(
+        remote_tags.contains(&format!("{released}\trefs/tags/v1.0.0")),
+        "{remote_tags}"
+    );
+    assert!(!state_dir.join("state.json").exists());
+
+    let output = resume();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No unfinished publish to resume"));
+}
+
+#[test]
+fn test_publish_dry_run_go_module_and_gem() {
+    if !is_git_available() {
+        eprintln!("Git not available, skipping test");
+        return;
+    }
+
+    let workspace = TempDir::new().expect("Failed to create workspace");
+    let go = workspace.path().join("widget-go");
+    let gem = workspace.path().join("widget-gem");
+    for dir in [&go, &gem] {
+        fs::create_dir(dir).expect("Failed to create repo dir");
+        common::git::setup_git_repo(dir).expect("Failed to init repo");
+    }
+    common::git::create_test_commit(
+        &go,
+        "go.mod",
+        "module github.com/acme/widget\n\ngo 1.22\n",
+        "Add go.mod",
+    )
+    .expect("commit");
+    common::git::run_git_ok(&go, &["tag", "v1.2.0"]);
+    fs::write(
+        gem.join("widget.gemspec"),
+        "Gem::Specification.new do |spec|\n  spec.name = \"widget\"\n  spec.version = \"0.3.0\"\nend\n",
+    )
+    .expect("Failed to write gemspec");
+
+    // Module proxy stand-in already serves v1.2.0; the gem API knows nothing.
+    let registry = TempDir::new().expect("Failed to create registry");
+    let module = registry.path().join("github.com/acme/widget/@v");
+    fs::create_dir_all(&module).expect("Failed to create proxy dir");
+    fs::write(module.join("v1.2.0.info"), "{\"Version\":\"v1.2.0\"}").expect("write");
+    let base = format!("file://{}", registry.path().display());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_repos"))
+        .args(["publish", "--dry-run", "--all"])
+        .env("REPOS_PUBLISH_GO_PROXY_URL", &base)
+        .env("REPOS_PUBLISH_RUBYGEMS_URL", &base)
+        .current_dir(workspace.path())
+        .output()
+        .expect("Failed to run repos");
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = |name: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(name))
+            .unwrap_or_default()
+            .to_string()
+    };
+    assert!(line("widget-go").contains("(go"), "{stdout}");
+    assert!(
+        line("widget-go").contains("v1.2.0  already published"),
+        "{stdout}"
+    );
+    assert!(line("widget-gem").contains("(gem"), "{stdout}");
+    assert!(line("widget-gem").ends_with("v0.3.0"), "{stdout}");
+}